@@ -0,0 +1,37 @@
+//! Cost of turning collected segments into the ANSI statusline, without
+//! collecting them: `cargo bench --bench render`
+
+use ccometixline_packycc::config::Config;
+use ccometixline_packycc::core::mock::MockScenario;
+use ccometixline_packycc::core::StatusLineGenerator;
+use ccometixline_packycc::ui::themes::ThemePresets;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+fn render(c: &mut Criterion) {
+    // Built-in themes only, so files in the config directory do not matter
+    let themes: [(&str, Config); 3] = [
+        ("plain", ThemePresets::get_minimal()),
+        ("nerd_font", ThemePresets::get_default()),
+        ("powerline", ThemePresets::get_powerline_dark()),
+    ];
+    let scenario = MockScenario::load("long-session").expect("built-in scenario");
+
+    let mut group = c.benchmark_group("render");
+    for (name, mut config) in themes {
+        // Skip font detection, which reads the disk
+        config.style.nerd_font = Some(true);
+        let segments = scenario.collect(&config);
+        let generator = StatusLineGenerator::new(config);
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || segments.clone(),
+                |segments| generator.generate(segments),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, render);
+criterion_main!(benches);
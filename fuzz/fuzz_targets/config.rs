@@ -0,0 +1,26 @@
+//! config.toml, parsed and migrated on every render, then checked and
+//! rendered against the default mock scenario
+#![no_main]
+
+use ccometixline_packycc::config::migrate;
+use ccometixline_packycc::core::mock::MockScenario;
+use ccometixline_packycc::StatusLineGenerator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(migrated) = migrate::parse(content) else {
+        return;
+    };
+
+    let mut config = migrated.config;
+    if config.check().is_err() {
+        return;
+    }
+    config.style.nerd_font = Some(true);
+    let scenario = MockScenario::load("default").expect("built-in scenario");
+    let segments = scenario.collect(&config);
+    StatusLineGenerator::new(config).generate(segments);
+});
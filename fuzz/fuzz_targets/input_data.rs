@@ -0,0 +1,48 @@
+//! The statusline payload Claude Code writes to stdin on every render, and
+//! the segments that only read the payload
+#![no_main]
+
+use ccometixline_packycc::config::{Config, SegmentConfig, SegmentId};
+use ccometixline_packycc::core::collect_segment;
+use ccometixline_packycc::{parse_input, StatusLineGenerator};
+use libfuzzer_sys::fuzz_target;
+use std::sync::Once;
+
+const SEGMENTS: &[SegmentId] = &[
+    SegmentId::Model,
+    SegmentId::Directory,
+    SegmentId::Cost,
+    SegmentId::OutputStyle,
+    SegmentId::Vim,
+];
+
+fuzz_target!(|data: &[u8]| {
+    // The cost segment records spending; keep it out of the real directory
+    static SANDBOX: Once = Once::new();
+    SANDBOX.call_once(|| {
+        std::env::set_var("CCLINE_HOME", std::env::temp_dir().join("ccline-fuzz"));
+    });
+
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(input) = parse_input(json) else {
+        return;
+    };
+
+    let mut config = Config::default();
+    config.style.nerd_font = Some(true);
+    config.resolve_theme_overrides(&input);
+    let segments = SEGMENTS
+        .iter()
+        .map(|id| SegmentConfig {
+            enabled: true,
+            ..SegmentConfig::default_for(*id)
+        })
+        .filter_map(|segment| {
+            let data = collect_segment(&segment, &input)?;
+            Some((segment, data))
+        })
+        .collect();
+    StatusLineGenerator::new(config).generate(segments);
+});
@@ -0,0 +1,30 @@
+//! A user info endpoint's answer to the quota segment: rate limit headers,
+//! then the JSON body. Relays are third-party servers, so neither is trusted.
+#![no_main]
+
+use ccometixline_packycc::core::segments::QuotaSegment;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Header lines up to the first empty line, the body after it; no body
+    // is a throttled response
+    let text = String::from_utf8_lossy(data);
+    let (head, body) = match text.split_once("\n\n") {
+        Some((head, body)) => (head, Some(body.as_bytes())),
+        None => (text.as_ref(), None),
+    };
+    let headers: Vec<(&str, &str)> = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .collect();
+
+    let segment = QuotaSegment::new()
+        .with_rate_limit(true)
+        .with_daily_limit(Some(10.0), 80, 95)
+        .with_reset(true, 16, vec![("packycode.com".to_string(), 8)]);
+    segment.from_response(
+        "https://www.packycode.com/api/backend/users/info",
+        headers,
+        body,
+    );
+});
@@ -0,0 +1,59 @@
+//! Session transcripts: JSON lines Claude Code appends to while the session
+//! runs, read by the segments on every render and by `ccline report`
+#![no_main]
+
+use ccometixline_packycc::config::{InputData, PricingConfig, SegmentConfig, SegmentId};
+use ccometixline_packycc::core::collect_segment;
+use ccometixline_packycc::core::summary::summarize;
+use ccometixline_packycc::parse_input;
+use libfuzzer_sys::fuzz_target;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Once;
+
+const SEGMENTS: &[SegmentId] = &[
+    SegmentId::Usage,
+    SegmentId::Session,
+    SegmentId::Attention,
+    SegmentId::Hooks,
+    SegmentId::Transcript,
+];
+
+/// A payload pointing at the transcript at `path`
+fn input(path: &str) -> InputData {
+    let payload = serde_json::json!({
+        "model": { "id": "claude-sonnet-4", "display_name": "Sonnet 4" },
+        "workspace": { "current_dir": "/tmp" },
+        "transcript_path": path,
+    });
+    parse_input(&payload.to_string()).expect("valid payload")
+}
+
+fuzz_target!(|data: &[u8]| {
+    static SANDBOX: Once = Once::new();
+    SANDBOX.call_once(|| {
+        std::env::set_var("CCLINE_HOME", std::env::temp_dir().join("ccline-fuzz"));
+    });
+
+    // A new path per run: segments cache what they read per transcript
+    static RUN: AtomicU64 = AtomicU64::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "ccline-fuzz-{}-{}.jsonl",
+        std::process::id(),
+        RUN.fetch_add(1, Ordering::Relaxed)
+    ));
+    if std::fs::write(&path, data).is_err() {
+        return;
+    }
+
+    let input = input(&path.to_string_lossy());
+    for id in SEGMENTS {
+        let segment = SegmentConfig {
+            enabled: true,
+            ..SegmentConfig::default_for(*id)
+        };
+        collect_segment(&segment, &input);
+    }
+    let _ = summarize(&path, &PricingConfig::default());
+
+    let _ = std::fs::remove_file(&path);
+});
@@ -1,10 +1,16 @@
-use clap::Parser;
+use crate::commands::report::ReportFormat;
+use crate::core::output::OutputFormat;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "ccline")]
 #[command(version, about = "High-performance Claude Code StatusLine")]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
     /// Enter TUI configuration mode
+    #[cfg(feature = "tui")]
     #[arg(short = 'c', long = "config")]
     pub config: bool,
 
@@ -16,23 +22,176 @@ pub struct Cli {
     #[arg(long = "print")]
     pub print: bool,
 
-    /// Initialize config file
+    /// Initialize config file, point Claude Code's statusLine setting at
+    /// ccline and check that the command renders
     #[arg(long = "init")]
     pub init: bool,
 
+    /// With --init, the statusLine command to write instead of the path of
+    /// the installed binary
+    #[arg(long = "command", value_name = "COMMAND", requires = "init")]
+    pub statusline_command: Option<String>,
+
     /// Check configuration
     #[arg(long = "check")]
     pub check: bool,
 
     /// Check for updates
+    #[cfg(feature = "self-update")]
     #[arg(short = 'u', long = "update")]
     pub update: bool,
 
+    /// Run one update check and exit (started in the background by the update segment)
+    #[cfg(feature = "self-update")]
+    #[arg(long = "check-update-background", hide = true)]
+    pub check_update_background: bool,
+
+    /// Upload queued usage reports and exit (started in the background after renders)
+    #[cfg(feature = "report")]
+    #[arg(long = "report-background", hide = true)]
+    pub report_background: bool,
+
+    /// Render a fabricated payload instead of reading stdin ("list" shows scenarios)
+    #[arg(
+        long = "mock",
+        value_name = "SCENARIO",
+        num_args = 0..=1,
+        default_missing_value = "default"
+    )]
+    pub mock: Option<String>,
+
+    /// Read the statusline payload from FILE instead of stdin; with --config the
+    /// preview renders from it
+    #[arg(long = "input", value_name = "FILE")]
+    pub input: Option<std::path::PathBuf>,
+
+    /// Serve rendered statuslines on a local socket, keeping config and
+    /// network results in memory
+    #[arg(long = "daemon", conflicts_with = "client")]
+    pub daemon: bool,
+
+    /// Render through a running daemon, starting one for later renders when
+    /// none answers
+    #[arg(long = "client")]
+    pub client: bool,
+
+    /// Enable segments for this render only, e.g. --enable cost,session
+    #[arg(long = "enable", value_name = "SEGMENTS", value_delimiter = ',')]
+    pub enable: Vec<String>,
+
+    /// Disable segments for this render only, e.g. --disable quota
+    #[arg(long = "disable", value_name = "SEGMENTS", value_delimiter = ',')]
+    pub disable: Vec<String>,
+
+    /// Output format; waybar and polybar render the last payload from Claude
+    /// Code when stdin has none
+    #[arg(long = "output", value_enum, default_value_t = OutputFormat::Ansi)]
+    pub output: OutputFormat,
+
     /// Patch Claude Code cli.js to disable context warnings
     #[arg(long = "patch")]
     pub patch: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Inspect the segments known to this build
+    Segments {
+        #[command(subcommand)]
+        action: SegmentsAction,
+    },
+    /// Work with built-in and user themes
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+    /// Summarize a session from its transcript: duration, models, tokens,
+    /// cost, tools and files
+    Report {
+        /// Session id or id prefix, a transcript path, or "latest"
+        #[arg(long = "session", value_name = "ID", default_value = "latest")]
+        session: String,
+        /// Output format
+        #[arg(long = "format", value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// Inspect and prune the files ccline accumulates
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Show today's spend, budget and balance of every relay account in
+    /// [balance] side by side, queried concurrently
+    Balance {
+        /// Print as JSON instead of a table
+        #[arg(long = "json")]
+        json: bool,
+    },
+    /// Collect segments once and print whether their sources are healthy as
+    /// JSON; exits 0 when ok, 1 when degraded and 2 when failed
+    Health {
+        /// Segments to check, e.g. --segment quota,plan; the enabled network
+        /// segments when omitted
+        #[arg(long = "segment", value_name = "SEGMENTS", value_delimiter = ',')]
+        segments: Vec<String>,
+    },
+    /// Show where config, caches and state are kept
+    Paths {
+        /// Move files from ~/.claude/ccline to the XDG directories, or to
+        /// CCLINE_HOME when set
+        #[arg(long = "migrate")]
+        migrate: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SegmentsAction {
+    /// List all segments with their options, required features and data sources
+    List {
+        /// Print as JSON instead of a human-readable listing
+        #[arg(long = "json")]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Count and size the cached and kept files by kind
+    Stats {
+        /// Print as JSON instead of a table
+        #[arg(long = "json")]
+        json: bool,
+    },
+    /// Remove expired files and the oldest beyond the size limit now
+    Prune,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ThemeAction {
+    /// Render every theme (or only NAME) against a sample payload, one line per theme
+    Preview {
+        /// Theme to preview; all built-in and user themes when omitted
+        name: Option<String>,
+        /// Force Nerd Font icons
+        #[arg(long = "nerd-font", conflicts_with = "plain")]
+        nerd_font: bool,
+        /// Force plain icons
+        #[arg(long = "plain")]
+        plain: bool,
+    },
+    /// Check text and icon contrast per segment against WCAG AA
+    Check {
+        /// Theme to check; all built-in and user themes when omitted
+        name: Option<String>,
+    },
+    /// Build a starter theme from a few questions on the terminal and
+    /// optionally switch config.toml to it; no TUI needed
+    Create {
+        /// Name of the new theme; asked when omitted
+        name: Option<String>,
+    },
+}
+
 impl Cli {
     pub fn parse_args() -> Self {
         Self::parse()
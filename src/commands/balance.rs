@@ -0,0 +1,169 @@
+use crate::config::{Config, NumberFormat};
+use crate::core::executor::{join_with_deadline, Task, NETWORK_DEADLINE};
+use crate::core::segments::quota::{query_account, AccountQuota};
+use crate::error::Error;
+use crate::utils::redact::redact;
+use crate::utils::{credentials, number};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct Row {
+    account: String,
+    #[serde(flatten)]
+    quota: Option<AccountQuota>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// An account to query, with its key or why it has none
+struct Target {
+    name: String,
+    key: Result<String, String>,
+    endpoints: Vec<String>,
+}
+
+/// The configured accounts, or without any, the key the quota segment uses
+fn accounts(config: &Config) -> Vec<Target> {
+    if config.balance.accounts.is_empty() {
+        return vec![Target {
+            name: "default".to_string(),
+            key: credentials::api_key().ok_or_else(|| "No API key found".to_string()),
+            endpoints: Vec::new(),
+        }];
+    }
+
+    config
+        .balance
+        .accounts
+        .iter()
+        .map(|account| {
+            let key = match (&account.api_key_env, &account.api_key) {
+                (Some(name), _) => std::env::var(name)
+                    .ok()
+                    .filter(|key| !key.is_empty())
+                    .ok_or_else(|| format!("${} is not set", name)),
+                (None, Some(key)) => Ok(key.clone()),
+                (None, None) => Err("No api_key or api_key_env".to_string()),
+            };
+            Target {
+                name: account.name.clone(),
+                key,
+                endpoints: account.endpoints.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Query every account at once
+fn collect(config: &Config) -> Vec<Row> {
+    let accounts = accounts(config);
+    let tasks: Vec<Task<Result<AccountQuota, Error>>> = accounts
+        .iter()
+        .filter_map(|target| Some((target.key.clone().ok()?, target.endpoints.clone())))
+        .map(|(key, endpoints)| {
+            Box::new(move || query_account(&key, &endpoints)) as Task<Result<AccountQuota, Error>>
+        })
+        .collect();
+    // Each query gives up after NETWORK_DEADLINE; the margin is for the threads
+    let mut results = join_with_deadline(tasks, NETWORK_DEADLINE * 2).into_iter();
+
+    accounts
+        .into_iter()
+        .map(|target| {
+            let (quota, error) = match target.key {
+                Err(error) => (None, Some(error)),
+                Ok(_) => match results.next().flatten() {
+                    Some(Ok(quota)) => (Some(quota), None),
+                    Some(Err(error)) => (None, Some(redact(&error.to_string()))),
+                    None => (None, Some("No endpoint answered".to_string())),
+                },
+            };
+            Row {
+                account: target.name,
+                quota,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// "$1.23", or "-" when unknown
+fn money(amount: Option<f64>, numbers: &NumberFormat) -> String {
+    amount
+        .map(|amount| format!("${}", number::format_cost(amount, numbers)))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Host of an endpoint URL
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Print today's spend, daily budget and balance of every account, queried
+/// concurrently, with totals across the accounts that answered
+pub fn run(config: &Config, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !cfg!(feature = "quota") {
+        return Err("ccline was built without the quota feature".into());
+    }
+    let rows = collect(config);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    let numbers = &config.style.numbers;
+    let width = rows
+        .iter()
+        .map(|row| row.account.chars().count())
+        .chain(["Account".len(), "Total".len()])
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{:<width$}  {:>10}  {:>10}  {:>10}  {:<5}  Endpoint",
+        "Account",
+        "Today",
+        "Budget",
+        "Balance",
+        "Opus",
+        width = width
+    );
+    for row in &rows {
+        match &row.quota {
+            Some(quota) => println!(
+                "{:<width$}  {:>10}  {:>10}  {:>10}  {:<5}  {}",
+                row.account,
+                money(quota.daily_spent_usd, numbers),
+                money(quota.daily_budget_usd, numbers),
+                money(quota.balance_usd, numbers),
+                if quota.opus_enabled { "yes" } else { "no" },
+                host(&quota.endpoint),
+                width = width
+            ),
+            None => println!(
+                "{:<width$}  {}",
+                row.account,
+                row.error.as_deref().unwrap_or_default(),
+                width = width
+            ),
+        }
+    }
+
+    let answered: Vec<&AccountQuota> = rows.iter().filter_map(|row| row.quota.as_ref()).collect();
+    if answered.len() > 1 {
+        // A total is only shown when every account that answered reports it
+        let total = |field: fn(&AccountQuota) -> Option<f64>| -> Option<f64> {
+            answered.iter().map(|quota| field(quota)).sum()
+        };
+        println!(
+            "{:<width$}  {:>10}  {:>10}  {:>10}",
+            "Total",
+            money(total(|quota| quota.daily_spent_usd), numbers),
+            money(total(|quota| quota.daily_budget_usd), numbers),
+            money(total(|quota| quota.balance_usd), numbers),
+            width = width
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use crate::config::{CacheConfig, NumberFormat};
+use crate::core::cache_gc::{self, CacheFile, Kind};
+use crate::utils::{number, paths};
+
+const KINDS: &[Kind] = &[
+    Kind::Sessions,
+    Kind::Handoff,
+    Kind::Updates,
+    Kind::Api,
+    Kind::Temporary,
+    Kind::Kept,
+];
+
+/// "1.2 MB"
+fn format_bytes(bytes: u64) -> String {
+    let numbers = NumberFormat {
+        precision: Some(1),
+        ..NumberFormat::default()
+    };
+    if bytes < 1024 * 1024 {
+        format!(
+            "{} KB",
+            number::format_cost(bytes as f64 / 1024.0, &numbers)
+        )
+    } else {
+        format!(
+            "{} MB",
+            number::format_cost(bytes as f64 / (1024.0 * 1024.0), &numbers)
+        )
+    }
+}
+
+/// "off" for a limit of 0
+fn limit(value: u64, unit: &str) -> String {
+    if value == 0 {
+        "off".to_string()
+    } else {
+        format!("{} {}", value, unit)
+    }
+}
+
+/// Print the files ccline keeps by kind, with their count and size
+pub fn stats(config: &CacheConfig, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let files = cache_gc::inventory();
+    let of_kind =
+        |kind: Kind| -> Vec<&CacheFile> { files.iter().filter(|file| file.kind == kind).collect() };
+    let last_prune = cache_gc::last_prune()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|since| since.as_secs());
+
+    if json {
+        let kinds: Vec<serde_json::Value> = KINDS
+            .iter()
+            .map(|kind| {
+                let files = of_kind(*kind);
+                serde_json::json!({
+                    "kind": kind,
+                    "files": files.len(),
+                    "bytes": files.iter().map(|file| file.size).sum::<u64>(),
+                })
+            })
+            .collect();
+        let dirs = paths::dirs();
+        let stats = serde_json::json!({
+            "cache_dir": dirs.cache,
+            "state_dir": dirs.state,
+            "kinds": kinds,
+            "max_size_mb": config.max_size_mb,
+            "max_age_days": config.max_age_days,
+            "last_prune": last_prune,
+        });
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    let dirs = paths::dirs();
+    println!("{:<20}{}", "Cache", dirs.cache.display());
+    println!("{:<20}{}", "State", dirs.state.display());
+    println!();
+    println!("{:<20}{:>6}  {:>10}", "", "Files", "Size");
+    for kind in KINDS {
+        let files = of_kind(*kind);
+        println!(
+            "{:<20}{:>6}  {:>10}",
+            kind.label(),
+            files.len(),
+            format_bytes(files.iter().map(|file| file.size).sum())
+        );
+    }
+    let total: u64 = files.iter().map(|file| file.size).sum();
+    println!(
+        "{:<20}{:>6}  {:>10}",
+        "Total",
+        files.len(),
+        format_bytes(total)
+    );
+    println!();
+    println!(
+        "{:<20}{}, pruned after {}",
+        "Limits",
+        limit(config.max_size_mb, "MB"),
+        limit(config.max_age_days, "days")
+    );
+    let last = match cache_gc::last_prune().and_then(|time| time.elapsed().ok()) {
+        Some(elapsed) => format!(
+            "{} ago",
+            crate::core::segments::session::format_duration(elapsed.as_millis() as u64)
+        ),
+        None => "never".to_string(),
+    };
+    println!("{:<20}{}", "Last pruned", last);
+    Ok(())
+}
+
+/// Prune now, whenever the last run was
+pub fn prune(config: &CacheConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let pruned = cache_gc::prune(config);
+    println!(
+        "Removed {} files, {}",
+        pruned.files,
+        format_bytes(pruned.bytes)
+    );
+    Ok(())
+}
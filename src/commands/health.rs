@@ -0,0 +1,194 @@
+use crate::config::{Config, InputData, SegmentConfig};
+use crate::core::mock::MockScenario;
+use crate::core::segments::{lookup_segment, with_registry, SegmentData};
+use crate::core::statusline::try_collect_segment;
+use crate::utils::redact::redact;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+/// Outcome of a check, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Health {
+    Ok,
+    Degraded,
+    Failed,
+}
+
+impl Health {
+    /// 0, 1 or 2
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Health::Ok => 0,
+            Health::Degraded => 1,
+            Health::Failed => 2,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    segment: &'static str,
+    status: Health,
+    detail: String,
+    elapsed_ms: u128,
+    /// The text the segment would show
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    metadata: BTreeMap<String, String>,
+}
+
+/// Health of collected data: missing data and unreachable providers fail,
+/// throttling, stale values, expired tokens and budgets near their limit
+/// degrade
+fn classify(data: Option<&SegmentData>) -> (Health, String) {
+    let Some(data) = data else {
+        return (
+            Health::Failed,
+            "No data: credentials are missing or the source is unavailable".to_string(),
+        );
+    };
+    let meta = |key: &str| data.metadata.get(key).map(String::as_str);
+
+    if meta("status") == Some("rate_limited") {
+        return (
+            Health::Degraded,
+            "The provider is throttling requests (429)".to_string(),
+        );
+    }
+    if meta("token_expired") == Some("true") {
+        return (
+            Health::Degraded,
+            "OAuth token expired; showing the last fetched values".to_string(),
+        );
+    }
+    if meta("stale") == Some("true") {
+        return (
+            Health::Degraded,
+            "Showing a cached value; the source did not answer in time".to_string(),
+        );
+    }
+    if let Some(level) = meta("level") {
+        return (Health::Degraded, format!("At the {} level", level));
+    }
+    (Health::Ok, "Ok".to_string())
+}
+
+/// The configured segments named, or the enabled network segments when no
+/// names are given. Segments missing from the config are checked with their
+/// defaults.
+fn select(
+    config: &Config,
+    names: &[String],
+) -> Result<Vec<SegmentConfig>, Box<dyn std::error::Error>> {
+    let segments = config.resolved_segments();
+    if names.is_empty() {
+        return Ok(segments
+            .into_iter()
+            .filter(|segment| {
+                segment.enabled
+                    && with_registry(|registry| {
+                        registry.info(segment.id).is_some_and(|info| info.network)
+                    })
+            })
+            .collect());
+    }
+
+    let mut selected = Vec::new();
+    for name in names {
+        let id = lookup_segment(name)?.id;
+        let segment = segments
+            .iter()
+            .find(|segment| segment.id == id)
+            .cloned()
+            .unwrap_or_else(|| SegmentConfig::default_for(id));
+        selected.push(segment);
+    }
+    Ok(selected)
+}
+
+/// The last payload from Claude Code, else the default mock scenario's
+fn input() -> Result<InputData, Box<dyn std::error::Error>> {
+    match crate::core::captured_input::load_last() {
+        Some(input) => Ok(input),
+        None => Ok(MockScenario::load("default")?.input),
+    }
+}
+
+/// Collect each segment once, bypassing refresh intervals, print the
+/// results as JSON and return the exit code of the worst
+pub fn run(config: &Config, names: &[String]) -> Result<i32, Box<dyn std::error::Error>> {
+    crate::i18n::set_language(config.language.as_deref());
+    let segments = select(config, names)?;
+    let input = input()?;
+
+    let mut checks: Vec<Check> = Vec::new();
+    // Every request fails with the same error, reported once here
+    #[cfg(feature = "http")]
+    if let Some(error) = crate::utils::http::tls_error() {
+        checks.push(Check {
+            segment: "network",
+            status: Health::Failed,
+            detail: format!("{}; requests are refused until it is fixed", error),
+            elapsed_ms: 0,
+            value: None,
+            metadata: BTreeMap::new(),
+        });
+    }
+    checks.extend(segments.iter().map(|segment| {
+        let started = Instant::now();
+        let result = try_collect_segment(segment, &input);
+        let elapsed_ms = started.elapsed().as_millis();
+        // The error itself says more than a guess from missing data
+        let (status, detail, data) = match result {
+            Ok(data) => {
+                let (status, detail) = classify(data.as_ref());
+                (status, detail, data)
+            }
+            Err(error) => {
+                let message = redact(&error.to_string());
+                let metadata = [
+                    ("error".to_string(), message.clone()),
+                    ("error_kind".to_string(), error.kind().to_string()),
+                ];
+                let data = SegmentData {
+                    primary: String::new(),
+                    secondary: String::new(),
+                    metadata: metadata.into_iter().collect(),
+                };
+                (Health::Failed, message, Some(data))
+            }
+        };
+        let value = data
+            .as_ref()
+            .map(|data| {
+                format!("{} {}", data.primary, data.secondary)
+                    .trim()
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty());
+        Check {
+            segment: segment.id.as_str(),
+            status,
+            detail,
+            elapsed_ms,
+            value,
+            metadata: data
+                .map(|data| data.metadata.into_iter().collect())
+                .unwrap_or_default(),
+        }
+    }));
+
+    let status = checks
+        .iter()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(Health::Ok);
+    let report = serde_json::json!({
+        "status": status,
+        "segments": checks,
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(status.exit_code())
+}
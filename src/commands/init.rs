@@ -0,0 +1,221 @@
+//! `ccline --init`: create config and themes, check that the statusline
+//! command renders, then point the `statusLine` setting in
+//! `~/.claude/settings.json` (`%USERPROFILE%\.claude\settings.json` on
+//! Windows) at it. A command that does not render is not written, so it
+//! cannot replace one that works.
+//!
+//! The command is written with the home directory expanded: Claude Code on
+//! Windows runs it through Git Bash, which leaves `%USERPROFILE%` as it is.
+//! Windows paths use forward slashes, which Git Bash would otherwise read
+//! as escapes, and are quoted when they contain spaces. The command is
+//! tried under Git Bash itself, the way Claude Code will run it.
+
+use crate::config::Config;
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(windows)]
+const BINARY: &str = "ccline.exe";
+#[cfg(not(windows))]
+const BINARY: &str = "ccline";
+
+/// Where Claude Code reads its user settings
+fn settings_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = crate::utils::paths::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join(".claude").join("settings.json"))
+}
+
+/// The binary the npm package installs to `~/.claude/ccline`, else this one
+fn binary_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(home) = crate::utils::paths::home_dir() {
+        let installed = home.join(".claude").join("ccline").join(BINARY);
+        if installed.is_file() {
+            return Ok(installed);
+        }
+    }
+    Ok(std::env::current_exe()?)
+}
+
+/// `path` as a command line for the platform's shells
+fn command_for(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    #[cfg(windows)]
+    let path = path.replace('\\', "/");
+    if path.contains(' ') {
+        format!("\"{}\"", path)
+    } else {
+        path.to_string()
+    }
+}
+
+/// Git Bash as Claude Code finds it: `CLAUDE_CODE_GIT_BASH_PATH`, else next
+/// to the `git` on `PATH`, else the default install. Not `bash` on `PATH`,
+/// which may be WSL's.
+#[cfg(windows)]
+fn git_bash() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("CLAUDE_CODE_GIT_BASH_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    let beside_git = std::env::var_os("PATH").and_then(|path| {
+        std::env::split_paths(&path)
+            .find(|dir| dir.join("git.exe").is_file())
+            // <Git>\cmd\git.exe and <Git>\bin\bash.exe
+            .and_then(|dir| Some(dir.parent()?.join("bin").join("bash.exe")))
+            .filter(|bash| bash.is_file())
+    });
+    beside_git.or_else(|| {
+        Some(PathBuf::from(r"C:\Program Files\Git\bin\bash.exe")).filter(|bash| bash.is_file())
+    })
+}
+
+/// The shell Claude Code runs the command in, with the arguments that run a
+/// command line: Git Bash on Windows, falling back to cmd when it is not
+/// installed, `sh` elsewhere
+fn shell() -> (String, &'static [&'static str]) {
+    #[cfg(windows)]
+    let git_bash = git_bash();
+    #[cfg(not(windows))]
+    let git_bash: Option<PathBuf> = None;
+
+    if let Some(bash) = git_bash {
+        (bash.to_string_lossy().into_owned(), &["-c"])
+    } else if cfg!(windows) {
+        ("cmd".to_string(), &["/C"])
+    } else {
+        ("sh".to_string(), &["-c"])
+    }
+}
+
+/// Run `command` in `shell` with a sample payload on stdin, without the
+/// render keeping the payload; the first line of the error when it fails or
+/// prints nothing
+fn try_shell(shell: &str, args: &[&str], command: &str) -> Result<(), String> {
+    let cwd = std::env::current_dir()
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let payload = json!({
+        "session_id": "ccline-init",
+        "transcript_path": "",
+        "cwd": cwd,
+        "model": { "id": "claude-sonnet-4", "display_name": "Sonnet 4" },
+        "workspace": { "current_dir": cwd, "project_dir": cwd },
+    });
+
+    let mut child = Command::new(shell)
+        .args(args)
+        .arg(command)
+        .env(crate::core::captured_input::NO_CAPTURE_ENV, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("could not start {}: {}", shell, error))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| error.to_string())?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let first_line = stderr.lines().find(|line| !line.trim().is_empty());
+    if !output.status.success() {
+        return Err(first_line
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("exited with {}", output.status)));
+    }
+    if output.stdout.iter().all(u8::is_ascii_whitespace) {
+        return Err("printed nothing".to_string());
+    }
+    Ok(())
+}
+
+/// Set `statusLine` in Claude Code's settings, keeping everything else;
+/// returns the previous command. The first run keeps the file as it was in
+/// a `.backup`, which later runs leave alone.
+fn write_settings(
+    path: &Path,
+    command: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut settings = match std::fs::read_to_string(path) {
+        Ok(content) if !content.trim().is_empty() => {
+            let backup = path.with_extension("json.backup");
+            if !backup.exists() {
+                std::fs::write(&backup, &content)?;
+                println!("Saved the previous settings to {}", backup.display());
+            }
+            serde_json::from_str::<Map<String, Value>>(&content)
+                .map_err(|error| format!("{} is not a JSON object: {}", path.display(), error))?
+        }
+        Ok(_) => Map::new(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Map::new(),
+        Err(error) => return Err(error.into()),
+    };
+
+    let previous = settings
+        .get("statusLine")
+        .and_then(|line| line.get("command"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    settings.insert(
+        "statusLine".to_string(),
+        json!({ "type": "command", "command": command, "padding": 0 }),
+    );
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut content = serde_json::to_string_pretty(&settings)?;
+    content.push('\n');
+    crate::utils::atomic::write(path, content.as_bytes())?;
+    Ok(previous)
+}
+
+/// Try `command` in the shell Claude Code runs it in; it is only written
+/// when it renders there
+fn checked_command(command: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let (shell, args) = shell();
+    match try_shell(&shell, args, command) {
+        Ok(()) => {
+            println!("✓ Renders under {}", shell);
+            Ok(command.to_string())
+        }
+        Err(error) => Err(format!(
+            "The statusline command does not run under {} ({}), so settings.json was left as it is: {}",
+            shell, error, command
+        )
+        .into()),
+    }
+}
+
+/// Create config and themes, check that `command` (the installed binary
+/// when `None`) renders and register it as Claude Code's statusline
+pub fn run(command: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    Config::init()?;
+
+    let command = match command {
+        Some(command) => command.to_string(),
+        None => command_for(&binary_path()?),
+    };
+    let command = checked_command(&command)?;
+    let path = settings_path()?;
+    let previous = write_settings(&path, &command)?;
+
+    println!("Updated {}", path.display());
+    match previous {
+        Some(previous) if previous == command => {}
+        Some(previous) => {
+            println!("  statusLine: {} (was {})", command, previous);
+            if previous.contains('%') {
+                println!(
+                    "  The old command relied on %VARIABLE% expansion, which Git Bash does not do"
+                );
+            }
+        }
+        None => println!("  statusLine: {}", command),
+    }
+    println!("Restart Claude Code to pick up the statusline");
+    Ok(())
+}
@@ -0,0 +1,8 @@
+pub mod balance;
+pub mod cache;
+pub mod health;
+pub mod init;
+pub mod paths;
+pub mod report;
+pub mod segments;
+pub mod theme;
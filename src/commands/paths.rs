@@ -0,0 +1,39 @@
+use crate::utils::paths::{self, Dirs};
+
+fn print_dirs(dirs: &Dirs) {
+    println!("{:<8}{}", "Config", dirs.config.display());
+    println!("{:<8}{}", "Cache", dirs.cache.display());
+    println!("{:<8}{}", "State", dirs.state.display());
+}
+
+/// Print the directories in use and why; with `migrate`, first move the
+/// files of the `~/.claude/ccline` layout to the XDG directories (or
+/// `CCLINE_HOME`)
+pub fn run(migrate: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !migrate {
+        let dirs = paths::dirs();
+        let source = if paths::ccline_home().is_some() {
+            "CCLINE_HOME"
+        } else if dirs.config == paths::legacy_dir() {
+            "~/.claude/ccline (run `ccline paths --migrate` to move to the XDG directories)"
+        } else {
+            "XDG base directories"
+        };
+        println!("{:<8}{}", "Layout", source);
+        print_dirs(dirs);
+        return Ok(());
+    }
+
+    let target = paths::migration_target().ok_or("No home directory to migrate into")?;
+    let moved = paths::migrate_legacy(&target)?;
+    if moved.is_empty() {
+        println!("Nothing to move from {}", paths::legacy_dir().display());
+    } else {
+        for (from, to) in &moved {
+            println!("Moved {} -> {}", from.display(), to.display());
+        }
+    }
+    println!();
+    print_dirs(&target);
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use crate::config::{Config, NumberFormat};
+use crate::core::segments::session::format_duration;
+use crate::core::summary::{self, SessionSummary, TokenCounts};
+use crate::utils::number;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned lines for the terminal
+    #[default]
+    Text,
+    /// Markdown tables, e.g. for a pull request description
+    Md,
+    /// A header and one row, for spreadsheets
+    Csv,
+    /// The full summary as JSON, for scripts
+    Json,
+}
+
+/// "2025-10-16 14:05" in local time (UTC without chrono)
+fn format_timestamp(timestamp: &str) -> String {
+    #[cfg(feature = "chrono")]
+    {
+        if let Ok(time) = chrono::DateTime::parse_from_rfc3339(timestamp) {
+            return time
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string();
+        }
+    }
+    let minutes = timestamp.get(..16).unwrap_or(timestamp);
+    format!("{} UTC", minutes.replace('T', " "))
+}
+
+/// "1 prompt", "3 prompts"
+fn counted(count: u64, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+fn format_tokens(tokens: &TokenCounts) -> String {
+    let numbers = NumberFormat::default();
+    format!(
+        "{} in, {} out, {} cache write, {} cache read",
+        number::format_tokens(tokens.input, &numbers),
+        number::format_tokens(tokens.output, &numbers),
+        number::format_tokens(tokens.cache_creation, &numbers),
+        number::format_tokens(tokens.cache_read, &numbers)
+    )
+}
+
+/// The recorded cost, else "~$0.42 (estimated)" from model prices
+fn format_cost(summary: &SessionSummary) -> String {
+    let numbers = NumberFormat::default();
+    match (summary.cost_usd, summary.estimated_cost_usd) {
+        (Some(cost), _) => format!("${}", number::format_cost(cost, &numbers)),
+        (None, Some(estimate)) => {
+            format!("~${} (estimated)", number::format_cost(estimate, &numbers))
+        }
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+/// Files listed one per line, the first after the label
+fn print_files(label: &str, files: &[String]) {
+    for (i, file) in files.iter().enumerate() {
+        if i == 0 {
+            println!("{:<10}{}", label, file);
+        } else {
+            println!("{:<10}{}", "", file);
+        }
+    }
+}
+
+fn print_text(summary: &SessionSummary) {
+    let numbers = NumberFormat::default();
+    let mut session = summary.session.clone();
+    if let Some(project) = &summary.project {
+        session.push_str(&format!("  {}", project));
+    }
+    if let Some(branch) = &summary.git_branch {
+        session.push_str(&format!(" ({})", branch));
+    }
+    println!("{:<10}{}", "Session", session);
+
+    if let (Some(started), Some(ended)) = (&summary.started_at, &summary.ended_at) {
+        println!(
+            "{:<10}{} - {}",
+            "Time",
+            format_timestamp(started),
+            format_timestamp(ended)
+        );
+    }
+    if let Some(duration) = summary.duration_ms {
+        println!("{:<10}{}", "Duration", format_duration(duration));
+    }
+    println!(
+        "{:<10}{}, {}",
+        "Messages",
+        counted(summary.prompts, "prompt"),
+        counted(summary.responses, "response")
+    );
+    for (i, model) in summary.models.iter().enumerate() {
+        println!(
+            "{:<10}{}: {}, {} tokens",
+            if i == 0 { "Models" } else { "" },
+            model.model,
+            counted(model.responses, "response"),
+            number::format_tokens(model.tokens.total(), &numbers)
+        );
+    }
+    println!("{:<10}{}", "Tokens", format_tokens(&summary.tokens));
+    println!("{:<10}{}", "Cost", format_cost(summary));
+    if !summary.tools.is_empty() {
+        let tools: Vec<String> = summary
+            .tools
+            .iter()
+            .map(|tool| format!("{} {}", tool.name, tool.calls))
+            .collect();
+        println!("{:<10}{}", "Tools", tools.join(", "));
+    }
+    print_files("Edited", &summary.files_edited);
+    print_files("Read", &summary.files_read);
+}
+
+/// Table cell text; a pipe would end the cell
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn print_markdown(summary: &SessionSummary) {
+    let numbers = NumberFormat::default();
+    println!("## Session `{}`", summary.session);
+    println!();
+    println!("| | |");
+    println!("|---|---|");
+    if let Some(project) = &summary.project {
+        println!("| Project | `{}` |", md_cell(project));
+    }
+    if let Some(branch) = &summary.git_branch {
+        println!("| Branch | `{}` |", md_cell(branch));
+    }
+    if let (Some(started), Some(ended)) = (&summary.started_at, &summary.ended_at) {
+        println!(
+            "| Time | {} - {} |",
+            format_timestamp(started),
+            format_timestamp(ended)
+        );
+    }
+    if let Some(duration) = summary.duration_ms {
+        println!("| Duration | {} |", format_duration(duration));
+    }
+    println!(
+        "| Messages | {}, {} |",
+        counted(summary.prompts, "prompt"),
+        counted(summary.responses, "response")
+    );
+    println!("| Tokens | {} |", format_tokens(&summary.tokens));
+    println!("| Cost | {} |", format_cost(summary));
+
+    if !summary.models.is_empty() {
+        println!();
+        println!("| Model | Responses | Input | Output | Cache write | Cache read |");
+        println!("|---|--:|--:|--:|--:|--:|");
+        for model in &summary.models {
+            println!(
+                "| {} | {} | {} | {} | {} | {} |",
+                md_cell(&model.model),
+                model.responses,
+                number::format_tokens(model.tokens.input, &numbers),
+                number::format_tokens(model.tokens.output, &numbers),
+                number::format_tokens(model.tokens.cache_creation, &numbers),
+                number::format_tokens(model.tokens.cache_read, &numbers)
+            );
+        }
+    }
+    if !summary.tools.is_empty() {
+        println!();
+        println!("| Tool | Calls |");
+        println!("|---|--:|");
+        for tool in &summary.tools {
+            println!("| {} | {} |", md_cell(&tool.name), tool.calls);
+        }
+    }
+    for (heading, files) in [
+        ("Files edited", &summary.files_edited),
+        ("Files read", &summary.files_read),
+    ] {
+        if files.is_empty() {
+            continue;
+        }
+        println!();
+        println!("**{}**", heading);
+        println!();
+        for file in files {
+            println!("- `{}`", file);
+        }
+    }
+}
+
+/// A CSV field, quoted when it holds a comma, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+const CSV_HEADER: &[&str] = &[
+    "session",
+    "project",
+    "git_branch",
+    "started_at",
+    "ended_at",
+    "duration_ms",
+    "prompts",
+    "responses",
+    "models",
+    "input_tokens",
+    "output_tokens",
+    "cache_creation_tokens",
+    "cache_read_tokens",
+    "cost_usd",
+    "estimated_cost_usd",
+    "tools",
+    "files_edited",
+    "files_read",
+];
+
+/// A header and one row; lists are joined with ";" and unknown values left
+/// empty
+fn print_csv(summary: &SessionSummary) {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    let row = [
+        summary.session.clone(),
+        optional(summary.project.clone()),
+        optional(summary.git_branch.clone()),
+        optional(summary.started_at.clone()),
+        optional(summary.ended_at.clone()),
+        optional(summary.duration_ms.map(|ms| ms.to_string())),
+        summary.prompts.to_string(),
+        summary.responses.to_string(),
+        summary
+            .models
+            .iter()
+            .map(|model| format!("{}:{}", model.model, model.responses))
+            .collect::<Vec<_>>()
+            .join(";"),
+        summary.tokens.input.to_string(),
+        summary.tokens.output.to_string(),
+        summary.tokens.cache_creation.to_string(),
+        summary.tokens.cache_read.to_string(),
+        optional(summary.cost_usd.map(|cost| format!("{:.4}", cost))),
+        optional(
+            summary
+                .estimated_cost_usd
+                .map(|cost| format!("{:.4}", cost)),
+        ),
+        summary
+            .tools
+            .iter()
+            .map(|tool| format!("{}:{}", tool.name, tool.calls))
+            .collect::<Vec<_>>()
+            .join(";"),
+        summary.files_edited.join(";"),
+        summary.files_read.join(";"),
+    ];
+    println!("{}", CSV_HEADER.join(","));
+    println!(
+        "{}",
+        row.iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+}
+
+/// Print a summary of the session given as a transcript path, `latest`, or a
+/// session id
+pub fn run(
+    config: &Config,
+    session: &str,
+    format: ReportFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = summary::find_transcript(session)?;
+    let summary = summary::summarize(&path, &config.pricing)?;
+    match format {
+        ReportFormat::Text => print_text(&summary),
+        ReportFormat::Md => print_markdown(&summary),
+        ReportFormat::Csv => print_csv(&summary),
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+    }
+    Ok(())
+}
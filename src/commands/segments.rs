@@ -0,0 +1,85 @@
+use crate::config::Config;
+use crate::core::segments::{with_registry, SegmentInfo};
+
+/// Config state of a segment: Some(enabled) when present in config, None otherwise
+fn configured_state(config: &Config, info: &SegmentInfo) -> Option<bool> {
+    config
+        .segments
+        .iter()
+        .find(|segment| segment.id == info.id)
+        .map(|segment| segment.enabled)
+}
+
+/// Print every known segment with its status, options and data sources
+pub fn list(config: &Config, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let infos: Vec<SegmentInfo> = with_registry(|registry| registry.infos().cloned().collect());
+
+    if json {
+        let entries: Vec<serde_json::Value> = infos
+            .iter()
+            .map(|info| {
+                serde_json::json!({
+                    "id": info.id,
+                    "name": info.name,
+                    "description": info.description,
+                    "enabled": configured_state(config, info),
+                    "available": info.is_available(),
+                    "features": info.features,
+                    "options": info.options,
+                    "data_sources": info.data_sources,
+                    "icons": info.icons,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    for (i, info) in infos.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let status = match configured_state(config, info) {
+            Some(true) => "enabled",
+            Some(false) => "disabled",
+            None => "not configured",
+        };
+        let availability = if info.is_available() {
+            String::new()
+        } else {
+            format!(" (not compiled in: requires {})", info.features.join(", "))
+        };
+
+        println!(
+            "{} ({}) - {}{}",
+            info.id.as_str(),
+            info.name,
+            status,
+            availability
+        );
+        println!("  {}", info.description);
+
+        if info.features.is_empty() {
+            println!("  features: none");
+        } else {
+            println!("  features: {}", info.features.join(", "));
+        }
+
+        if info.options.is_empty() {
+            println!("  options:  none");
+        } else {
+            println!("  options:");
+            for option in info.options {
+                println!(
+                    "    {}: {} = {}  {}",
+                    option.name, option.kind, option.default, option.description
+                );
+            }
+        }
+
+        println!("  sources:  {}", info.data_sources.join(", "));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use crate::config::{AnsiColor, Config, SegmentConfig, SegmentId, StyleMode, ThemeBackground};
+use crate::core::mock::MockScenario;
+use crate::core::segments::{lookup_segment, with_registry};
+use crate::core::StatusLineGenerator;
+use crate::ui::themes::ThemePresets;
+use crate::utils::color::{contrast_ratio, to_rgb};
+use std::io::{self, BufRead, Write};
+
+/// Scenario used as the sample payload for theme previews
+const PREVIEW_SCENARIO: &str = "default";
+
+/// Background assumed for segments without their own background color
+const DARK_TERMINAL: (u8, u8, u8) = (30, 30, 30);
+const LIGHT_TERMINAL: (u8, u8, u8) = (255, 255, 255);
+
+/// WCAG AA minimum contrast for text, and for non-text graphics such as icons
+const MIN_TEXT_CONTRAST: f64 = 4.5;
+const MIN_ICON_CONTRAST: f64 = 3.0;
+
+/// The named theme, or every built-in and user theme when no name is given
+fn select_themes(name: Option<&str>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let available = ThemePresets::list_available_themes();
+    let Some(name) = name else {
+        return Ok(available);
+    };
+
+    if !available.iter().any(|theme| theme == name) {
+        return Err(format!(
+            "Unknown theme '{}'. Available themes: {}",
+            name,
+            available.join(", ")
+        )
+        .into());
+    }
+    Ok(vec![name.to_string()])
+}
+
+/// Render one statusline per theme against a sample payload. `mode` overrides
+/// each theme's icon style when set (`--plain` / `--nerd-font`).
+pub fn preview(
+    name: Option<&str>,
+    mode: Option<StyleMode>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let themes = select_themes(name)?;
+    let scenario = MockScenario::load(PREVIEW_SCENARIO)?;
+    let width = themes.iter().map(|theme| theme.len()).max().unwrap_or(0);
+
+    for theme in &themes {
+        let mut config = ThemePresets::get_theme(theme);
+        if let Some(mode) = mode {
+            // Powerline themes already use Nerd Font glyphs for their arrows
+            if mode == StyleMode::Plain || config.style.mode == StyleMode::Plain {
+                config.style.mode = mode;
+            }
+            // An explicit --nerd-font skips font detection
+            if mode == StyleMode::NerdFont {
+                config.style.nerd_font = Some(true);
+            }
+        }
+
+        let segments = scenario.collect(&config);
+        let line = StatusLineGenerator::new(config).generate(segments);
+        println!("{:<width$}  {}", theme, line, width = width);
+    }
+
+    Ok(())
+}
+
+/// Contrast of a foreground color against the segment (or terminal) background,
+/// or None when the terminal's default foreground is used
+fn contrast(color: Option<&AnsiColor>, background: (u8, u8, u8)) -> Option<f64> {
+    color.map(|color| contrast_ratio(to_rgb(color), background))
+}
+
+fn format_contrast(ratio: Option<f64>, minimum: f64) -> String {
+    match ratio {
+        Some(ratio) if ratio >= 7.0 => format!("{:>5.2}:1 AAA ", ratio),
+        Some(ratio) if ratio >= minimum => format!("{:>5.2}:1 AA  ", ratio),
+        Some(ratio) => format!("{:>5.2}:1 FAIL", ratio),
+        None => format!("{:>7} -   ", "default"),
+    }
+}
+
+/// Print text and icon contrast ratios per segment and flag WCAG AA failures
+pub fn check(name: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let themes = select_themes(name)?;
+    let mut failures = 0;
+
+    for (i, theme) in themes.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let config = ThemePresets::get_theme(theme);
+        let terminal = match config.style.background {
+            ThemeBackground::Dark => DARK_TERMINAL,
+            ThemeBackground::Light => LIGHT_TERMINAL,
+        };
+        println!("{}", theme);
+
+        for segment in &config.segments {
+            let background = segment.colors.background.as_ref().map(to_rgb);
+            let background = background.unwrap_or(terminal);
+            let text = contrast(segment.colors.text.as_ref(), background);
+            let icon = contrast(segment.colors.icon.as_ref(), background);
+
+            if text.is_some_and(|ratio| ratio < MIN_TEXT_CONTRAST)
+                || icon.is_some_and(|ratio| ratio < MIN_ICON_CONTRAST)
+            {
+                failures += 1;
+            }
+
+            let line = format!(
+                "  {:<14} text {}  icon {}",
+                segment.id.as_str(),
+                format_contrast(text, MIN_TEXT_CONTRAST),
+                format_contrast(icon, MIN_ICON_CONTRAST)
+            );
+            println!("{}", line.trim_end());
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!(
+            "{} segment(s) below WCAG AA contrast (text {}:1, icons {}:1)",
+            failures, MIN_TEXT_CONTRAST, MIN_ICON_CONTRAST
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Palettes offered by `theme create`: name, flat theme and Powerline theme
+const PALETTES: &[(&str, &str, &str)] = &[
+    ("default", "default", "powerline-dark"),
+    ("gruvbox", "gruvbox", "powerline-gruvbox"),
+    ("catppuccin", "catppuccin", "powerline-catppuccin"),
+    ("dracula", "dracula", "powerline-dracula"),
+    ("tokyo-night", "tokyo-night", "powerline-tokyo-night"),
+    ("light", "solarized-light", "powerline-light"),
+];
+
+/// Print `question` and read one line of answer; an empty answer is
+/// `default`
+fn ask(question: &str, default: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut stdout = io::stdout();
+    if default.is_empty() {
+        write!(stdout, "{}: ", question)?;
+    } else {
+        write!(stdout, "{} [{}]: ", question, default)?;
+    }
+    stdout.flush()?;
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer)? == 0 {
+        return Err("input ended before the wizard finished".into());
+    }
+    let answer = answer.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+/// Ask a yes/no question until the answer is one
+fn ask_yes_no(question: &str, default: bool) -> Result<bool, Box<dyn std::error::Error>> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    loop {
+        match ask(&format!("{} ({})", question, hint), "")?
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("Answer y or n"),
+        }
+    }
+}
+
+/// Ask for one of `choices` by number or name until the answer is one
+fn ask_choice(question: &str, choices: &[&str]) -> Result<usize, Box<dyn std::error::Error>> {
+    println!("{}", question);
+    for (i, choice) in choices.iter().enumerate() {
+        println!("  {}) {}", i + 1, choice);
+    }
+    loop {
+        let answer = ask("Choice", "1")?;
+        let index = match answer.parse::<usize>() {
+            Ok(number) => number.checked_sub(1),
+            Err(_) => choices.iter().position(|choice| *choice == answer),
+        };
+        match index.filter(|i| *i < choices.len()) {
+            Some(index) => return Ok(index),
+            None => println!("Enter a number from 1 to {}", choices.len()),
+        }
+    }
+}
+
+/// Ask which segments to show, in order, by number or config key
+fn ask_segments(base: &Config) -> Result<Vec<SegmentConfig>, Box<dyn std::error::Error>> {
+    // The base theme's segments first, then the other segments of this build.
+    // Computed segments need an expression, which is config.toml material.
+    let mut offered: Vec<SegmentId> = base.segments.iter().map(|s| s.id).collect();
+    with_registry(|registry| {
+        for info in registry.infos() {
+            if info.is_available() && info.id != SegmentId::Computed && !offered.contains(&info.id)
+            {
+                offered.push(info.id);
+            }
+        }
+    });
+
+    println!("Segments:");
+    for (i, id) in offered.iter().enumerate() {
+        println!("  {:>2}) {}", i + 1, id.as_str());
+    }
+    let defaults: Vec<String> = base
+        .segments
+        .iter()
+        .filter(|segment| segment.enabled)
+        .filter_map(|segment| offered.iter().position(|id| *id == segment.id))
+        .map(|i| (i + 1).to_string())
+        .collect();
+
+    'ask: loop {
+        let answer = ask("Segments to show, in order", &defaults.join(","))?;
+        let mut chosen = Vec::new();
+        for name in answer.split([',', ' ']).filter(|name| !name.is_empty()) {
+            let id = match name.parse::<usize>() {
+                Ok(number) => number.checked_sub(1).and_then(|i| offered.get(i)).copied(),
+                Err(_) => lookup_segment(name).ok().map(|info| info.id),
+            };
+            match id {
+                Some(id) if !chosen.contains(&id) => chosen.push(id),
+                Some(_) => {}
+                None => {
+                    println!("Unknown segment '{}'", name);
+                    continue 'ask;
+                }
+            }
+        }
+        if chosen.is_empty() {
+            println!("Choose at least one segment");
+            continue;
+        }
+
+        return Ok(chosen
+            .into_iter()
+            .map(|id| {
+                let segment = base
+                    .segments
+                    .iter()
+                    .find(|segment| segment.id == id)
+                    .cloned()
+                    .unwrap_or_else(|| SegmentConfig::default_for(id));
+                SegmentConfig {
+                    enabled: true,
+                    ..segment
+                }
+            })
+            .collect());
+    }
+}
+
+/// Ask a few questions over plain stdin (palette, Powerline or flat, Nerd
+/// Font, segments), save the answers as a user theme and optionally switch
+/// config.toml to it
+pub fn create(name: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let builtin: Vec<&str> = ThemePresets::get_available_themes()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    let name = loop {
+        let candidate = match name {
+            Some(name) => name.to_string(),
+            None => ask("Theme name", "my-theme")?,
+        };
+        let valid = !candidate.is_empty()
+            && candidate
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        let problem = if !valid {
+            Some("Use letters, digits, '-' and '_' only".to_string())
+        } else if builtin.contains(&candidate.as_str()) {
+            Some(format!("'{}' is a built-in theme", candidate))
+        } else if ThemePresets::list_available_themes().contains(&candidate)
+            && !ask_yes_no(&format!("Replace your theme '{}'?", candidate), false)?
+        {
+            Some(format!("Theme '{}' already exists", candidate))
+        } else {
+            None
+        };
+        match problem {
+            None => break candidate,
+            // A name from the command line is not asked again
+            Some(problem) if name.is_some() => return Err(problem.into()),
+            Some(problem) => println!("{}", problem),
+        }
+    };
+
+    let names: Vec<&str> = PALETTES.iter().map(|(name, _, _)| *name).collect();
+    let (_, flat, powerline) = PALETTES[ask_choice("Base palette:", &names)?];
+
+    let nerd_font = ask_yes_no("Does your terminal font have Nerd Font glyphs?", false)?;
+    // Powerline arrows are Nerd Font glyphs
+    let use_powerline = nerd_font && ask_yes_no("Use Powerline arrows between segments?", false)?;
+
+    let mut theme = ThemePresets::get_theme(if use_powerline { powerline } else { flat });
+    theme.style.nerd_font = Some(nerd_font);
+    if !nerd_font {
+        theme.style.mode = StyleMode::Plain;
+    }
+    theme.segments = ask_segments(&theme)?;
+    theme.theme = name.as_str().into();
+
+    ThemePresets::save_theme(&name, &theme)?;
+    println!(
+        "Saved {}",
+        crate::config::ConfigLoader::get_themes_path()
+            .join(format!("{}.toml", name))
+            .display()
+    );
+
+    let scenario = MockScenario::load(PREVIEW_SCENARIO)?;
+    let segments = scenario.collect(&theme);
+    println!(
+        "{}",
+        StatusLineGenerator::new(theme.clone()).generate(segments)
+    );
+
+    if ask_yes_no("Use it in config.toml?", true)? {
+        let config = Config::load().unwrap_or_else(|_| Config::default());
+        config.with_theme(theme).save()?;
+        println!("Updated {}", Config::get_config_path().display());
+    }
+
+    Ok(())
+}
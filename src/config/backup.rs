@@ -0,0 +1,83 @@
+//! Copies of config.toml taken before it is overwritten, kept in
+//! `~/.claude/ccline/backups/` as `config-20251016T140512Z.toml`. Only the
+//! newest [`KEEP`] are kept.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Backups kept before the oldest is removed
+pub const KEEP: usize = 10;
+
+fn backups_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups")
+}
+
+/// "20251016T140512Z" for a time in Unix seconds
+fn utc_stamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let secs_of_day = secs % 86_400;
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+/// Copy the config at `config_path` into the backups directory, unless it
+/// is missing or already holds `new_content`, and remove the oldest backups
+/// beyond [`KEEP`]
+pub fn backup(config_path: &Path, new_content: &str) -> std::io::Result<()> {
+    let Ok(current) = fs::read_to_string(config_path) else {
+        return Ok(());
+    };
+    if current == new_content {
+        return Ok(());
+    }
+
+    let dir = backups_dir(config_path);
+    fs::create_dir_all(&dir)?;
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(format!("config-{}.toml", utc_stamp(secs)));
+    // Several saves within a second keep the state from before the first
+    if !path.exists() {
+        crate::utils::atomic::write(&path, current.as_bytes())?;
+    }
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("config-") && name.ends_with(".toml"))
+        })
+        .collect();
+    // The stamp sorts by time
+    backups.sort();
+    let excess = backups.len().saturating_sub(KEEP);
+    for old in &backups[..excess] {
+        let _ = fs::remove_file(old);
+    }
+    Ok(())
+}
@@ -0,0 +1,226 @@
+//! `include = ["colors.toml", "~/work.toml"]` in a config file: the listed
+//! files are merged in order, later ones overriding earlier ones, and the
+//! including file is merged last. Tables merge key by key; `[[segments]]`
+//! entries merge by `id` (computed segments by their `name` option, else by
+//! their position among unnamed computed segments), with segments new to the
+//! list appended. A file that lists every segment also sets their order.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// How deep includes may nest before a file is assumed to include itself
+const MAX_DEPTH: usize = 8;
+
+/// The `include` list of a parsed file
+pub fn includes_of(table: &Table) -> Vec<String> {
+    table
+        .get("include")
+        .and_then(Value::as_array)
+        .map(|paths| {
+            paths
+                .iter()
+                .filter_map(|path| path.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `~/…` from the home directory, relative paths from the including file's
+/// directory
+fn expand(path: &str, dir: &Path) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        if let Some(home) = crate::utils::paths::home_dir() {
+            return home.join(rest);
+        }
+    }
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        dir.join(path)
+    }
+}
+
+/// The merged contents of the files `file` includes, without `file` itself
+pub fn merge_includes(
+    file: &Path,
+    includes: &[String],
+) -> Result<Table, Box<dyn std::error::Error>> {
+    let mut visited = HashSet::new();
+    visited.insert(file.canonicalize().unwrap_or_else(|_| file.to_path_buf()));
+    let dir = file.parent().unwrap_or(Path::new("."));
+    merge_includes_from(includes, dir, &mut visited, 0)
+}
+
+fn merge_includes_from(
+    includes: &[String],
+    dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    depth: usize,
+) -> Result<Table, Box<dyn std::error::Error>> {
+    let mut merged = Table::new();
+    for include in includes {
+        let path = expand(include, dir);
+        merge(&mut merged, load(&path, visited, depth + 1)?);
+    }
+    Ok(merged)
+}
+
+/// A file with its own includes merged beneath it
+fn load(
+    path: &Path,
+    visited: &mut HashSet<PathBuf>,
+    depth: usize,
+) -> Result<Table, Box<dyn std::error::Error>> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if depth > MAX_DEPTH || !visited.insert(canonical.clone()) {
+        return Err(format!("{} is included recursively", path.display()).into());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read included file {}: {}", path.display(), e))?;
+    let mut table: Table = toml::from_str(&content)
+        .map_err(|e| format!("invalid included file {}: {}", path.display(), e))?;
+    let includes = includes_of(&table);
+    table.remove("include");
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut merged = merge_includes_from(&includes, dir, visited, depth)?;
+    merge(&mut merged, table);
+    visited.remove(&canonical);
+    Ok(merged)
+}
+
+/// Merge `over` into `base`
+pub fn merge(base: &mut Table, over: Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Array(base_segments)), Value::Array(segments)) if key == "segments" => {
+                merge_segments(base_segments, segments)
+            }
+            (Some(Value::Table(base_table)), Value::Table(table)) => merge(base_table, table),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// What identifies each entry of a segment list across files: its `id`,
+/// plus for computed segments, of which a list may hold several, the `name`
+/// option or else the entry's position among unnamed ones
+fn segment_keys(segments: &[Value]) -> Vec<Option<(String, String)>> {
+    let mut unnamed = 0;
+    segments
+        .iter()
+        .map(|segment| {
+            let id = segment.get("id").and_then(Value::as_str)?;
+            if id != "computed" {
+                return Some((id.to_string(), String::new()));
+            }
+            let name = segment
+                .get("options")
+                .and_then(|options| options.get("name"))
+                .and_then(Value::as_str);
+            let discriminator = match name {
+                Some(name) => format!("name:{}", name),
+                None => {
+                    unnamed += 1;
+                    format!("#{}", unnamed)
+                }
+            };
+            Some((id.to_string(), discriminator))
+        })
+        .collect()
+}
+
+fn merge_segments(base: &mut Vec<Value>, over: Vec<Value>) {
+    let mut base_keys = segment_keys(base);
+    let over_keys = segment_keys(&over);
+    let lists_every_segment = base_keys
+        .iter()
+        .all(|key| key.is_some() && over_keys.contains(key));
+
+    // Indexes into `base` in the order `over` lists them
+    let mut order = Vec::with_capacity(over.len());
+    for (entry, key) in over.into_iter().zip(over_keys) {
+        let position = key
+            .as_ref()
+            .and_then(|key| base_keys.iter().position(|k| k.as_ref() == Some(key)));
+        match (position, entry) {
+            (Some(index), Value::Table(entry)) => {
+                if let Value::Table(segment) = &mut base[index] {
+                    merge(segment, entry);
+                }
+                order.push(index);
+            }
+            (_, entry) => {
+                base.push(entry);
+                base_keys.push(key);
+                order.push(base.len() - 1);
+            }
+        }
+    }
+
+    if lists_every_segment {
+        let mut slots: Vec<Option<Value>> = base.drain(..).map(Some).collect();
+        let mut ordered: Vec<Value> = order.iter().filter_map(|&i| slots[i].take()).collect();
+        ordered.extend(slots.into_iter().flatten());
+        *base = ordered;
+    }
+}
+
+/// Remove from `table` what `base` already sets, so that merging the result
+/// over `base` gives `table` again. Used to save a config with includes
+/// without copying the included settings into it.
+pub fn strip_base(table: &mut Table, base: &Table) {
+    table.retain(|key, value| match (base.get(key), value) {
+        (Some(Value::Array(base_segments)), Value::Array(segments)) if key == "segments" => {
+            strip_segments(segments, base_segments);
+            true
+        }
+        (Some(Value::Table(base_table)), Value::Table(table)) => {
+            strip_base(table, base_table);
+            !table.is_empty()
+        }
+        (Some(base_value), value) => base_value != value,
+        (None, _) => true,
+    });
+}
+
+/// Segment entries are reduced to `id`, a `name` option, and what differs.
+/// All of them are kept, so the saved list also keeps the segment order.
+fn strip_segments(segments: &mut [Value], base: &[Value]) {
+    let keys = segment_keys(segments);
+    let base_keys = segment_keys(base);
+    for (segment, key) in segments.iter_mut().zip(keys) {
+        let Some(base_segment) = key
+            .and_then(|key| base_keys.iter().position(|k| k.as_ref() == Some(&key)))
+            .and_then(|index| base[index].as_table())
+        else {
+            continue;
+        };
+        if let Value::Table(table) = segment {
+            // The name of a computed segment is part of what identifies it
+            let id = table.remove("id");
+            let name = table
+                .get("options")
+                .and_then(|options| options.get("name"))
+                .cloned();
+            strip_base(table, base_segment);
+            if let Some(id) = id {
+                table.insert("id".to_string(), id);
+            }
+            if let Some(name) = name {
+                if let Value::Table(options) = table
+                    .entry("options")
+                    .or_insert_with(|| Value::Table(Table::new()))
+                {
+                    options.insert("name".to_string(), name);
+                }
+            }
+        }
+    }
+}
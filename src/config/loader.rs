@@ -1,4 +1,4 @@
-use super::types::Config;
+use super::types::{Config, SegmentId};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,9 +10,7 @@ impl ConfigLoader {
     }
 
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Config, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        let config: Config = toml::from_str(&content)?;
-        Ok(config)
+        super::migrate::load_file(path.as_ref())
     }
 
     /// Initialize themes directory and create built-in theme files
@@ -22,17 +20,10 @@ impl ConfigLoader {
         // Create themes directory
         fs::create_dir_all(&themes_dir)?;
 
-        let builtin_themes = [
-            "cometix",
-            "default",
-            "minimal",
-            "gruvbox",
-            "nord",
-            "powerline-dark",
-            "powerline-light",
-            "powerline-rose-pine",
-            "powerline-tokyo-night",
-        ];
+        let builtin_themes: Vec<&str> = crate::ui::themes::ThemePresets::get_available_themes()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
         let mut created_any = false;
 
         for theme_name in &builtin_themes {
@@ -54,13 +45,9 @@ impl ConfigLoader {
         Ok(())
     }
 
-    /// Get the themes directory path (~/.claude/ccline/themes/)
+    /// Get the themes directory path (themes/ in the config directory)
     pub fn get_themes_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("themes")
-        } else {
-            PathBuf::from(".claude/ccline/themes")
-        }
+        crate::utils::paths::config_dir().join("themes")
     }
 
     /// Ensure themes directory exists and has built-in themes (silent mode)
@@ -76,17 +63,10 @@ impl ConfigLoader {
         // Create themes directory
         fs::create_dir_all(&themes_dir)?;
 
-        let builtin_themes = [
-            "default",
-            "minimal",
-            "gruvbox",
-            "nord",
-            "cometix",
-            "powerline-dark",
-            "powerline-light",
-            "powerline-rose-pine",
-            "powerline-tokyo-night",
-        ];
+        let builtin_themes: Vec<&str> = crate::ui::themes::ThemePresets::get_available_themes()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
 
         for theme_name in &builtin_themes {
             let theme_path = themes_dir.join(format!("{}.toml", theme_name));
@@ -114,12 +94,29 @@ impl Config {
             return Ok(Config::default());
         }
 
-        let content = fs::read_to_string(config_path)?;
-        let config: Config = toml::from_str(&content)?;
+        let mut config = super::migrate::load_file(&config_path)?;
+        config.resolve_adaptive_theme();
         Ok(config)
     }
 
-    /// Save configuration to default location
+    /// For `theme = { dark = "...", light = "..." }`, replace style and segments
+    /// with the theme matching the detected terminal background
+    pub fn resolve_adaptive_theme(&mut self) {
+        if !self.theme.is_adaptive() {
+            return;
+        }
+
+        let background = crate::utils::background::detect();
+        let theme =
+            crate::ui::themes::ThemePresets::get_theme(self.theme.for_background(background));
+        self.style = theme.style;
+        self.segments = theme.segments;
+        // The detected background wins over the one the theme was designed for
+        self.style.background = background;
+    }
+
+    /// Save configuration to default location, keeping a backup of the
+    /// previous file and replacing it atomically
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let config_path = Self::get_config_path();
 
@@ -128,18 +125,24 @@ impl Config {
             fs::create_dir_all(parent)?;
         }
 
-        let content = toml::to_string_pretty(self)?;
-        fs::write(config_path, content)?;
+        // With includes, only what differs from the included files is written
+        let content = if self.include.is_empty() {
+            toml::to_string_pretty(self)?
+        } else {
+            let base = super::include::merge_includes(&config_path, &self.include)?;
+            let mut table = toml::Table::try_from(self)?;
+            super::include::strip_base(&mut table, &base);
+            toml::to_string_pretty(&table)?
+        };
+        super::backup::backup(&config_path, &content)?;
+        crate::utils::atomic::write(&config_path, content.as_bytes())?;
         Ok(())
     }
 
-    /// Get the default config file path (~/.claude/ccline/config.toml)
-    fn get_config_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("config.toml")
-        } else {
-            PathBuf::from(".claude/ccline/config.toml")
-        }
+    /// Get the default config file path (config.toml in the config
+    /// directory, see [`crate::utils::paths`])
+    pub fn get_config_path() -> PathBuf {
+        crate::utils::paths::config_dir().join("config.toml")
     }
 
     /// Initialize config directory and create default config
@@ -173,12 +176,79 @@ impl Config {
             return Err("No segments configured".into());
         }
 
-        // Validate segment IDs are unique
+        // Validate segment IDs are unique; computed segments may repeat, with
+        // names of their own
         let mut seen_ids = std::collections::HashSet::new();
+        let mut computed_names = std::collections::HashSet::new();
         for segment in &self.segments {
-            if !seen_ids.insert(segment.id) {
+            if segment.id == SegmentId::Computed {
+                let name = crate::core::segments::ComputedSegment::name(segment);
+                if !computed_names.insert(name) {
+                    return Err(if name.is_empty() {
+                        "Several computed segments: each needs options.name".to_string()
+                    } else {
+                        format!("Duplicate computed segment name: {}", name)
+                    }
+                    .into());
+                }
+                if let Some(other) = self
+                    .segments
+                    .iter()
+                    .find(|s| s.id != SegmentId::Computed && s.id.as_str() == name)
+                {
+                    return Err(format!(
+                        "computed segment name \"{}\" is taken by the {} segment",
+                        name,
+                        other.id.as_str()
+                    )
+                    .into());
+                }
+            } else if !seen_ids.insert(segment.id) {
                 return Err(format!("Duplicate segment ID: {:?}", segment.id).into());
             }
+            let registered = crate::core::segments::with_registry(|registry| {
+                registry.info(segment.id).is_some()
+            });
+            if !registered {
+                return Err(format!("Unknown segment ID: {}", segment.id.as_str()).into());
+            }
+            if segment.id == SegmentId::Computed {
+                let expression = segment
+                    .options
+                    .get("expression")
+                    .and_then(|v| v.as_str())
+                    .ok_or("computed segment: options.expression is missing")?;
+                crate::utils::expression::Expression::parse(expression).map_err(|e| {
+                    format!("computed segment: expression \"{}\": {}", expression, e)
+                })?;
+            }
+            for transform in &segment.transforms {
+                transform
+                    .validate()
+                    .map_err(|e| format!("{} segment: {}", segment.id.as_str(), e))?;
+            }
+        }
+
+        for segment in &self.segments {
+            if let Some(policy) = segment.options.get("on_error") {
+                if !matches!(policy.as_str(), Some("hide" | "dash" | "icon")) {
+                    return Err(format!(
+                        "Invalid on_error for segment {:?}: expected \"hide\", \"dash\" or \"icon\"",
+                        segment.id
+                    )
+                    .into());
+                }
+            }
+        }
+
+        self.report.validate()?;
+        self.network.validate()?;
+        self.focus.validate()?;
+        for entry in &self.theme_overrides {
+            entry.validate()?;
+        }
+        for entry in &self.platform_defaults {
+            entry.validate()?;
         }
 
         Ok(())
@@ -0,0 +1,247 @@
+//! Schema versions of `config.toml` and the migrations between them.
+//!
+//! A config file records the schema it was written for in `config_version`;
+//! files from before versioning count as version 0. On load, every migration
+//! newer than the file runs on the raw TOML, so a file from an older release
+//! keeps its settings instead of failing to parse and falling back to the
+//! defaults. When a migration changes anything, the old file is kept as
+//! `config.v<N>.toml.bak` and a summary is printed and appended to
+//! `migrations.log`.
+
+use super::include;
+use super::types::{Config, SegmentConfig, SegmentId};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Schema version written by this release
+pub const CONFIG_VERSION: u32 = 1;
+
+/// A migration from version `from` to `from + 1`: rewrites the table in place
+/// and returns one line per change
+struct Migration {
+    from: u32,
+    apply: fn(&mut Table) -> Vec<String>,
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    apply: v0_to_v1,
+}];
+
+/// Result of migrating a file
+pub struct Migrated {
+    pub config: Config,
+    /// Version the file was written for
+    pub from: u32,
+    pub changes: Vec<String>,
+}
+
+/// Parse `content`, migrating it to [`CONFIG_VERSION`] first
+pub fn parse(content: &str) -> Result<Migrated, Box<dyn std::error::Error>> {
+    migrate(toml::from_str(content)?)
+}
+
+fn migrate(mut table: Table) -> Result<Migrated, Box<dyn std::error::Error>> {
+    let from = table
+        .get("config_version")
+        .and_then(Value::as_integer)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(0);
+    if from > CONFIG_VERSION {
+        return Err(format!(
+            "config_version {} is newer than this ccline supports ({}); please update ccline",
+            from, CONFIG_VERSION
+        )
+        .into());
+    }
+
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= from) {
+        changes.extend((migration.apply)(&mut table));
+    }
+    table.insert(
+        "config_version".to_string(),
+        Value::Integer(CONFIG_VERSION.into()),
+    );
+
+    let config = Value::Table(table).try_into()?;
+    Ok(Migrated {
+        config,
+        from,
+        changes,
+    })
+}
+
+/// Load the config file at `path` with its includes, migrating it. When the
+/// migration changed anything, the original is backed up, the migrated config
+/// written in its place and a summary reported. Configs with includes are
+/// migrated in memory only, since the merged result belongs to several files.
+pub fn load_file(path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let own: Table = toml::from_str(&content)?;
+    let includes = include::includes_of(&own);
+    if !includes.is_empty() {
+        let mut merged = include::merge_includes(path, &includes)?;
+        include::merge(&mut merged, own);
+        return Ok(migrate(merged)?.config);
+    }
+
+    let migrated = migrate(own)?;
+    if migrated.changes.is_empty() {
+        return Ok(migrated.config);
+    }
+
+    let backup = backup_path(path, migrated.from);
+    fs::write(&backup, &content)?;
+    crate::utils::atomic::write(path, toml::to_string_pretty(&migrated.config)?.as_bytes())?;
+
+    let mut summary = format!(
+        "ccline: migrated {} from config version {} to {} (backup: {})\n",
+        path.display(),
+        migrated.from,
+        CONFIG_VERSION,
+        backup.display()
+    );
+    for change in &migrated.changes {
+        summary.push_str(&format!("  - {}\n", change));
+    }
+    eprint!("{}", summary);
+    let dir = crate::utils::paths::state_dir();
+    if fs::create_dir_all(&dir).is_ok() {
+        if let Ok(mut log) = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("migrations.log"))
+        {
+            let _ = log.write_all(summary.as_bytes());
+        }
+    }
+    Ok(migrated.config)
+}
+
+/// `config.toml` is backed up as `config.v0.toml.bak`, or `config.v0.2.toml.bak`
+/// and so on when earlier backups exist
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("config");
+    let mut backup = path.with_file_name(format!("{}.v{}.toml.bak", stem, version));
+    let mut n = 2;
+    while backup.exists() {
+        backup = path.with_file_name(format!("{}.v{}.{}.toml.bak", stem, version, n));
+        n += 1;
+    }
+    backup
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Option<Value> {
+    Value::try_from(value).ok()
+}
+
+/// Version 0 covers every config written before versioning:
+/// - `[segments]` as a table of booleans (`directory = true`) becomes the
+///   list of segment entries, with those segments switched on or off
+/// - a missing `theme` or `[style]` is filled in from the default theme
+/// - `[[segments]]` entries missing `enabled`, `icon`, `colors`, `styles` or
+///   `options` get the segment's defaults instead of failing the whole file
+fn v0_to_v1(table: &mut Table) -> Vec<String> {
+    let defaults = Config::default();
+    let mut changes = Vec::new();
+
+    if !table.contains_key("theme") {
+        table.insert("theme".to_string(), Value::String("default".to_string()));
+        changes.push("added theme = \"default\"".to_string());
+    }
+    if !table.contains_key("style") {
+        if let Some(style) = to_value(&defaults.style) {
+            table.insert("style".to_string(), style);
+            changes.push("added [style] from the default theme".to_string());
+        }
+    }
+
+    if let Some(Value::Table(legacy)) = table.get("segments") {
+        let legacy = legacy.clone();
+        let segments: Vec<SegmentConfig> = defaults
+            .segments
+            .iter()
+            .cloned()
+            .map(|mut segment| {
+                if let Some(enabled) = legacy.get(segment.id.as_str()).and_then(Value::as_bool) {
+                    segment.enabled = enabled;
+                }
+                segment
+            })
+            .collect();
+        if let Some(segments) = to_value(&segments) {
+            table.insert("segments".to_string(), segments);
+            changes.push("converted the [segments] switches into [[segments]] entries".to_string());
+        }
+    }
+
+    match table.get_mut("segments") {
+        Some(Value::Array(segments)) => {
+            for segment in segments.iter_mut().filter_map(Value::as_table_mut) {
+                changes.extend(complete_segment(segment));
+            }
+        }
+        None => {
+            if let Some(segments) = to_value(&defaults.segments) {
+                table.insert("segments".to_string(), segments);
+                changes.push("added the default theme's segments".to_string());
+            }
+        }
+        _ => {}
+    }
+    changes
+}
+
+/// Fill in the tables a `[[segments]]` entry needs from the segment's defaults
+fn complete_segment(segment: &mut Table) -> Vec<String> {
+    let Some(id) = segment
+        .get("id")
+        .cloned()
+        .and_then(|id| id.try_into::<SegmentId>().ok())
+    else {
+        return Vec::new();
+    };
+    let Some(Value::Table(defaults)) = to_value(&SegmentConfig::default_for(id)) else {
+        return Vec::new();
+    };
+
+    let mut added = Vec::new();
+    for key in ["enabled", "icon", "colors", "styles", "options"] {
+        if segment.contains_key(key) {
+            continue;
+        }
+        if let Some(value) = defaults.get(key) {
+            // An entry without `enabled` was written to be shown
+            let value = if key == "enabled" {
+                Value::Boolean(true)
+            } else {
+                value.clone()
+            };
+            segment.insert(key.to_string(), value);
+            added.push(key);
+        }
+    }
+    // `text_bold` is required even in a `styles` table that sets other keys
+    if let Some(Value::Table(styles)) = segment.get_mut("styles") {
+        if !styles.contains_key("text_bold") {
+            styles.insert("text_bold".to_string(), Value::Boolean(false));
+            added.push("styles.text_bold");
+        }
+    }
+
+    if added.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!(
+            "segment '{}': added default {}",
+            id.as_str(),
+            added.join(", ")
+        )]
+    }
+}
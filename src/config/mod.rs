@@ -1,8 +1,14 @@
+pub mod backup;
 pub mod defaults;
+pub mod include;
 pub mod loader;
+pub mod migrate;
 pub mod models;
+pub mod overrides;
+pub mod platform;
 pub mod types;
 
 pub use loader::ConfigLoader;
+pub use migrate::CONFIG_VERSION;
 pub use models::*;
 pub use types::*;
@@ -9,11 +9,52 @@ pub struct ModelConfig {
     pub model_entries: Vec<ModelEntry>,
 }
 
+/// What ccline knows about the models matching `pattern`. Models served
+/// through relays in the Anthropic API shape, such as GPT or Gemini, do not
+/// necessarily count tokens the way Claude does.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelEntry {
     pub pattern: String,
     pub display_name: String,
     pub context_limit: u32,
+    /// The reported input tokens already include cached ones, as OpenAI's
+    /// and Gemini's prompt counts do, so cache reads are not added on top
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub input_includes_cache: bool,
+    /// Prices for costs ccline works out itself, before the price list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPrice>,
+}
+
+/// USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+}
+
+impl ModelPrice {
+    pub const fn new(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write,
+            cache_read,
+        }
+    }
+
+    /// USD for the given token counts
+    pub fn cost(&self, input: u64, output: u64, cache_write: u64, cache_read: u64) -> f64 {
+        (input as f64 * self.input
+            + output as f64 * self.output
+            + cache_write as f64 * self.cache_write
+            + cache_read as f64 * self.cache_read)
+            / 1_000_000.0
+    }
 }
 
 impl ModelConfig {
@@ -28,7 +69,7 @@ impl ModelConfig {
     pub fn load() -> Self {
         // Try loading from user config directory first
         let config_paths = [
-            dirs::home_dir().map(|d| d.join(".claude").join("ccline").join("models.toml")),
+            Some(crate::utils::paths::config_dir().join("models.toml")),
             Some(Path::new("models.toml").to_path_buf()),
         ];
 
@@ -44,59 +85,38 @@ impl ModelConfig {
         Self::default()
     }
 
-    /// Get context limit for a model based on ID pattern matching
-    /// Checks external config first, then falls back to built-in config
-    pub fn get_context_limit(&self, model_id: &str) -> u32 {
+    /// The entry for a model: the first matching pattern in models.toml,
+    /// then in the built-in table
+    pub fn entry(&self, model_id: &str) -> Option<ModelEntry> {
         let model_lower = model_id.to_lowercase();
+        let matches = |entry: &&ModelEntry| model_lower.contains(&entry.pattern.to_lowercase());
 
-        // First check external config if it exists
         if let Ok(external_config) = Self::load_external_config() {
-            for entry in &external_config.model_entries {
-                if model_lower.contains(&entry.pattern.to_lowercase()) {
-                    return entry.context_limit;
-                }
-            }
-        }
-
-        // Fall back to built-in config
-        for entry in &self.model_entries {
-            if model_lower.contains(&entry.pattern.to_lowercase()) {
-                return entry.context_limit;
+            if let Some(entry) = external_config.model_entries.iter().find(matches) {
+                return Some(entry.clone());
             }
         }
+        self.model_entries.iter().find(matches).cloned()
+    }
 
-        self.default_context_limit
+    /// Get context limit for a model based on ID pattern matching
+    /// Checks external config first, then falls back to built-in config
+    pub fn get_context_limit(&self, model_id: &str) -> u32 {
+        self.entry(model_id)
+            .map_or(self.default_context_limit, |entry| entry.context_limit)
     }
 
     /// Get display name for a model based on ID pattern matching
     /// Checks external config first, then falls back to built-in config
     /// Returns None if no match found (should use fallback display_name)
     pub fn get_display_name(&self, model_id: &str) -> Option<String> {
-        let model_lower = model_id.to_lowercase();
-
-        // First check external config if it exists
-        if let Ok(external_config) = Self::load_external_config() {
-            for entry in &external_config.model_entries {
-                if model_lower.contains(&entry.pattern.to_lowercase()) {
-                    return Some(entry.display_name.clone());
-                }
-            }
-        }
-
-        // Fall back to built-in config
-        for entry in &self.model_entries {
-            if model_lower.contains(&entry.pattern.to_lowercase()) {
-                return Some(entry.display_name.clone());
-            }
-        }
-
-        None
+        self.entry(model_id).map(|entry| entry.display_name)
     }
 
     /// Load external configuration file only
     fn load_external_config() -> Result<Self, Box<dyn std::error::Error>> {
         let config_paths = [
-            dirs::home_dir().map(|d| d.join(".claude").join("ccline").join("models.toml")),
+            Some(crate::utils::paths::config_dir().join("models.toml")),
             Some(Path::new("models.toml").to_path_buf()),
         ];
 
@@ -135,7 +155,16 @@ impl ModelConfig {
              # [[models]]\n\
              # pattern = \"glm-4.5\"\n\
              # display_name = \"GLM-4.5\"\n\
-             # context_limit = 128000\n",
+             # context_limit = 128000\n\
+             \n\
+             # Models whose reported input tokens already include cached ones\n\
+             # (OpenAI, Gemini), with prices in USD per million tokens:\n\
+             # [[models]]\n\
+             # pattern = \"gpt-5\"\n\
+             # display_name = \"GPT-5\"\n\
+             # context_limit = 400000\n\
+             # input_includes_cache = true\n\
+             # pricing = {{ input = 1.25, output = 10.0, cache_read = 0.125 }}\n",
             toml_content.trim()
         );
 
@@ -159,52 +188,109 @@ impl Default for ModelConfig {
                     pattern: "[1m]".to_string(),
                     display_name: "Sonnet 4 1M".to_string(),
                     context_limit: 1_000_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "claude-sonnet-4".to_string(),
                     display_name: "Sonnet 4".to_string(),
                     context_limit: 200_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "claude-4-sonnet".to_string(),
                     display_name: "Sonnet 4".to_string(),
                     context_limit: 200_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "claude-4-opus".to_string(),
                     display_name: "Opus 4".to_string(),
                     context_limit: 200_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "sonnet-4".to_string(),
                     display_name: "Sonnet 4".to_string(),
                     context_limit: 200_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "claude-3-7-sonnet".to_string(),
                     display_name: "Sonnet 3.7".to_string(),
                     context_limit: 200_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 // Third-party models
                 ModelEntry {
                     pattern: "glm-4.5".to_string(),
                     display_name: "GLM-4.5".to_string(),
                     context_limit: 128_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "kimi-k2-turbo".to_string(),
                     display_name: "Kimi K2 Turbo".to_string(),
                     context_limit: 128_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "kimi-k2".to_string(),
                     display_name: "Kimi K2".to_string(),
                     context_limit: 128_000,
+                    input_includes_cache: false,
+                    pricing: None,
+                },
+                // Non-Claude models behind relays; their prompt counts
+                // include cached tokens
+                ModelEntry {
+                    pattern: "gpt-5".to_string(),
+                    display_name: "GPT-5".to_string(),
+                    context_limit: 400_000,
+                    input_includes_cache: true,
+                    pricing: None,
+                },
+                ModelEntry {
+                    pattern: "gpt-4.1".to_string(),
+                    display_name: "GPT-4.1".to_string(),
+                    context_limit: 1_047_576,
+                    input_includes_cache: true,
+                    pricing: None,
+                },
+                ModelEntry {
+                    pattern: "gpt-4o".to_string(),
+                    display_name: "GPT-4o".to_string(),
+                    context_limit: 128_000,
+                    input_includes_cache: true,
+                    pricing: None,
+                },
+                ModelEntry {
+                    pattern: "gemini-2.5-pro".to_string(),
+                    display_name: "Gemini 2.5 Pro".to_string(),
+                    context_limit: 1_048_576,
+                    input_includes_cache: true,
+                    pricing: None,
+                },
+                ModelEntry {
+                    pattern: "gemini-2.5-flash".to_string(),
+                    display_name: "Gemini 2.5 Flash".to_string(),
+                    context_limit: 1_048_576,
+                    input_includes_cache: true,
+                    pricing: None,
                 },
                 ModelEntry {
                     pattern: "qwen3-coder".to_string(),
                     display_name: "Qwen Coder".to_string(),
                     context_limit: 256_000,
+                    input_includes_cache: false,
+                    pricing: None,
                 },
             ],
         }
@@ -0,0 +1,80 @@
+//! `[[theme_overrides]]`: a different theme or an accent color while Claude
+//! Code is in a given output style or permission mode, so a mode change is
+//! visible at a glance. Resolved per render, after the adaptive theme; the
+//! first entry whose conditions all match applies.
+
+use super::types::{Config, InputData, ThemeOverride};
+use crate::core::segments::ModesSegment;
+
+/// Permission modes an override can name, as the modes segment reports them
+const MODES: &[&str] = &["plan", "accept_edits", "bypass"];
+
+impl ThemeOverride {
+    fn matches(&self, output_style: Option<&str>, mode: Option<&str>) -> bool {
+        let style_matches = self.output_style.as_deref().is_none_or(|wanted| {
+            output_style.is_some_and(|style| style.eq_ignore_ascii_case(wanted))
+        });
+        let mode_matches = self
+            .mode
+            .as_deref()
+            .is_none_or(|wanted| mode == Some(wanted));
+        style_matches && mode_matches
+    }
+
+    /// Reject entries without a condition and modes that do not exist
+    pub fn validate(&self) -> Result<(), String> {
+        if self.output_style.is_none() && self.mode.is_none() {
+            return Err("theme_overrides: each entry needs output_style or mode".to_string());
+        }
+        if let Some(mode) = &self.mode {
+            if !MODES.contains(&mode.as_str()) {
+                return Err(format!(
+                    "theme_overrides: unknown mode \"{}\"; use one of {}",
+                    mode,
+                    MODES.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Apply the first theme override matching `input`'s output style and
+    /// the session's permission mode
+    pub fn resolve_theme_overrides(&mut self, input: &InputData) {
+        if self.theme_overrides.is_empty() {
+            return;
+        }
+
+        let output_style = input.output_style.as_ref().map(|style| style.name.as_str());
+        // Only looked up when an entry asks for it, since it may read
+        // Claude Code's settings
+        let needs_mode = self
+            .theme_overrides
+            .iter()
+            .any(|entry| entry.mode.is_some());
+        let mode = needs_mode
+            .then(|| ModesSegment::permission_mode(input))
+            .flatten();
+        let Some(entry) = self
+            .theme_overrides
+            .iter()
+            .find(|entry| entry.matches(output_style, mode))
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Some(theme) = &entry.theme {
+            *self = self.with_theme(crate::ui::themes::ThemePresets::get_theme(theme));
+        }
+        if let Some(accent) = entry.accent {
+            self.style.separator_color = Some(accent.clone());
+            for segment in &mut self.segments {
+                segment.colors.icon = Some(accent.clone());
+                segment.colors.separator = None;
+            }
+        }
+    }
+}
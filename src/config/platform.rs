@@ -0,0 +1,114 @@
+//! `[[platform_defaults]]`: icon sets, Nerd Font availability and a theme
+//! for an operating system and terminal program, so a fresh install does not
+//! print glyphs the terminal cannot show. They fill in only what config.toml
+//! leaves unset, and a theme only replaces the untouched default theme.
+//! Resolved per render; the first matching entry sets each value.
+//!
+//! The built-in entries cover the Windows consoles: the classic console host
+//! shows neither emoji nor Nerd Font glyphs, and Windows Terminal's bundled
+//! font has no Nerd Font glyphs even when one is installed system-wide.
+//! Setting `platform_defaults` replaces them.
+
+use super::types::{Config, IconSet, PlatformDefault, ThemeSelection};
+use crate::utils::terminal;
+
+/// Operating systems an entry can name
+const SYSTEMS: &[&str] = &["windows", "macos", "linux", "wsl"];
+
+impl PlatformDefault {
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            PlatformDefault {
+                os: Some("windows".to_string()),
+                terminal: Some("conhost".to_string()),
+                theme: None,
+                icons: Some(IconSet::Ascii),
+                nerd_font: Some(false),
+            },
+            PlatformDefault {
+                os: None,
+                terminal: Some("windows-terminal".to_string()),
+                theme: None,
+                icons: None,
+                nerd_font: Some(false),
+            },
+        ]
+    }
+
+    pub fn is_builtin(defaults: &[PlatformDefault]) -> bool {
+        defaults == Self::builtin().as_slice()
+    }
+
+    fn matches(&self, os: &str, terminal: Option<&str>) -> bool {
+        let os_matches = self
+            .os
+            .as_deref()
+            .is_none_or(|wanted| wanted == os || (wanted == "linux" && os == "wsl"));
+        let terminal_matches = self
+            .terminal
+            .as_deref()
+            .is_none_or(|wanted| terminal == Some(wanted));
+        os_matches && terminal_matches
+    }
+
+    /// Reject operating systems and terminals that are never detected
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(os) = &self.os {
+            if !SYSTEMS.contains(&os.as_str()) {
+                return Err(format!(
+                    "platform_defaults: unknown os \"{}\"; use one of {}",
+                    os,
+                    SYSTEMS.join(", ")
+                ));
+            }
+        }
+        if let Some(name) = &self.terminal {
+            if !terminal::TERMINALS.contains(&name.as_str()) {
+                return Err(format!(
+                    "platform_defaults: unknown terminal \"{}\"; use one of {}",
+                    name,
+                    terminal::TERMINALS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Fill in unset icon and font settings, and the theme of an untouched
+    /// default config, from the entries matching this OS and terminal
+    pub fn resolve_platform_defaults(&mut self) {
+        if self.platform_defaults.is_empty() {
+            return;
+        }
+
+        let os = terminal::os();
+        let terminal = terminal::detect();
+        let matching: Vec<PlatformDefault> = self
+            .platform_defaults
+            .iter()
+            .filter(|entry| entry.matches(os, terminal))
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return;
+        }
+
+        let theme = matching.iter().find_map(|entry| entry.theme.as_deref());
+        if let Some(theme) = theme {
+            let is_default = matches!(&self.theme, ThemeSelection::Name(name) if name == "default");
+            if is_default && self.matches_theme("default") {
+                *self = self.with_theme(crate::ui::themes::ThemePresets::get_theme(theme));
+            }
+        }
+        if self.style.icons.is_none() {
+            self.style.icons = matching.iter().find_map(|entry| entry.icons);
+        }
+        // An answer given with CCLINE_NERD_FONT or in the TUI beats a guess
+        // from the terminal's name
+        if self.style.nerd_font.is_none() && !crate::utils::nerd_font::is_answered() {
+            self.style.nerd_font = matching.iter().find_map(|entry| entry.nerd_font);
+        }
+    }
+}
@@ -1,12 +1,605 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 // Main config structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Config files merged beneath this one, in order; see
+    /// [`super::include`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
     pub style: StyleConfig,
     pub segments: Vec<SegmentConfig>,
-    pub theme: String,
+    pub theme: ThemeSelection,
+    /// Theme changes for output styles and permission modes; see
+    /// [`super::overrides`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub theme_overrides: Vec<ThemeOverride>,
+    /// Icon and theme defaults for operating systems and terminals; see
+    /// [`super::platform`]
+    #[serde(
+        default = "PlatformDefault::builtin",
+        skip_serializing_if = "PlatformDefault::is_builtin"
+    )]
+    pub platform_defaults: Vec<PlatformDefault>,
+    #[serde(default, skip_serializing_if = "UpdateConfig::is_default")]
+    pub update: UpdateConfig,
+    #[serde(default, skip_serializing_if = "ReportConfig::is_default")]
+    pub report: ReportConfig,
+    #[serde(default, skip_serializing_if = "HandoffConfig::is_default")]
+    pub handoff: HandoffConfig,
+    #[serde(default, skip_serializing_if = "EmergencyConfig::is_default")]
+    pub emergency: EmergencyConfig,
+    #[serde(default, skip_serializing_if = "PricingConfig::is_default")]
+    pub pricing: PricingConfig,
+    #[serde(default, skip_serializing_if = "CacheConfig::is_default")]
+    pub cache: CacheConfig,
+    #[serde(default, skip_serializing_if = "BalanceConfig::is_default")]
+    pub balance: BalanceConfig,
+    #[serde(default, skip_serializing_if = "NetworkConfig::is_default")]
+    pub network: NetworkConfig,
+    #[serde(default, skip_serializing_if = "AlertsConfig::is_default")]
+    pub alerts: AlertsConfig,
+    #[serde(default, skip_serializing_if = "FocusConfig::is_default")]
+    pub focus: FocusConfig,
+    /// Language of segment text: "en", "zh-CN", another language with a
+    /// table in `locales/` in the config directory, or "auto" (the default)
+    /// for `LANG`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Schema version the file was written for; see [`super::migrate`]
+    #[serde(default)]
+    pub config_version: u32,
+}
+
+/// Where the update segment and `--update` look for releases. Both default
+/// to GitHub; set them where github.com is unreachable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    /// Release list in GitHub API format, e.g. an API proxy or a Gitea mirror
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_url: Option<String>,
+    /// Asset download URL with `{tag}`, `{version}` and `{asset}` placeholders,
+    /// e.g. "https://mirror.example.com/ccline/{tag}/{asset}". With either URL
+    /// set, the mirror must publish a SHA-256 checksum for each asset, or
+    /// `--update` refuses to download it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+}
+
+impl UpdateConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Team usage reporting: after a render, a summary of the session (model,
+/// token totals, cost, hashed project and session ids) is POSTed to
+/// `endpoint`. Off unless `enabled` is set and an endpoint is configured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// HTTPS URL receiving the summaries; plain HTTP is accepted for localhost only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Minimum minutes between two reports of the same session
+    #[serde(default = "ReportConfig::default_interval_minutes")]
+    pub interval_minutes: u64,
+    /// Environment variable holding a bearer token for the endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+    /// Name sent along with the summaries, e.g. a team or an engineer's
+    /// handle; reports are anonymous without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            interval_minutes: Self::default_interval_minutes(),
+            token_env: None,
+            member: None,
+        }
+    }
+}
+
+/// Hand-off file: after each render the line and the segment data are
+/// written to `path` for tmux, desktop widgets and overlays to read without
+/// running ccline themselves
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HandoffConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// File to write, `{session}` replaced by the session id; defaults to
+    /// `current-{session}.json` in the state directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Emergency rendering: once a watched segment reports a `critical` level,
+/// such as the quota past its critical share of the daily limit or the cost
+/// projection past the daily budget, it moves to the front of the line in
+/// blinking white on red, optionally followed by a warning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmergencyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Segments whose `critical` level triggers the mode
+    #[serde(default = "EmergencyConfig::default_segments")]
+    pub segments: Vec<SegmentId>,
+    /// Move the triggering segments to the front of the line
+    #[serde(default = "EmergencyConfig::default_true")]
+    pub move_to_front: bool,
+    #[serde(default = "EmergencyConfig::default_true")]
+    pub blink: bool,
+    /// Text appended to the line, e.g. "⚠ top up now"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+impl Default for EmergencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            segments: Self::default_segments(),
+            move_to_front: true,
+            blink: true,
+            warning: None,
+        }
+    }
+}
+
+impl EmergencyConfig {
+    fn default_segments() -> Vec<SegmentId> {
+        vec![SegmentId::Quota, SegmentId::Cost]
+    }
+
+    fn default_true() -> bool {
+        true
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Model prices for costs ccline works out itself, such as `ccline report`
+/// for sessions without a recorded cost. Built-in list prices are used
+/// unless `auto_update` refreshes them from `url` once they are older than
+/// `ttl_days`; without network the last fetched prices stay in use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PricingConfig {
+    #[serde(default)]
+    pub auto_update: bool,
+    /// Price list in LiteLLM's `model_prices_and_context_window.json`
+    /// format; defaults to LiteLLM's copy on GitHub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default = "PricingConfig::default_ttl_days")]
+    pub ttl_days: u64,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            auto_update: false,
+            url: None,
+            ttl_days: Self::default_ttl_days(),
+        }
+    }
+}
+
+impl PricingConfig {
+    fn default_ttl_days() -> u64 {
+        7
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Limits on the files ccline accumulates in its cache and state
+/// directories: per-session caches, hand-off files and downloaded updates.
+/// Older files are pruned after a render at most once a day, then the
+/// oldest until the total fits `max_size_mb`; 0 turns a limit off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    #[serde(default = "CacheConfig::default_max_size_mb")]
+    pub max_size_mb: u64,
+    #[serde(default = "CacheConfig::default_max_age_days")]
+    pub max_age_days: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: Self::default_max_size_mb(),
+            max_age_days: Self::default_max_age_days(),
+        }
+    }
+}
+
+impl CacheConfig {
+    fn default_max_size_mb() -> u64 {
+        50
+    }
+
+    fn default_max_age_days() -> u64 {
+        30
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// TLS and name resolution of ccline's own HTTPS requests: quota and plan
+/// lookups, update checks, usage reports and price lists
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// PEM file of CA certificates to trust besides the built-in roots, such
+    /// as a corporate proxy's. `~/` is the home directory and relative paths
+    /// are from the config directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+    /// Accept any certificate. Anyone between ccline and the server can
+    /// then read API keys, so only use this to find out whether a proxy is
+    /// what breaks requests.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub danger_accept_invalid_certs: bool,
+    /// Fixed addresses per host name, e.g. "www.packycode.com" =
+    /// "104.21.32.1", used instead of any DNS lookup
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hosts: BTreeMap<String, String>,
+    /// DNS-over-HTTPS endpoint answering JSON queries (`?name=…&type=A`),
+    /// asked before the system resolver, e.g. "https://1.1.1.1/dns-query"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doh: Option<String>,
+}
+
+impl NetworkConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `ca_bundle` resolved to a path
+    pub fn ca_bundle_path(&self) -> Option<PathBuf> {
+        let path = self.ca_bundle.as_deref()?;
+        if let Some(rest) = path.strip_prefix("~/") {
+            return Some(crate::utils::paths::home_dir()?.join(rest));
+        }
+        Some(crate::utils::paths::config_dir().join(path))
+    }
+
+    /// Reject a CA bundle that is missing or holds no certificates, host
+    /// addresses that are not IP addresses and a DoH endpoint that is not
+    /// https
+    pub fn validate(&self) -> Result<(), String> {
+        for (host, address) in &self.hosts {
+            if address.trim().parse::<std::net::IpAddr>().is_err() {
+                return Err(format!(
+                    "network.hosts: \"{}\" for {} is not an IP address",
+                    address, host
+                ));
+            }
+        }
+        if let Some(doh) = &self.doh {
+            if !doh.starts_with("https://") {
+                return Err(format!("network.doh must be an https:// URL: {}", doh));
+            }
+        }
+
+        let Some(path) = self.ca_bundle_path() else {
+            return Ok(());
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("network.ca_bundle {}: {}", path.display(), e))?;
+        if !content.contains("-----BEGIN CERTIFICATE-----") {
+            return Err(format!(
+                "network.ca_bundle {} holds no PEM certificates",
+                path.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Focus mode: during `hours`, or while `toggle_file` exists, the listed
+/// segments are left out of the line unless they turn critical
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusConfig {
+    #[serde(default = "FocusConfig::default_segments")]
+    pub segments: Vec<SegmentId>,
+    /// Local time ranges such as "09:00-12:00"; "22:00-02:00" runs past
+    /// midnight
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hours: Vec<String>,
+    /// File whose existence turns focus mode on, relative to the state
+    /// directory unless absolute or starting with `~/`
+    #[serde(default = "FocusConfig::default_toggle_file")]
+    pub toggle_file: String,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            segments: Self::default_segments(),
+            hours: Vec::new(),
+            toggle_file: Self::default_toggle_file(),
+        }
+    }
+}
+
+impl FocusConfig {
+    fn default_segments() -> Vec<SegmentId> {
+        vec![SegmentId::Update, SegmentId::Quota]
+    }
+
+    fn default_toggle_file() -> String {
+        "focus".to_string()
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `toggle_file` resolved to a path
+    pub fn toggle_path(&self) -> PathBuf {
+        if let Some(rest) = self.toggle_file.strip_prefix("~/") {
+            if let Some(home) = crate::utils::paths::home_dir() {
+                return home.join(rest);
+            }
+        }
+        crate::utils::paths::state_dir().join(&self.toggle_file)
+    }
+
+    /// Reject hours that are not "HH:MM-HH:MM"
+    pub fn validate(&self) -> Result<(), String> {
+        for range in &self.hours {
+            if crate::core::rules::parse_range(range).is_none() {
+                return Err(format!(
+                    "focus.hours: \"{}\" is not a range like \"09:00-12:00\"",
+                    range
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Commands run when a render finds that something crossed a threshold:
+/// the budget, the quota API, an update, the context window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    /// Context window usage in percent at which the `context` event fires
+    #[serde(default = "AlertsConfig::default_context_percent")]
+    pub context_percent: u8,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<AlertHook>,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            context_percent: Self::default_context_percent(),
+            hooks: Vec::new(),
+        }
+    }
+}
+
+impl AlertsConfig {
+    fn default_context_percent() -> u8 {
+        90
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A shell command given the event as JSON on stdin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertHook {
+    pub command: String,
+    /// Events the command runs for; all of them when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<AlertEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    /// The cost or quota segment reached the warning or critical level of
+    /// its budget
+    Budget,
+    /// No quota endpoint answered
+    QuotaOffline,
+    /// The update segment found a newer release
+    UpdateAvailable,
+    /// Context window usage reached `context_percent`
+    Context,
+}
+
+impl AlertEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertEvent::Budget => "budget",
+            AlertEvent::QuotaOffline => "quota_offline",
+            AlertEvent::UpdateAvailable => "update_available",
+            AlertEvent::Context => "context",
+        }
+    }
+}
+
+/// Relay accounts `ccline balance` queries side by side. Without any it
+/// queries the key the quota segment uses.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BalanceConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<BalanceAccount>,
+}
+
+impl BalanceConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceAccount {
+    pub name: String,
+    /// Environment variable holding the account's key, which keeps the key
+    /// out of config.toml and its backups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// User info endpoints that answer like PackyCode's; PackyCode's own
+    /// when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
+}
+
+impl HandoffConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ReportConfig {
+    fn default_interval_minutes() -> u64 {
+        15
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Reject endpoints that would send usage in the clear to another host
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(endpoint) = &self.endpoint else {
+            return if self.enabled {
+                Err("report.enabled is set but report.endpoint is missing".to_string())
+            } else {
+                Ok(())
+            };
+        };
+
+        if endpoint.starts_with("https://") {
+            return Ok(());
+        }
+        let host = endpoint
+            .strip_prefix("http://")
+            .map(|rest| rest.split(['/', '?']).next().unwrap_or(""))
+            .map(|authority| match authority.find(']') {
+                Some(end) => &authority[..=end],
+                None => authority.split(':').next().unwrap_or(""),
+            });
+        match host {
+            Some("localhost" | "127.0.0.1" | "[::1]") => Ok(()),
+            Some(_) => Err(format!(
+                "report.endpoint must use https:// (plain http is allowed for localhost only): {}",
+                endpoint
+            )),
+            None => Err(format!(
+                "report.endpoint is not an http(s) URL: {}",
+                endpoint
+            )),
+        }
+    }
+}
+
+/// Theme name, or one theme per terminal background:
+/// `theme = { dark = "nord", light = "solarized-light" }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ThemeSelection {
+    Name(String),
+    Adaptive { dark: String, light: String },
+}
+
+/// A theme, an accent color or both, used while Claude Code is in an output
+/// style or permission mode. Every condition set must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeOverride {
+    /// Output style name, e.g. "Explanatory"; compared ignoring case
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<String>,
+    /// Permission mode: "plan", "accept_edits" or "bypass"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Theme replacing the configured one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Color of every icon and separator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<AnsiColor>,
+}
+
+/// Settings for an operating system and terminal, used where config.toml
+/// leaves them unset. Every condition set must match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformDefault {
+    /// "windows", "macos", "linux" or "wsl"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// Terminal program, e.g. "windows-terminal", "conhost" or "iterm2"; see
+    /// [`crate::utils::terminal`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<String>,
+    /// Theme used while the config is the untouched default theme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// `style.icons` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icons: Option<IconSet>,
+    /// `style.nerd_font` when unset, in place of font detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nerd_font: Option<bool>,
+}
+
+impl ThemeSelection {
+    /// Theme to use on a terminal with the given background
+    pub fn for_background(&self, background: ThemeBackground) -> &str {
+        match self {
+            ThemeSelection::Name(name) => name,
+            ThemeSelection::Adaptive { dark, .. } if background == ThemeBackground::Dark => dark,
+            ThemeSelection::Adaptive { light, .. } => light,
+        }
+    }
+
+    pub fn is_adaptive(&self) -> bool {
+        matches!(self, ThemeSelection::Adaptive { .. })
+    }
+}
+
+impl From<&str> for ThemeSelection {
+    fn from(name: &str) -> Self {
+        ThemeSelection::Name(name.to_string())
+    }
+}
+
+impl From<String> for ThemeSelection {
+    fn from(name: String) -> Self {
+        ThemeSelection::Name(name)
+    }
+}
+
+impl std::fmt::Display for ThemeSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeSelection::Name(name) => write!(f, "{}", name),
+            ThemeSelection::Adaptive { dark, light } => {
+                write!(f, "{} (dark) / {} (light)", dark, light)
+            }
+        }
+    }
 }
 
 // Default implementation moved to ui/themes/presets.rs
@@ -15,6 +608,122 @@ pub struct Config {
 pub struct StyleConfig {
     pub mode: StyleMode,
     pub separator: String,
+    /// Terminal background the theme is designed for
+    #[serde(default)]
+    pub background: ThemeBackground,
+    /// Color of every separator; unset, Powerline arrows take the
+    /// backgrounds of the segments they join and plain separators a neutral
+    /// color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator_color: Option<AnsiColor>,
+    /// Resolve segment icons from a built-in icon set instead of each
+    /// segment's own `icon` table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icons: Option<IconSet>,
+    /// Whether the terminal font has Nerd Font glyphs; detected when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nerd_font: Option<bool>,
+    /// Print only ASCII, for serial consoles, CI logs and fonts without
+    /// emoji: icons from the ascii set as tags like "[git]", "|" and ">"
+    /// separators
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ascii: bool,
+    /// Print descriptive sentences for screen readers instead of the
+    /// statusline, like `--output a11y`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub accessible: bool,
+    #[serde(default)]
+    pub spacing: SpacingConfig,
+    /// How segments write token counts, costs and other numbers; a segment's
+    /// `numbers` option table overrides single fields
+    #[serde(default, skip_serializing_if = "NumberFormat::is_default")]
+    pub numbers: NumberFormat,
+}
+
+/// Number formatting. Unset fields keep each value's classic format:
+/// compact token counts ("127.4k"), costs with two decimals, plain counts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notation: Option<NumberNotation>,
+    /// Decimal places of compact numbers and costs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<usize>,
+    /// Digit grouping, e.g. "," or " "; "" for none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<String>,
+    /// Locale whose separators to use, e.g. "de-DE", or "auto" for the
+    /// `LC_ALL`/`LC_NUMERIC`/`LANG` environment. Explicit separators win.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberNotation {
+    /// SI suffixes: 127.4k, 1.2M
+    Compact,
+    /// Every digit, grouped: 127,431
+    Full,
+}
+
+impl NumberFormat {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fields set in `overrides` replace those of `self`
+    pub fn merged(&self, overrides: &NumberFormat) -> NumberFormat {
+        NumberFormat {
+            notation: overrides.notation.or(self.notation),
+            precision: overrides.precision.or(self.precision),
+            thousands_separator: overrides
+                .thousands_separator
+                .clone()
+                .or_else(|| self.thousands_separator.clone()),
+            decimal_separator: overrides
+                .decimal_separator
+                .clone()
+                .or_else(|| self.decimal_separator.clone()),
+            locale: overrides.locale.clone().or_else(|| self.locale.clone()),
+        }
+    }
+}
+
+/// Renderer spacing; the defaults reproduce the classic layout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpacingConfig {
+    /// Padding inside every segment. When unset, segments with a background
+    /// get one space on each side and others none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<PaddingConfig>,
+    /// Extra spaces on both sides of non-Powerline separators
+    pub gap: usize,
+    /// Draw a separator before the first segment
+    pub leading_separator: bool,
+    /// Draw a separator after the last segment
+    pub trailing_separator: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaddingConfig {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl PaddingConfig {
+    /// This padding with `extra` added on each side
+    pub fn widened(self, extra: PaddingConfig) -> Self {
+        Self {
+            left: self.left + extra.left,
+            right: self.right + extra.right,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -25,6 +734,27 @@ pub enum StyleMode {
     Powerline,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IconSet {
+    #[serde(rename = "ascii")]
+    Ascii,
+    #[serde(rename = "emoji")]
+    Emoji,
+    /// Nerd Font v3
+    #[serde(rename = "nerdfont", alias = "nerdfont-v3")]
+    NerdFont,
+    #[serde(rename = "nerdfont-v2")]
+    NerdFontV2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeBackground {
+    #[default]
+    Dark,
+    Light,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SegmentConfig {
     pub id: SegmentId,
@@ -33,6 +763,126 @@ pub struct SegmentConfig {
     pub colors: ColorConfig,
     pub styles: TextStyleConfig,
     pub options: HashMap<String, serde_json::Value>,
+    /// Overrides `style.spacing.padding` for this segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<PaddingConfig>,
+    /// Reuse the last collected value until it is this old instead of
+    /// collecting on every render
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_ms: Option<u64>,
+    /// Width the segment's content is padded to, so the line does not
+    /// shift as numbers grow and shrink
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<WidthConfig>,
+    /// Changes made to the collected text before it is rendered, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transforms: Vec<TransformConfig>,
+}
+
+/// A change to a segment's collected text, e.g. shortening `feature/` to
+/// `f/` in branch names
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformConfig {
+    /// Which text is changed; the primary text unless set
+    #[serde(default)]
+    pub on: TransformTarget,
+    #[serde(flatten)]
+    pub kind: Transform,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Transform {
+    /// Every match of the regex `pattern` replaced by `with`, in which `$1`
+    /// or `${name}` stand for capture groups
+    Replace {
+        pattern: String,
+        #[serde(default)]
+        with: String,
+    },
+    Case {
+        case: CaseMapping,
+    },
+    Prefix {
+        text: String,
+    },
+    Suffix {
+        text: String,
+    },
+    /// The first number in the text rounded to `decimals` places
+    Round {
+        #[serde(default)]
+        decimals: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformTarget {
+    #[default]
+    Primary,
+    Secondary,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseMapping {
+    Upper,
+    Lower,
+    /// The first letter of every word upper case
+    Title,
+}
+
+impl TransformConfig {
+    /// Reject replace patterns that are not valid regular expressions
+    pub fn validate(&self) -> Result<(), String> {
+        if let Transform::Replace { pattern, .. } = &self.kind {
+            crate::utils::pattern::Regex::new(pattern)
+                .map_err(|e| format!("transform pattern \"{}\": {}", pattern, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// A segment's content width in columns, not counting its padding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WidthConfig {
+    /// Shorter content is padded to this width
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    /// Longer content is cut to this width
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    /// Content is padded or cut to exactly this width; overrides `min` and
+    /// `max`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<usize>,
+    pub align: Alignment,
+    /// Which part of cut text gives way to the ellipsis
+    pub truncate: Truncation,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Truncation {
+    /// "feature/lo…"
+    #[default]
+    End,
+    /// "feat…n-form"
+    Middle,
+    /// "…login-form"
+    Start,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+    Center,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,11 +896,81 @@ pub struct ColorConfig {
     pub icon: Option<AnsiColor>,
     pub text: Option<AnsiColor>,
     pub background: Option<AnsiColor>,
+    /// Background interpolated across the segment width on truecolor terminals;
+    /// elsewhere `background` (or the gradient's start color) is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_gradient: Option<GradientConfig>,
+    /// Color of the separator after the segment, overriding
+    /// `style.separator_color` and the derived Powerline arrow color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<AnsiColor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradientConfig {
+    pub from: AnsiColor,
+    pub to: AnsiColor,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TextStyleConfig {
+    /// Bold primary and secondary text, kept for configs written before the
+    /// per-part styles existed
     pub text_bold: bool,
+    #[serde(default, skip_serializing_if = "TextStyle::is_plain")]
+    pub icon: TextStyle,
+    #[serde(default, skip_serializing_if = "TextStyle::is_plain")]
+    pub primary: TextStyle,
+    #[serde(default, skip_serializing_if = "TextStyle::is_plain")]
+    pub secondary: TextStyle,
+}
+
+impl TextStyleConfig {
+    pub fn primary_style(&self) -> TextStyle {
+        TextStyle {
+            bold: self.primary.bold || self.text_bold,
+            ..self.primary
+        }
+    }
+
+    pub fn secondary_style(&self) -> TextStyle {
+        TextStyle {
+            bold: self.secondary.bold || self.text_bold,
+            ..self.secondary
+        }
+    }
+}
+
+/// SGR text attributes for one part of a segment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct TextStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub dim: bool,
+    pub blink: bool,
+}
+
+impl TextStyle {
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// SGR parameters that turn the attributes on
+    pub fn sgr_codes(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.blink, "5"),
+            (self.strikethrough, "9"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, code)| enabled.then_some(code))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,8 +981,9 @@ pub enum AnsiColor {
     Rgb { r: u8, g: u8, b: u8 },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+/// A segment's identity: a built-in segment, or one registered at runtime
+/// under its own name. Written in config files as its name, e.g. "git".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SegmentId {
     Model,
     Directory,
@@ -73,6 +994,115 @@ pub enum SegmentId {
     OutputStyle,
     Update,
     Quota,
+    Container,
+    Plan,
+    Identity,
+    Vim,
+    Attention,
+    Modes,
+    Memory,
+    Hooks,
+    Transcript,
+    Computed,
+    /// A segment registered at runtime, see
+    /// [`crate::core::segments::register_segment`]
+    Custom(&'static str),
+}
+
+impl SegmentId {
+    /// Every built-in segment, in default display order
+    pub const BUILTIN: &'static [SegmentId] = &[
+        SegmentId::Model,
+        SegmentId::Directory,
+        SegmentId::Git,
+        SegmentId::Usage,
+        SegmentId::Cost,
+        SegmentId::Session,
+        SegmentId::OutputStyle,
+        SegmentId::Update,
+        SegmentId::Quota,
+        SegmentId::Container,
+        SegmentId::Plan,
+        SegmentId::Identity,
+        SegmentId::Vim,
+        SegmentId::Attention,
+        SegmentId::Modes,
+        SegmentId::Memory,
+        SegmentId::Hooks,
+        SegmentId::Transcript,
+        SegmentId::Computed,
+    ];
+
+    /// The segment a config key names: a built-in one, or else one that is
+    /// (or may later be) registered at runtime under that name
+    pub fn named(name: &str) -> SegmentId {
+        Self::BUILTIN
+            .iter()
+            .copied()
+            .find(|id| id.as_str() == name)
+            .unwrap_or_else(|| SegmentId::Custom(intern_segment_name(name)))
+    }
+
+    /// Key used for this segment in config files and on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentId::Model => "model",
+            SegmentId::Directory => "directory",
+            SegmentId::Git => "git",
+            SegmentId::Usage => "usage",
+            SegmentId::Cost => "cost",
+            SegmentId::Session => "session",
+            SegmentId::OutputStyle => "output_style",
+            SegmentId::Update => "update",
+            SegmentId::Quota => "quota",
+            SegmentId::Container => "container",
+            SegmentId::Plan => "plan",
+            SegmentId::Identity => "identity",
+            SegmentId::Vim => "vim",
+            SegmentId::Attention => "attention",
+            SegmentId::Modes => "modes",
+            SegmentId::Memory => "memory",
+            SegmentId::Hooks => "hooks",
+            SegmentId::Transcript => "transcript",
+            SegmentId::Computed => "computed",
+            SegmentId::Custom(name) => name,
+        }
+    }
+}
+
+/// A `'static` copy of a runtime segment name, made once per name so
+/// reloading config does not leak a copy every time
+fn intern_segment_name(name: &str) -> &'static str {
+    use std::collections::HashSet;
+    use std::sync::{Mutex, OnceLock};
+
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+impl Serialize for SegmentId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SegmentId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name.trim().is_empty() {
+            return Err(serde::de::Error::custom("segment id is empty"));
+        }
+        Ok(SegmentId::named(&name))
+    }
 }
 
 // Legacy compatibility structure
@@ -85,18 +1115,22 @@ pub struct SegmentsConfig {
 }
 
 // Data structures compatible with existing main.rs
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Model {
     pub id: String,
     pub display_name: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Workspace {
     pub current_dir: String,
+    /// Directory Claude Code was started in; the working directory may be
+    /// below it
+    #[serde(default)]
+    pub project_dir: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct Cost {
     pub total_cost_usd: Option<f64>,
     pub total_duration_ms: Option<u64>,
@@ -105,18 +1139,31 @@ pub struct Cost {
     pub total_lines_removed: Option<u32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct OutputStyle {
     pub name: String,
 }
 
-#[derive(Deserialize)]
+/// Present while vim editing is enabled in Claude Code
+#[derive(Clone, Deserialize)]
+pub struct VimState {
+    /// "INSERT" or "NORMAL"
+    pub mode: String,
+}
+
+#[derive(Clone, Deserialize)]
 pub struct InputData {
     pub model: Model,
     pub workspace: Workspace,
     pub transcript_path: String,
     pub cost: Option<Cost>,
     pub output_style: Option<OutputStyle>,
+    /// Set by Claude Code once the latest request's input passed 200k tokens,
+    /// which only the 1M context window allows
+    #[serde(default)]
+    pub exceeds_200k_tokens: Option<bool>,
+    #[serde(default)]
+    pub vim: Option<VimState>,
 }
 
 // OpenAI-style nested token details
@@ -178,6 +1225,15 @@ pub struct NormalizedUsage {
 }
 
 impl NormalizedUsage {
+    /// Usage of a model whose reported input tokens already include the
+    /// cached ones (OpenAI, Gemini), with the cached part taken out of
+    /// `input_tokens` so it is not counted twice
+    pub fn without_cached_input(mut self) -> Self {
+        let cached = self.cache_read_input_tokens + self.cache_creation_input_tokens;
+        self.input_tokens = self.input_tokens.saturating_sub(cached);
+        self
+    }
+
     /// Get tokens that count toward context window
     /// This includes all tokens that consume context window space
     /// Output tokens from this turn will become input tokens in the next turn
@@ -221,6 +1277,49 @@ impl NormalizedUsage {
     }
 }
 
+impl SegmentConfig {
+    /// The segment as the default theme configures it, or, for segments the
+    /// theme leaves out, disabled with its registry icon and no colors
+    pub fn default_for(id: SegmentId) -> SegmentConfig {
+        if let Some(segment) = crate::ui::themes::ThemePresets::get_default()
+            .segments
+            .into_iter()
+            .find(|segment| segment.id == id)
+        {
+            return segment;
+        }
+
+        let icons = crate::core::segments::with_registry(|registry| {
+            registry.info(id).map(|info| info.icons.clone())
+        });
+        SegmentConfig {
+            id,
+            enabled: false,
+            icon: IconConfig {
+                plain: icons.as_ref().map(|i| i.emoji).unwrap_or("").to_string(),
+                nerd_font: icons
+                    .as_ref()
+                    .map(|i| i.nerd_font)
+                    .unwrap_or("")
+                    .to_string(),
+            },
+            colors: ColorConfig {
+                icon: None,
+                text: None,
+                background: None,
+                background_gradient: None,
+                separator: None,
+            },
+            styles: TextStyleConfig::default(),
+            options: HashMap::new(),
+            padding: None,
+            refresh_interval_ms: None,
+            width: None,
+            transforms: Vec::new(),
+        }
+    }
+}
+
 impl Config {
     /// Check if current config matches the specified theme preset
     pub fn matches_theme(&self, theme_name: &str) -> bool {
@@ -229,6 +1328,11 @@ impl Config {
         // Compare style config
         if self.style.mode != theme_preset.style.mode
             || self.style.separator != theme_preset.style.separator
+            || self.style.spacing != theme_preset.style.spacing
+            || !self.color_matches(
+                &self.style.separator_color,
+                &theme_preset.style.separator_color,
+            )
         {
             return false;
         }
@@ -248,9 +1352,101 @@ impl Config {
         true
     }
 
+    /// Name of the theme in effect: the configured theme, or for an adaptive
+    /// selection the one matching the detected background
+    pub fn active_theme(&self) -> &str {
+        self.theme.for_background(self.style.background)
+    }
+
+    /// `theme` with the settings a theme does not own carried over from
+    /// `self`: release, reporting, hand-off, emergency, pricing, cache,
+    /// balance, network, alert and focus settings, theme overrides, platform
+    /// defaults, the language, includes, ASCII and accessible mode, and the
+    /// number format unless the theme sets its own
+    pub fn with_theme(&self, theme: Config) -> Config {
+        let mut config = Config {
+            update: self.update.clone(),
+            report: self.report.clone(),
+            handoff: self.handoff.clone(),
+            emergency: self.emergency.clone(),
+            pricing: self.pricing.clone(),
+            cache: self.cache.clone(),
+            balance: self.balance.clone(),
+            network: self.network.clone(),
+            alerts: self.alerts.clone(),
+            theme_overrides: self.theme_overrides.clone(),
+            platform_defaults: self.platform_defaults.clone(),
+            focus: self.focus.clone(),
+            language: self.language.clone(),
+            include: self.include.clone(),
+            ..theme
+        };
+        if config.style.numbers.is_default() {
+            config.style.numbers = self.style.numbers.clone();
+        }
+        config.style.ascii |= self.style.ascii;
+        config.style.accessible |= self.style.accessible;
+        config
+    }
+
+    /// Turn segments on or off by config key ("cost", "output_style") without
+    /// touching the file, for a single render. Enabling a segment the config
+    /// does not list appends it with its defaults; a segment named in both
+    /// lists ends up disabled.
+    pub fn override_segments(
+        &mut self,
+        enable: &[String],
+        disable: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::core::segments::lookup_segment;
+
+        for name in enable {
+            let info = lookup_segment(name)?;
+            match self
+                .segments
+                .iter_mut()
+                .find(|segment| segment.id == info.id)
+            {
+                Some(segment) => segment.enabled = true,
+                None => self.segments.push(SegmentConfig {
+                    enabled: true,
+                    ..SegmentConfig::default_for(info.id)
+                }),
+            }
+        }
+        for name in disable {
+            let info = lookup_segment(name)?;
+            for segment in self.segments.iter_mut().filter(|s| s.id == info.id) {
+                segment.enabled = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// The segments as collectors see them: global settings such as
+    /// `style.numbers` are folded into each segment's options, under any
+    /// values the segment sets itself
+    pub fn resolved_segments(&self) -> Vec<SegmentConfig> {
+        let mut segments = self.segments.clone();
+        if self.style.numbers.is_default() {
+            return segments;
+        }
+        for segment in &mut segments {
+            let own = segment
+                .options
+                .get("numbers")
+                .and_then(|value| serde_json::from_value::<NumberFormat>(value.clone()).ok())
+                .unwrap_or_default();
+            if let Ok(value) = serde_json::to_value(self.style.numbers.merged(&own)) {
+                segment.options.insert("numbers".to_string(), value);
+            }
+        }
+        segments
+    }
+
     /// Check if current config has been modified from the selected theme
     pub fn is_modified_from_theme(&self) -> bool {
-        !self.matches_theme(&self.theme)
+        !self.matches_theme(self.active_theme())
     }
 
     /// Compare two segment configs for equality
@@ -262,8 +1458,17 @@ impl Config {
             && self.color_matches(&current.colors.icon, &preset.colors.icon)
             && self.color_matches(&current.colors.text, &preset.colors.text)
             && self.color_matches(&current.colors.background, &preset.colors.background)
+            && current.colors.background_gradient == preset.colors.background_gradient
+            && self.color_matches(&current.colors.separator, &preset.colors.separator)
             && current.styles.text_bold == preset.styles.text_bold
+            && current.styles.icon == preset.styles.icon
+            && current.styles.primary == preset.styles.primary
+            && current.styles.secondary == preset.styles.secondary
             && current.options == preset.options
+            && current.padding == preset.padding
+            && current.refresh_interval_ms == preset.refresh_interval_ms
+            && current.width == preset.width
+            && current.transforms == preset.transforms
     }
 
     /// Compare two optional colors for equality
@@ -370,6 +1575,9 @@ pub type Usage = RawUsage;
 
 #[derive(Deserialize)]
 pub struct Message {
+    /// API message id; one response can span several transcript lines
+    pub id: Option<String>,
+    pub model: Option<String>,
     pub usage: Option<Usage>,
 }
 
@@ -0,0 +1,208 @@
+//! Commands from `[alerts]` in config.toml, run when a render finds that
+//! something crossed a threshold since the session's previous render.
+//!
+//! Events are read from the collected segments' metadata, so the segments
+//! behind them (cost, quota, update, usage) must be in the config; they may
+//! be disabled. Each event fires once when its condition starts, or when a
+//! budget moves from warning to critical, and again only after the
+//! condition ended. Commands run in the background with the event as JSON
+//! on stdin and `CCLINE_EVENT` set to its name; the render does not wait
+//! for them.
+
+use crate::config::{AlertEvent, AlertsConfig, InputData, SegmentConfig, SegmentId};
+use crate::core::segments::SegmentData;
+use crate::core::session_cache;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What is written to a hook's stdin
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub event: AlertEvent,
+    /// "warning" or "critical" for budgets, the version for updates, the
+    /// percentage for the context window
+    pub state: String,
+    pub message: String,
+    pub segment: &'static str,
+    pub transcript_path: String,
+    pub cwd: String,
+    /// Unix seconds
+    pub at: u64,
+}
+
+/// A condition holding in this render
+struct Condition {
+    /// What tracks the condition across renders
+    key: String,
+    event: AlertEvent,
+    segment: SegmentId,
+    state: String,
+    message: String,
+}
+
+fn conditions(config: &AlertsConfig, segments: &[(SegmentConfig, SegmentData)]) -> Vec<Condition> {
+    let mut found = Vec::new();
+    for (segment, data) in segments {
+        let metadata = &data.metadata;
+        match segment.id {
+            SegmentId::Cost | SegmentId::Quota => {
+                if let Some(level) = metadata.get("level") {
+                    let text = format!("{} {}", data.primary, data.secondary);
+                    found.push(Condition {
+                        key: format!("budget:{}", segment.id.as_str()),
+                        event: AlertEvent::Budget,
+                        segment: segment.id,
+                        state: level.clone(),
+                        message: format!("Budget {}: {}", level, text.trim()),
+                    });
+                }
+                let unreachable = matches!(
+                    metadata.get("error_kind").map(String::as_str),
+                    Some("network" | "status" | "timeout")
+                );
+                if segment.id == SegmentId::Quota && unreachable {
+                    found.push(Condition {
+                        key: "quota_offline".to_string(),
+                        event: AlertEvent::QuotaOffline,
+                        segment: segment.id,
+                        state: "offline".to_string(),
+                        message: "Quota API unreachable".to_string(),
+                    });
+                }
+            }
+            SegmentId::Update => {
+                if let Some(version) = metadata.get("latest_version") {
+                    found.push(Condition {
+                        key: "update_available".to_string(),
+                        event: AlertEvent::UpdateAvailable,
+                        segment: segment.id,
+                        state: version.clone(),
+                        message: format!("ccline {} is available", version),
+                    });
+                }
+            }
+            SegmentId::Usage => {
+                let percent = metadata
+                    .get("percentage")
+                    .and_then(|value| value.parse::<f64>().ok());
+                if let Some(percent) = percent.filter(|p| *p >= config.context_percent as f64) {
+                    found.push(Condition {
+                        key: "context".to_string(),
+                        event: AlertEvent::Context,
+                        segment: segment.id,
+                        state: format!("{:.0}", percent),
+                        message: format!("Context window at {:.0}%", percent),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Whether `state` is news after `previous`. Budgets fire again when they
+/// get worse; the context window fires once per crossing whatever the
+/// percentage does above the threshold.
+fn is_new(event: AlertEvent, previous: Option<&String>, state: &str) -> bool {
+    match (event, previous) {
+        (_, None) => true,
+        (AlertEvent::Budget, Some(previous)) => previous == "warning" && state == "critical",
+        (AlertEvent::UpdateAvailable, Some(previous)) => previous != state,
+        (_, Some(_)) => false,
+    }
+}
+
+/// Find the events of this render and run the hooks for them
+pub fn check(config: &AlertsConfig, input: &InputData, segments: &[(SegmentConfig, SegmentData)]) {
+    if config.hooks.is_empty() || input.transcript_path.is_empty() {
+        return;
+    }
+
+    let current = conditions(config, segments);
+    let alerts = session_cache::update_session(&input.transcript_path, |session| {
+        let mut alerts = Vec::new();
+        let mut next = HashMap::new();
+        for condition in current {
+            let Condition {
+                key,
+                event,
+                segment,
+                state,
+                message,
+            } = condition;
+            if is_new(event, session.alerts.get(&key), &state) {
+                alerts.push(Alert {
+                    event,
+                    state: state.clone(),
+                    message,
+                    segment: segment.as_str(),
+                    transcript_path: input.transcript_path.clone(),
+                    cwd: input.workspace.current_dir.clone(),
+                    at: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0),
+                });
+            }
+            // A budget that eased from critical to warning keeps its
+            // critical state, so the way back up does not fire again
+            let keep = match session.alerts.get(&key) {
+                Some(previous) if event == AlertEvent::Budget && previous == "critical" => {
+                    previous.clone()
+                }
+                _ => state,
+            };
+            next.insert(key, keep);
+        }
+        session.alerts = next;
+        alerts
+    });
+
+    for alert in &alerts {
+        let Ok(json) = serde_json::to_vec(alert) else {
+            continue;
+        };
+        for hook in &config.hooks {
+            if hook.events.is_empty() || hook.events.contains(&alert.event) {
+                run(&hook.command, alert.event, &json);
+            }
+        }
+    }
+}
+
+/// Start `command` in the platform shell with `json` on its stdin
+fn run(command: &str, event: AlertEvent, json: &[u8]) {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+
+    let Ok(mut child) = shell
+        .env("CCLINE_EVENT", event.as_str())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(json);
+    }
+    // Reaped in the background, for the daemon's sake
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
@@ -0,0 +1,230 @@
+//! Pruning of the files ccline accumulates: per-session render caches,
+//! hand-off files of past sessions, downloaded updates, expired API caches
+//! and temporary files left by interrupted writes.
+//!
+//! Only files ccline names itself are considered, since in the
+//! `~/.claude/ccline` layout the cache, state and config directories are
+//! one. The spend ledger, queued reports, fetched prices and the weekly usage
+//! tally are counted by `ccline cache stats` but never pruned.
+
+use crate::config::CacheConfig;
+use crate::utils::paths;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often pruning runs after renders
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Temporary files older than this belong to a write that never finished
+const STALE_TEMP: Duration = Duration::from_secs(60 * 60);
+
+/// API responses that are refetched when missing
+const API_CACHES: &[&str] = &[
+    "endpoint_cache.json",
+    "plan_cache.json",
+    "background_cache.json",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// `cache/session-*.bin`, one per session
+    Sessions,
+    /// `current-*.json` hand-off files, one per session
+    Handoff,
+    /// Release assets downloaded by `--update`
+    Updates,
+    /// Quota, plan and terminal background responses, and HTTP bodies
+    /// kept for revalidation
+    Api,
+    /// `.name.<pid>.tmp` from atomic writes
+    Temporary,
+    /// Spend ledger, queued reports, fetched prices, weekly usage tally;
+    /// never pruned
+    Kept,
+}
+
+impl Kind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Kind::Sessions => "Session caches",
+            Kind::Handoff => "Hand-off files",
+            Kind::Updates => "Downloaded updates",
+            Kind::Api => "API caches",
+            Kind::Temporary => "Temporary files",
+            Kind::Kept => "Kept state",
+        }
+    }
+
+    /// Whether files of this kind count toward `max_size_mb`, oldest
+    /// removed first
+    fn capped(self) -> bool {
+        matches!(self, Kind::Sessions | Kind::Handoff | Kind::Updates)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheFile {
+    pub path: PathBuf,
+    pub kind: Kind,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// The files of `dir` whose names `matches` accepts
+fn files_in(dir: &Path, kind: Kind, matches: impl Fn(&str) -> bool) -> Vec<CacheFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            meta.is_file().then(|| CacheFile {
+                path: entry.path(),
+                kind,
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            })
+        })
+        .collect()
+}
+
+/// Every file ccline keeps in its cache and state directories
+pub fn inventory() -> Vec<CacheFile> {
+    let dirs = paths::dirs();
+    let render_cache = dirs.cache.join("cache");
+    let mut files = Vec::new();
+
+    files.extend(files_in(&render_cache, Kind::Sessions, |name| {
+        name.starts_with("session-") && name.ends_with(".bin")
+    }));
+    files.extend(files_in(&dirs.state, Kind::Handoff, |name| {
+        name.starts_with("current-") && name.ends_with(".json")
+    }));
+    files.extend(files_in(&dirs.cache.join("updates"), Kind::Updates, |_| {
+        true
+    }));
+    files.extend(files_in(&dirs.cache, Kind::Api, |name| {
+        API_CACHES.contains(&name)
+    }));
+    files.extend(files_in(&render_cache, Kind::Api, |name| {
+        name == "config.bin"
+    }));
+    files.extend(files_in(&dirs.cache.join("http"), Kind::Api, |name| {
+        name.ends_with(".json")
+    }));
+
+    let http_cache = dirs.cache.join("http");
+    let mut temp_dirs = vec![
+        &dirs.config,
+        &dirs.cache,
+        &dirs.state,
+        &render_cache,
+        &http_cache,
+    ];
+    temp_dirs.dedup();
+    for dir in temp_dirs {
+        files.extend(files_in(dir, Kind::Temporary, |name| {
+            name.starts_with('.') && name.ends_with(".tmp")
+        }));
+    }
+
+    files.extend(files_in(&dirs.state, Kind::Kept, |name| {
+        name == "spend.json"
+    }));
+    files.extend(files_in(&dirs.state.join("reports"), Kind::Kept, |_| true));
+    files.extend(files_in(&dirs.cache, Kind::Kept, |name| {
+        name == "pricing.json" || name == "weekly_usage.json"
+    }));
+    files
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct Pruned {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+fn age(file: &CacheFile, now: SystemTime) -> Duration {
+    now.duration_since(file.modified).unwrap_or_default()
+}
+
+/// Remove expired files, then the oldest capped files until they fit
+/// `max_size_mb`
+pub fn prune(config: &CacheConfig) -> Pruned {
+    let now = SystemTime::now();
+    let max_age = Duration::from_secs(config.max_age_days.saturating_mul(24 * 60 * 60));
+    let mut pruned = Pruned::default();
+    let mut remove = |file: &CacheFile| {
+        if fs::remove_file(&file.path).is_ok() {
+            pruned.files += 1;
+            pruned.bytes += file.size;
+        }
+    };
+
+    let mut capped = Vec::new();
+    for file in inventory() {
+        let expired = match file.kind {
+            Kind::Temporary => age(&file, now) > STALE_TEMP,
+            Kind::Kept => false,
+            _ => config.max_age_days > 0 && age(&file, now) > max_age,
+        };
+        if expired {
+            remove(&file);
+        } else if file.kind.capped() {
+            capped.push(file);
+        }
+    }
+
+    if config.max_size_mb > 0 {
+        let cap = config.max_size_mb.saturating_mul(1024 * 1024);
+        let mut total: u64 = capped.iter().map(|file| file.size).sum();
+        capped.sort_by_key(|file| file.modified);
+        for file in &capped {
+            if total <= cap {
+                break;
+            }
+            total -= file.size;
+            remove(file);
+        }
+    }
+    pruned
+}
+
+/// Holds the Unix seconds of the last run
+fn marker() -> PathBuf {
+    paths::cache_dir().join("cache").join("last_prune")
+}
+
+/// When pruning last ran
+pub fn last_prune() -> Option<SystemTime> {
+    let secs = fs::read_to_string(marker()).ok()?.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Prune when the last run is more than a day ago; called after renders
+pub fn maybe_prune(config: &CacheConfig) {
+    let due = last_prune()
+        .and_then(|last| last.elapsed().ok())
+        .is_none_or(|elapsed| elapsed >= PRUNE_INTERVAL);
+    if !due {
+        return;
+    }
+    // Mark first, so concurrent renders do not all prune
+    let marker = marker();
+    if let Some(dir) = marker.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if fs::write(&marker, now.to_string()).is_err() {
+        return;
+    }
+    prune(config);
+}
@@ -0,0 +1,60 @@
+use crate::config::InputData;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Get the path of the last payload received from Claude Code
+/// (last_input.json in the state directory)
+pub fn get_capture_path() -> PathBuf {
+    crate::utils::paths::state_dir().join("last_input.json")
+}
+
+/// Set for renders that only check the statusline command works, such as
+/// those of `ccline --init`, so their sample payload is not kept
+pub const NO_CAPTURE_ENV: &str = "CCLINE_NO_CAPTURE";
+
+/// Whether renders keep their payload; see [`NO_CAPTURE_ENV`]
+pub fn enabled() -> bool {
+    std::env::var_os(NO_CAPTURE_ENV).is_none()
+}
+
+/// Keep the raw payload so the TUI can preview with real data.
+/// Skips the write when the payload did not change since the last render.
+pub fn save(raw: &str) {
+    let path = get_capture_path();
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == raw) {
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = crate::utils::atomic::write(&path, raw.as_bytes());
+}
+
+/// The last captured payload, if any was saved and still parses
+pub fn load_last() -> Option<InputData> {
+    let content = fs::read_to_string(get_capture_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Load a payload from a file, e.g. one saved from a Claude Code session
+pub fn load_file(path: &Path) -> Result<InputData, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read input file {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Payload for the TUI preview: the given file, or the last captured payload
+/// when launched from inside a Claude Code session
+pub fn preview_input(path: Option<&Path>) -> Result<Option<InputData>, Box<dyn std::error::Error>> {
+    match path {
+        Some(path) => load_file(path).map(Some),
+        None if in_claude_session() => Ok(load_last()),
+        None => Ok(None),
+    }
+}
+
+/// Whether we run inside a Claude Code session (its shells set `CLAUDECODE`)
+pub fn in_claude_session() -> bool {
+    std::env::var_os("CLAUDECODE").is_some()
+}
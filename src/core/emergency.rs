@@ -0,0 +1,69 @@
+//! Emergency rendering: when a watched segment reports a `critical` level
+//! (the quota nearly used up, the day's spend past budget), the line is
+//! rearranged so it cannot be missed.
+
+use crate::config::{AnsiColor, EmergencyConfig, SegmentConfig};
+use crate::core::segments::SegmentData;
+
+const BACKGROUND: AnsiColor = AnsiColor::Color256 { c256: 160 };
+const TEXT: AnsiColor = AnsiColor::Color256 { c256: 231 };
+
+/// Whether a watched segment is critical
+pub fn is_active(config: &EmergencyConfig, segments: &[(SegmentConfig, SegmentData)]) -> bool {
+    config.enabled
+        && segments
+            .iter()
+            .any(|(segment, data)| is_critical(config, segment, data))
+}
+
+fn is_critical(config: &EmergencyConfig, segment: &SegmentConfig, data: &SegmentData) -> bool {
+    segment.enabled
+        && config.segments.contains(&segment.id)
+        && data.metadata.get("level").map(String::as_str) == Some("critical")
+}
+
+/// The segments as they render in an emergency: critical watched segments
+/// in white on red, blinking unless `blink` is off, and first in line unless
+/// `move_to_front` is off. Unchanged when no watched segment is critical.
+pub fn apply(
+    config: &EmergencyConfig,
+    mut segments: Vec<(SegmentConfig, SegmentData)>,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    if !is_active(config, &segments) {
+        return segments;
+    }
+
+    let critical: Vec<bool> = segments
+        .iter()
+        .map(|(segment, data)| is_critical(config, segment, data))
+        .collect();
+    for ((segment, data), _) in segments
+        .iter_mut()
+        .zip(&critical)
+        .filter(|(_, critical)| **critical)
+    {
+        segment.colors.background = Some(BACKGROUND);
+        segment.colors.background_gradient = None;
+        segment.colors.icon = Some(TEXT);
+        segment.colors.text = Some(TEXT);
+        segment.styles.primary.bold = true;
+        // The level's red text would vanish on the red background
+        data.metadata.remove("level");
+        if config.blink {
+            segment.styles.icon.blink = true;
+            segment.styles.primary.blink = true;
+            segment.styles.secondary.blink = true;
+        }
+    }
+
+    if config.move_to_front {
+        // Critical segments first; both groups keep their configured order
+        let (mut front, rest): (Vec<_>, Vec<_>) = segments
+            .into_iter()
+            .zip(critical)
+            .partition(|(_, critical)| *critical);
+        front.extend(rest);
+        return front.into_iter().map(|(segment, _)| segment).collect();
+    }
+    segments
+}
@@ -0,0 +1,135 @@
+//! Runs blocking work, mostly network requests, on worker threads and waits
+//! for it up to a deadline. Tasks that are still running at the deadline
+//! are not waited for, but they are cancelled: a worker knows its deadline
+//! ([`time_left`]), every request made through `utils::http` gives up when
+//! it passes, and loops can check [`cancelled`] between steps.
+
+use std::cell::Cell;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A unit of blocking work that can be moved to a worker thread
+pub type Task<T> = Box<dyn FnOnce() -> T + Send + 'static>;
+
+/// Upper bound on how long rendering waits for network segments in total
+pub const NETWORK_DEADLINE: Duration = Duration::from_millis(3000);
+
+thread_local! {
+    /// When the task running on this worker thread is no longer waited for
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Time left until the deadline of the task running on this thread; `None`
+/// outside of tasks
+pub fn time_left() -> Option<Duration> {
+    DEADLINE
+        .with(Cell::get)
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Whether the task running on this thread is past its deadline, and its
+/// result no longer wanted
+pub fn cancelled() -> bool {
+    time_left() == Some(Duration::ZERO)
+}
+
+/// Run all tasks concurrently and wait until every task finished or the
+/// deadline passed. Results are returned in task order; tasks that did not
+/// finish in time yield `None` and are cancelled.
+pub fn join_with_deadline<T: Send + 'static>(
+    tasks: Vec<Task<T>>,
+    deadline: Duration,
+) -> Vec<Option<T>> {
+    let total = tasks.len();
+    let mut results: Vec<Option<T>> = (0..total).map(|_| None).collect();
+    if total == 0 {
+        return results;
+    }
+
+    let started = Instant::now();
+    let receiver = spawn_all(tasks, started + deadline);
+    let mut received = 0;
+
+    while received < total {
+        let remaining = match deadline.checked_sub(started.elapsed()) {
+            Some(remaining) => remaining,
+            None => break,
+        };
+
+        match receiver.recv_timeout(remaining) {
+            Ok((index, value)) => {
+                results[index] = Some(value);
+                received += 1;
+            }
+            Err(_) => break,
+        }
+    }
+
+    results
+}
+
+/// Run all tasks concurrently and return the first `Ok` result to arrive,
+/// together with the index of the task that produced it. When every task
+/// failed, the error of the last one to fail is returned; `None` when the
+/// deadline passed first. The other tasks are cancelled at the deadline
+/// even when a result arrived before it.
+pub fn first_ok<T: Send + 'static, E: Send + 'static>(
+    tasks: Vec<Task<Result<T, E>>>,
+    deadline: Duration,
+) -> Option<Result<(usize, T), E>> {
+    let total = tasks.len();
+    if total == 0 {
+        return None;
+    }
+
+    let started = Instant::now();
+    let receiver = spawn_all(tasks, started + deadline);
+    let mut received = 0;
+
+    while received < total {
+        let remaining = deadline.checked_sub(started.elapsed())?;
+        match receiver.recv_timeout(remaining) {
+            Ok((index, Ok(value))) => return Some(Ok((index, value))),
+            Ok((_, Err(error))) => {
+                received += 1;
+                if received == total {
+                    return Some(Err(error));
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+
+    None
+}
+
+/// `task` started on a thread of its own, which the deadline of the task
+/// waiting for it does not cancel: for work whose result is kept for later,
+/// such as the daemon's network cache. The returned task waits for the
+/// result, `None` when `task` panicked.
+pub fn uncancelled<T: Send + 'static>(task: Task<T>) -> Task<Option<T>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(task());
+    });
+    Box::new(move || receiver.recv().ok())
+}
+
+fn spawn_all<T: Send + 'static>(
+    tasks: Vec<Task<T>>,
+    deadline: Instant,
+) -> mpsc::Receiver<(usize, T)> {
+    let (sender, receiver) = mpsc::channel();
+
+    for (index, task) in tasks.into_iter().enumerate() {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            DEADLINE.with(|cell| cell.set(Some(deadline)));
+            // The receiver may be gone once the deadline passed
+            let _ = sender.send((index, task()));
+        });
+    }
+
+    receiver
+}
@@ -0,0 +1,109 @@
+//! The hand-off file: the latest rendered statusline and the data behind it,
+//! written after each render for tmux, desktop widgets and stream overlays.
+//! Readers never see a half-written file.
+
+use crate::config::{HandoffConfig, InputData, SegmentConfig};
+use crate::core::segments::SegmentData;
+use crate::utils::width::strip_ansi;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// In the state directory
+const DEFAULT_FILE: &str = "current-{session}.json";
+
+#[derive(Serialize)]
+struct Handoff<'a> {
+    session: &'a str,
+    /// Unix seconds
+    updated_at: u64,
+    cwd: &'a str,
+    model: &'a str,
+    /// The statusline with ANSI colors
+    line: &'a str,
+    /// The statusline without escape sequences
+    text: String,
+    segments: Vec<HandoffSegment<'a>>,
+}
+
+#[derive(Serialize)]
+struct HandoffSegment<'a> {
+    id: &'static str,
+    #[serde(flatten)]
+    data: &'a SegmentData,
+}
+
+/// Session id: the transcript's file name without `.jsonl`, limited to
+/// characters that are safe in a file name
+pub fn session_id(transcript_path: &str) -> String {
+    let stem = std::path::Path::new(transcript_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let id: String = stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    if id.is_empty() {
+        "unknown".to_string()
+    } else {
+        id
+    }
+}
+
+/// Where the hand-off file of a session goes
+pub fn path(config: &HandoffConfig, transcript_path: &str) -> Option<PathBuf> {
+    let Some(template) = config.path.as_deref() else {
+        let file = DEFAULT_FILE.replace("{session}", &session_id(transcript_path));
+        return Some(crate::utils::paths::state_dir().join(file));
+    };
+    let path = template.replace("{session}", &session_id(transcript_path));
+    match path.strip_prefix("~/") {
+        Some(rest) => Some(crate::utils::paths::home_dir()?.join(rest)),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+/// Write the hand-off file when it is enabled. Failures are ignored: the
+/// statusline itself must not depend on the file.
+pub fn write(
+    config: &HandoffConfig,
+    input: &InputData,
+    segments: &[(SegmentConfig, SegmentData)],
+    line: &str,
+) {
+    if !config.enabled {
+        return;
+    }
+    let Some(path) = path(config, &input.transcript_path) else {
+        return;
+    };
+
+    let session = session_id(&input.transcript_path);
+    let handoff = Handoff {
+        session: &session,
+        updated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        cwd: &input.workspace.current_dir,
+        model: &input.model.display_name,
+        line,
+        text: strip_ansi(line),
+        segments: segments
+            .iter()
+            .filter(|(config, _)| config.enabled)
+            .map(|(config, data)| HandoffSegment {
+                id: config.id.as_str(),
+                data,
+            })
+            .collect(),
+    };
+    let Ok(json) = serde_json::to_vec_pretty(&handoff) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = crate::utils::atomic::write(&path, &json);
+}
@@ -0,0 +1,166 @@
+use crate::config::{Config, InputData, SegmentConfig, SegmentId};
+use crate::core::segments::{with_registry, SegmentData};
+use crate::core::statusline::{collect_segment, placeholder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Scenarios compiled into the binary, also usable as templates for custom fixtures
+const BUILTIN_SCENARIOS: &[(&str, &str)] = &[
+    ("default", include_str!("../../assets/mock/default.json")),
+    (
+        "long-session",
+        include_str!("../../assets/mock/long-session.json"),
+    ),
+    (
+        "huge-context",
+        include_str!("../../assets/mock/huge-context.json"),
+    ),
+    (
+        "dirty-git",
+        include_str!("../../assets/mock/dirty-git.json"),
+    ),
+    (
+        "offline-quota",
+        include_str!("../../assets/mock/offline-quota.json"),
+    ),
+];
+
+/// Segment data supplied by a fixture instead of live collection
+#[derive(Debug, Clone, Deserialize)]
+struct MockSegmentData {
+    primary: String,
+    #[serde(default)]
+    secondary: String,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// A fabricated statusline payload plus optional per-segment data overrides
+#[derive(Deserialize)]
+pub struct MockScenario {
+    #[serde(default)]
+    pub description: String,
+    pub input: InputData,
+    /// Fixed data for segments that depend on the environment (git, network).
+    /// A `null` value hides the segment.
+    #[serde(default)]
+    segments: HashMap<SegmentId, Option<MockSegmentData>>,
+}
+
+impl MockScenario {
+    /// Load a scenario by fixture path, user scenario name, or built-in name
+    pub fn load(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Path::new(name);
+        if path.is_file() {
+            return Self::load_from_file(path);
+        }
+
+        let user_path = Self::get_scenarios_path().join(format!("{}.json", name));
+        if user_path.is_file() {
+            return Self::load_from_file(&user_path);
+        }
+
+        if let Some((_, content)) = BUILTIN_SCENARIOS.iter().find(|(n, _)| *n == name) {
+            return Ok(serde_json::from_str(content)?);
+        }
+
+        Err(format!(
+            "Unknown mock scenario '{}'. Run `ccline --mock list` to see available scenarios",
+            name
+        )
+        .into())
+    }
+
+    fn load_from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Get the user scenarios directory (mock/ in the config directory)
+    pub fn get_scenarios_path() -> PathBuf {
+        crate::utils::paths::config_dir().join("mock")
+    }
+
+    /// List all available scenarios (built-in + user fixtures) with descriptions
+    pub fn list_available() -> Vec<(String, String)> {
+        let mut scenarios: Vec<(String, String)> = BUILTIN_SCENARIOS
+            .iter()
+            .map(|(name, content)| {
+                let description = serde_json::from_str::<MockScenario>(content)
+                    .map(|s| s.description)
+                    .unwrap_or_default();
+                (name.to_string(), description)
+            })
+            .collect();
+
+        if let Ok(entries) = fs::read_dir(Self::get_scenarios_path()) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                let description = match Self::load_from_file(&path) {
+                    Ok(scenario) => scenario.description,
+                    Err(e) => format!("invalid fixture: {}", e),
+                };
+
+                if let Some(existing) = scenarios.iter_mut().find(|(n, _)| n == name) {
+                    existing.1 = description;
+                } else {
+                    scenarios.push((name.to_string(), description));
+                }
+            }
+        }
+
+        scenarios
+    }
+
+    /// Collect segment data for the scenario. Fixture overrides win; network
+    /// segments without an override are skipped so mock renders never go online.
+    pub fn collect(&self, config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+        crate::i18n::set_language(config.language.as_deref());
+        let segments = config.resolved_segments();
+
+        let mut collected: Vec<Option<SegmentData>> = segments
+            .iter()
+            .map(
+                |segment_config| match self.segments.get(&segment_config.id) {
+                    Some(Some(mock)) => Some(SegmentData {
+                        primary: mock.primary.clone(),
+                        secondary: mock.secondary.clone(),
+                        metadata: mock.metadata.clone(),
+                    }),
+                    Some(None) => None,
+                    None => {
+                        let is_network = with_registry(|registry| {
+                            registry
+                                .info(segment_config.id)
+                                .map(|info| info.network)
+                                .unwrap_or(false)
+                        });
+                        if is_network {
+                            None
+                        } else {
+                            collect_segment(segment_config, &self.input)
+                        }
+                    }
+                },
+            )
+            .collect();
+        crate::core::segments::computed::fill(&segments, &mut collected);
+
+        segments
+            .into_iter()
+            .zip(collected)
+            .filter_map(|(segment_config, data)| {
+                let data = data.or_else(|| placeholder(&segment_config))?;
+                Some((segment_config, data))
+            })
+            .collect()
+    }
+}
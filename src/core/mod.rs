@@ -1,4 +1,22 @@
+pub mod alerts;
+pub mod cache_gc;
+pub mod captured_input;
+pub mod emergency;
+pub mod executor;
+pub mod handoff;
+pub mod mock;
+pub mod output;
+pub mod pricing;
+pub mod rules;
 pub mod segments;
+pub mod session_cache;
+pub mod spend;
 pub mod statusline;
+pub mod summary;
+pub mod transform;
+pub mod weekly;
 
-pub use statusline::{collect_all_segments, StatusLineGenerator};
+pub use statusline::{
+    collect_all_segments, collect_segment, collect_segment_cached, try_collect_segment,
+    StatusLineGenerator,
+};
@@ -0,0 +1,337 @@
+//! Output formats besides the ANSI line Claude Code shows: plain text,
+//! descriptive sentences for screen readers, and the formats of Waybar and
+//! Polybar modules, so desktop bars can show the same segments.
+
+use crate::config::{AnsiColor, SegmentConfig, SegmentId};
+use crate::core::segments::{segment_name, SegmentData};
+use crate::i18n;
+use crate::utils::color::to_rgb;
+use crate::utils::width::strip_ansi;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// The colored statusline
+    #[default]
+    Ansi,
+    /// The statusline without escape sequences
+    Plain,
+    /// JSON for a Waybar custom module with `"return-type": "json"`
+    Waybar,
+    /// The statusline with Polybar color tags
+    Polybar,
+    /// Sentences for screen readers, without icons, symbols or colors
+    A11y,
+}
+
+impl OutputFormat {
+    /// Desktop bars run ccline on their own schedule, without a payload on
+    /// stdin, and render the last one Claude Code sent instead
+    pub fn is_widget(&self) -> bool {
+        matches!(self, OutputFormat::Waybar | OutputFormat::Polybar)
+    }
+}
+
+/// The output for `format` from a rendered line and the segments behind it
+pub fn format(
+    format: OutputFormat,
+    segments: &[(SegmentConfig, SegmentData)],
+    line: &str,
+) -> String {
+    match format {
+        OutputFormat::Ansi => line.to_string(),
+        OutputFormat::Plain => strip_ansi(line),
+        OutputFormat::Waybar => waybar(segments, line),
+        OutputFormat::Polybar => polybar(line),
+        OutputFormat::A11y => a11y(segments),
+    }
+}
+
+/// Words for the symbols in segment text; sparkline blocks say nothing
+/// useful when read out and are dropped
+const SPOKEN: &[(&str, &str)] = &[
+    (" · ", ", "),
+    ("→", "to"),
+    ("✓", "yes"),
+    ("✗", "no"),
+    ("…", ""),
+    ("▁", ""),
+    ("▂", ""),
+    ("▃", ""),
+    ("▄", ""),
+    ("▅", ""),
+    ("▆", ""),
+    ("▇", ""),
+    ("█", ""),
+];
+
+fn spoken(text: &str) -> String {
+    let mut text = strip_ansi(text);
+    for (symbol, words) in SPOKEN {
+        text = text.replace(symbol, words);
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// One sentence per segment: "Model: Sonnet 4. Git branch main, uncommitted
+/// changes, 2 ahead."
+fn a11y(segments: &[(SegmentConfig, SegmentData)]) -> String {
+    segments
+        .iter()
+        .filter(|(config, _)| config.enabled)
+        .map(|(config, data)| {
+            let sentence = match config.id {
+                SegmentId::Git if data.metadata.contains_key("branch") => git_sentence(data),
+                _ => {
+                    let mut sentence =
+                        format!("{}: {}", segment_name(config.id), spoken(&data.primary));
+                    let secondary = spoken(&data.secondary);
+                    if !secondary.is_empty() {
+                        sentence.push_str(", ");
+                        sentence.push_str(&secondary);
+                    }
+                    sentence
+                }
+            };
+            format!("{}.", sentence.trim_end_matches(['.', ',', ' ']))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The git segment's symbols spelled out from its metadata
+fn git_sentence(data: &SegmentData) -> String {
+    let metadata = &data.metadata;
+    let mut parts = vec![i18n::text_with(
+        "a11y.git_branch",
+        &[metadata.get("branch").map(String::as_str).unwrap_or("")],
+    )];
+    parts.push(match metadata.get("status").map(String::as_str) {
+        Some("Dirty") => i18n::text("a11y.git_dirty"),
+        Some("Conflicts") => i18n::text("a11y.git_conflicts"),
+        _ => i18n::text("a11y.git_clean"),
+    });
+    for (key, text) in [("ahead", "a11y.ahead"), ("behind", "a11y.behind")] {
+        if let Some(count) = metadata.get(key).filter(|count| count.as_str() != "0") {
+            parts.push(i18n::text_with(text, &[count]));
+        }
+    }
+    parts.join(", ")
+}
+
+/// Output for a bar when there is nothing to show yet
+pub fn empty(format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Waybar => waybar(&[], ""),
+        _ => String::new(),
+    }
+}
+
+#[derive(Serialize)]
+struct WaybarModule {
+    text: String,
+    tooltip: String,
+    class: Vec<String>,
+    /// Context window used, for `format-icons` and `states`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<u8>,
+}
+
+/// Waybar treats `text` and `tooltip` as Pango markup
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// `text` is the plain statusline, `tooltip` one line per segment. `class`
+/// holds `warning` or `critical` when a segment crossed a threshold, and
+/// `<segment>-<state>` for segments that report a state (`attention-permission`,
+/// `vim-normal`), for styling in Waybar's CSS.
+fn waybar(segments: &[(SegmentConfig, SegmentData)], line: &str) -> String {
+    let enabled: Vec<_> = segments
+        .iter()
+        .filter(|(config, _)| config.enabled)
+        .collect();
+
+    let tooltip = enabled
+        .iter()
+        .map(|(config, data)| {
+            // A segment that failed says why rather than showing its icon
+            if let Some(tooltip) = data.metadata.get("tooltip") {
+                return escape_markup(&format!("{}: {}", segment_name(config.id), tooltip));
+            }
+            let mut text = format!("{}: {}", segment_name(config.id), data.primary);
+            if !data.secondary.is_empty() {
+                text.push(' ');
+                text.push_str(&data.secondary);
+            }
+            escape_markup(&text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut class = Vec::new();
+    for level in ["critical", "warning"] {
+        if enabled
+            .iter()
+            .any(|(_, data)| data.metadata.get("level").map(String::as_str) == Some(level))
+        {
+            class.push(level.to_string());
+            break;
+        }
+    }
+    for (config, data) in &enabled {
+        if let Some(state) = data.metadata.get("state") {
+            class.push(format!("{}-{}", config.id.as_str(), state));
+        }
+    }
+
+    let percentage = enabled
+        .iter()
+        .find_map(|(config, data)| {
+            (config.id == SegmentId::Usage)
+                .then(|| data.metadata.get("percentage")?.parse::<f64>().ok())
+                .flatten()
+        })
+        .map(|percentage| percentage.round().clamp(0.0, 100.0) as u8);
+
+    let module = WaybarModule {
+        text: escape_markup(&strip_ansi(line)),
+        tooltip,
+        class,
+        percentage,
+    };
+    serde_json::to_string(&module).unwrap_or_default()
+}
+
+fn hex(color: &AnsiColor) -> String {
+    let (r, g, b) = to_rgb(color);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Translate the SGR color codes of a rendered line into Polybar's
+/// `%{F#rrggbb}` and `%{B#rrggbb}` tags; other escape sequences are dropped
+fn polybar(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            // Polybar reads %{ as the start of a tag
+            if ch == '%' {
+                out.push_str("%%");
+            } else {
+                out.push(ch);
+            }
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut command = None;
+        for c in chars.by_ref() {
+            if c.is_ascii_digit() || c == ';' {
+                params.push(c);
+            } else {
+                command = Some(c);
+                break;
+            }
+        }
+        if command == Some('m') {
+            out.push_str(&polybar_tags(&params));
+        }
+    }
+    out
+}
+
+/// Polybar tags for the parameters of one SGR sequence
+fn polybar_tags(params: &str) -> String {
+    let codes: Vec<u16> = params
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut tags = String::new();
+    let mut index = 0;
+    while index < codes.len() {
+        let code = codes[index];
+        let (tag, color, used) = match code {
+            0 => {
+                tags.push_str("%{F-}%{B-}");
+                index += 1;
+                continue;
+            }
+            39 => ("F", None, 1),
+            49 => ("B", None, 1),
+            30..=37 => (
+                "F",
+                Some(AnsiColor::Color16 {
+                    c16: (code - 30) as u8,
+                }),
+                1,
+            ),
+            90..=97 => (
+                "F",
+                Some(AnsiColor::Color16 {
+                    c16: (code - 82) as u8,
+                }),
+                1,
+            ),
+            40..=47 => (
+                "B",
+                Some(AnsiColor::Color16 {
+                    c16: (code - 40) as u8,
+                }),
+                1,
+            ),
+            100..=107 => (
+                "B",
+                Some(AnsiColor::Color16 {
+                    c16: (code - 92) as u8,
+                }),
+                1,
+            ),
+            38 | 48 => {
+                let tag = if code == 38 { "F" } else { "B" };
+                match codes.get(index + 1) {
+                    Some(5) => (
+                        tag,
+                        codes
+                            .get(index + 2)
+                            .map(|&c| AnsiColor::Color256 { c256: c as u8 }),
+                        3,
+                    ),
+                    Some(2) => (
+                        tag,
+                        match (
+                            codes.get(index + 2),
+                            codes.get(index + 3),
+                            codes.get(index + 4),
+                        ) {
+                            (Some(&r), Some(&g), Some(&b)) => Some(AnsiColor::Rgb {
+                                r: r as u8,
+                                g: g as u8,
+                                b: b as u8,
+                            }),
+                            _ => None,
+                        },
+                        5,
+                    ),
+                    _ => (tag, None, 1),
+                }
+            }
+            // Bold, italic and the like have no Polybar equivalent
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        match color {
+            Some(color) => tags.push_str(&format!("%{{{}{}}}", tag, hex(&color))),
+            None => tags.push_str(&format!("%{{{}-}}", tag)),
+        }
+        index += used;
+    }
+    tags
+}
@@ -0,0 +1,191 @@
+//! Model prices for costs ccline works out from token counts.
+//!
+//! Claude Code reports a session's cost itself, so prices are only needed
+//! where no reported cost exists. A `pricing` entry in models.toml comes
+//! first, and built-in list prices cover Claude models; with
+//! `pricing.auto_update` a published price list (LiteLLM's format) is fetched
+//! into `pricing.json` in the cache directory once the last copy is older than
+//! `ttl_days`. A failed fetch keeps the last copy, and without one the
+//! built-in prices apply.
+
+use crate::config::{ModelConfig, ModelPrice, PricingConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "pricing")]
+const DEFAULT_URL: &str =
+    "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
+
+/// List prices matched against the model id, most specific pattern first;
+/// cache writes at the 5-minute rate
+const BUILT_IN: &[(&str, ModelPrice)] = &[
+    ("opus-4-5", ModelPrice::new(5.0, 25.0, 6.25, 0.5)),
+    ("opus-4", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+    ("3-opus", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+    ("sonnet", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+    ("haiku-4-5", ModelPrice::new(1.0, 5.0, 1.25, 0.1)),
+    ("3-5-haiku", ModelPrice::new(0.8, 4.0, 1.0, 0.08)),
+    ("3-haiku", ModelPrice::new(0.25, 1.25, 0.3, 0.03)),
+];
+
+/// An entry of the published price list, prices per token
+#[derive(Deserialize)]
+struct FeedEntry {
+    input_cost_per_token: Option<f64>,
+    output_cost_per_token: Option<f64>,
+    cache_creation_input_token_cost: Option<f64>,
+    cache_read_input_token_cost: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PricingFile {
+    /// Unix seconds
+    fetched_at: u64,
+    /// Keyed by model name as the feed lists it, e.g. "claude-sonnet-4-5"
+    /// or "openrouter/openai/gpt-4o"
+    models: HashMap<String, ModelPrice>,
+}
+
+fn pricing_path() -> PathBuf {
+    crate::utils::paths::cache_dir().join("pricing.json")
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn load() -> Option<PricingFile> {
+    let content = fs::read_to_string(pricing_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Prices from a LiteLLM-format list; entries without input and output
+/// prices, such as image and embedding models, are left out
+#[cfg_attr(not(feature = "pricing"), allow(dead_code))]
+fn parse_feed(feed: HashMap<String, serde_json::Value>) -> HashMap<String, ModelPrice> {
+    feed.into_iter()
+        .filter_map(|(name, entry)| {
+            let entry: FeedEntry = serde_json::from_value(entry).ok()?;
+            let per_million = |per_token: Option<f64>| per_token.unwrap_or(0.0) * 1_000_000.0;
+            let input = entry.input_cost_per_token?;
+            let output = entry.output_cost_per_token?;
+            Some((
+                name,
+                ModelPrice {
+                    input: input * 1_000_000.0,
+                    output: output * 1_000_000.0,
+                    cache_write: per_million(entry.cache_creation_input_token_cost),
+                    cache_read: per_million(entry.cache_read_input_token_cost),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(feature = "pricing")]
+fn fetch(config: &PricingConfig) -> Option<PricingFile> {
+    let url = config.url.as_deref().unwrap_or(DEFAULT_URL);
+    // `ttl_days` already decided a refresh is due; an unchanged feed is
+    // answered with 304 instead of the whole list
+    let body = crate::utils::http::get_cached(
+        crate::utils::http::agent().get(url),
+        std::time::Duration::ZERO,
+    )
+    .ok()?;
+    let feed: HashMap<String, serde_json::Value> = serde_json::from_str(&body).ok()?;
+    let file = PricingFile {
+        fetched_at: unix_now(),
+        models: parse_feed(feed),
+    };
+    if file.models.is_empty() {
+        return None;
+    }
+
+    if let Ok(json) = serde_json::to_vec(&file) {
+        let path = pricing_path();
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = crate::utils::atomic::write(&path, &json);
+    }
+    Some(file)
+}
+
+#[cfg(not(feature = "pricing"))]
+fn fetch(_config: &PricingConfig) -> Option<PricingFile> {
+    None
+}
+
+/// Fetched prices, refreshed first when `auto_update` is on and the last
+/// copy is older than `ttl_days`
+fn fetched(config: &PricingConfig) -> Option<PricingFile> {
+    let cached = load();
+    if !config.auto_update {
+        return cached;
+    }
+    let fresh = cached
+        .as_ref()
+        .is_some_and(|file| unix_now().saturating_sub(file.fetched_at) < config.ttl_days * 86_400);
+    if fresh {
+        return cached;
+    }
+    fetch(config).or(cached)
+}
+
+/// The feed's price for `model`: its own entry, else one listed under a
+/// provider prefix such as "anthropic/"
+fn feed_price(models: &HashMap<String, ModelPrice>, model: &str) -> Option<ModelPrice> {
+    if let Some(price) = models.get(model) {
+        return Some(*price);
+    }
+    let mut prefixed: Vec<(&String, &ModelPrice)> = models
+        .iter()
+        .filter(|(name, _)| name.rsplit('/').next() == Some(model))
+        .collect();
+    // The same model under several providers; pick one deterministically
+    prefixed.sort_by(|a, b| a.0.cmp(b.0));
+    prefixed.first().map(|(_, price)| **price)
+}
+
+fn built_in_price(model: &str) -> Option<ModelPrice> {
+    let model = model.to_lowercase();
+    BUILT_IN
+        .iter()
+        .find(|(pattern, _)| model.contains(pattern))
+        .map(|(_, price)| *price)
+}
+
+/// Prices looked up for one report or render, so the lists are read once
+pub struct Prices {
+    models: ModelConfig,
+    fetched: Option<HashMap<String, ModelPrice>>,
+}
+
+impl Prices {
+    pub fn load(config: &PricingConfig) -> Self {
+        Self {
+            models: ModelConfig::load(),
+            fetched: fetched(config).map(|file| file.models),
+        }
+    }
+
+    /// Price of `model` from its models.toml entry, else the fetched list,
+    /// else the built-in one
+    pub fn get(&self, model: &str) -> Option<ModelPrice> {
+        self.models
+            .entry(model)
+            .and_then(|entry| entry.pricing)
+            .or_else(|| {
+                self.fetched
+                    .as_ref()
+                    .and_then(|models| feed_price(models, model))
+            })
+            .or_else(|| built_in_price(model))
+    }
+}
@@ -0,0 +1,80 @@
+//! Rules deciding which enabled segments a render shows, evaluated after
+//! collection and before styling.
+//!
+//! Focus mode (`[focus]`) hides the segments it lists during its hours or
+//! while its toggle file exists. A hidden segment that reports a `critical`
+//! level is shown anyway, since that is what emergency rendering is for.
+
+use crate::config::{Config, FocusConfig, SegmentConfig};
+use crate::core::segments::SegmentData;
+
+/// Enabled segments that no rule hides, in their order
+pub fn visible(
+    config: &Config,
+    segments: Vec<(SegmentConfig, SegmentData)>,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    let focus = is_focus_active(&config.focus);
+    segments
+        .into_iter()
+        .filter(|(segment, _)| segment.enabled)
+        .filter(|(segment, data)| {
+            !focus
+                || !config.focus.segments.contains(&segment.id)
+                || data.metadata.get("level").map(String::as_str) == Some("critical")
+        })
+        .collect()
+}
+
+/// Whether focus mode is on: the toggle file exists or the local time is
+/// within one of the hours
+pub fn is_focus_active(focus: &FocusConfig) -> bool {
+    if focus.segments.is_empty() {
+        return false;
+    }
+    if focus.toggle_path().exists() {
+        return true;
+    }
+    if focus.hours.is_empty() {
+        return false;
+    }
+    let now = minutes_since_midnight();
+    focus
+        .hours
+        .iter()
+        .filter_map(|range| parse_range(range))
+        .any(|(start, end)| {
+            if start <= end {
+                (start..end).contains(&now)
+            } else {
+                now >= start || now < end
+            }
+        })
+}
+
+/// "09:00-12:00" as minutes since midnight
+pub fn parse_range(range: &str) -> Option<(u32, u32)> {
+    let (start, end) = range.split_once('-')?;
+    Some((parse_time(start)?, parse_time(end)?))
+}
+
+fn parse_time(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours <= 24 && minutes < 60 && hours * 60 + minutes <= 24 * 60).then_some(hours * 60 + minutes)
+}
+
+/// Local time of day (UTC without chrono)
+fn minutes_since_midnight() -> u32 {
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::Timelike;
+        chrono::Local::now().num_seconds_from_midnight() / 60
+    }
+    #[cfg(not(feature = "chrono"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|now| (now.as_secs() % 86_400 / 60) as u32)
+            .unwrap_or(0)
+    }
+}
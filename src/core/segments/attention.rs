@@ -0,0 +1,284 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{AnsiColor, InputData, SegmentId};
+use crate::i18n;
+use crate::utils::claude_settings::ClaudeSettings;
+use crate::utils::transcript;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// How much of the end of the transcript is read
+const TAIL_BYTES: u64 = 256 * 1024;
+
+/// Tools Claude Code runs without asking
+const READ_ONLY_TOOLS: &[&str] = &[
+    "Read",
+    "Glob",
+    "Grep",
+    "LS",
+    "NotebookRead",
+    "TodoRead",
+    "TodoWrite",
+    "Task",
+    "BashOutput",
+    "KillShell",
+    "WebSearch",
+];
+
+/// Tools `acceptEdits` mode approves
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Badge colors used when the segment sets no `state_colors`: state,
+/// background, text
+pub const DEFAULT_STATE_COLORS: &[(&str, AnsiColor, AnsiColor)] = &[
+    (
+        "permission",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "question",
+        AnsiColor::Color256 { c256: 208 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+    (
+        "plan",
+        AnsiColor::Color256 { c256: 208 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+    (
+        "interrupted",
+        AnsiColor::Color256 { c256: 240 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+];
+
+/// What the session is waiting for
+#[derive(Debug, Clone, PartialEq)]
+enum Waiting {
+    /// Approval of a tool call
+    Permission(String),
+    /// An answer to a question Claude asked
+    Question,
+    /// Approval of a plan
+    Plan,
+    /// The user interrupted the last request
+    Interrupted,
+}
+
+impl Waiting {
+    fn state(&self) -> &'static str {
+        match self {
+            Waiting::Permission(_) => "permission",
+            Waiting::Question => "question",
+            Waiting::Plan => "plan",
+            Waiting::Interrupted => "interrupted",
+        }
+    }
+}
+
+pub struct AttentionSegment {
+    after: Duration,
+    show_interrupted: bool,
+}
+
+impl Default for AttentionSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AttentionSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Attention,
+        name: "Attention",
+        description: "Badge while Claude waits for a permission, an answer or plan approval",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "after_seconds",
+                kind: "integer",
+                default: "3",
+                description: "How long a tool call must wait before it counts as a permission prompt",
+            },
+            SegmentOptionInfo {
+                name: "show_interrupted",
+                kind: "bool",
+                default: "true",
+                description: "Also show a badge after a request was interrupted",
+            },
+            SegmentOptionInfo {
+                name: "state_colors",
+                kind: "table",
+                default: "{}",
+                description: "Colors per state: permission, question, plan, interrupted",
+            },
+        ],
+        data_sources: &[
+            "transcript (last 256 KiB)",
+            "~/.claude/settings.json, .claude/settings.json, .claude/settings.local.json: permissions",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "!",
+            emoji: "🔔",
+            nerd_font: "\u{f0f3}",
+            nerd_font_v2: "\u{f0f3}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            after: Duration::from_secs(3),
+            show_interrupted: true,
+        }
+    }
+
+    pub fn with_after(mut self, after: Duration) -> Self {
+        self.after = after;
+        self
+    }
+
+    pub fn with_interrupted(mut self, show_interrupted: bool) -> Self {
+        self.show_interrupted = show_interrupted;
+        self
+    }
+
+    /// Tool calls without a result, and whether the last user message was an
+    /// interruption, after replaying the transcript's tail
+    fn pending(tail: &str) -> (Vec<String>, bool) {
+        let mut pending: Vec<(String, String)> = Vec::new();
+        let mut interrupted = false;
+
+        for line in tail.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let Ok(entry) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            let Some(content) = entry
+                .get("message")
+                .and_then(|message| message.get("content"))
+            else {
+                continue;
+            };
+            match entry.get("type").and_then(Value::as_str) {
+                Some("assistant") => {
+                    interrupted = false;
+                    for item in content.as_array().into_iter().flatten() {
+                        if item.get("type").and_then(Value::as_str) == Some("tool_use") {
+                            let id = item.get("id").and_then(Value::as_str).unwrap_or_default();
+                            let name = item.get("name").and_then(Value::as_str).unwrap_or_default();
+                            pending.push((id.to_string(), name.to_string()));
+                        }
+                    }
+                }
+                Some("user") => {
+                    let items: Vec<&Value> = match content {
+                        Value::Array(items) => items.iter().collect(),
+                        text => vec![text],
+                    };
+                    for item in items {
+                        if item.get("type").and_then(Value::as_str) == Some("tool_result") {
+                            let id = item.get("tool_use_id").and_then(Value::as_str);
+                            pending.retain(|(pending_id, _)| Some(pending_id.as_str()) != id);
+                        }
+                        let text = item
+                            .as_str()
+                            .or_else(|| item.get("text").and_then(Value::as_str))
+                            .unwrap_or_default();
+                        interrupted = text.starts_with("[Request interrupted by user");
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (
+            pending.into_iter().map(|(_, name)| name).collect(),
+            interrupted,
+        )
+    }
+
+    /// Whether the settings approve `tool` without asking: a rule naming the
+    /// whole tool ("Bash", "mcp__github"), or the edit tools in `acceptEdits`
+    /// mode. Rules with a pattern ("Bash(npm test:*)") may or may not match
+    /// the call, so they do not count.
+    fn approved(tool: &str, settings: &ClaudeSettings) -> bool {
+        match settings.default_mode() {
+            Some("bypassPermissions") => return true,
+            Some("acceptEdits") if EDIT_TOOLS.contains(&tool) => return true,
+            _ => {}
+        }
+        settings.allowed().iter().any(|rule| {
+            *rule == tool
+                || (rule.starts_with("mcp__")
+                    && tool
+                        .strip_prefix(*rule)
+                        .is_some_and(|rest| rest.starts_with("__")))
+        })
+    }
+
+    fn waiting(&self, input: &InputData) -> Option<(Waiting, Duration)> {
+        let path = Path::new(&input.transcript_path);
+        let idle = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .unwrap_or_default();
+        let (pending, interrupted) = Self::pending(&transcript::tail(path, TAIL_BYTES)?);
+
+        if pending.iter().any(|tool| tool == "AskUserQuestion") {
+            return Some((Waiting::Question, idle));
+        }
+        if pending.iter().any(|tool| tool == "ExitPlanMode") {
+            return Some((Waiting::Plan, idle));
+        }
+
+        // A tool that is still running looks the same as one waiting for
+        // approval; only tools the settings do not approve count, and only
+        // once the transcript has been quiet for a moment
+        if idle >= self.after {
+            let settings = ClaudeSettings::load(&input.workspace.current_dir);
+            if let Some(tool) = pending.iter().find(|tool| {
+                !READ_ONLY_TOOLS.contains(&tool.as_str()) && !Self::approved(tool, &settings)
+            }) {
+                return Some((Waiting::Permission(tool.clone()), idle));
+            }
+        }
+
+        if interrupted && pending.is_empty() && self.show_interrupted {
+            return Some((Waiting::Interrupted, idle));
+        }
+        None
+    }
+}
+
+impl Segment for AttentionSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (waiting, idle) = self.waiting(input)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("state".to_string(), waiting.state().to_string());
+        metadata.insert("waiting_seconds".to_string(), idle.as_secs().to_string());
+        let primary = match &waiting {
+            Waiting::Permission(tool) => {
+                metadata.insert("tool".to_string(), tool.clone());
+                i18n::text_with("attention.permission", &[tool])
+            }
+            Waiting::Question => i18n::text("attention.question"),
+            Waiting::Plan => i18n::text("attention.plan"),
+            Waiting::Interrupted => i18n::text("attention.interrupted"),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Attention
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, SegmentConfig, SegmentId};
+use crate::utils::expression::Expression;
+use std::collections::HashMap;
+
+/// A value worked out from the metadata of other segments, such as cost per
+/// hour from `cost.cost / (cost.duration_ms / 3600000)`. It has nothing to
+/// collect itself: [`ComputedSegment::compute`] runs once the other
+/// segments are collected. A config may hold several, told apart by their
+/// `name` option.
+pub struct ComputedSegment {
+    expression: Option<Expression>,
+    format: String,
+    decimals: usize,
+}
+
+impl Default for ComputedSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ComputedSegment {
+    /// More decimal places than `f64` holds would only show noise
+    pub const MAX_DECIMALS: usize = 15;
+
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Computed,
+        name: "Computed",
+        description: "A value worked out from other segments' metadata, such as cost per hour",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "name",
+                kind: "string",
+                default: "",
+                description: "Tells several computed segments apart; later ones can use this one's value as <name>.value",
+            },
+            SegmentOptionInfo {
+                name: "expression",
+                kind: "string",
+                default: "",
+                description: "Arithmetic over <segment>.<metadata> names, e.g. \"cost.cost / (cost.duration_ms / 3600000)\"",
+            },
+            SegmentOptionInfo {
+                name: "format",
+                kind: "string",
+                default: "{}",
+                description: "Text shown, with {} replaced by the value, e.g. \"${}/h\"",
+            },
+            SegmentOptionInfo {
+                name: "decimals",
+                kind: "integer",
+                default: "2",
+                description: "Decimal places of the value, at most 15",
+            },
+        ],
+        data_sources: &["metadata of the segments named in the expression"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "=",
+            emoji: "🧮",
+            nerd_font: "\u{f00ec}",
+            nerd_font_v2: "\u{f1ec}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            expression: None,
+            format: "{}".to_string(),
+            decimals: 2,
+        }
+    }
+
+    /// The segment as its `options` describe it; an expression that does
+    /// not parse leaves it empty
+    pub fn from_config(config: &SegmentConfig) -> Self {
+        let option = |name: &str| config.options.get(name);
+        let mut segment = Self::new();
+        if let Some(expression) = option("expression").and_then(|v| v.as_str()) {
+            segment = segment.with_expression(expression);
+        }
+        if let Some(format) = option("format").and_then(|v| v.as_str()) {
+            segment.format = format.to_string();
+        }
+        if let Some(decimals) = option("decimals").and_then(|v| v.as_u64()) {
+            segment.decimals = (decimals as usize).min(Self::MAX_DECIMALS);
+        }
+        segment
+    }
+
+    pub fn with_expression(mut self, expression: &str) -> Self {
+        self.expression = Expression::parse(expression).ok();
+        self
+    }
+
+    /// The name of a computed segment's config entry, which its value is
+    /// found under by later computed segments
+    pub fn name(config: &SegmentConfig) -> &str {
+        config
+            .options
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+    }
+
+    /// The value over the data of the collected segments, keyed by segment
+    /// name; `None` when a name in the expression has no numeric value
+    pub fn compute<'a>(
+        &self,
+        collected: impl IntoIterator<Item = (&'a str, &'a SegmentData)>,
+    ) -> Option<SegmentData> {
+        let expression = self.expression.as_ref()?;
+        let collected: Vec<(&str, &SegmentData)> = collected.into_iter().collect();
+        let lookup = |name: &str| {
+            let (segment, key) = name.split_once('.')?;
+            let (_, data) = collected.iter().find(|(name, _)| *name == segment)?;
+            data.metadata.get(key)?.trim().parse::<f64>().ok()
+        };
+        let value = expression.evaluate(&lookup)?;
+
+        let text = format!("{:.*}", self.decimals, value);
+        let mut metadata = HashMap::new();
+        metadata.insert("value".to_string(), value.to_string());
+        Some(SegmentData {
+            primary: self.format.replace("{}", &text),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+}
+
+impl Segment for ComputedSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        None
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Computed
+    }
+}
+
+/// Fill in the computed segments among `segments` from what was collected
+/// for the others, with their transforms applied. A computed segment also
+/// sees the named computed segments before it.
+pub fn fill(segments: &[SegmentConfig], collected: &mut [Option<SegmentData>]) {
+    for index in 0..segments.len() {
+        let config = &segments[index];
+        if config.id != SegmentId::Computed {
+            continue;
+        }
+        let data = ComputedSegment::from_config(config).compute(
+            segments
+                .iter()
+                .zip(collected.iter())
+                .enumerate()
+                .filter_map(|(other_index, (other, data))| {
+                    let name = if other.id != SegmentId::Computed {
+                        other.id.as_str()
+                    } else if other_index < index {
+                        ComputedSegment::name(other)
+                    } else {
+                        return None;
+                    };
+                    Some((name, data.as_ref()?))
+                })
+                .filter(|(name, _)| !name.is_empty()),
+        );
+        collected[index] = data
+            .map(|data| crate::core::transform::apply(&config.transforms, data))
+            .map(SegmentData::sanitized);
+    }
+}
@@ -0,0 +1,219 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, SegmentId};
+use crate::i18n;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContainerKind {
+    Codespaces,
+    DevContainer,
+    Docker,
+    Podman,
+    Kubernetes,
+}
+
+impl ContainerKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContainerKind::Codespaces => "Codespaces",
+            ContainerKind::DevContainer => "Dev Container",
+            ContainerKind::Docker => "Docker",
+            ContainerKind::Podman => "Podman",
+            ContainerKind::Kubernetes => "Kubernetes",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            ContainerKind::Codespaces => "codespaces",
+            ContainerKind::DevContainer => "devcontainer",
+            ContainerKind::Docker => "docker",
+            ContainerKind::Podman => "podman",
+            ContainerKind::Kubernetes => "kubernetes",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ContainerInfo {
+    pub kind: ContainerKind,
+    /// Codespace, dev container, image or container name when one is known
+    pub name: Option<String>,
+}
+
+pub struct ContainerSegment {
+    show_host: bool,
+}
+
+impl Default for ContainerSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContainerSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Container,
+        name: "Container",
+        description: "Codespace, dev container or Docker container the session runs in",
+        features: &[],
+        options: &[SegmentOptionInfo {
+            name: "show_host",
+            kind: "bool",
+            default: "false",
+            description: "Show \"host\" instead of hiding the segment outside containers",
+        }],
+        data_sources: &[
+            "env: CODESPACES, CODESPACE_NAME, REMOTE_CONTAINERS, KUBERNETES_SERVICE_HOST",
+            "/.dockerenv",
+            "/run/.containerenv",
+            "/proc/1/cgroup",
+            ".devcontainer/devcontainer.json in workspace.current_dir",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "ctr",
+            emoji: "📦",
+            nerd_font: "\u{f308}",
+            nerd_font_v2: "\u{f308}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self { show_host: false }
+    }
+
+    pub fn with_host(mut self, show_host: bool) -> Self {
+        self.show_host = show_host;
+        self
+    }
+
+    /// Work out where the session runs, most specific environment first:
+    /// a codespace and a dev container are also Docker containers
+    pub fn detect(working_dir: &str) -> Option<ContainerInfo> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        if env("CODESPACES").as_deref() == Some("true") {
+            return Some(ContainerInfo {
+                kind: ContainerKind::Codespaces,
+                name: env("CODESPACE_NAME"),
+            });
+        }
+
+        if env("REMOTE_CONTAINERS").as_deref() == Some("true")
+            || env("DEVCONTAINER").as_deref() == Some("true")
+            || Path::new("/usr/local/etc/vscode-dev-containers").exists()
+        {
+            return Some(ContainerInfo {
+                kind: ContainerKind::DevContainer,
+                name: Self::devcontainer_name(working_dir),
+            });
+        }
+
+        if env("KUBERNETES_SERVICE_HOST").is_some() {
+            return Some(ContainerInfo {
+                kind: ContainerKind::Kubernetes,
+                name: env("HOSTNAME"),
+            });
+        }
+
+        // Podman describes the container in /run/.containerenv
+        if let Ok(containerenv) = fs::read_to_string("/run/.containerenv") {
+            let field = |key: &str| {
+                containerenv.lines().find_map(|line| {
+                    let value = line.strip_prefix(key)?.strip_prefix('=')?;
+                    Some(value.trim_matches('"').to_string()).filter(|v| !v.is_empty())
+                })
+            };
+            return Some(ContainerInfo {
+                kind: ContainerKind::Podman,
+                name: field("image").or_else(|| field("name")),
+            });
+        }
+
+        let in_docker = Path::new("/.dockerenv").exists()
+            || env("container").as_deref() == Some("docker")
+            || fs::read_to_string("/proc/1/cgroup")
+                .map(|cgroup| cgroup.contains("/docker/") || cgroup.contains("/docker-"))
+                .unwrap_or(false);
+        if in_docker {
+            // Docker exposes no image name inside the container; the
+            // hostname defaults to the short container id
+            return Some(ContainerInfo {
+                kind: ContainerKind::Docker,
+                name: env("HOSTNAME"),
+            });
+        }
+
+        None
+    }
+
+    /// `name` from the workspace's devcontainer.json, which is JSON with comments
+    fn devcontainer_name(working_dir: &str) -> Option<String> {
+        let dir = Path::new(working_dir);
+        let content = [".devcontainer/devcontainer.json", ".devcontainer.json"]
+            .iter()
+            .find_map(|file| fs::read_to_string(dir.join(file)).ok())?;
+
+        // Drop whole-line comments; when the file still does not parse
+        // (trailing commas, block comments) look for the name line instead
+        let stripped: String = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&stripped) {
+            return value
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(str::to_string);
+        }
+
+        content.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("\"name\"")?.trim_start();
+            let value = rest.strip_prefix(':')?.trim().trim_end_matches(',');
+            Some(value.trim_matches('"').to_string()).filter(|v| !v.is_empty())
+        })
+    }
+}
+
+impl Segment for ContainerSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mut metadata = HashMap::new();
+
+        let Some(container) = Self::detect(&input.workspace.current_dir) else {
+            if !self.show_host {
+                return None;
+            }
+            metadata.insert("kind".to_string(), "host".to_string());
+            return Some(SegmentData {
+                primary: i18n::text("container.host"),
+                secondary: String::new(),
+                metadata,
+            });
+        };
+
+        metadata.insert("kind".to_string(), container.kind.key().to_string());
+        if let Some(name) = &container.name {
+            metadata.insert("name".to_string(), name.clone());
+        }
+
+        // Prefer the name; the kind alone still says "not on the host"
+        let (primary, secondary) = match container.name {
+            Some(name) => (name, container.kind.label().to_string()),
+            None => (container.kind.label().to_string(), String::new()),
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Container
+    }
+}
@@ -1,38 +1,388 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use super::{session, Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, NumberFormat, SegmentId};
+use crate::core::session_cache::{self, CostSample};
+use crate::i18n;
+use crate::utils::number;
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
 
-#[derive(Default)]
-pub struct CostSegment;
+/// Sessions shorter than this have too little history for a spend rate
+const MIN_PROJECTION_ELAPSED_MS: u64 = 10 * 60 * 1000;
+
+/// Cost changes kept per session; enough for hours of 5-minute buckets
+const MAX_COST_SAMPLES: usize = 512;
+
+pub struct CostSegment {
+    show_cost: bool,
+    show_api_duration: bool,
+    show_wall_duration: bool,
+    show_project_total: bool,
+    show_projection: bool,
+    daily_budget: Option<f64>,
+    warning_percent: u64,
+    show_sparkline: bool,
+    sparkline_buckets: usize,
+    sparkline_interval: Duration,
+    numbers: NumberFormat,
+}
+
+impl Default for CostSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl CostSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Cost,
+        name: "Cost",
+        description: "Total session cost in USD, optionally with API and wall-clock time",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_cost",
+                kind: "bool",
+                default: "true",
+                description: "Show the session cost",
+            },
+            SegmentOptionInfo {
+                name: "show_api_duration",
+                kind: "bool",
+                default: "false",
+                description: "Show the time spent waiting on the API",
+            },
+            SegmentOptionInfo {
+                name: "show_wall_duration",
+                kind: "bool",
+                default: "false",
+                description: "Show the wall-clock time the session has run",
+            },
+            SegmentOptionInfo {
+                name: "show_project_total",
+                kind: "bool",
+                default: "false",
+                description: "Show the lifetime spend of the project over all its sessions",
+            },
+            SegmentOptionInfo {
+                name: "show_projection",
+                kind: "bool",
+                default: "false",
+                description: "Extrapolate the session's spend rate to the end of the day",
+            },
+            SegmentOptionInfo {
+                name: "daily_budget",
+                kind: "float",
+                default: "",
+                description:
+                    "Daily budget in USD; the projection turns yellow, then red, as it nears it",
+            },
+            SegmentOptionInfo {
+                name: "budget_warning_percent",
+                kind: "integer",
+                default: "80",
+                description: "Share of the daily budget at which the projection turns yellow",
+            },
+            SegmentOptionInfo {
+                name: "show_sparkline",
+                kind: "bool",
+                default: "false",
+                description: "Show spend per recent interval as a sparkline",
+            },
+            SegmentOptionInfo {
+                name: "sparkline_buckets",
+                kind: "integer",
+                default: "8",
+                description: "Intervals in the sparkline, oldest on the left",
+            },
+            SegmentOptionInfo {
+                name: "sparkline_interval_minutes",
+                kind: "integer",
+                default: "5",
+                description: "Length of one sparkline interval",
+            },
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the amounts, overriding style.numbers",
+            },
+        ],
+        data_sources: &[
+            "stdin: cost.total_cost_usd, cost.total_duration_ms, cost.total_api_duration_ms",
+            "~/.claude/ccline/cache/ (cost history for the sparkline)",
+            "~/.claude/ccline/spend.json (cost per session and project)",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "$",
+            emoji: "💰",
+            nerd_font: "\u{eec1}",
+            nerd_font_v2: "\u{f155}",
+        },
+    };
+
+    pub const DEFAULT_WARNING_PERCENT: u64 = 80;
+    pub const DEFAULT_SPARKLINE_BUCKETS: u64 = 8;
+    pub const DEFAULT_SPARKLINE_INTERVAL_MINUTES: u64 = 5;
+
     pub fn new() -> Self {
-        Self
+        Self {
+            show_cost: true,
+            show_api_duration: false,
+            show_wall_duration: false,
+            show_project_total: false,
+            show_projection: false,
+            daily_budget: None,
+            warning_percent: Self::DEFAULT_WARNING_PERCENT,
+            show_sparkline: false,
+            sparkline_buckets: Self::DEFAULT_SPARKLINE_BUCKETS as usize,
+            sparkline_interval: Duration::from_secs(Self::DEFAULT_SPARKLINE_INTERVAL_MINUTES * 60),
+            numbers: NumberFormat::default(),
+        }
+    }
+
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    pub fn with_sparkline(mut self, show_sparkline: bool) -> Self {
+        self.show_sparkline = show_sparkline;
+        self
+    }
+
+    /// `buckets` intervals of `interval_minutes` each; zero keeps the default
+    pub fn with_sparkline_shape(mut self, buckets: u64, interval_minutes: u64) -> Self {
+        if buckets > 0 {
+            self.sparkline_buckets = buckets.min(60) as usize;
+        }
+        if interval_minutes > 0 {
+            self.sparkline_interval = Duration::from_secs(interval_minutes * 60);
+        }
+        self
+    }
+
+    /// What the primary text shows; with all three off the segment is hidden
+    pub fn with_parts(mut self, cost: bool, api_duration: bool, wall_duration: bool) -> Self {
+        self.show_cost = cost;
+        self.show_api_duration = api_duration;
+        self.show_wall_duration = wall_duration;
+        self
+    }
+
+    pub fn with_project_total(mut self, show_project_total: bool) -> Self {
+        self.show_project_total = show_project_total;
+        self
+    }
+
+    pub fn with_projection(mut self, show_projection: bool) -> Self {
+        self.show_projection = show_projection;
+        self
+    }
+
+    pub fn with_daily_budget(mut self, daily_budget: Option<f64>) -> Self {
+        self.daily_budget = daily_budget.filter(|budget| *budget > 0.0);
+        self
+    }
+
+    pub fn with_warning_percent(mut self, warning_percent: u64) -> Self {
+        self.warning_percent = warning_percent;
+        self
+    }
+
+    /// Spend at midnight if the session keeps its average rate so far
+    fn project_today(cost: f64, elapsed_ms: u64) -> Option<f64> {
+        if elapsed_ms < MIN_PROJECTION_ELAPSED_MS {
+            return None;
+        }
+        let rate_per_second = cost / (elapsed_ms as f64 / 1000.0);
+        Some(cost + rate_per_second * seconds_until_midnight() as f64)
+    }
+
+    /// Record `cost` when it changed since the last render and return the
+    /// session's cost history
+    fn record_cost(transcript_path: &str, cost: f64) -> Vec<CostSample> {
+        let mut samples =
+            session_cache::with_session(transcript_path, |state| state.cost_samples.clone());
+        if samples.last().is_some_and(|last| last.total_usd == cost) {
+            return samples;
+        }
+
+        let sample = CostSample {
+            at: SystemTime::now(),
+            total_usd: cost,
+        };
+        session_cache::update_session(transcript_path, |state| {
+            state.cost_samples.push(sample);
+            let excess = state.cost_samples.len().saturating_sub(MAX_COST_SAMPLES);
+            state.cost_samples.drain(..excess);
+        });
+        samples.push(sample);
+        samples
     }
+
+    /// Spend in each of the last `sparkline_buckets` intervals, oldest first
+    fn spend_per_interval(&self, samples: &[CostSample], now: SystemTime) -> Vec<f64> {
+        // Cost at a moment is the last recorded total before it; before the
+        // first sample, that sample is the baseline
+        let cost_at = |at: SystemTime| {
+            samples
+                .iter()
+                .rev()
+                .find(|sample| sample.at <= at)
+                .or(samples.first())
+                .map_or(0.0, |sample| sample.total_usd)
+        };
+
+        (0..self.sparkline_buckets)
+            .map(|index| {
+                let back = (self.sparkline_buckets - index) as u32;
+                let start = now - self.sparkline_interval * back;
+                let end = start + self.sparkline_interval;
+                (cost_at(end) - cost_at(start)).max(0.0)
+            })
+            .collect()
+    }
+
+    /// `warning` or `critical` once the projection nears or passes the budget
+    fn budget_level(&self, projected: f64) -> Option<&'static str> {
+        let budget = self.daily_budget?;
+        if projected >= budget {
+            Some("critical")
+        } else if projected >= budget * self.warning_percent as f64 / 100.0 {
+            Some("warning")
+        } else {
+            None
+        }
+    }
+}
+
+/// Seconds left in the local day (UTC day without chrono)
+fn seconds_until_midnight() -> u64 {
+    #[cfg(feature = "chrono")]
+    let elapsed_today = {
+        use chrono::Timelike;
+        chrono::Local::now().num_seconds_from_midnight() as u64
+    };
+    #[cfg(not(feature = "chrono"))]
+    let elapsed_today = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_secs() % 86_400)
+        .unwrap_or(0);
+
+    86_400 - elapsed_today
 }
 
 impl Segment for CostSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
         let cost_data = input.cost.as_ref()?;
 
-        // Primary display: total cost
-        let primary = if let Some(cost) = cost_data.total_cost_usd {
-            if cost == 0.0 || cost < 0.01 {
+        // Primary display: total cost, then the durations that are enabled
+        let mut parts = Vec::new();
+        if self.show_cost {
+            let cost = cost_data.total_cost_usd?;
+            parts.push(if cost == 0.0 || cost < 0.01 {
                 "$0".to_string()
             } else {
-                format!("${:.2}", cost)
+                format!("${}", number::format_cost(cost, &self.numbers))
+            });
+        }
+        if self.show_api_duration {
+            if let Some(api_ms) = cost_data.total_api_duration_ms {
+                parts.push(i18n::text_with(
+                    "cost.api_duration",
+                    &[&session::format_duration(api_ms)],
+                ));
             }
-        } else {
+        }
+        if self.show_wall_duration {
+            if let Some(wall_ms) = cost_data.total_duration_ms {
+                parts.push(i18n::text_with(
+                    "cost.wall_duration",
+                    &[&session::format_duration(wall_ms)],
+                ));
+            }
+        }
+        if parts.is_empty() {
             return None;
-        };
+        }
 
-        // Secondary display: empty for cost segment
-        let secondary = String::new();
+        // Lifetime spend of the project, this session included
+        let mut project_total = None;
+        if self.show_project_total && !input.transcript_path.is_empty() {
+            if let Some(cost) = cost_data.total_cost_usd {
+                let project = input
+                    .workspace
+                    .project_dir
+                    .as_deref()
+                    .unwrap_or(&input.workspace.current_dir);
+                let session = crate::core::handoff::session_id(&input.transcript_path);
+                let spend = crate::core::spend::record(project, &session, cost);
+                let total = spend.total();
+                parts.push(i18n::text_with(
+                    "cost.project_total",
+                    &[&format!("${}", number::format_cost(total, &self.numbers))],
+                ));
+                project_total = Some((total, spend.sessions.len()));
+            }
+        }
+        let primary = parts.join(" ");
 
         let mut metadata = HashMap::new();
         if let Some(cost) = cost_data.total_cost_usd {
             metadata.insert("cost".to_string(), cost.to_string());
         }
+        if let Some(api_ms) = cost_data.total_api_duration_ms {
+            metadata.insert("api_duration_ms".to_string(), api_ms.to_string());
+        }
+        if let Some(wall_ms) = cost_data.total_duration_ms {
+            metadata.insert("duration_ms".to_string(), wall_ms.to_string());
+        }
+        if let Some((total, sessions)) = project_total {
+            metadata.insert("project_total".to_string(), format!("{:.4}", total));
+            metadata.insert("project_sessions".to_string(), sessions.to_string());
+        }
+
+        // Secondary display: the end-of-day projection when enabled
+        let mut secondary = String::new();
+        if self.show_projection {
+            let projected = cost_data
+                .total_cost_usd
+                .zip(cost_data.total_duration_ms)
+                .and_then(|(cost, elapsed_ms)| Self::project_today(cost, elapsed_ms));
+            if let Some(projected) = projected {
+                let amount = format!("${}", number::format_cost(projected, &self.numbers));
+                secondary = i18n::text_with("cost.projected_today", &[&amount]);
+                metadata.insert("projected_today".to_string(), format!("{:.4}", projected));
+                if let Some(level) = self.budget_level(projected) {
+                    metadata.insert("level".to_string(), level.to_string());
+                }
+            }
+        }
+        if let Some(budget) = self.daily_budget {
+            metadata.insert("daily_budget".to_string(), budget.to_string());
+        }
+
+        // Spend per recent interval, so bursts stand out
+        if self.show_sparkline && !input.transcript_path.is_empty() {
+            if let Some(cost) = cost_data.total_cost_usd {
+                let samples = Self::record_cost(&input.transcript_path, cost);
+                let spend = self.spend_per_interval(&samples, SystemTime::now());
+                let sparkline = crate::utils::sparkline::sparkline(&spend);
+                metadata.insert(
+                    "sparkline_values".to_string(),
+                    spend
+                        .iter()
+                        .map(|value| format!("{:.4}", value))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                if !secondary.is_empty() {
+                    secondary.push(' ');
+                }
+                secondary.push_str(&sparkline);
+            }
+        }
 
         Some(SegmentData {
             primary,
@@ -1,32 +1,101 @@
-use super::{Segment, SegmentData};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
 use crate::config::{InputData, SegmentId};
+use crate::utils::wsl;
 use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct DirectorySegment;
+pub struct DirectorySegment {
+    show_wsl_badge: bool,
+    translate_wsl_paths: bool,
+}
+
+impl Default for DirectorySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DirectorySegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Directory,
+        name: "Directory",
+        description: "Name of the current working directory",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_wsl_badge",
+                kind: "bool",
+                default: "false",
+                description: "Show a WSL badge when running inside the Windows Subsystem for Linux",
+            },
+            SegmentOptionInfo {
+                name: "translate_wsl_paths",
+                kind: "bool",
+                default: "true",
+                description: "Under WSL, show Windows paths such as C:\\work as /mnt/c/work",
+            },
+        ],
+        data_sources: &[
+            "stdin: workspace.current_dir",
+            "/proc/version",
+            "/etc/wsl.conf",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "dir",
+            emoji: "📁",
+            nerd_font: "\u{f024b}",
+            nerd_font_v2: "\u{f07b}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self
+        Self {
+            show_wsl_badge: false,
+            translate_wsl_paths: true,
+        }
+    }
+
+    pub fn with_wsl_badge(mut self, show_wsl_badge: bool) -> Self {
+        self.show_wsl_badge = show_wsl_badge;
+        self
+    }
+
+    pub fn with_wsl_path_translation(mut self, translate_wsl_paths: bool) -> Self {
+        self.translate_wsl_paths = translate_wsl_paths;
+        self
+    }
+
+    /// Turn Windows verbatim paths into their everyday form:
+    /// `\\?\C:\work` becomes `C:\work` and `\\?\UNC\server\share` becomes
+    /// `\\server\share`
+    pub fn normalize_windows_path(path: &str) -> String {
+        if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+            format!(r"\\{}", unc)
+        } else if let Some(local) = path
+            .strip_prefix(r"\\?\")
+            .or_else(|| path.strip_prefix(r"\\.\"))
+        {
+            local.to_string()
+        } else {
+            path.to_string()
+        }
     }
 
     /// Extract directory name from path, handling both Unix and Windows separators
     fn extract_directory_name(path: &str) -> String {
-        // Handle both Unix and Windows separators by trying both
-        let unix_name = path.split('/').next_back().unwrap_or("");
-        let windows_name = path.split('\\').next_back().unwrap_or("");
-
-        // Choose the name that indicates actual path splitting occurred
-        let result = if windows_name.len() < path.len() {
-            // Windows path separator was found
-            windows_name
-        } else if unix_name.len() < path.len() {
-            // Unix path separator was found
-            unix_name
-        } else {
-            // No separator found, use the whole path
-            path
-        };
+        let path = Self::normalize_windows_path(path);
+
+        // Trailing separators would leave an empty last component
+        let trimmed = path.trim_end_matches(['/', '\\']);
+
+        // A drive root such as `C:\` is named after the drive
+        if trimmed.len() == 2 && trimmed.ends_with(':') {
+            return trimmed.to_string();
+        }
+
+        // Handle both Unix and Windows separators; for a UNC share root
+        // (`\\server\share`) this yields the share name
+        let result = trimmed.rsplit(['/', '\\']).next().unwrap_or("");
 
         if result.is_empty() {
             "root".to_string()
@@ -38,18 +107,39 @@ impl DirectorySegment {
 
 impl Segment for DirectorySegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let current_dir = &input.workspace.current_dir;
+        let mut current_dir = Self::normalize_windows_path(&input.workspace.current_dir);
+        let mut metadata = HashMap::new();
+
+        // Claude Code started from Windows can hand a WSL build Windows paths
+        let wsl = wsl::detect();
+        if let Some(wsl) = wsl {
+            metadata.insert("wsl".to_string(), "true".to_string());
+            if let Some(distro) = &wsl.distro {
+                metadata.insert("wsl_distro".to_string(), distro.clone());
+            }
+            if self.translate_wsl_paths {
+                if let Some(translated) = wsl.translate_windows_path(&current_dir) {
+                    metadata.insert("windows_path".to_string(), current_dir);
+                    current_dir = translated;
+                }
+            }
+        }
 
         // Handle cross-platform path separators manually for better compatibility
-        let dir_name = Self::extract_directory_name(current_dir);
+        let dir_name = Self::extract_directory_name(&current_dir);
 
         // Store the full path in metadata for potential use
-        let mut metadata = HashMap::new();
-        metadata.insert("full_path".to_string(), current_dir.clone());
+        metadata.insert("full_path".to_string(), current_dir);
+
+        let secondary = if self.show_wsl_badge && wsl.is_some() {
+            "WSL".to_string()
+        } else {
+            String::new()
+        };
 
         Some(SegmentData {
             primary: dir_name,
-            secondary: String::new(),
+            secondary,
             metadata,
         })
     }
@@ -1,7 +1,37 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{AnsiColor, InputData, SegmentId};
+use crate::core::session_cache::{self, GitDiscovery};
+use crate::error::Error;
+use crate::i18n;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::SystemTime;
+
+/// Colors for the working tree state used when `color_by_state` is on and
+/// the segment sets no `state_colors`: state, background, text
+pub const DEFAULT_STATE_COLORS: &[(&str, AnsiColor, AnsiColor)] = &[
+    (
+        "clean",
+        AnsiColor::Color256 { c256: 28 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "dirty",
+        AnsiColor::Color256 { c256: 178 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+    (
+        "conflicts",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "diverged",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+];
 
 #[derive(Debug)]
 pub struct GitInfo {
@@ -21,6 +51,7 @@ pub enum GitStatus {
 
 pub struct GitSegment {
     show_sha: bool,
+    color_by_state: bool,
 }
 
 impl Default for GitSegment {
@@ -30,8 +61,46 @@ impl Default for GitSegment {
 }
 
 impl GitSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Git,
+        name: "Git",
+        description: "Branch, working tree status and ahead/behind counts",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_sha",
+                kind: "bool",
+                default: "false",
+                description: "Append the short commit SHA",
+            },
+            SegmentOptionInfo {
+                name: "color_by_state",
+                kind: "bool",
+                default: "false",
+                description: "Color the segment by working tree state: clean, dirty, conflicts or diverged",
+            },
+            SegmentOptionInfo {
+                name: "state_colors",
+                kind: "table",
+                default: "{}",
+                description: "Colors per state, e.g. dirty = { background = { c256 = 178 } }; implies color_by_state",
+            },
+        ],
+        data_sources: &["git CLI in workspace.current_dir"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "git",
+            emoji: "🌿",
+            nerd_font: "\u{f02a2}",
+            nerd_font_v2: "\u{e0a0}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self { show_sha: false }
+        Self {
+            show_sha: false,
+            color_by_state: false,
+        }
     }
 
     pub fn with_sha(mut self, show_sha: bool) -> Self {
@@ -39,14 +108,23 @@ impl GitSegment {
         self
     }
 
-    fn get_git_info(&self, working_dir: &str) -> Option<GitInfo> {
-        if !self.is_git_repository(working_dir) {
-            return None;
+    pub fn with_color_by_state(mut self, color_by_state: bool) -> Self {
+        self.color_by_state = color_by_state;
+        self
+    }
+
+    fn get_git_info(
+        &self,
+        working_dir: &str,
+        transcript_path: &str,
+    ) -> Result<Option<GitInfo>, Error> {
+        if !self.is_git_repository(working_dir, transcript_path)? {
+            return Ok(None);
         }
 
         let branch = self
             .get_branch(working_dir)
-            .unwrap_or_else(|| "detached".to_string());
+            .unwrap_or_else(|| i18n::text("git.detached"));
         let status = self.get_status(working_dir);
         let (ahead, behind) = self.get_ahead_behind(working_dir);
         let sha = if self.show_sha {
@@ -55,22 +133,51 @@ impl GitSegment {
             None
         };
 
-        Some(GitInfo {
+        Ok(Some(GitInfo {
             branch,
             status,
             ahead,
             behind,
             sha,
-        })
+        }))
     }
 
-    fn is_git_repository(&self, working_dir: &str) -> bool {
-        Command::new("git")
-            .args(["rev-parse", "--git-dir"])
+    /// Whether `working_dir` is inside a repository, reusing the session's
+    /// earlier answer while it still holds. Fails when git cannot be run at
+    /// all, e.g. when it is not installed.
+    fn is_git_repository(&self, working_dir: &str, transcript_path: &str) -> Result<bool, Error> {
+        let cached = session_cache::with_session(transcript_path, |session| {
+            session
+                .git
+                .get(working_dir)
+                .filter(|discovery| discovery.is_valid_for(working_dir))
+                .map(|discovery| discovery.git_dir.is_some())
+        });
+        if let Some(is_repository) = cached {
+            return Ok(is_repository);
+        }
+
+        let output = Command::new("git")
+            .args(["rev-parse", "--absolute-git-dir"])
             .current_dir(working_dir)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+            .output()?;
+        let git_dir = Some(output)
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|dir| PathBuf::from(dir.trim()));
+        let is_repository = git_dir.is_some();
+
+        let discovery = GitDiscovery {
+            git_dir,
+            dir_modified: std::fs::metadata(working_dir)
+                .and_then(|meta| meta.modified())
+                .ok(),
+            discovered_at: SystemTime::now(),
+        };
+        session_cache::update_session(transcript_path, |session| {
+            session.git.insert(working_dir.to_string(), discovery);
+        });
+        Ok(is_repository)
     }
 
     fn get_branch(&self, working_dir: &str) -> Option<String> {
@@ -171,9 +278,28 @@ impl GitSegment {
     }
 }
 
+/// State the segment is colored by: conflicts first, then a branch that is
+/// both ahead of and behind its upstream, then uncommitted changes
+fn state(info: &GitInfo) -> &'static str {
+    match info.status {
+        GitStatus::Conflicts => "conflicts",
+        _ if info.ahead > 0 && info.behind > 0 => "diverged",
+        GitStatus::Dirty => "dirty",
+        GitStatus::Clean => "clean",
+    }
+}
+
 impl Segment for GitSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let git_info = self.get_git_info(&input.workspace.current_dir)?;
+        self.try_collect(input).ok().flatten()
+    }
+
+    fn try_collect(&self, input: &InputData) -> Result<Option<SegmentData>, Error> {
+        let Some(git_info) =
+            self.get_git_info(&input.workspace.current_dir, &input.transcript_path)?
+        else {
+            return Ok(None);
+        };
 
         let mut metadata = HashMap::new();
         metadata.insert("branch".to_string(), git_info.branch.clone());
@@ -184,6 +310,9 @@ impl Segment for GitSegment {
         if let Some(ref sha) = git_info.sha {
             metadata.insert("sha".to_string(), sha.clone());
         }
+        if self.color_by_state {
+            metadata.insert("state".to_string(), state(&git_info).to_string());
+        }
 
         let primary = git_info.branch;
         let mut status_parts = Vec::new();
@@ -205,11 +334,11 @@ impl Segment for GitSegment {
             status_parts.push(sha.clone());
         }
 
-        Some(SegmentData {
+        Ok(Some(SegmentData {
             primary,
             secondary: status_parts.join(" "),
             metadata,
-        })
+        }))
     }
 
     fn id(&self) -> SegmentId {
@@ -0,0 +1,217 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{AnsiColor, InputData, SegmentId};
+use crate::i18n;
+use crate::utils::transcript;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How much of the end of the transcript is searched for hook results
+const TAIL_BYTES: u64 = 512 * 1024;
+
+/// Badge colors used when the segment sets no `state_colors`: state,
+/// background, text
+pub const DEFAULT_STATE_COLORS: &[(&str, AnsiColor, AnsiColor)] = &[
+    (
+        "failed",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "blocked",
+        AnsiColor::Color256 { c256: 208 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Succeeded,
+    /// The hook ran into an error; Claude Code carried on
+    Failed,
+    /// The hook blocked the action on purpose (exit code 2)
+    Blocked,
+}
+
+impl Outcome {
+    fn state(&self) -> &'static str {
+        match self {
+            Outcome::Succeeded => "ok",
+            Outcome::Failed => "failed",
+            Outcome::Blocked => "blocked",
+        }
+    }
+}
+
+/// The last hook run recorded in the transcript
+#[derive(Debug)]
+struct HookRun {
+    /// Event and matcher, e.g. "PreToolUse:Bash"
+    name: String,
+    outcome: Outcome,
+    exit_code: Option<i64>,
+}
+
+/// A hook result from a transcript entry. Claude Code records hooks either
+/// as `attachment` entries (`hook_success`, `hook_non_blocking_error`,
+/// `hook_blocking_error`) or, in older versions, as `system` messages such as
+/// "PostToolUse:Edit [fmt.sh] completed successfully" or
+/// "PreToolUse:Bash [check.sh] failed with non-blocking status code 1: …".
+fn hook_run(entry: &Value) -> Option<HookRun> {
+    match entry.get("type").and_then(Value::as_str)? {
+        "attachment" => {
+            let attachment = entry.get("attachment")?;
+            let outcome = match attachment.get("type").and_then(Value::as_str)? {
+                "hook_success" => Outcome::Succeeded,
+                "hook_non_blocking_error" | "hook_error" | "hook_cancelled" => Outcome::Failed,
+                "hook_blocking_error" => Outcome::Blocked,
+                _ => return None,
+            };
+            let name = attachment
+                .get("hookName")
+                .or_else(|| attachment.get("hookEvent"))
+                .and_then(Value::as_str)
+                .unwrap_or("hook");
+            Some(HookRun {
+                name: name.to_string(),
+                outcome,
+                exit_code: attachment.get("exitCode").and_then(Value::as_i64),
+            })
+        }
+        "system" => {
+            let content = entry.get("content").and_then(Value::as_str)?;
+            let (name, rest) = content.split_once(' ')?;
+            let event = name.split(':').next().unwrap_or(name);
+            if !event.ends_with("ToolUse")
+                && ![
+                    "UserPromptSubmit",
+                    "Stop",
+                    "SubagentStop",
+                    "Notification",
+                    "PreCompact",
+                    "SessionStart",
+                    "SessionEnd",
+                ]
+                .contains(&event)
+            {
+                return None;
+            }
+
+            let outcome = if rest.contains("completed successfully") {
+                Outcome::Succeeded
+            } else if rest.contains("non-blocking") || rest.contains("failed") {
+                Outcome::Failed
+            } else if rest.contains("blocking") || rest.contains("blocked") {
+                Outcome::Blocked
+            } else {
+                return None;
+            };
+            let exit_code = rest
+                .split("status code ")
+                .nth(1)
+                .and_then(|code| code.split(|c: char| !c.is_ascii_digit()).next())
+                .and_then(|code| code.parse().ok());
+            Some(HookRun {
+                name: name.to_string(),
+                outcome,
+                exit_code,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub struct HooksSegment {
+    only_failures: bool,
+}
+
+impl Default for HooksSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HooksSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Hooks,
+        name: "Hooks",
+        description: "Result of the last hook run, with an error badge when it failed",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "only_failures",
+                kind: "bool",
+                default: "false",
+                description: "Hide the segment unless the last hook run failed or blocked",
+            },
+            SegmentOptionInfo {
+                name: "state_colors",
+                kind: "table",
+                default: "{}",
+                description: "Colors per result: ok, failed, blocked",
+            },
+        ],
+        data_sources: &["transcript (last 512 KiB): hook attachments and system messages"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "hook",
+            emoji: "🪝",
+            nerd_font: "\u{f0c1}",
+            nerd_font_v2: "\u{f0c1}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            only_failures: false,
+        }
+    }
+
+    pub fn with_only_failures(mut self, only_failures: bool) -> Self {
+        self.only_failures = only_failures;
+        self
+    }
+
+    fn last_run(transcript_path: &str) -> Option<HookRun> {
+        let tail = transcript::tail(Path::new(transcript_path), TAIL_BYTES)?;
+        tail.lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .find_map(|entry| hook_run(&entry))
+    }
+}
+
+impl Segment for HooksSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let run = Self::last_run(&input.transcript_path)?;
+        if self.only_failures && run.outcome == Outcome::Succeeded {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("state".to_string(), run.outcome.state().to_string());
+        metadata.insert("hook".to_string(), run.name.clone());
+        if let Some(code) = run.exit_code {
+            metadata.insert("exit_code".to_string(), code.to_string());
+        }
+
+        let status = match run.outcome {
+            Outcome::Succeeded => i18n::text("hooks.ok"),
+            Outcome::Failed => match run.exit_code {
+                Some(code) => i18n::text_with("hooks.failed_code", &[&code.to_string()]),
+                None => i18n::text("hooks.failed"),
+            },
+            Outcome::Blocked => i18n::text("hooks.blocked"),
+        };
+
+        Some(SegmentData {
+            primary: run.name,
+            secondary: status,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Hooks
+    }
+}
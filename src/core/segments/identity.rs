@@ -0,0 +1,175 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, SegmentId};
+use crate::i18n;
+use crate::utils::credentials;
+use std::collections::HashMap;
+
+const ADJECTIVES: &[&str] = &[
+    "amber", "azure", "bold", "brave", "brisk", "calm", "clever", "coral", "crimson", "dapper",
+    "eager", "early", "fancy", "fleet", "gentle", "glad", "golden", "grand", "happy", "hazel",
+    "humble", "icy", "jade", "jolly", "keen", "kind", "lively", "lucky", "lunar", "mellow",
+    "misty", "noble", "olive", "plucky", "polar", "proud", "quick", "quiet", "rapid", "rosy",
+    "ruby", "rustic", "sandy", "scarlet", "shy", "silver", "sleek", "snowy", "solar", "spry",
+    "steady", "stormy", "sunny", "swift", "tawny", "tidy", "vivid", "warm", "wild", "windy",
+    "wise", "witty", "young", "zesty",
+];
+
+const NOUNS: &[&str] = &[
+    "badger", "beacon", "bison", "brook", "canyon", "cedar", "comet", "crane", "delta", "dune",
+    "eagle", "ember", "falcon", "fern", "fjord", "fox", "glacier", "grove", "harbor", "hawk",
+    "heron", "island", "jaguar", "kestrel", "lagoon", "lark", "lynx", "maple", "meadow", "mesa",
+    "moose", "nebula", "oak", "orca", "otter", "owl", "panda", "pebble", "pine", "plover",
+    "prairie", "quail", "raven", "reef", "ridge", "river", "robin", "sage", "salmon", "sparrow",
+    "spruce", "summit", "swan", "thicket", "tiger", "tundra", "valley", "walrus", "willow", "wolf",
+    "wren", "yak", "zebra", "zephyr",
+];
+
+/// How the credential is shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FingerprintStyle {
+    /// Two words derived from a hash, e.g. "amber-falcon"
+    Words,
+    /// The last four characters, e.g. "…x9Qa"
+    Last4,
+    /// Eight hex digits of a hash
+    Hash,
+}
+
+impl FingerprintStyle {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "words" => Some(FingerprintStyle::Words),
+            "last4" => Some(FingerprintStyle::Last4),
+            "hash" => Some(FingerprintStyle::Hash),
+            _ => None,
+        }
+    }
+}
+
+pub struct IdentitySegment {
+    style: FingerprintStyle,
+    /// Names for known fingerprints, e.g. "amber-falcon" = "work"
+    labels: HashMap<String, String>,
+}
+
+impl Default for IdentitySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdentitySegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Identity,
+        name: "Identity",
+        description: "Short fingerprint of the API key or account the session bills to",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "style",
+                kind: "string",
+                default: "words",
+                description: "words (\"amber-falcon\"), last4 (\"…x9Qa\") or hash (8 hex digits)",
+            },
+            SegmentOptionInfo {
+                name: "labels",
+                kind: "table",
+                default: "{}",
+                description:
+                    "Fingerprints mapped to names shown instead, e.g. \"amber-falcon\" = \"work\"",
+            },
+        ],
+        data_sources: &[
+            "env: PACKYCODE_API_KEY / ANTHROPIC_API_KEY / ANTHROPIC_AUTH_TOKEN",
+            "~/.claude/settings.json",
+            "~/.claude/api_key",
+            "~/.claude.json: oauthAccount",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "id",
+            emoji: "🔑",
+            nerd_font: "\u{f084}",
+            nerd_font_v2: "\u{f084}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            style: FingerprintStyle::Words,
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Unknown style names keep the default
+    pub fn with_style(mut self, style: Option<&str>) -> Self {
+        if let Some(style) = style.and_then(FingerprintStyle::parse) {
+            self.style = style;
+        }
+        self
+    }
+
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// A short, non-reversible name for `secret`
+    pub fn fingerprint(secret: &str, style: FingerprintStyle) -> String {
+        let hash = crate::utils::hash::fnv1a(secret.trim().as_bytes());
+        match style {
+            FingerprintStyle::Words => format!(
+                "{}-{}",
+                ADJECTIVES[(hash % ADJECTIVES.len() as u64) as usize],
+                NOUNS[((hash >> 32) % NOUNS.len() as u64) as usize]
+            ),
+            FingerprintStyle::Last4 => {
+                let chars: Vec<char> = secret.trim().chars().collect();
+                let tail: String = chars[chars.len().saturating_sub(4)..].iter().collect();
+                format!("…{}", tail)
+            }
+            FingerprintStyle::Hash => format!("{:08x}", (hash >> 32) as u32),
+        }
+    }
+}
+
+impl Segment for IdentitySegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let mut metadata = HashMap::new();
+
+        // An API key takes precedence over a subscription sign-in, as in
+        // Claude Code. OAuth tokens rotate, so sign-ins are fingerprinted by
+        // account id.
+        let (fingerprint, kind) = if let Some((key, source)) =
+            credentials::api_key_with_source().filter(|(key, _)| !key.trim().is_empty())
+        {
+            metadata.insert("source".to_string(), source);
+            (Self::fingerprint(&key, self.style), "key")
+        } else if let Some(account) = credentials::oauth_account_id() {
+            metadata.insert("source".to_string(), "oauth".to_string());
+            (Self::fingerprint(&account, self.style), "account")
+        } else {
+            return None;
+        };
+
+        metadata.insert("fingerprint".to_string(), fingerprint.clone());
+        metadata.insert("kind".to_string(), kind.to_string());
+        let primary = match self.labels.get(&fingerprint) {
+            Some(label) => {
+                metadata.insert("label".to_string(), label.clone());
+                label.clone()
+            }
+            None => fingerprint,
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: i18n::text(&format!("identity.{}", kind)),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Identity
+    }
+}
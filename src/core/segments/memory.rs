@@ -0,0 +1,183 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, NumberFormat, SegmentId};
+use crate::i18n;
+use crate::utils::number;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Memory file names Claude Code loads from each project directory
+const PROJECT_FILES: &[&str] = &["CLAUDE.md", "CLAUDE.local.md", ".claude/CLAUDE.md"];
+
+/// Where organisations deploy an enterprise-wide CLAUDE.md
+fn enterprise_file() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/ClaudeCode/CLAUDE.md")
+    } else if cfg!(windows) {
+        PathBuf::from(r"C:\ProgramData\ClaudeCode\CLAUDE.md")
+    } else {
+        PathBuf::from("/etc/claude-code/CLAUDE.md")
+    }
+}
+
+/// A memory file that exists
+#[derive(Debug)]
+struct MemoryFile {
+    scope: &'static str,
+    path: PathBuf,
+    bytes: u64,
+}
+
+pub struct MemorySegment {
+    warn_kb: u64,
+    numbers: NumberFormat,
+}
+
+impl Default for MemorySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemorySegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Memory,
+        name: "Memory",
+        description: "CLAUDE.md files loaded into every request and their combined size",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "warn_kb",
+                kind: "integer",
+                default: "40",
+                description: "Combined size in KB above which the size turns yellow; twice that turns it red",
+            },
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the size, overriding style.numbers",
+            },
+        ],
+        data_sources: &[
+            "CLAUDE.md, CLAUDE.local.md, .claude/CLAUDE.md in workspace.current_dir and its parents",
+            "~/.claude/CLAUDE.md",
+            "/etc/claude-code/CLAUDE.md (Linux), /Library/Application Support/ClaudeCode/CLAUDE.md (macOS), C:\\ProgramData\\ClaudeCode\\CLAUDE.md (Windows)",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "mem",
+            emoji: "🧠",
+            nerd_font: "\u{f02d}",
+            nerd_font_v2: "\u{f02d}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            warn_kb: 40,
+            numbers: NumberFormat::default(),
+        }
+    }
+
+    pub fn with_warn_kb(mut self, warn_kb: u64) -> Self {
+        self.warn_kb = warn_kb;
+        self
+    }
+
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Memory files in scope for `working_dir`, the way Claude Code finds
+    /// them: enterprise, user, then every directory from the filesystem root
+    /// down to the working directory. Files pulled in with `@path` imports
+    /// are not followed.
+    fn find(working_dir: &str) -> Vec<MemoryFile> {
+        let mut candidates: Vec<(&'static str, PathBuf)> = vec![("enterprise", enterprise_file())];
+        let home = crate::utils::paths::home_dir();
+        if let Some(home) = &home {
+            candidates.push(("user", home.join(".claude").join("CLAUDE.md")));
+        }
+
+        let mut dirs: Vec<&Path> = Path::new(working_dir).ancestors().collect();
+        dirs.reverse();
+        for dir in dirs {
+            // Claude Code stops at the root, which holds no project
+            if dir.parent().is_none() {
+                continue;
+            }
+            for name in PROJECT_FILES {
+                candidates.push(("project", dir.join(name)));
+            }
+        }
+
+        let mut found: Vec<MemoryFile> = Vec::new();
+        for (scope, path) in candidates {
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            // ~/.claude/CLAUDE.md is also a project file when working in ~
+            if !metadata.is_file() || found.iter().any(|file| file.path == path) {
+                continue;
+            }
+            found.push(MemoryFile {
+                scope,
+                path,
+                bytes: metadata.len(),
+            });
+        }
+        found
+    }
+}
+
+impl Segment for MemorySegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let files = Self::find(&input.workspace.current_dir);
+        if files.is_empty() {
+            return None;
+        }
+
+        let bytes: u64 = files.iter().map(|file| file.bytes).sum();
+        let kb = bytes as f64 / 1024.0;
+        let size_format = NumberFormat {
+            precision: Some(self.numbers.precision.unwrap_or(1)),
+            ..self.numbers.clone()
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("count".to_string(), files.len().to_string());
+        metadata.insert("bytes".to_string(), bytes.to_string());
+        for scope in ["enterprise", "user", "project"] {
+            let count = files.iter().filter(|file| file.scope == scope).count();
+            metadata.insert(format!("{}_count", scope), count.to_string());
+        }
+        metadata.insert(
+            "files".to_string(),
+            files
+                .iter()
+                .map(|file| file.path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(";"),
+        );
+        if self.warn_kb > 0 && kb > (self.warn_kb * 2) as f64 {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if self.warn_kb > 0 && kb > self.warn_kb as f64 {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: i18n::text_with(
+                "memory.files",
+                &[&number::format_count(files.len() as u64, &self.numbers)],
+            ),
+            secondary: i18n::text_with("memory.kb", &[&number::format_cost(kb, &size_format)]),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Memory
+    }
+}
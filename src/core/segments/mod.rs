@@ -1,36 +1,92 @@
+pub mod attention;
+pub mod computed;
+pub mod container;
 pub mod cost;
 pub mod directory;
 pub mod git;
+pub mod hooks;
+pub mod identity;
+pub mod memory;
 pub mod model;
-pub mod quota;
+pub mod modes;
 pub mod output_style;
+pub mod plan;
+pub mod quota;
+pub mod registry;
 pub mod session;
+pub mod transcript;
 pub mod update;
 pub mod usage;
+pub mod vim;
 
 use crate::config::{InputData, SegmentId};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // New Segment trait for data collection only
 pub trait Segment {
     fn collect(&self, input: &InputData) -> Option<SegmentData>;
     fn id(&self) -> SegmentId;
+
+    /// Like `collect`, but says why collecting failed; `Ok(None)` when the
+    /// segment has nothing to show
+    fn try_collect(&self, input: &InputData) -> Result<Option<SegmentData>, Error> {
+        Ok(self.collect(input))
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SegmentData {
     pub primary: String,
     pub secondary: String,
     pub metadata: HashMap<String, String>,
 }
 
+impl SegmentData {
+    /// Strip escape sequences and control characters from every value.
+    /// Segments show text from the statusline JSON, git and remote APIs,
+    /// none of which may drive the terminal.
+    pub fn sanitized(self) -> Self {
+        use crate::utils::sanitize::sanitize;
+
+        let clean = |text: String| match sanitize(&text) {
+            std::borrow::Cow::Borrowed(_) => text,
+            std::borrow::Cow::Owned(clean) => clean,
+        };
+        Self {
+            primary: clean(self.primary),
+            secondary: clean(self.secondary),
+            metadata: self
+                .metadata
+                .into_iter()
+                .map(|(key, value)| (key, clean(value)))
+                .collect(),
+        }
+    }
+}
+
 // Re-export all segment types
+pub use attention::AttentionSegment;
+pub use computed::ComputedSegment;
+pub use container::ContainerSegment;
 pub use cost::CostSegment;
 pub use directory::DirectorySegment;
 pub use git::GitSegment;
+pub use hooks::HooksSegment;
+pub use identity::IdentitySegment;
+pub use memory::MemorySegment;
 pub use model::ModelSegment;
-pub use quota::QuotaSegment;
+pub use modes::ModesSegment;
 pub use output_style::OutputStyleSegment;
+pub use plan::PlanSegment;
+pub use quota::QuotaSegment;
+pub use registry::{
+    collect_registered, lookup_segment, register_segment, segment_name, with_registry,
+    SegmentIcons, SegmentInfo, SegmentOptionInfo, SegmentRegistry,
+};
 pub use session::SessionSegment;
+pub use transcript::TranscriptSegment;
 pub use update::UpdateSegment;
 pub use usage::UsageSegment;
+pub use vim::VimSegment;
@@ -1,14 +1,140 @@
-use super::{Segment, SegmentData};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
 use crate::config::{InputData, ModelConfig, SegmentId};
 use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct ModelSegment;
+/// Labels for well-known relays, matched against the base URL after the
+/// user's own `providers`
+const BUILTIN_PROVIDERS: &[(&str, &str)] = &[
+    ("packycode.com", "packy"),
+    ("openrouter.ai", "openrouter"),
+    ("api.deepseek.com", "deepseek"),
+    ("api.moonshot", "kimi"),
+    ("bigmodel.cn", "zhipu"),
+    ("dashscope.aliyuncs.com", "qwen"),
+];
+
+pub struct ModelSegment {
+    show_provider: bool,
+    /// Base URL substring and the label shown when it matches
+    providers: Vec<(String, String)>,
+}
+
+impl Default for ModelSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ModelSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Model,
+        name: "Model",
+        description: "Current model display name",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_provider",
+                kind: "bool",
+                default: "false",
+                description: "Show \"@label\" when ANTHROPIC_BASE_URL points to a relay",
+            },
+            SegmentOptionInfo {
+                name: "providers",
+                kind: "table",
+                default: "{}",
+                description: "Base URL substrings mapped to provider labels",
+            },
+        ],
+        data_sources: &[
+            "stdin: model",
+            "~/.claude/ccline/models.toml",
+            "env: ANTHROPIC_BASE_URL",
+            "~/.claude/settings.json",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "mdl",
+            emoji: "🤖",
+            nerd_font: "\u{e26d}",
+            nerd_font_v2: "\u{e26d}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self
+        Self {
+            show_provider: false,
+            providers: Vec::new(),
+        }
+    }
+
+    pub fn with_provider(mut self, show_provider: bool) -> Self {
+        self.show_provider = show_provider;
+        self
+    }
+
+    /// Labels checked before the built-in ones, e.g. `"relay.corp.com" = "corp"`
+    pub fn with_providers(mut self, providers: Vec<(String, String)>) -> Self {
+        self.providers = providers;
+        self
     }
+
+    /// Label for the relay behind `base_url`; `None` for Anthropic's own API
+    fn provider_label(&self, base_url: &str) -> Option<String> {
+        let url = base_url.to_ascii_lowercase();
+        let configured = self
+            .providers
+            .iter()
+            .map(|(pattern, label)| (pattern.as_str(), label.as_str()));
+        if let Some((_, label)) = configured
+            .chain(BUILTIN_PROVIDERS.iter().copied())
+            .find(|(pattern, _)| url.contains(&pattern.to_ascii_lowercase()))
+        {
+            return Some(label.to_string()).filter(|label| !label.is_empty());
+        }
+
+        // Unknown relays are named after their domain: "relay.example.com"
+        // becomes "example"; hosts without one (localhost, IPs) as they are
+        let host = host_of(&url)?;
+        if host == "api.anthropic.com" {
+            return None;
+        }
+        if host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+            return Some(host.to_string());
+        }
+        let labels: Vec<&str> = host.split('.').collect();
+        // Second-level suffixes such as "co.uk" and "com.cn" are not the name
+        let suffix_len = match labels.as_slice() {
+            [.., second, country]
+                if labels.len() > 2
+                    && country.len() == 2
+                    && matches!(*second, "co" | "com" | "net" | "org" | "ac" | "gov" | "edu") =>
+            {
+                3
+            }
+            _ => 2,
+        };
+        Some(
+            labels
+                .len()
+                .checked_sub(suffix_len)
+                .map_or(host, |index| labels[index])
+                .to_string(),
+        )
+    }
+}
+
+/// Host of a URL, without scheme, credentials, port or path
+fn host_of(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match authority.find(']') {
+        Some(end) => &authority[..=end],
+        None => authority.split(':').next().unwrap_or(authority),
+    };
+    Some(host).filter(|host| !host.is_empty())
 }
 
 impl Segment for ModelSegment {
@@ -17,9 +143,22 @@ impl Segment for ModelSegment {
         metadata.insert("model_id".to_string(), input.model.id.clone());
         metadata.insert("display_name".to_string(), input.model.display_name.clone());
 
+        let mut secondary = String::new();
+        if self.show_provider {
+            if let Some(base_url) = crate::utils::credentials::base_url() {
+                if let Some(host) = host_of(&base_url) {
+                    metadata.insert("base_url_host".to_string(), host.to_string());
+                }
+                if let Some(label) = self.provider_label(&base_url) {
+                    secondary = format!("@{}", label);
+                    metadata.insert("provider".to_string(), label);
+                }
+            }
+        }
+
         Some(SegmentData {
             primary: self.format_model_name(&input.model.id, &input.model.display_name),
-            secondary: String::new(),
+            secondary,
             metadata,
         })
     }
@@ -0,0 +1,314 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{AnsiColor, InputData, SegmentId};
+use crate::core::session_cache;
+use crate::i18n;
+use crate::utils::claude_settings::ClaudeSettings;
+use std::collections::HashMap;
+
+/// How many parent processes are searched for Claude Code's command line;
+/// the statusline command usually runs under a shell started by Claude Code
+#[cfg(unix)]
+const MAX_ANCESTORS: usize = 4;
+
+/// Badge colors used when the segment sets no `state_colors`: state,
+/// background, text
+pub const DEFAULT_STATE_COLORS: &[(&str, AnsiColor, AnsiColor)] = &[
+    (
+        "bypass",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "accept_edits",
+        AnsiColor::Color256 { c256: 172 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+    (
+        "plan",
+        AnsiColor::Color256 { c256: 31 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "no_auto_compact",
+        AnsiColor::Color256 { c256: 240 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+];
+
+/// A session mode worth a badge, most risky first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// `--dangerously-skip-permissions` or `bypassPermissions`
+    Bypass,
+    AcceptEdits,
+    Plan,
+    NoAutoCompact,
+}
+
+impl Mode {
+    fn state(&self) -> &'static str {
+        match self {
+            Mode::Bypass => "bypass",
+            Mode::AcceptEdits => "accept_edits",
+            Mode::Plan => "plan",
+            Mode::NoAutoCompact => "no_auto_compact",
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Mode::Bypass => i18n::text("modes.bypass"),
+            Mode::AcceptEdits => i18n::text("modes.accept_edits"),
+            Mode::Plan => i18n::text("modes.plan"),
+            Mode::NoAutoCompact => i18n::text("modes.no_auto_compact"),
+        }
+    }
+
+    /// A `--permission-mode` or `permissions.defaultMode` value
+    fn from_permission_mode(mode: &str) -> Option<Self> {
+        match mode {
+            "bypassPermissions" => Some(Mode::Bypass),
+            "acceptEdits" => Some(Mode::AcceptEdits),
+            "plan" => Some(Mode::Plan),
+            _ => None,
+        }
+    }
+}
+
+pub struct ModesSegment {
+    show_auto_compact: bool,
+}
+
+impl Default for ModesSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModesSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Modes,
+        name: "Session Modes",
+        description: "Badges for risky modes: bypassed permissions (YOLO), auto-accepted edits, plan mode, auto-compact off",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_auto_compact",
+                kind: "bool",
+                default: "true",
+                description: "Show a badge when auto-compact is switched off",
+            },
+            SegmentOptionInfo {
+                name: "state_colors",
+                kind: "table",
+                default: "{}",
+                description: "Colors per most risky mode: bypass, accept_edits, plan, no_auto_compact",
+            },
+        ],
+        data_sources: &[
+            "Claude Code command line: --dangerously-skip-permissions, --permission-mode",
+            "~/.claude/settings.json, .claude/settings.json, .claude/settings.local.json: permissions.defaultMode",
+            "~/.claude.json: autoCompactEnabled",
+        ],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "mode",
+            emoji: "⚠️",
+            nerd_font: "\u{f071}",
+            nerd_font_v2: "\u{f071}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            show_auto_compact: true,
+        }
+    }
+
+    pub fn with_auto_compact(mut self, show_auto_compact: bool) -> Self {
+        self.show_auto_compact = show_auto_compact;
+        self
+    }
+
+    /// State name of the permission mode in effect ("plan", "accept_edits",
+    /// "bypass"): the launch flags, else the settings' default mode
+    pub fn permission_mode(input: &InputData) -> Option<&'static str> {
+        Self::launch_mode(&input.transcript_path)
+            .or_else(|| {
+                ClaudeSettings::load(&input.workspace.current_dir)
+                    .default_mode()
+                    .and_then(Mode::from_permission_mode)
+            })
+            .map(|mode| mode.state())
+    }
+
+    /// Permission mode Claude Code was started with. Flags cannot change
+    /// during a session, so they are cached with it.
+    fn launch_mode(transcript_path: &str) -> Option<Mode> {
+        let args =
+            session_cache::with_session(transcript_path, |session| session.launch_args.clone())
+                .unwrap_or_else(|| {
+                    let args = claude_code_args();
+                    session_cache::update_session(transcript_path, |session| {
+                        session.launch_args = Some(args.clone());
+                    });
+                    args
+                });
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--dangerously-skip-permissions" {
+                return Some(Mode::Bypass);
+            }
+            let mode = match arg.strip_prefix("--permission-mode") {
+                Some("") => args.next().map(String::as_str),
+                Some(rest) => rest.strip_prefix('='),
+                None => None,
+            };
+            if let Some(mode) = mode.and_then(Mode::from_permission_mode) {
+                return Some(mode);
+            }
+        }
+        None
+    }
+
+    /// `autoCompactEnabled` in `~/.claude.json`, which `/config` writes
+    fn auto_compact_disabled() -> bool {
+        let Some(path) = crate::utils::paths::home_dir().map(|home| home.join(".claude.json"))
+        else {
+            return false;
+        };
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|state| state.get("autoCompactEnabled")?.as_bool())
+            .map(|enabled| !enabled)
+            .unwrap_or(false)
+    }
+}
+
+/// Permission flags of the nearest parent process that has any: Claude Code,
+/// unless it was started without them
+#[cfg(target_os = "linux")]
+fn claude_code_args() -> Vec<String> {
+    let mut pid = std::os::unix::process::parent_id();
+    for _ in 0..MAX_ANCESTORS {
+        let Ok(cmdline) = std::fs::read(format!("/proc/{}/cmdline", pid)) else {
+            break;
+        };
+        let args: Vec<String> = cmdline
+            .split(|byte| *byte == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        let flags = permission_flags(&args);
+        if !flags.is_empty() {
+            return flags;
+        }
+
+        // The parent pid follows the command name, which is in parentheses
+        // and may itself contain spaces
+        let Some(parent) = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .ok()
+            .and_then(|stat| {
+                let rest = &stat[stat.rfind(')')? + 1..];
+                rest.split_whitespace().nth(1)?.parse().ok()
+            })
+        else {
+            break;
+        };
+        if parent <= 1 {
+            break;
+        }
+        pid = parent;
+    }
+    Vec::new()
+}
+
+/// macOS and the BSDs have no /proc; `ps` lists the parents instead
+#[cfg(all(unix, not(target_os = "linux")))]
+fn claude_code_args() -> Vec<String> {
+    let mut pid = std::os::unix::process::parent_id();
+    for _ in 0..MAX_ANCESTORS {
+        let Ok(output) = std::process::Command::new("ps")
+            .args(["-o", "ppid=,args=", "-p", &pid.to_string()])
+            .output()
+        else {
+            break;
+        };
+        let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let Some((parent, command)) = line.split_once(char::is_whitespace) else {
+            break;
+        };
+        // `ps` joins the arguments with spaces; the flags have none
+        let args: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+        let flags = permission_flags(&args);
+        if !flags.is_empty() {
+            return flags;
+        }
+        match parent.trim().parse() {
+            Ok(parent) if parent > 1 => pid = parent,
+            _ => break,
+        }
+    }
+    Vec::new()
+}
+
+/// Windows offers no cheap way to read another process's command line
+#[cfg(not(unix))]
+fn claude_code_args() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(unix)]
+/// Only the permission flags and their values are kept; the rest of the
+/// command line may hold anything, secrets included
+fn permission_flags(args: &[String]) -> Vec<String> {
+    let mut flags = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--dangerously-skip-permissions" || arg.starts_with("--permission-mode=") {
+            flags.push(arg.clone());
+        } else if arg == "--permission-mode" {
+            flags.push(arg.clone());
+            flags.extend(args.next().cloned());
+        }
+    }
+    flags
+}
+
+impl Segment for ModesSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        // The command line overrides the settings' default mode
+        let permission = Self::launch_mode(&input.transcript_path).or_else(|| {
+            ClaudeSettings::load(&input.workspace.current_dir)
+                .default_mode()
+                .and_then(Mode::from_permission_mode)
+        });
+        let mut modes: Vec<Mode> = permission.into_iter().collect();
+        if self.show_auto_compact && Self::auto_compact_disabled() {
+            modes.push(Mode::NoAutoCompact);
+        }
+
+        let most_risky = *modes.first()?;
+        let mut metadata = HashMap::new();
+        metadata.insert("state".to_string(), most_risky.state().to_string());
+        metadata.insert(
+            "modes".to_string(),
+            modes.iter().map(Mode::state).collect::<Vec<_>>().join(","),
+        );
+        if most_risky == Mode::Bypass {
+            metadata.insert("level".to_string(), "critical".to_string());
+        }
+
+        Some(SegmentData {
+            primary: modes.iter().map(Mode::label).collect::<Vec<_>>().join(" "),
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Modes
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Segment, SegmentData};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo};
 use crate::config::{InputData, SegmentId};
 use std::collections::HashMap;
 
@@ -6,6 +6,22 @@ use std::collections::HashMap;
 pub struct OutputStyleSegment;
 
 impl OutputStyleSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::OutputStyle,
+        name: "Output Style",
+        description: "Active Claude Code output style",
+        features: &[],
+        options: &[],
+        data_sources: &["stdin: output_style"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "style",
+            emoji: "🎯",
+            nerd_font: "\u{f12f5}",
+            nerd_font_v2: "\u{f140}",
+        },
+    };
+
     pub fn new() -> Self {
         Self
     }
@@ -0,0 +1,596 @@
+// Without the `quota` feature the segment never goes online and its helpers are unused
+#![cfg_attr(not(feature = "quota"), allow(dead_code, unused_imports))]
+
+use super::session::format_duration;
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, SegmentId};
+use crate::core::weekly;
+use crate::error::Error;
+use crate::i18n;
+use crate::utils::credentials::{self, OAuthCredentials};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const PROFILE_URL: &str = "https://api.anthropic.com/api/oauth/profile";
+const USAGE_URL: &str = "https://api.anthropic.com/api/oauth/usage";
+/// Beta header the OAuth endpoints require
+const OAUTH_BETA: &str = "oauth-2025-04-20";
+
+/// How long fetched profile and usage data is reused
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Length of the session window the usage API reports as `five_hour`
+pub const SESSION_WINDOW_SECS: u64 = 5 * 3600;
+/// The pace over less time than this is too noisy to project
+pub const MIN_PACE_SECS: u64 = 10 * 60;
+/// The pace is measured over this much of the recent past, so a burst after
+/// an idle start is not averaged away
+pub const PACE_WINDOW_SECS: u64 = 30 * 60;
+/// Projections shorter than this are critical rather than a warning
+const CRITICAL_LEFT_SECS: u64 = 30 * 60;
+/// Percent of a weekly cap from which the segment warns
+const CAP_WARNING_PERCENT: f64 = 80.0;
+
+/// `organization` of the OAuth profile
+#[derive(Debug, Deserialize)]
+struct ProfileOrganization {
+    #[serde(default)]
+    organization_type: Option<String>,
+    #[serde(default)]
+    rate_limit_tier: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    #[serde(default)]
+    organization: Option<ProfileOrganization>,
+}
+
+/// One limit window of the OAuth usage endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UsageWindow {
+    /// Percent of the window's allowance used, 0–100
+    utilization: f64,
+    #[serde(default)]
+    resets_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageResponse {
+    #[serde(default)]
+    five_hour: Option<UsageWindow>,
+    #[serde(default)]
+    seven_day: Option<UsageWindow>,
+}
+
+/// What was fetched for a token, kept in `~/.claude/ccline/plan_cache.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PlanCache {
+    token_hash: u64,
+    fetched_at: SystemTime,
+    plan: Option<String>,
+    /// The 5-hour session window
+    #[serde(default)]
+    session: Option<UsageWindow>,
+    /// Earlier readings of the session window's utilization, as Unix
+    /// seconds and percent, from the last [`PACE_WINDOW_SECS`]
+    #[serde(default)]
+    session_history: Vec<(u64, f64)>,
+    weekly: Option<UsageWindow>,
+}
+
+pub struct PlanSegment {
+    /// Plan set in the segment options, overriding detection
+    plan: Option<String>,
+    show_weekly: bool,
+    show_time_left: bool,
+    /// Tokens a week, all models and Opus, tallied from local transcripts
+    weekly_cap: Option<u64>,
+    weekly_opus_cap: Option<u64>,
+}
+
+impl Default for PlanSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlanSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Plan,
+        name: "Plan",
+        description: "Claude subscription plan (Pro, Max 5x, Max 20x), weekly allowance or weekly caps used, and when the 5-hour session allowance runs out at the current pace",
+        features: &["quota"],
+        options: &[
+            SegmentOptionInfo {
+                name: "plan",
+                kind: "string",
+                default: "",
+                description:
+                    "Plan to show instead of detecting it: pro, max5, max20, team, enterprise",
+            },
+            SegmentOptionInfo {
+                name: "show_weekly",
+                kind: "bool",
+                default: "true",
+                description: "Show the percentage of the weekly allowance left",
+            },
+            SegmentOptionInfo {
+                name: "show_time_left",
+                kind: "bool",
+                default: "true",
+                description: "Show how long the 5-hour session allowance lasts at the current pace, when it runs out before the window resets",
+            },
+            SegmentOptionInfo {
+                name: "weekly_cap",
+                kind: "integer",
+                default: "",
+                description: "Tokens a week to measure local usage against, over a rolling seven days of transcripts; replaces the weekly allowance from the API",
+            },
+            SegmentOptionInfo {
+                name: "weekly_opus_cap",
+                kind: "integer",
+                default: "",
+                description: "Tokens a week on Opus models to measure local usage against",
+            },
+        ],
+        data_sources: &[
+            "~/.claude/.credentials.json",
+            "macOS Keychain: Claude Code-credentials",
+            "Anthropic OAuth API: /api/oauth/profile, /api/oauth/usage",
+            "~/.claude/ccline/plan_cache.json",
+            "~/.claude/projects/*/*.jsonl (with a weekly cap)",
+            "~/.claude/ccline/weekly_usage.json",
+        ],
+        network: true,
+        icons: SegmentIcons {
+            ascii: "plan",
+            emoji: "🎫",
+            nerd_font: "\u{f0c0}",
+            nerd_font_v2: "\u{f0c0}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            plan: None,
+            show_weekly: true,
+            show_time_left: true,
+            weekly_cap: None,
+            weekly_opus_cap: None,
+        }
+    }
+
+    pub fn with_plan(mut self, plan: Option<&str>) -> Self {
+        self.plan = plan
+            .map(str::trim)
+            .filter(|plan| !plan.is_empty())
+            .map(plan_from_option);
+        self
+    }
+
+    pub fn with_weekly(mut self, show_weekly: bool) -> Self {
+        self.show_weekly = show_weekly;
+        self
+    }
+
+    pub fn with_time_left(mut self, show_time_left: bool) -> Self {
+        self.show_time_left = show_time_left;
+        self
+    }
+
+    /// Weekly token caps for all models and for Opus; zero means no cap
+    pub fn with_weekly_caps(mut self, all: Option<u64>, opus: Option<u64>) -> Self {
+        self.weekly_cap = all.filter(|cap| *cap > 0);
+        self.weekly_opus_cap = opus.filter(|cap| *cap > 0);
+        self
+    }
+
+    /// "41% of weekly cap" for each configured cap, from the local tally,
+    /// with when the usage over a reached cap has rolled out of the window
+    fn weekly_caps(&self, now: u64, metadata: &mut HashMap<String, String>) -> Vec<String> {
+        if self.weekly_cap.is_none() && self.weekly_opus_cap.is_none() {
+            return Vec::new();
+        }
+        let usage = weekly::usage();
+        metadata.insert("weekly_tokens".to_string(), usage.tokens.to_string());
+        metadata.insert(
+            "weekly_opus_tokens".to_string(),
+            usage.opus_tokens.to_string(),
+        );
+
+        let mut parts = Vec::new();
+        let caps = [
+            (
+                self.weekly_cap,
+                usage.tokens,
+                false,
+                "plan.weekly_cap",
+                "weekly_cap",
+            ),
+            (
+                self.weekly_opus_cap,
+                usage.opus_tokens,
+                true,
+                "plan.weekly_opus_cap",
+                "weekly_opus_cap",
+            ),
+        ];
+        for (cap, tokens, opus, text, key) in caps {
+            let Some(cap) = cap else {
+                continue;
+            };
+            let percent = tokens as f64 / cap as f64 * 100.0;
+            metadata.insert(format!("{}_used_percent", key), format!("{:.1}", percent));
+            let mut part = i18n::text_with(text, &[&format!("{:.0}", percent)]);
+            if let Some(seconds) = usage.under_cap_in(cap, opus, now) {
+                metadata.insert(format!("{}_under_in_secs", key), seconds.to_string());
+                raise_level(metadata, "critical");
+                part.push_str(", ");
+                part.push_str(&i18n::text_with(
+                    "plan.under_cap_in",
+                    &[&format_duration(seconds.saturating_mul(1000))],
+                ));
+            } else if percent >= CAP_WARNING_PERCENT {
+                raise_level(metadata, "warning");
+            }
+            parts.push(part);
+        }
+        parts
+    }
+
+    fn cache_path() -> PathBuf {
+        crate::utils::paths::cache_dir().join("plan_cache.json")
+    }
+
+    fn token_hash(token: &str) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        token.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Profile plan and session and weekly usage for the signed-in account, fetched at
+    /// most once per [`CACHE_TTL`]. A failed fetch keeps the last result.
+    fn fetch(credentials: &OAuthCredentials) -> Option<PlanCache> {
+        let token_hash = Self::token_hash(&credentials.access_token);
+        let cached = fs::read_to_string(Self::cache_path())
+            .ok()
+            .and_then(|json| serde_json::from_str::<PlanCache>(&json).ok())
+            .filter(|cache| cache.token_hash == token_hash);
+        let fresh = cached
+            .as_ref()
+            .is_some_and(|cache| cache.fetched_at.elapsed().is_ok_and(|age| age < CACHE_TTL));
+        if fresh || credentials.is_expired() {
+            return cached;
+        }
+
+        let profile = Self::get::<ProfileResponse>(PROFILE_URL, &credentials.access_token);
+        let usage = Self::get::<UsageResponse>(USAGE_URL, &credentials.access_token);
+        if profile.is_none() && usage.is_none() {
+            return cached;
+        }
+
+        let plan = profile
+            .and_then(|profile| profile.organization)
+            .and_then(|org| {
+                plan_label(
+                    org.organization_type.as_deref(),
+                    org.rate_limit_tier.as_deref(),
+                )
+            })
+            .or_else(|| cached.as_ref().and_then(|cache| cache.plan.clone()));
+        let (session, weekly) = usage
+            .map(|usage| (usage.five_hour, usage.seven_day))
+            .unwrap_or_default();
+        let session_history = match (&cached, &session) {
+            (Some(cached), Some(session)) => session_history(cached, session),
+            _ => Vec::new(),
+        };
+        let (cached_session, cached_weekly) = cached
+            .map(|cache| (cache.session, cache.weekly))
+            .unwrap_or_default();
+        let cache = PlanCache {
+            token_hash,
+            fetched_at: SystemTime::now(),
+            plan,
+            session: session.or(cached_session),
+            session_history,
+            weekly: weekly.or(cached_weekly),
+        };
+
+        let path = Self::cache_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_vec(&cache) {
+            let _ = crate::utils::atomic::write(&path, &json);
+        }
+        Some(cache)
+    }
+
+    #[cfg(feature = "quota")]
+    fn get<T: serde::de::DeserializeOwned>(url: &str, token: &str) -> Option<T> {
+        crate::utils::http::agent()
+            .get(url)
+            .set("Authorization", &format!("Bearer {}", token))
+            .set("anthropic-beta", OAUTH_BETA)
+            .timeout(Duration::from_secs(3))
+            .call()
+            .ok()?
+            .into_json::<T>()
+            .ok()
+    }
+
+    #[cfg(not(feature = "quota"))]
+    fn get<T>(_url: &str, _token: &str) -> Option<T> {
+        None
+    }
+}
+
+/// Display name for a plan, from the subscription or organization type
+/// ("max", "claude_max") and the rate limit tier ("default_claude_max_20x")
+fn plan_label(kind: Option<&str>, rate_limit_tier: Option<&str>) -> Option<String> {
+    let tier = rate_limit_tier.unwrap_or("").to_ascii_lowercase();
+    if tier.contains("max_20x") {
+        return Some("Max 20x".to_string());
+    }
+    if tier.contains("max_5x") {
+        return Some("Max 5x".to_string());
+    }
+
+    let kind = kind?.to_ascii_lowercase();
+    let label = match kind.strip_prefix("claude_").unwrap_or(&kind) {
+        "max" => "Max",
+        "pro" => "Pro",
+        "team" => "Team",
+        "enterprise" => "Enterprise",
+        "free" => "Free",
+        "" => return None,
+        other => return Some(other.to_string()),
+    };
+    Some(label.to_string())
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The readings of `cached` to keep next to the newly fetched `session`:
+/// those of the same window from the last [`PACE_WINDOW_SECS`]
+fn session_history(cached: &PlanCache, session: &UsageWindow) -> Vec<(u64, f64)> {
+    let resets_at = |window: &UsageWindow| {
+        window
+            .resets_at
+            .as_deref()
+            .and_then(crate::utils::rate_limit::parse_rfc3339)
+    };
+    let Some(previous) = &cached.session else {
+        return Vec::new();
+    };
+    // A window that reset starts over; the reset time may move by a few
+    // seconds between answers
+    let same_window = match (resets_at(previous), resets_at(session)) {
+        (Some(before), Some(now)) => before.abs_diff(now) < 60,
+        _ => false,
+    };
+    if !same_window || previous.utilization > session.utilization {
+        return Vec::new();
+    }
+
+    let since = unix_seconds(SystemTime::now()).saturating_sub(PACE_WINDOW_SECS);
+    cached
+        .session_history
+        .iter()
+        .copied()
+        .chain(std::iter::once((
+            unix_seconds(cached.fetched_at),
+            previous.utilization,
+        )))
+        .filter(|(at, _)| *at >= since)
+        .collect()
+}
+
+/// Seconds until the session allowance runs out if it keeps being used at
+/// its recent pace: `used_percent` of it is used at `now`, the window
+/// resets at `resets_at`, and `history` holds earlier readings of the same
+/// window as Unix seconds and percent. The pace is measured from the oldest
+/// reading of the last [`PACE_WINDOW_SECS`], or from the window opening at
+/// 0% when that was more recent. `None` when the allowance lasts until the
+/// reset, or when the readings span less than [`MIN_PACE_SECS`].
+#[doc(hidden)]
+pub fn session_time_left(
+    used_percent: f64,
+    resets_at: u64,
+    now: u64,
+    history: &[(u64, f64)],
+) -> Option<u64> {
+    let until_reset = resets_at.checked_sub(now)?;
+    if used_percent.is_nan() || used_percent <= 0.0 {
+        return None;
+    }
+    if used_percent >= 100.0 {
+        return Some(0);
+    }
+
+    let since = now.saturating_sub(PACE_WINDOW_SECS);
+    let opened = (resets_at.saturating_sub(SESSION_WINDOW_SECS), 0.0);
+    let (from, from_percent) = history
+        .iter()
+        .copied()
+        .chain(std::iter::once(opened))
+        .filter(|(at, percent)| (since..now).contains(at) && *percent <= used_percent)
+        .min_by_key(|(at, _)| *at)?;
+    let elapsed = now - from;
+    if elapsed < MIN_PACE_SECS {
+        return None;
+    }
+
+    let percent_per_second = (used_percent - from_percent) / elapsed as f64;
+    if percent_per_second <= 0.0 {
+        return None;
+    }
+    let left = ((100.0 - used_percent) / percent_per_second) as u64;
+    (left < until_reset).then_some(left)
+}
+
+/// Set the `level` metadata to `level` unless it is already critical
+fn raise_level(metadata: &mut HashMap<String, String>, level: &str) {
+    if metadata.get("level").map(String::as_str) != Some("critical") {
+        metadata.insert("level".to_string(), level.to_string());
+    }
+}
+
+/// "1.4h" from an hour on, whole minutes below
+fn format_time_left(seconds: u64) -> String {
+    if seconds >= 3600 {
+        let hours = format!("{:.1}", seconds as f64 / 3600.0);
+        i18n::text_with("duration.h", &[&hours])
+    } else {
+        i18n::text_with("duration.m", &[&(seconds / 60).to_string()])
+    }
+}
+
+/// Plan written in the segment options; unknown names are shown as written
+fn plan_from_option(plan: &str) -> String {
+    match plan
+        .to_ascii_lowercase()
+        .replace([' ', '_', '-'], "")
+        .as_str()
+    {
+        "pro" => "Pro".to_string(),
+        "max" | "max5" | "max5x" => "Max 5x".to_string(),
+        "max20" | "max20x" => "Max 20x".to_string(),
+        "team" => "Team".to_string(),
+        "enterprise" => "Enterprise".to_string(),
+        _ => plan.to_string(),
+    }
+}
+
+impl Segment for PlanSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        self.try_collect(input).ok().flatten()
+    }
+
+    fn try_collect(&self, _input: &InputData) -> Result<Option<SegmentData>, Error> {
+        let mut metadata = HashMap::new();
+        let credentials = credentials::oauth();
+        // Past expiry nothing is fetched; the last results are shown until
+        // Claude Code refreshes the token
+        if credentials.as_ref().is_some_and(|c| c.is_expired()) {
+            metadata.insert("token_expired".to_string(), "true".to_string());
+        }
+        let local_plan = self.plan.clone().map(|plan| (plan, "option")).or_else(|| {
+            let c = credentials.as_ref()?;
+            plan_label(c.subscription_type.as_deref(), c.rate_limit_tier.as_deref())
+                .map(|plan| (plan, "credentials"))
+        });
+
+        // Only subscription sign-ins have a plan or weekly allowance to
+        // fetch, and only go online for what is not known locally
+        let fetched = match &credentials {
+            Some(credentials)
+                if cfg!(feature = "quota")
+                    && (self.show_weekly || self.show_time_left || local_plan.is_none()) =>
+            {
+                Self::fetch(credentials)
+            }
+            _ => None,
+        };
+
+        let (plan, source) = if let Some(local) = local_plan {
+            local
+        } else if let Some(plan) = fetched.as_ref().and_then(|cache| cache.plan.clone()) {
+            (plan, "profile")
+        } else if credentials.is_none() {
+            return Err(Error::MissingCredentials(
+                "subscription sign-in, or the plan option",
+            ));
+        } else {
+            return Ok(None);
+        };
+        metadata.insert("plan".to_string(), plan.clone());
+        metadata.insert("source".to_string(), source.to_string());
+
+        let now = unix_seconds(SystemTime::now());
+        let mut parts = Vec::new();
+        let (session, history, fetched_at, weekly) = fetched
+            .map(|cache| {
+                let fetched_at = unix_seconds(cache.fetched_at);
+                (
+                    cache.session,
+                    cache.session_history,
+                    fetched_at,
+                    cache.weekly,
+                )
+            })
+            .unwrap_or_default();
+        if let Some(session) = session {
+            metadata.insert(
+                "session_used_percent".to_string(),
+                format!("{:.1}", session.utilization),
+            );
+            let resets_at = session.resets_at.as_deref();
+            if let Some(resets_at) = resets_at {
+                metadata.insert("session_resets_at".to_string(), resets_at.to_string());
+            }
+            // The reading is from the fetch, and the time since it passed
+            let time_left = resets_at
+                .and_then(crate::utils::rate_limit::parse_rfc3339)
+                .and_then(|resets_at| {
+                    session_time_left(session.utilization, resets_at, fetched_at, &history)
+                })
+                .map(|left| left.saturating_sub(now.saturating_sub(fetched_at)));
+            if let Some(seconds) = time_left {
+                metadata.insert("session_time_left_secs".to_string(), seconds.to_string());
+                let level = if seconds < CRITICAL_LEFT_SECS {
+                    "critical"
+                } else {
+                    "warning"
+                };
+                raise_level(&mut metadata, level);
+                if self.show_time_left {
+                    parts.push(i18n::text_with(
+                        "plan.time_left",
+                        &[&format_time_left(seconds)],
+                    ));
+                }
+            }
+        }
+        let caps = self.weekly_caps(now, &mut metadata);
+        if let Some(weekly) = weekly {
+            let left = (100.0 - weekly.utilization).clamp(0.0, 100.0);
+            metadata.insert(
+                "weekly_used_percent".to_string(),
+                format!("{:.1}", weekly.utilization),
+            );
+            metadata.insert("weekly_left_percent".to_string(), format!("{:.1}", left));
+            if let Some(resets_at) = weekly.resets_at {
+                metadata.insert("weekly_resets_at".to_string(), resets_at);
+            }
+            if self.show_weekly && caps.is_empty() {
+                parts.push(i18n::text_with(
+                    "plan.weekly_left",
+                    &[&format!("{:.0}", left)],
+                ));
+            }
+        }
+
+        parts.extend(caps);
+
+        Ok(Some(SegmentData {
+            primary: plan,
+            secondary: parts.join(", "),
+            metadata,
+        }))
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Plan
+    }
+}
@@ -1,5 +1,15 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+// Without the `quota` feature the segment never goes online and its helpers are unused
+#![cfg_attr(not(feature = "quota"), allow(dead_code, unused_imports))]
+
+use super::session::format_duration;
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, NumberFormat, SegmentId};
+use crate::core::executor::{first_ok, Task, NETWORK_DEADLINE};
+use crate::error::Error;
+use crate::i18n;
+use crate::utils::number;
+use crate::utils::rate_limit::{RateLimit, RateLimits};
+use crate::utils::redact::debug_log;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
@@ -13,6 +23,20 @@ fn default_opus_enabled() -> bool {
     true
 }
 
+/// An amount the API sends as a string or a number
+fn amount<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(number)) => number.as_f64(),
+            Some(serde_json::Value::String(text)) => text.trim().parse().ok(),
+            _ => None,
+        },
+    )
+}
+
 // API 响应结构
 #[derive(Debug, Deserialize)]
 struct PackyCodeApiResponse {
@@ -20,6 +44,68 @@ struct PackyCodeApiResponse {
     daily_spent_usd: String,
     #[serde(rename = "opus_enabled", default = "default_opus_enabled")]
     opus_enabled: bool,
+    #[serde(default, deserialize_with = "amount")]
+    daily_budget_usd: Option<f64>,
+    #[serde(default, deserialize_with = "amount")]
+    balance_usd: Option<f64>,
+}
+
+/// An endpoint's answer: the account data, or none when it throttled the
+/// request (429), with the rate limits its headers reported
+struct Reply {
+    body: Option<PackyCodeApiResponse>,
+    rate_limits: RateLimits,
+}
+
+/// What a user info endpoint reports for one account
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountQuota {
+    pub endpoint: String,
+    pub daily_spent_usd: Option<f64>,
+    /// Only when the endpoint reports them
+    pub daily_budget_usd: Option<f64>,
+    pub balance_usd: Option<f64>,
+    pub opus_enabled: bool,
+}
+
+/// Ask all `endpoints` (PackyCode's when empty) about the account behind
+/// `api_key` at once and take the first answer, or the last error when none
+/// answers. Unlike the segment this leaves the endpoint cache alone, since it
+/// remembers a single key.
+pub fn query_account(api_key: &str, endpoints: &[String]) -> Result<AccountQuota, Error> {
+    let endpoints: Vec<EndpointConfig> = if endpoints.is_empty() {
+        packycode_endpoints()
+    } else {
+        endpoints
+            .iter()
+            .map(|url| EndpointConfig {
+                url: url.clone(),
+                name: url.clone(),
+            })
+            .collect()
+    };
+    let tasks: Vec<Task<Result<PackyCodeApiResponse, Error>>> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let endpoint = endpoint.clone();
+            let api_key = api_key.to_string();
+            Box::new(move || {
+                // Throttled: no figures, and the other endpoints may still have them
+                SmartEndpointDetector::try_endpoint(&endpoint, &api_key)?
+                    .body
+                    .ok_or(Error::Status(429))
+            }) as Task<Result<PackyCodeApiResponse, Error>>
+        })
+        .collect();
+
+    let (index, response) = first_ok(tasks, NETWORK_DEADLINE).unwrap_or(Err(Error::Timeout))?;
+    Ok(AccountQuota {
+        endpoint: endpoints[index].url.clone(),
+        daily_spent_usd: response.daily_spent_usd.trim().parse().ok(),
+        daily_budget_usd: response.daily_budget_usd,
+        balance_usd: response.balance_usd,
+        opus_enabled: response.opus_enabled,
+    })
 }
 
 // 端点配置
@@ -45,18 +131,22 @@ struct SmartEndpointDetector {
     cache_file_path: PathBuf,
 }
 
+fn packycode_endpoints() -> Vec<EndpointConfig> {
+    vec![
+        EndpointConfig {
+            url: "https://www.packycode.com/api/backend/users/info".to_string(),
+            name: "main".to_string(),
+        },
+        EndpointConfig {
+            url: "https://share.packycode.com/api/backend/users/info".to_string(),
+            name: "share".to_string(),
+        },
+    ]
+}
+
 impl SmartEndpointDetector {
     fn new() -> Self {
-        let endpoints = vec![
-            EndpointConfig {
-                url: "https://www.packycode.com/api/backend/users/info".to_string(),
-                name: "main".to_string(),
-            },
-            EndpointConfig {
-                url: "https://share.packycode.com/api/backend/users/info".to_string(),
-                name: "share".to_string(),
-            },
-        ];
+        let endpoints = packycode_endpoints();
 
         let cache_file_path = Self::get_cache_file_path();
         let cache = Self::load_cache(&cache_file_path);
@@ -69,13 +159,7 @@ impl SmartEndpointDetector {
     }
 
     fn get_cache_file_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude")
-                .join("ccline")
-                .join("endpoint_cache.json")
-        } else {
-            PathBuf::from("endpoint_cache.json")
-        }
+        crate::utils::paths::cache_dir().join("endpoint_cache.json")
     }
 
     fn load_cache(cache_path: &PathBuf) -> Option<EndpointCache> {
@@ -93,8 +177,8 @@ impl SmartEndpointDetector {
                 let _ = fs::create_dir_all(parent);
             }
 
-            if let Ok(content) = serde_json::to_string_pretty(cache) {
-                let _ = fs::write(&self.cache_file_path, content);
+            if let Ok(content) = serde_json::to_vec(cache) {
+                let _ = crate::utils::atomic::write(&self.cache_file_path, &content);
             }
         }
     }
@@ -119,19 +203,27 @@ impl SmartEndpointDetector {
         }
     }
 
-    fn try_endpoint(
-        &self,
-        endpoint: &EndpointConfig,
-        api_key: &str,
-    ) -> Option<PackyCodeApiResponse> {
+    #[cfg(feature = "quota")]
+    fn rate_limits(response: &ureq::Response) -> RateLimits {
+        let names = response.headers_names();
+        RateLimits::from_headers(
+            names
+                .iter()
+                .filter_map(|name| Some((name.as_str(), response.header(name)?))),
+        )
+    }
+
+    #[cfg(feature = "quota")]
+    fn try_endpoint(endpoint: &EndpointConfig, api_key: &str) -> Result<Reply, Error> {
         let debug = env::var("PACKYCODE_DEBUG").is_ok();
 
         if debug {
-            eprintln!("[DEBUG] Trying endpoint: {}", endpoint.url);
+            debug_log(&format!("Trying endpoint: {}", endpoint.url), &[api_key]);
         }
 
         let start_time = SystemTime::now();
-        let result = ureq::get(&endpoint.url)
+        let result = crate::utils::http::agent()
+            .get(&endpoint.url)
             .set("Authorization", &format!("Bearer {}", api_key))
             .set("accept", "*/*")
             .set("content-type", "application/json")
@@ -143,35 +235,56 @@ impl SmartEndpointDetector {
                 if response.status() == 200 {
                     let elapsed = start_time.elapsed().unwrap_or(Duration::from_secs(0));
                     if debug {
-                        eprintln!(
-                            "[DEBUG] Success: {} in {}ms",
-                            endpoint.name,
-                            elapsed.as_millis()
+                        debug_log(
+                            &format!("Success: {} in {}ms", endpoint.name, elapsed.as_millis()),
+                            &[api_key],
                         );
                     }
 
-                    response.into_json::<PackyCodeApiResponse>().ok()
+                    let rate_limits = Self::rate_limits(&response);
+                    let body = response
+                        .into_json::<PackyCodeApiResponse>()
+                        .map_err(|e| Error::Parse(e.to_string()))?;
+                    Ok(Reply {
+                        body: Some(body),
+                        rate_limits,
+                    })
                 } else {
                     if debug {
-                        eprintln!(
-                            "[DEBUG] Failed: {} status {}",
-                            endpoint.name,
-                            response.status()
+                        debug_log(
+                            &format!("Failed: {} status {}", endpoint.name, response.status()),
+                            &[api_key],
                         );
                     }
-                    None
+                    Err(Error::Status(response.status()))
+                }
+            }
+            Err(ureq::Error::Status(429, response)) => {
+                if debug {
+                    debug_log(&format!("Throttled: {}", endpoint.name), &[api_key]);
                 }
+                Ok(Reply {
+                    body: None,
+                    rate_limits: Self::rate_limits(&response),
+                })
             }
             Err(e) => {
                 if debug {
-                    eprintln!("[DEBUG] Error: {} - {}", endpoint.name, e);
+                    debug_log(&format!("Error: {} - {}", endpoint.name, e), &[api_key]);
                 }
-                None
+                Err(e.into())
             }
         }
     }
 
-    fn detect_endpoint(&mut self, api_key: &str) -> Option<(String, PackyCodeApiResponse)> {
+    #[cfg(not(feature = "quota"))]
+    fn try_endpoint(_endpoint: &EndpointConfig, _api_key: &str) -> Result<Reply, Error> {
+        Err(Error::Network(
+            "built without the quota feature".to_string(),
+        ))
+    }
+
+    fn detect_endpoint(&mut self, api_key: &str) -> Result<(String, Reply), Error> {
         // 检查缓存是否有效
         if self.is_cache_valid(api_key) {
             if let Some(ref cache) = self.cache.clone() {
@@ -179,120 +292,405 @@ impl SmartEndpointDetector {
 
                 // 尝试使用缓存的端点
                 if let Some(endpoint) = self.endpoints.iter().find(|e| e.url == *cached_endpoint) {
-                    if let Some(response) = self.try_endpoint(endpoint, api_key) {
+                    if let Ok(reply) = Self::try_endpoint(endpoint, api_key) {
                         // 更新缓存统计
-                        self.update_cache_stats(api_key, cached_endpoint);
-                        return Some((cached_endpoint.clone(), response));
+                        if reply.body.is_some() {
+                            self.update_cache_stats(api_key, cached_endpoint);
+                        }
+                        return Ok((cached_endpoint.clone(), reply));
                     }
                 }
             }
         }
 
-        // 缓存失效或失败，尝试所有端点
-        let endpoints_clone = self.endpoints.clone();
-        for endpoint in &endpoints_clone {
-            if let Some(response) = self.try_endpoint(endpoint, api_key) {
-                // 更新缓存
-                self.update_cache(api_key, &endpoint.url);
-                return Some((endpoint.url.clone(), response));
-            }
+        // 缓存失效或失败，并发尝试所有端点，采用最先成功的结果
+        // A throttled endpoint answers too, since the others serve the same
+        // account
+        let tasks: Vec<Task<Result<Reply, Error>>> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let endpoint = endpoint.clone();
+                let api_key = api_key.to_string();
+                Box::new(move || Self::try_endpoint(&endpoint, &api_key))
+                    as Task<Result<Reply, Error>>
+            })
+            .collect();
+
+        let (index, reply) = first_ok(tasks, NETWORK_DEADLINE).unwrap_or(Err(Error::Timeout))?;
+        let endpoint_url = self.endpoints[index].url.clone();
+
+        // 更新缓存
+        if reply.body.is_some() {
+            self.update_cache(api_key, &endpoint_url);
         }
-
-        None
+        Ok((endpoint_url, reply))
     }
 
     fn update_cache(&mut self, api_key: &str, successful_endpoint: &str) {
-        let new_cache = EndpointCache {
-            api_key_hash: Self::hash_api_key(api_key),
-            successful_endpoint: successful_endpoint.to_string(),
-            last_success_time: SystemTime::now(),
-            success_count: 1,
-        };
-
-        self.cache = Some(new_cache);
-        self.save_cache();
+        let api_key_hash = Self::hash_api_key(api_key);
+        let path = self.cache_file_path.clone();
+        // Sessions detect endpoints at the same time; the count another one
+        // saved for the same key and endpoint is carried on
+        crate::utils::atomic::locked(&path, || {
+            let success_count = Self::load_cache(&path)
+                .filter(|cache| {
+                    cache.api_key_hash == api_key_hash
+                        && cache.successful_endpoint == successful_endpoint
+                })
+                .map_or(1, |cache| cache.success_count.saturating_add(1));
+            self.cache = Some(EndpointCache {
+                api_key_hash,
+                successful_endpoint: successful_endpoint.to_string(),
+                last_success_time: SystemTime::now(),
+                success_count,
+            });
+            self.save_cache();
+        });
     }
 
-    fn update_cache_stats(&mut self, _api_key: &str, _successful_endpoint: &str) {
-        if let Some(ref mut cache) = self.cache {
-            cache.last_success_time = SystemTime::now();
-            cache.success_count += 1;
-            self.save_cache();
+    fn update_cache_stats(&mut self, api_key: &str, successful_endpoint: &str) {
+        if self.cache.is_none() {
+            return;
         }
+        let path = self.cache_file_path.clone();
+        crate::utils::atomic::locked(&path, || {
+            // What was loaded may be stale by now
+            if let Some(cache) = Self::load_cache(&path) {
+                if cache.api_key_hash == Self::hash_api_key(api_key)
+                    && cache.successful_endpoint == successful_endpoint
+                {
+                    self.cache = Some(cache);
+                }
+            }
+            if let Some(cache) = &mut self.cache {
+                cache.last_success_time = SystemTime::now();
+                cache.success_count = cache.success_count.saturating_add(1);
+            }
+            self.save_cache();
+        });
     }
 
-    fn detect_endpoint_static(api_key: &str) -> Option<(String, PackyCodeApiResponse)> {
+    fn detect_endpoint_static(api_key: &str) -> Result<(String, Reply), Error> {
         let mut detector = SmartEndpointDetector::new();
         detector.detect_endpoint(api_key)
     }
 }
 
-#[derive(Default)]
-pub struct QuotaSegment;
+pub struct QuotaSegment {
+    numbers: NumberFormat,
+    show_reset: bool,
+    /// UTC hour the daily budget resets at, when no provider entry matches
+    reset_hour: u32,
+    /// Endpoint host suffixes and the UTC hour their budgets reset at
+    reset_hours: Vec<(String, u32)>,
+    /// Daily budget of the account in USD
+    daily_limit: Option<f64>,
+    warning_percent: u64,
+    critical_percent: u64,
+    /// Show the rate limit with the least left, from the endpoint's headers
+    show_rate_limit: bool,
+}
+
+impl Default for QuotaSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// "requests 12/50 left, resets 30s" for the tightest limit
+fn format_rate_limit(limit: &RateLimit) -> String {
+    let left = match limit.limit {
+        Some(max) => format!("{}/{}", limit.remaining, max),
+        None => limit.remaining.to_string(),
+    };
+    let mut text = i18n::text_with("quota.rate_limit", &[&limit.name, &left]);
+    if let Some(seconds) = limit.reset_in {
+        text.push_str(", ");
+        text.push_str(&i18n::text_with(
+            "quota.resets",
+            &[&format_duration(seconds.saturating_mul(1000))],
+        ));
+    }
+    text
+}
+
+/// "3h12m", or "12m" in the last hour
+fn format_countdown(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours == 0 {
+        i18n::text_with("duration.m", &[&minutes.to_string()])
+    } else {
+        i18n::text_with("duration.h_m", &[&hours.to_string(), &minutes.to_string()])
+    }
+}
 
 impl QuotaSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Quota,
+        name: "Quota",
+        description: "PackyCode daily spend and Opus availability",
+        features: &["quota"],
+        options: &[
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the amount spent, overriding style.numbers",
+            },
+            SegmentOptionInfo {
+                name: "daily_limit",
+                kind: "float",
+                default: "",
+                description: "Daily budget in USD; the amount turns yellow, then red, as spending nears it",
+            },
+            SegmentOptionInfo {
+                name: "warning_percent",
+                kind: "integer",
+                default: "80",
+                description: "Share of daily_limit spent at which the amount turns yellow",
+            },
+            SegmentOptionInfo {
+                name: "critical_percent",
+                kind: "integer",
+                default: "95",
+                description: "Share of daily_limit spent at which the amount turns red and emergency mode starts",
+            },
+            SegmentOptionInfo {
+                name: "show_reset",
+                kind: "bool",
+                default: "false",
+                description: "Show the time left until the daily budget resets, e.g. \"resets 3h12m\"",
+            },
+            SegmentOptionInfo {
+                name: "reset_hour_utc",
+                kind: "integer",
+                default: "0",
+                description: "UTC hour (0-23) the daily budget resets at",
+            },
+            SegmentOptionInfo {
+                name: "reset_hours",
+                kind: "table",
+                default: "{}",
+                description: "Reset hour per provider host, e.g. \"packycode.com\" = 16; before reset_hour_utc",
+            },
+            SegmentOptionInfo {
+                name: "show_rate_limit",
+                kind: "bool",
+                default: "false",
+                description: "Show the rate limit with the least left when the provider sends rate limit headers, e.g. \"requests 12/50 left\"",
+            },
+        ],
+        data_sources: &[
+            "PackyCode API",
+            "env: PACKYCODE_API_KEY / ANTHROPIC_API_KEY / ANTHROPIC_AUTH_TOKEN",
+            "~/.claude/settings.json",
+            "~/.claude/api_key",
+            "~/.claude/ccline/endpoint_cache.json",
+        ],
+        network: true,
+        icons: SegmentIcons {
+            ascii: "quota",
+            emoji: "💳",
+            nerd_font: "\u{f155}",
+            nerd_font_v2: "\u{f155}",
+        },
+    };
+
+    pub const DEFAULT_WARNING_PERCENT: u64 = 80;
+    pub const DEFAULT_CRITICAL_PERCENT: u64 = 95;
+
     pub fn new() -> Self {
-        Self
+        Self {
+            numbers: NumberFormat::default(),
+            show_reset: false,
+            reset_hour: 0,
+            reset_hours: Vec::new(),
+            daily_limit: None,
+            warning_percent: Self::DEFAULT_WARNING_PERCENT,
+            critical_percent: Self::DEFAULT_CRITICAL_PERCENT,
+            show_rate_limit: false,
+        }
     }
 
-    fn load_api_key(&self) -> Option<String> {
-        // 优先级：环境变量 > Claude Code settings.json > api_key 文件
+    pub fn with_rate_limit(mut self, show_rate_limit: bool) -> Self {
+        self.show_rate_limit = show_rate_limit;
+        self
+    }
 
-        // 1. 环境变量
-        if let Ok(key) = env::var("PACKYCODE_API_KEY") {
-            return Some(key);
-        }
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
 
-        if let Ok(key) = env::var("ANTHROPIC_API_KEY") {
-            return Some(key);
-        }
+    pub fn with_daily_limit(
+        mut self,
+        daily_limit: Option<f64>,
+        warning_percent: u64,
+        critical_percent: u64,
+    ) -> Self {
+        self.daily_limit = daily_limit.filter(|limit| *limit > 0.0);
+        self.warning_percent = warning_percent;
+        self.critical_percent = critical_percent;
+        self
+    }
 
-        if let Ok(key) = env::var("ANTHROPIC_AUTH_TOKEN") {
-            return Some(key);
+    /// `warning` or `critical` once the amount spent nears the daily limit
+    fn limit_level(&self, spent: f64) -> Option<&'static str> {
+        let limit = self.daily_limit?;
+        let percent = spent / limit * 100.0;
+        if percent >= self.critical_percent as f64 {
+            Some("critical")
+        } else if percent >= self.warning_percent as f64 {
+            Some("warning")
+        } else {
+            None
         }
+    }
 
-        // 2. Claude Code settings.json
-        if let Some(key) = self.load_from_settings() {
-            return Some(key);
-        }
+    /// Hours past 23 are taken modulo 24
+    pub fn with_reset(
+        mut self,
+        show_reset: bool,
+        reset_hour: u32,
+        reset_hours: Vec<(String, u32)>,
+    ) -> Self {
+        self.show_reset = show_reset;
+        self.reset_hour = reset_hour % 24;
+        self.reset_hours = reset_hours
+            .into_iter()
+            .map(|(host, hour)| (host.to_lowercase(), hour % 24))
+            .collect();
+        self
+    }
 
-        // 3. api_key 文件
-        if let Some(home) = dirs::home_dir() {
-            let api_key_path = home.join(".claude").join("api_key");
-            if let Ok(key) = fs::read_to_string(api_key_path) {
-                return Some(key.trim().to_string());
-            }
-        }
+    /// The UTC reset hour of the provider behind `endpoint_url`: the entry
+    /// with the longest matching host suffix, else `reset_hour_utc`
+    fn reset_hour_for(&self, endpoint_url: &str) -> u32 {
+        let host = endpoint_url
+            .split("://")
+            .nth(1)
+            .unwrap_or(endpoint_url)
+            .split(['/', ':'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        self.reset_hours
+            .iter()
+            .filter(|(suffix, _)| {
+                host == *suffix || host.ends_with(&format!(".{}", suffix.trim_start_matches('.')))
+            })
+            .max_by_key(|(suffix, _)| suffix.len())
+            .map(|(_, hour)| *hour)
+            .unwrap_or(self.reset_hour)
+    }
 
-        None
+    /// Seconds from now until the next `hour`:00 UTC
+    fn seconds_until_reset(hour: u32) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let since_midnight = now % 86_400;
+        let reset = u64::from(hour) * 3600;
+        match (reset + 86_400 - since_midnight) % 86_400 {
+            0 => 86_400,
+            seconds => seconds,
+        }
     }
 
-    fn load_from_settings(&self) -> Option<String> {
-        if let Some(home) = dirs::home_dir() {
-            let settings_path = home.join(".claude").join("settings.json");
-            if let Ok(content) = fs::read_to_string(settings_path) {
-                if let Ok(settings) = serde_json::from_str::<serde_json::Value>(&content) {
-                    if let Some(env) = settings.get("env") {
-                        if let Some(token) = env.get("ANTHROPIC_AUTH_TOKEN") {
-                            if let Some(token_str) = token.as_str() {
-                                return Some(token_str.to_string());
-                            }
-                        }
-                        if let Some(key) = env.get("ANTHROPIC_API_KEY") {
-                            if let Some(key_str) = key.as_str() {
-                                return Some(key_str.to_string());
-                            }
-                        }
-                    }
+    /// What the segment shows for an endpoint's reply
+    fn segment_data(&self, endpoint_url: String, reply: Reply) -> SegmentData {
+        let mut metadata: HashMap<String, String> =
+            reply.rate_limits.metadata().into_iter().collect();
+        let Some(response) = reply.body else {
+            metadata.insert("status".to_string(), "rate_limited".to_string());
+            metadata.insert("endpoint_used".to_string(), endpoint_url);
+            let secondary = match reply.rate_limits.retry_after {
+                Some(seconds) => i18n::text_with(
+                    "quota.retry_in",
+                    &[&format_duration(seconds.saturating_mul(1000))],
+                ),
+                None => String::new(),
+            };
+            return SegmentData {
+                primary: i18n::text("quota.rate_limited"),
+                secondary,
+                metadata,
+            };
+        };
+
+        let daily_spent = self.format_daily_spent(&response.daily_spent_usd);
+        let mut opus_status = self.format_opus_status(response.opus_enabled);
+
+        if self.show_rate_limit {
+            if let Some(limit) = reply.rate_limits.tightest() {
+                opus_status.push(' ');
+                opus_status.push_str(&format_rate_limit(limit));
+            }
+        }
+        if self.show_reset {
+            let reset_hour = self.reset_hour_for(&endpoint_url);
+            let seconds = Self::seconds_until_reset(reset_hour);
+            metadata.insert("reset_hour_utc".to_string(), reset_hour.to_string());
+            metadata.insert("reset_in_seconds".to_string(), seconds.to_string());
+            opus_status.push(' ');
+            opus_status.push_str(&i18n::text_with(
+                "quota.resets",
+                &[&format_countdown(seconds)],
+            ));
+        }
+        if let Some(limit) = self.daily_limit {
+            metadata.insert("daily_limit".to_string(), limit.to_string());
+            if let Ok(spent) = response.daily_spent_usd.parse::<f64>() {
+                metadata.insert(
+                    "remaining".to_string(),
+                    format!("{:.2}", (limit - spent).max(0.0)),
+                );
+                if let Some(level) = self.limit_level(spent) {
+                    metadata.insert("level".to_string(), level.to_string());
                 }
             }
         }
-        None
+        metadata.insert("raw_spent".to_string(), response.daily_spent_usd);
+        metadata.insert(
+            "opus_enabled".to_string(),
+            response.opus_enabled.to_string(),
+        );
+        metadata.insert("endpoint_used".to_string(), endpoint_url);
+
+        SegmentData {
+            primary: daily_spent,
+            secondary: opus_status,
+            metadata,
+        }
+    }
+
+    /// What the segment shows for a user info response: the `body` of a 200
+    /// response, `None` when throttled, and its `headers`. `None` when the
+    /// body is not an account. For the fuzz targets, which stay offline.
+    #[doc(hidden)]
+    pub fn from_response<'a>(
+        &self,
+        endpoint_url: &str,
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+        body: Option<&[u8]>,
+    ) -> Option<SegmentData> {
+        let body = match body {
+            Some(body) => Some(serde_json::from_slice(body).ok()?),
+            None => None,
+        };
+        let reply = Reply {
+            body,
+            rate_limits: RateLimits::from_headers(headers),
+        };
+        Some(self.segment_data(endpoint_url.to_string(), reply))
     }
 
     fn format_daily_spent(&self, spent_str: &str) -> String {
         if let Ok(spent) = spent_str.parse::<f64>() {
-            format!("${:.2}", spent)
+            format!("${}", number::format_cost(spent, &self.numbers))
         } else {
             format!("${}", spent_str)
         }
@@ -308,47 +706,25 @@ impl QuotaSegment {
 }
 
 impl Segment for QuotaSegment {
-    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        self.try_collect(input).ok().flatten()
+    }
+
+    fn try_collect(&self, _input: &InputData) -> Result<Option<SegmentData>, Error> {
         #[cfg(not(feature = "quota"))]
         {
-            return None;
+            Ok(None)
         }
 
         #[cfg(feature = "quota")]
         {
-            let api_key = self.load_api_key()?;
-
-            // 使用静态方法进行端点检测
-            if let Some((endpoint_url, response)) =
-                SmartEndpointDetector::detect_endpoint_static(&api_key)
-            {
-                let daily_spent = self.format_daily_spent(&response.daily_spent_usd);
-                let opus_status = self.format_opus_status(response.opus_enabled);
+            let api_key = crate::utils::credentials::api_key().ok_or(Error::MissingCredentials(
+                "PACKYCODE_API_KEY, ANTHROPIC_API_KEY or ANTHROPIC_AUTH_TOKEN",
+            ))?;
 
-                let mut metadata = HashMap::new();
-                metadata.insert("raw_spent".to_string(), response.daily_spent_usd);
-                metadata.insert(
-                    "opus_enabled".to_string(),
-                    response.opus_enabled.to_string(),
-                );
-                metadata.insert("endpoint_used".to_string(), endpoint_url);
-
-                Some(SegmentData {
-                    primary: daily_spent,
-                    secondary: opus_status,
-                    metadata,
-                })
-            } else {
-                // 所有端点都失败
-                let mut metadata = HashMap::new();
-                metadata.insert("status".to_string(), "offline".to_string());
-
-                Some(SegmentData {
-                    primary: "Offline".to_string(),
-                    secondary: "".to_string(),
-                    metadata,
-                })
-            }
+            // 使用静态方法进行端点检测；所有端点都失败时返回最后的错误
+            let (endpoint_url, reply) = SmartEndpointDetector::detect_endpoint_static(&api_key)?;
+            Ok(Some(self.segment_data(endpoint_url, reply)))
         }
     }
 
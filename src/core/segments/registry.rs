@@ -0,0 +1,582 @@
+use super::{Segment, SegmentData};
+use crate::config::{IconSet, InputData, NumberFormat, SegmentConfig, SegmentId};
+use crate::error::Error;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Describes a single option accepted in a segment's `options` table
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentOptionInfo {
+    pub name: &'static str,
+    /// Value type as written in TOML: "bool", "string", "integer", "float", "table"
+    pub kind: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// The segment's icon in every icon set, used when `style.icons` is set
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentIcons {
+    pub ascii: &'static str,
+    pub emoji: &'static str,
+    /// Nerd Font v3 codepoints
+    pub nerd_font: &'static str,
+    /// Codepoints that also exist in Nerd Font v2, before v3 relocated the
+    /// Material Design icons
+    pub nerd_font_v2: &'static str,
+}
+
+impl SegmentIcons {
+    pub fn get(&self, set: IconSet) -> &'static str {
+        match set {
+            IconSet::Ascii => self.ascii,
+            IconSet::Emoji => self.emoji,
+            IconSet::NerdFont => self.nerd_font,
+            IconSet::NerdFontV2 => self.nerd_font_v2,
+        }
+    }
+}
+
+/// Static description of a segment: what it shows, what it needs, what it reads
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentInfo {
+    pub id: SegmentId,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Cargo features that must be enabled for the segment to produce data
+    pub features: &'static [&'static str],
+    pub options: &'static [SegmentOptionInfo],
+    /// Files, commands and network endpoints touched during collection
+    pub data_sources: &'static [&'static str],
+    /// Whether collection performs network requests
+    pub network: bool,
+    pub icons: SegmentIcons,
+}
+
+impl SegmentInfo {
+    /// Whether every feature this segment needs was compiled in
+    pub fn is_available(&self) -> bool {
+        self.features.iter().all(|feature| {
+            (*feature == "self-update" && cfg!(feature = "self-update"))
+                || (*feature == "quota" && cfg!(feature = "quota"))
+                || (*feature == "tui" && cfg!(feature = "tui"))
+        })
+    }
+}
+
+/// Builds a collector for a segment from its config entry (options, etc.).
+/// Shared so a collector can be built and run outside the registry lock.
+pub type SegmentFactory = Arc<dyn Fn(&SegmentConfig) -> Box<dyn Segment> + Send + Sync>;
+
+struct RegisteredSegment {
+    info: SegmentInfo,
+    factory: SegmentFactory,
+}
+
+/// Maps segment names to their description and collector factory. Built-in
+/// segments and segments registered at runtime ([`SegmentId::Custom`]) are
+/// looked up the same way, by the name they are configured under.
+#[derive(Default)]
+pub struct SegmentRegistry {
+    /// In registration order, which is the default display order
+    entries: Vec<RegisteredSegment>,
+    /// Index into `entries` by segment name
+    by_name: HashMap<&'static str, usize>,
+}
+
+static GLOBAL_REGISTRY: OnceLock<RwLock<SegmentRegistry>> = OnceLock::new();
+
+impl SegmentRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry containing all built-in segments in default display order
+    pub fn with_builtins() -> Self {
+        use super::*;
+
+        let mut registry = Self::new();
+        registry.register(ModelSegment::INFO, |config| {
+            let show_provider = config
+                .options
+                .get("show_provider")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let providers = config
+                .options
+                .get("providers")
+                .and_then(|v| v.as_object())
+                .map(|table| {
+                    table
+                        .iter()
+                        .filter_map(|(pattern, label)| {
+                            Some((pattern.clone(), label.as_str()?.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Box::new(
+                ModelSegment::new()
+                    .with_provider(show_provider)
+                    .with_providers(providers),
+            )
+        });
+        registry.register(DirectorySegment::INFO, |config| {
+            let show_wsl_badge = config
+                .options
+                .get("show_wsl_badge")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let translate_wsl_paths = config
+                .options
+                .get("translate_wsl_paths")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            Box::new(
+                DirectorySegment::new()
+                    .with_wsl_badge(show_wsl_badge)
+                    .with_wsl_path_translation(translate_wsl_paths),
+            )
+        });
+        registry.register(GitSegment::INFO, |config| {
+            let show_sha = config
+                .options
+                .get("show_sha")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let color_by_state = config
+                .options
+                .get("color_by_state")
+                .and_then(|v| v.as_bool())
+                .unwrap_or_else(|| config.options.contains_key("state_colors"));
+            Box::new(
+                GitSegment::new()
+                    .with_sha(show_sha)
+                    .with_color_by_state(color_by_state),
+            )
+        });
+        registry.register(UsageSegment::INFO, |config| {
+            let display = config.options.get("display").and_then(|v| v.as_str());
+            let context_limits = config
+                .options
+                .get("context_limits")
+                .and_then(|v| v.as_object())
+                .map(|table| {
+                    table
+                        .iter()
+                        .filter_map(|(pattern, limit)| {
+                            Some((pattern.clone(), u32::try_from(limit.as_u64()?).ok()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Box::new(
+                UsageSegment::new()
+                    .with_display(display)
+                    .with_context_limits(context_limits)
+                    .with_number_format(number_format(config)),
+            )
+        });
+        registry.register(CostSegment::INFO, |config| {
+            let show_cost = config
+                .options
+                .get("show_cost")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let show_api_duration = config
+                .options
+                .get("show_api_duration")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_wall_duration = config
+                .options
+                .get("show_wall_duration")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_project_total = config
+                .options
+                .get("show_project_total")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let show_projection = config
+                .options
+                .get("show_projection")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let daily_budget = config.options.get("daily_budget").and_then(|v| v.as_f64());
+            let warning_percent = config
+                .options
+                .get("budget_warning_percent")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(CostSegment::DEFAULT_WARNING_PERCENT);
+            let show_sparkline = config
+                .options
+                .get("show_sparkline")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let sparkline_buckets = config
+                .options
+                .get("sparkline_buckets")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(CostSegment::DEFAULT_SPARKLINE_BUCKETS);
+            let sparkline_interval_minutes = config
+                .options
+                .get("sparkline_interval_minutes")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(CostSegment::DEFAULT_SPARKLINE_INTERVAL_MINUTES);
+            Box::new(
+                CostSegment::new()
+                    .with_parts(show_cost, show_api_duration, show_wall_duration)
+                    .with_project_total(show_project_total)
+                    .with_projection(show_projection)
+                    .with_daily_budget(daily_budget)
+                    .with_warning_percent(warning_percent)
+                    .with_sparkline(show_sparkline)
+                    .with_sparkline_shape(sparkline_buckets, sparkline_interval_minutes)
+                    .with_number_format(number_format(config)),
+            )
+        });
+        registry.register(SessionSegment::INFO, |config| {
+            let format = config.options.get("format").and_then(|v| v.as_str());
+            let idle_gap_minutes = config
+                .options
+                .get("idle_gap_minutes")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let show_start = config
+                .options
+                .get("show_start")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Box::new(
+                SessionSegment::new()
+                    .with_number_format(number_format(config))
+                    .with_format(format)
+                    .with_idle_gap(idle_gap_minutes)
+                    .with_show_start(show_start),
+            )
+        });
+        registry.register(OutputStyleSegment::INFO, |_| {
+            Box::new(OutputStyleSegment::new())
+        });
+        registry.register(UpdateSegment::INFO, |config| {
+            let show_version_gap = config
+                .options
+                .get("show_version_gap")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let check_interval_hours = config
+                .options
+                .get("check_interval_hours")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(UpdateSegment::DEFAULT_CHECK_INTERVAL_HOURS);
+            Box::new(
+                UpdateSegment::new()
+                    .with_version_gap(show_version_gap)
+                    .with_check_interval(check_interval_hours),
+            )
+        });
+        registry.register(QuotaSegment::INFO, |config| {
+            let show_reset = config
+                .options
+                .get("show_reset")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let reset_hour = config
+                .options
+                .get("reset_hour_utc")
+                .and_then(|v| v.as_u64())
+                .and_then(|hour| u32::try_from(hour).ok())
+                .unwrap_or(0);
+            let reset_hours = config
+                .options
+                .get("reset_hours")
+                .and_then(|v| v.as_object())
+                .map(|table| {
+                    table
+                        .iter()
+                        .filter_map(|(host, hour)| {
+                            Some((host.clone(), u32::try_from(hour.as_u64()?).ok()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let daily_limit = config.options.get("daily_limit").and_then(|v| v.as_f64());
+            let warning_percent = config
+                .options
+                .get("warning_percent")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(QuotaSegment::DEFAULT_WARNING_PERCENT);
+            let critical_percent = config
+                .options
+                .get("critical_percent")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(QuotaSegment::DEFAULT_CRITICAL_PERCENT);
+            let show_rate_limit = config
+                .options
+                .get("show_rate_limit")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Box::new(
+                QuotaSegment::new()
+                    .with_number_format(number_format(config))
+                    .with_daily_limit(daily_limit, warning_percent, critical_percent)
+                    .with_reset(show_reset, reset_hour, reset_hours)
+                    .with_rate_limit(show_rate_limit),
+            )
+        });
+        registry.register(ContainerSegment::INFO, |config| {
+            let show_host = config
+                .options
+                .get("show_host")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Box::new(ContainerSegment::new().with_host(show_host))
+        });
+        registry.register(PlanSegment::INFO, |config| {
+            let plan = config.options.get("plan").and_then(|v| v.as_str());
+            let show_weekly = config
+                .options
+                .get("show_weekly")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let show_time_left = config
+                .options
+                .get("show_time_left")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let cap = |name| config.options.get(name).and_then(|v| v.as_u64());
+            Box::new(
+                PlanSegment::new()
+                    .with_plan(plan)
+                    .with_weekly(show_weekly)
+                    .with_time_left(show_time_left)
+                    .with_weekly_caps(cap("weekly_cap"), cap("weekly_opus_cap")),
+            )
+        });
+        registry.register(IdentitySegment::INFO, |config| {
+            let style = config.options.get("style").and_then(|v| v.as_str());
+            let labels = config
+                .options
+                .get("labels")
+                .and_then(|v| v.as_object())
+                .map(|table| {
+                    table
+                        .iter()
+                        .filter_map(|(fingerprint, label)| {
+                            Some((fingerprint.clone(), label.as_str()?.to_string()))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Box::new(IdentitySegment::new().with_style(style).with_labels(labels))
+        });
+        registry.register(VimSegment::INFO, |config| {
+            let short = config
+                .options
+                .get("short")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Box::new(VimSegment::new().with_short(short))
+        });
+        registry.register(HooksSegment::INFO, |config| {
+            let only_failures = config
+                .options
+                .get("only_failures")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            Box::new(HooksSegment::new().with_only_failures(only_failures))
+        });
+        registry.register(MemorySegment::INFO, |config| {
+            let warn_kb = config
+                .options
+                .get("warn_kb")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(40);
+            Box::new(
+                MemorySegment::new()
+                    .with_warn_kb(warn_kb)
+                    .with_number_format(number_format(config)),
+            )
+        });
+        registry.register(TranscriptSegment::INFO, |config| {
+            let warn_mb = config
+                .options
+                .get("warn_mb")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(20);
+            Box::new(
+                TranscriptSegment::new()
+                    .with_warn_mb(warn_mb)
+                    .with_number_format(number_format(config)),
+            )
+        });
+        registry.register(ComputedSegment::INFO, |config| {
+            Box::new(ComputedSegment::from_config(config))
+        });
+        registry.register(ModesSegment::INFO, |config| {
+            let show_auto_compact = config
+                .options
+                .get("show_auto_compact")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            Box::new(ModesSegment::new().with_auto_compact(show_auto_compact))
+        });
+        registry.register(AttentionSegment::INFO, |config| {
+            let after_seconds = config
+                .options
+                .get("after_seconds")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(3);
+            let show_interrupted = config
+                .options
+                .get("show_interrupted")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            Box::new(
+                AttentionSegment::new()
+                    .with_after(std::time::Duration::from_secs(after_seconds))
+                    .with_interrupted(show_interrupted),
+            )
+        });
+        registry
+    }
+
+    /// Register a segment, replacing any existing registration with the same id
+    pub fn register<F>(&mut self, info: SegmentInfo, factory: F)
+    where
+        F: Fn(&SegmentConfig) -> Box<dyn Segment> + Send + Sync + 'static,
+    {
+        let name = info.id.as_str();
+        let entry = RegisteredSegment {
+            info,
+            factory: Arc::new(factory),
+        };
+
+        match self.by_name.get(name) {
+            Some(&index) => self.entries[index] = entry,
+            None => {
+                self.by_name.insert(name, self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    fn entry(&self, name: &str) -> Option<&RegisteredSegment> {
+        self.by_name.get(name).map(|&index| &self.entries[index])
+    }
+
+    /// Descriptions of all registered segments in registration order
+    pub fn infos(&self) -> impl Iterator<Item = &SegmentInfo> {
+        self.entries.iter().map(|entry| &entry.info)
+    }
+
+    /// Look up the description of a segment by id
+    pub fn info(&self, id: SegmentId) -> Option<&SegmentInfo> {
+        self.info_by_key(id.as_str())
+    }
+
+    /// Look up the description of a segment by its config key
+    pub fn info_by_key(&self, key: &str) -> Option<&SegmentInfo> {
+        self.entry(key).map(|entry| &entry.info)
+    }
+
+    /// The collector factory of a segment, to build and run it without
+    /// holding on to the registry
+    pub fn factory(&self, id: SegmentId) -> Option<SegmentFactory> {
+        self.entry(id.as_str()).map(|entry| entry.factory.clone())
+    }
+
+    /// Instantiate the collector for a configured segment
+    pub fn create(&self, config: &SegmentConfig) -> Option<Box<dyn Segment>> {
+        self.entry(config.id.as_str())
+            .map(|entry| (entry.factory)(config))
+    }
+
+    /// Instantiate and run the collector for a configured segment
+    pub fn collect(&self, config: &SegmentConfig, input: &InputData) -> Option<SegmentData> {
+        self.create(config)?.collect(input)
+    }
+
+    /// Like `collect`, but says why collecting failed
+    pub fn try_collect(
+        &self,
+        config: &SegmentConfig,
+        input: &InputData,
+    ) -> Result<Option<SegmentData>, Error> {
+        self.create(config)
+            .ok_or_else(|| Error::UnknownSegment(config.id.as_str().to_string()))?
+            .try_collect(input)
+    }
+
+    /// The process-wide registry, initialized with the built-in segments
+    pub fn global() -> &'static RwLock<SegmentRegistry> {
+        GLOBAL_REGISTRY.get_or_init(|| RwLock::new(Self::with_builtins()))
+    }
+}
+
+/// Instantiate and run the collector of a configured segment from the
+/// global registry. The lock is only held to find the factory, so slow
+/// collectors, such as network segments, do not block registration.
+pub fn collect_registered(
+    config: &SegmentConfig,
+    input: &InputData,
+) -> Result<Option<SegmentData>, Error> {
+    let factory = with_registry(|registry| registry.factory(config.id))
+        .ok_or_else(|| Error::UnknownSegment(config.id.as_str().to_string()))?;
+    factory(config).try_collect(input)
+}
+
+/// Run `f` with shared access to the global registry
+pub fn with_registry<R>(f: impl FnOnce(&SegmentRegistry) -> R) -> R {
+    let registry = SegmentRegistry::global()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&registry)
+}
+
+/// Register a segment in the global registry at runtime. A segment with a
+/// name of its own uses `SegmentId::named("weather")` as its id and is then
+/// configured as `id = "weather"`; a built-in id replaces that segment.
+pub fn register_segment<F>(info: SegmentInfo, factory: F)
+where
+    F: Fn(&SegmentConfig) -> Box<dyn Segment> + Send + Sync + 'static,
+{
+    SegmentRegistry::global()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .register(info, factory);
+}
+
+/// The registered segment a command-line name refers to, e.g. "output-style"
+pub fn lookup_segment(name: &str) -> Result<SegmentInfo, Box<dyn std::error::Error>> {
+    let key = name.trim().to_ascii_lowercase().replace('-', "_");
+    with_registry(|registry| {
+        if let Some(info) = registry.info_by_key(&key) {
+            return Ok(info.clone());
+        }
+        let known = registry
+            .infos()
+            .map(|info| info.id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(format!("Unknown segment '{}' (known: {})", name, known).into())
+    })
+}
+
+/// Display name of a segment, falling back to its config key when unregistered
+pub fn segment_name(id: SegmentId) -> &'static str {
+    with_registry(|registry| registry.info(id).map(|info| info.name)).unwrap_or(id.as_str())
+}
+
+/// The `numbers` option of a segment, with `style.numbers` already folded in
+/// by [`crate::config::Config::resolved_segments`]
+pub fn number_format(config: &SegmentConfig) -> NumberFormat {
+    config
+        .options
+        .get("numbers")
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
@@ -1,69 +1,308 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, NumberFormat, SegmentId};
+use crate::core::session_cache::{self, TranscriptActivity};
+use crate::i18n;
+use crate::utils::{number, transcript};
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum DurationFormat {
+    /// "45s", "23m45s", "1h23m"
+    #[default]
+    Auto,
+    /// "45s", "23m", "1h23m"
+    Compact,
+    /// "01:23:45"
+    Clock,
+    /// "83m"
+    Minutes,
+}
+
+impl DurationFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "compact" => Some(Self::Compact),
+            "clock" => Some(Self::Clock),
+            "minutes" => Some(Self::Minutes),
+            _ => None,
+        }
+    }
+}
+
+/// The one field of a transcript entry the segment reads
+#[derive(Deserialize)]
+struct Stamped {
+    timestamp: Option<String>,
+}
 
 #[derive(Default)]
-pub struct SessionSegment;
+pub struct SessionSegment {
+    numbers: NumberFormat,
+    format: DurationFormat,
+    /// Pauses longer than this many minutes are not counted; 0 counts
+    /// wall-clock time
+    idle_gap_minutes: u64,
+    show_start: bool,
+}
 
 impl SessionSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Session,
+        name: "Session",
+        description: "Session duration and lines added/removed",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the line counts, overriding style.numbers",
+            },
+            SegmentOptionInfo {
+                name: "format",
+                kind: "string",
+                default: "auto",
+                description: "Duration format: auto (23m45s), compact (1h23m), clock (01:23:45), minutes (83m)",
+            },
+            SegmentOptionInfo {
+                name: "idle_gap_minutes",
+                kind: "integer",
+                default: "0",
+                description: "Count only active time, leaving out pauses longer than this; 0 counts wall-clock time",
+            },
+            SegmentOptionInfo {
+                name: "show_start",
+                kind: "bool",
+                default: "false",
+                description: "Show when the session started instead of how long it has run",
+            },
+        ],
+        data_sources: &["stdin: cost", "transcript: entry timestamps (active time, start time)"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "time",
+            emoji: "⏱️",
+            nerd_font: "\u{f19bb}",
+            nerd_font_v2: "\u{f017}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
     }
 
-    fn format_duration(ms: u64) -> String {
-        if ms < 1000 {
-            format!("{}ms", ms)
-        } else if ms < 60_000 {
-            let seconds = ms / 1000;
-            format!("{}s", seconds)
-        } else if ms < 3_600_000 {
-            let minutes = ms / 60_000;
-            let seconds = (ms % 60_000) / 1000;
-            if seconds == 0 {
-                format!("{}m", minutes)
-            } else {
-                format!("{}m{}s", minutes, seconds)
+    pub fn with_format(mut self, format: Option<&str>) -> Self {
+        if let Some(format) = format.and_then(DurationFormat::parse) {
+            self.format = format;
+        }
+        self
+    }
+
+    pub fn with_idle_gap(mut self, idle_gap_minutes: u64) -> Self {
+        self.idle_gap_minutes = idle_gap_minutes;
+        self
+    }
+
+    pub fn with_show_start(mut self, show_start: bool) -> Self {
+        self.show_start = show_start;
+        self
+    }
+
+    fn format_elapsed(&self, ms: u64) -> String {
+        let seconds = ms / 1000;
+        let (hours, minutes) = (seconds / 3600, (seconds % 3600) / 60);
+        match self.format {
+            DurationFormat::Auto => format_duration(ms),
+            DurationFormat::Compact if hours > 0 => {
+                i18n::text_with("duration.h_m", &[&hours.to_string(), &minutes.to_string()])
+            }
+            DurationFormat::Compact if minutes > 0 => {
+                i18n::text_with("duration.m", &[&minutes.to_string()])
+            }
+            DurationFormat::Compact => i18n::text_with("duration.s", &[&seconds.to_string()]),
+            DurationFormat::Clock => {
+                format!("{:02}:{:02}:{:02}", hours, minutes, seconds % 60)
             }
+            DurationFormat::Minutes => {
+                i18n::text_with("duration.m", &[&(seconds / 60).to_string()])
+            }
+        }
+    }
+}
+
+/// "450ms", "45s", "23m45s", "1h23m"
+pub fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        i18n::text_with("duration.ms", &[&ms.to_string()])
+    } else if ms < 60_000 {
+        let seconds = ms / 1000;
+        i18n::text_with("duration.s", &[&seconds.to_string()])
+    } else if ms < 3_600_000 {
+        let minutes = ms / 60_000;
+        let seconds = (ms % 60_000) / 1000;
+        if seconds == 0 {
+            i18n::text_with("duration.m", &[&minutes.to_string()])
+        } else {
+            i18n::text_with(
+                "duration.m_s",
+                &[&minutes.to_string(), &seconds.to_string()],
+            )
+        }
+    } else {
+        let hours = ms / 3_600_000;
+        let minutes = (ms % 3_600_000) / 60_000;
+        if minutes == 0 {
+            i18n::text_with("duration.h", &[&hours.to_string()])
         } else {
-            let hours = ms / 3_600_000;
-            let minutes = (ms % 3_600_000) / 60_000;
-            if minutes == 0 {
-                format!("{}h", hours)
-            } else {
-                format!("{}h{}m", hours, minutes)
+            i18n::text_with("duration.h_m", &[&hours.to_string(), &minutes.to_string()])
+        }
+    }
+}
+
+/// Timestamps read from the transcript, continuing from the cached read.
+/// Gaps between entries count towards the active time unless longer than
+/// `idle_gap_seconds`.
+fn activity(transcript_path: &str, idle_gap_seconds: u64) -> Option<TranscriptActivity> {
+    let cached = session_cache::with_session(transcript_path, |session| session.activity.clone())
+        .filter(|activity| activity.idle_gap_seconds == idle_gap_seconds);
+    let mut activity = cached.unwrap_or(TranscriptActivity {
+        offset: 0,
+        idle_gap_seconds,
+        first: None,
+        last: None,
+        active_seconds: 0.0,
+    });
+
+    let (lines, offset) = match transcript::appended(Path::new(transcript_path), activity.offset) {
+        Some(appended) => appended,
+        // Rewritten rather than appended to
+        None => {
+            activity = TranscriptActivity {
+                offset: 0,
+                first: None,
+                last: None,
+                active_seconds: 0.0,
+                ..activity
+            };
+            transcript::appended(Path::new(transcript_path), 0)?
+        }
+    };
+    if offset == activity.offset {
+        return Some(activity);
+    }
+
+    for line in lines.lines().filter(|line| !line.trim().is_empty()) {
+        let Some(time) = serde_json::from_str::<Stamped>(line)
+            .ok()
+            .and_then(|entry| entry.timestamp)
+            .and_then(|timestamp| transcript::timestamp_seconds(&timestamp))
+        else {
+            continue;
+        };
+        if let Some(last) = activity.last {
+            let gap = time - last;
+            if gap > 0.0 && (idle_gap_seconds == 0 || gap <= idle_gap_seconds as f64) {
+                activity.active_seconds += gap;
             }
         }
+        activity.first = activity.first.or(Some(time));
+        activity.last = Some(time.max(activity.last.unwrap_or(time)));
     }
+    activity.offset = offset;
+
+    session_cache::update_session(transcript_path, |session| {
+        session.activity = Some(activity.clone());
+    });
+    Some(activity)
+}
+
+fn now_seconds() -> f64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// "14:05" in local time (UTC without chrono)
+fn format_clock_time(unix_seconds: f64) -> String {
+    #[cfg(feature = "chrono")]
+    {
+        use chrono::TimeZone;
+        if let Some(time) = chrono::Local.timestamp_opt(unix_seconds as i64, 0).single() {
+            return time.format("%H:%M").to_string();
+        }
+    }
+    let of_day = (unix_seconds as u64) % 86_400;
+    format!("{:02}:{:02}", of_day / 3600, (of_day % 3600) / 60)
 }
 
 impl Segment for SessionSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
         let cost_data = input.cost.as_ref()?;
 
-        // Primary display: total duration
-        let primary = if let Some(duration) = cost_data.total_duration_ms {
-            Self::format_duration(duration)
+        let duration = cost_data.total_duration_ms?;
+        let activity = (self.idle_gap_minutes > 0 || self.show_start)
+            .then(|| activity(&input.transcript_path, self.idle_gap_minutes * 60))
+            .flatten();
+
+        // Time since the last entry counts while it is shorter than the gap
+        let active_ms = activity
+            .as_ref()
+            .filter(|_| self.idle_gap_minutes > 0)
+            .map(|activity| {
+                let since_last = activity
+                    .last
+                    .map(|last| (now_seconds() - last).max(0.0))
+                    .filter(|since_last| *since_last <= (self.idle_gap_minutes * 60) as f64)
+                    .unwrap_or(0.0);
+                ((activity.active_seconds + since_last) * 1000.0) as u64
+            });
+
+        // Primary display: start time or duration
+        let started_at = activity
+            .as_ref()
+            .and_then(|activity| activity.first)
+            .unwrap_or_else(|| now_seconds() - duration as f64 / 1000.0);
+        let primary = if self.show_start {
+            i18n::text_with("session.since", &[&format_clock_time(started_at)])
         } else {
-            return None;
+            self.format_elapsed(active_ms.unwrap_or(duration))
         };
 
         // Secondary display: line changes if available
         let secondary = match (cost_data.total_lines_added, cost_data.total_lines_removed) {
             (Some(added), Some(removed)) if added > 0 || removed > 0 => {
-                format!("+{} -{}", added, removed)
+                format!(
+                    "+{} -{}",
+                    number::format_count(added.into(), &self.numbers),
+                    number::format_count(removed.into(), &self.numbers)
+                )
             }
             (Some(added), None) if added > 0 => {
-                format!("+{}", added)
+                format!("+{}", number::format_count(added.into(), &self.numbers))
             }
             (None, Some(removed)) if removed > 0 => {
-                format!("-{}", removed)
+                format!("-{}", number::format_count(removed.into(), &self.numbers))
             }
             _ => String::new(),
         };
 
         let mut metadata = HashMap::new();
-        if let Some(duration) = cost_data.total_duration_ms {
-            metadata.insert("duration_ms".to_string(), duration.to_string());
+        metadata.insert("duration_ms".to_string(), duration.to_string());
+        if let Some(active_ms) = active_ms {
+            metadata.insert("active_ms".to_string(), active_ms.to_string());
+        }
+        if self.show_start {
+            metadata.insert("started_at".to_string(), (started_at as u64).to_string());
         }
         if let Some(api_duration) = cost_data.total_api_duration_ms {
             metadata.insert("api_duration_ms".to_string(), api_duration.to_string());
@@ -0,0 +1,143 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{InputData, NumberFormat, SegmentId};
+use crate::core::session_cache::{self, TranscriptMessages};
+use crate::i18n;
+use crate::utils::{number, transcript};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The one field of a transcript entry the segment reads
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+pub struct TranscriptSegment {
+    warn_mb: u64,
+    numbers: NumberFormat,
+}
+
+impl Default for TranscriptSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TranscriptSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Transcript,
+        name: "Transcript",
+        description: "Size of the session transcript and the messages in it",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "warn_mb",
+                kind: "integer",
+                default: "20",
+                description: "Transcript size in MB above which the size turns yellow; twice that turns it red",
+            },
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the size and count, overriding style.numbers",
+            },
+        ],
+        data_sources: &["input: transcript_path"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "log",
+            emoji: "📜",
+            nerd_font: "\u{f15c}",
+            nerd_font_v2: "\u{f15c}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self {
+            warn_mb: 20,
+            numbers: NumberFormat::default(),
+        }
+    }
+
+    pub fn with_warn_mb(mut self, warn_mb: u64) -> Self {
+        self.warn_mb = warn_mb;
+        self
+    }
+
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
+}
+
+/// User and assistant messages in the transcript, continuing from the
+/// cached read
+fn messages(transcript_path: &str) -> Option<u64> {
+    let mut messages =
+        session_cache::with_session(transcript_path, |session| session.messages.clone())
+            .unwrap_or_default();
+
+    let (lines, offset) = match transcript::appended(Path::new(transcript_path), messages.offset) {
+        Some(appended) => appended,
+        // Rewritten rather than appended to
+        None => {
+            messages = TranscriptMessages::default();
+            transcript::appended(Path::new(transcript_path), 0)?
+        }
+    };
+    if offset == messages.offset {
+        return Some(messages.count);
+    }
+
+    messages.count += lines
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+        .filter(|entry| matches!(entry.kind.as_deref(), Some("user" | "assistant")))
+        .count() as u64;
+    messages.offset = offset;
+
+    let count = messages.count;
+    session_cache::update_session(transcript_path, |session| {
+        session.messages = Some(messages);
+    });
+    Some(count)
+}
+
+impl Segment for TranscriptSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let bytes = fs::metadata(&input.transcript_path).ok()?.len();
+        let count = messages(&input.transcript_path)?;
+        let mb = bytes as f64 / (1024.0 * 1024.0);
+        let size_format = NumberFormat {
+            precision: Some(self.numbers.precision.unwrap_or(1)),
+            ..self.numbers.clone()
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("bytes".to_string(), bytes.to_string());
+        metadata.insert("messages".to_string(), count.to_string());
+        // Large transcripts make resuming slow and the UI laggy
+        if self.warn_mb > 0 && mb > (self.warn_mb * 2) as f64 {
+            metadata.insert("level".to_string(), "critical".to_string());
+        } else if self.warn_mb > 0 && mb > self.warn_mb as f64 {
+            metadata.insert("level".to_string(), "warning".to_string());
+        }
+
+        Some(SegmentData {
+            primary: i18n::text_with(
+                "transcript.messages",
+                &[&number::format_count(count, &self.numbers)],
+            ),
+            secondary: i18n::text_with("transcript.mb", &[&number::format_cost(mb, &size_format)]),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Transcript
+    }
+}
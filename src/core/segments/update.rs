@@ -1,26 +1,131 @@
-use super::{Segment, SegmentData};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
 use crate::config::{InputData, SegmentId};
-use crate::updater::UpdateState;
+use crate::error::Error;
+#[cfg(feature = "self-update")]
+use crate::updater::{UpdateState, UpdateStatus};
 
-#[derive(Default)]
-pub struct UpdateSegment;
+pub struct UpdateSegment {
+    #[cfg_attr(not(feature = "self-update"), allow(dead_code))]
+    show_version_gap: bool,
+    #[cfg_attr(not(feature = "self-update"), allow(dead_code))]
+    check_interval_hours: u64,
+}
+
+impl Default for UpdateSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl UpdateSegment {
+    /// Hours between automatic update checks unless `check_interval_hours` is set
+    pub const DEFAULT_CHECK_INTERVAL_HOURS: u64 = 24;
+
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Update,
+        name: "Update",
+        description: "Notice when a newer ccline release is available",
+        features: &["self-update"],
+        options: &[
+            SegmentOptionInfo {
+                name: "show_version_gap",
+                kind: "bool",
+                default: "false",
+                description: "Show the installed and latest version, e.g. v1.2.0→1.4.1",
+            },
+            SegmentOptionInfo {
+                name: "check_interval_hours",
+                kind: "integer",
+                default: "24",
+                description: "Hours between background update checks; 0 disables them",
+            },
+        ],
+        data_sources: &["GitHub Releases API", "~/.claude/ccline/.update_state.json"],
+        network: true,
+        icons: SegmentIcons {
+            ascii: "upd",
+            emoji: "🔄",
+            nerd_font: "\u{f01da}",
+            nerd_font_v2: "\u{f021}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self
+        Self {
+            show_version_gap: false,
+            check_interval_hours: Self::DEFAULT_CHECK_INTERVAL_HOURS,
+        }
+    }
+
+    pub fn with_version_gap(mut self, show_version_gap: bool) -> Self {
+        self.show_version_gap = show_version_gap;
+        self
+    }
+
+    pub fn with_check_interval(mut self, check_interval_hours: u64) -> Self {
+        self.check_interval_hours = check_interval_hours;
+        self
     }
 }
 
 impl Segment for UpdateSegment {
-    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
-        // Load update state and check for update status
-        let update_state = UpdateState::load();
-
-        update_state.status_text().map(|status_text| SegmentData {
-            primary: status_text,
-            secondary: String::new(),
-            metadata: std::collections::HashMap::new(),
-        })
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        self.try_collect(input).ok().flatten()
+    }
+
+    fn try_collect(&self, _input: &InputData) -> Result<Option<SegmentData>, Error> {
+        #[cfg(not(feature = "self-update"))]
+        {
+            Ok(None)
+        }
+
+        #[cfg(feature = "self-update")]
+        {
+            // Show the last known state; a due check runs in the background
+            let mut update_state = UpdateState::load();
+            update_state.check_in_background(self.check_interval_hours);
+            if let UpdateStatus::Failed { error } = &update_state.status {
+                return Err(Error::Network(error.clone()));
+            }
+
+            let Some(status_text) = update_state.status_text() else {
+                return Ok(None);
+            };
+            let primary = if self.show_version_gap {
+                update_state.version_gap().unwrap_or(status_text)
+            } else {
+                status_text
+            };
+
+            // One release behind is implied by the notice itself
+            let versions_behind = update_state.versions_behind.unwrap_or(0);
+            let secondary = if versions_behind > 1 && update_state.version_gap().is_some() {
+                crate::i18n::text_with("update.behind", &[&versions_behind.to_string()])
+            } else {
+                String::new()
+            };
+
+            let mut metadata = std::collections::HashMap::new();
+            metadata.insert(
+                "current_version".to_string(),
+                env!("CARGO_PKG_VERSION").to_string(),
+            );
+            if let Some(latest_version) = &update_state.latest_version {
+                metadata.insert("latest_version".to_string(), latest_version.clone());
+            }
+            if let Some(versions_behind) = update_state.versions_behind {
+                metadata.insert("versions_behind".to_string(), versions_behind.to_string());
+            }
+            if let Some(changelog) = &update_state.changelog_headline {
+                metadata.insert("changelog".to_string(), changelog.clone());
+            }
+
+            Ok(Some(SegmentData {
+                primary,
+                secondary,
+                metadata,
+            }))
+        }
     }
 
     fn id(&self) -> SegmentId {
@@ -1,8 +1,14 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, ModelConfig, SegmentId, TranscriptEntry};
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{
+    InputData, Message, ModelConfig, NumberFormat, SegmentId, TranscriptEntry, Usage,
+};
+use crate::core::session_cache::{self, FileStamp, TranscriptUsage};
+use crate::error::Error;
+use crate::i18n;
+use crate::utils::number;
 use std::collections::HashMap;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 /// Get context limit for a specific model
@@ -15,12 +21,129 @@ fn get_context_limit(model_name: &str) -> u32 {
     }
 }
 
+/// The 1M context window, assumed once a request has passed 200k tokens
+const EXTENDED_CONTEXT_LIMIT: u32 = 1_000_000;
+
+/// What the usage segment shows
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum UsageDisplay {
+    /// "63.7% · 127.4k tokens"
+    #[default]
+    Tokens,
+    /// "64% of 200k"
+    OfLimit,
+    /// "64%"
+    Percent,
+}
+
+impl UsageDisplay {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().replace('-', "_").as_str() {
+            "tokens" => Some(UsageDisplay::Tokens),
+            "of_limit" | "limit" => Some(UsageDisplay::OfLimit),
+            "percent" | "percentage" => Some(UsageDisplay::Percent),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Default)]
-pub struct UsageSegment;
+pub struct UsageSegment {
+    numbers: NumberFormat,
+    display: UsageDisplay,
+    /// Model id patterns and their context limits, longest pattern first
+    context_limits: Vec<(String, u32)>,
+}
 
 impl UsageSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Usage,
+        name: "Usage",
+        description: "Context window usage of the latest assistant message",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "display",
+                kind: "string",
+                default: "tokens",
+                description: "tokens (\"63.7% · 127.4k tokens\"), of_limit (\"64% of 200k\") or percent",
+            },
+            SegmentOptionInfo {
+                name: "context_limits",
+                kind: "table",
+                default: "{}",
+                description:
+                    "Context limits by model id pattern, e.g. \"claude-opus-4\" = 1000000; before models.toml",
+            },
+            SegmentOptionInfo {
+                name: "numbers",
+                kind: "table",
+                default: "{}",
+                description: "Number format of the token count, overriding style.numbers",
+            },
+        ],
+        data_sources: &["transcript file", "~/.claude/ccline/models.toml"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "ctx",
+            emoji: "⚡️",
+            nerd_font: "\u{f49b}",
+            nerd_font_v2: "\u{f0e7}",
+        },
+    };
+
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_number_format(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// Unknown display names keep the default
+    pub fn with_display(mut self, display: Option<&str>) -> Self {
+        if let Some(display) = display.and_then(UsageDisplay::parse) {
+            self.display = display;
+        }
+        self
+    }
+
+    pub fn with_context_limits(mut self, context_limits: Vec<(String, u32)>) -> Self {
+        self.context_limits = context_limits
+            .into_iter()
+            .filter(|(pattern, limit)| !pattern.is_empty() && *limit > 0)
+            .map(|(pattern, limit)| (pattern.to_lowercase(), limit))
+            .collect();
+        self.context_limits
+            .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        self
+    }
+
+    /// The context window of the session's model: a `context_limits` option
+    /// first, then models.toml and the built-in table. A request past 200k
+    /// tokens means the 1M window is in use whatever the tables say.
+    fn context_limit(&self, input: &InputData) -> u32 {
+        let id = input.model.id.to_lowercase();
+        let configured = self
+            .context_limits
+            .iter()
+            .find(|(pattern, _)| id.contains(pattern.as_str()))
+            .map(|(_, limit)| *limit);
+
+        let limit = configured.unwrap_or_else(|| {
+            // Use both legacy and new context limit logic for compatibility,
+            // preferring the higher limit
+            let context_limit_legacy = get_context_limit(&input.model.display_name);
+            let context_limit_new = Self::get_context_limit_for_model(&input.model.id);
+            context_limit_legacy.max(context_limit_new)
+        });
+
+        if input.exceeds_200k_tokens == Some(true) && limit <= 200_000 {
+            EXTENDED_CONTEXT_LIMIT
+        } else {
+            limit
+        }
     }
 
     /// Get context limit for the specified model
@@ -32,20 +155,18 @@ impl UsageSegment {
 
 impl Segment for UsageSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        self.try_collect(input).ok().flatten()
+    }
+
+    fn try_collect(&self, input: &InputData) -> Result<Option<SegmentData>, Error> {
         let context_used_token = if input.transcript_path == "mock_preview" {
             // Hardcoded mock data for preview
             150000
         } else {
-            parse_transcript_usage(&input.transcript_path)
+            parse_transcript_usage(&input.transcript_path)?
         };
 
-        // Use both legacy and new context limit logic for compatibility
-        let context_limit_legacy = get_context_limit(&input.model.display_name);
-        let context_limit_new = Self::get_context_limit_for_model(&input.model.id);
-        
-        // Prefer the higher limit for better user experience
-        let context_limit = context_limit_legacy.max(context_limit_new);
-        
+        let context_limit = self.context_limit(input);
         let context_used_rate = (context_used_token as f64 / context_limit as f64) * 100.0;
 
         let percentage_display = if context_used_rate.fract() == 0.0 {
@@ -54,28 +175,38 @@ impl Segment for UsageSegment {
             format!("{:.1}%", context_used_rate)
         };
 
-        let tokens_display = if context_used_token >= 1000 {
-            let k_value = context_used_token as f64 / 1000.0;
-            if k_value.fract() == 0.0 {
-                format!("{}k", k_value as u32)
-            } else {
-                format!("{:.1}k", k_value)
-            }
-        } else {
-            context_used_token.to_string()
-        };
+        let tokens_display = number::format_tokens(context_used_token.into(), &self.numbers);
 
         let mut metadata = HashMap::new();
         metadata.insert("tokens".to_string(), context_used_token.to_string());
         metadata.insert("percentage".to_string(), context_used_rate.to_string());
         metadata.insert("limit".to_string(), context_limit.to_string());
         metadata.insert("model".to_string(), input.model.id.clone());
+        if let Some(exceeds) = input.exceeds_200k_tokens {
+            metadata.insert("exceeds_200k_tokens".to_string(), exceeds.to_string());
+        }
 
-        Some(SegmentData {
-            primary: format!("{} · {} tokens", percentage_display, tokens_display),
+        let primary = match self.display {
+            UsageDisplay::Tokens => format!(
+                "{} · {}",
+                percentage_display,
+                i18n::text_with("usage.tokens", &[&tokens_display])
+            ),
+            UsageDisplay::OfLimit => i18n::text_with(
+                "usage.of_limit",
+                &[
+                    &format!("{:.0}", context_used_rate),
+                    &number::format_tokens(context_limit.into(), &self.numbers),
+                ],
+            ),
+            UsageDisplay::Percent => format!("{:.0}%", context_used_rate),
+        };
+
+        Ok(Some(SegmentData {
+            primary,
             secondary: String::new(),
             metadata,
-        })
+        }))
     }
 
     fn id(&self) -> SegmentId {
@@ -83,22 +214,135 @@ impl Segment for UsageSegment {
     }
 }
 
-fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> u32 {
+/// Context tokens of an assistant message, with cached input counted once
+/// for models whose input tokens already include it
+fn message_tokens(message: &Message, raw_usage: &Usage) -> u32 {
+    let usage = raw_usage.clone().normalize();
+    let includes_cache = message
+        .model
+        .as_deref()
+        .and_then(|model| ModelConfig::load().entry(model))
+        .is_some_and(|entry| entry.input_includes_cache);
+    if includes_cache {
+        usage.without_cached_input().display_tokens()
+    } else {
+        usage.display_tokens()
+    }
+}
+
+/// Tokens of the last assistant message, 0 before the first one; fails
+/// when the transcript is there but cannot be read
+fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Result<u32, Error> {
     let path = transcript_path.as_ref();
 
+    // Continue from where the previous render stopped reading
+    if let Some(usage) = parse_appended_lines(path) {
+        return Ok(usage);
+    }
+
     // Try to parse from current transcript file
+    let stamp = FileStamp::of(path);
     if let Some(usage) = try_parse_transcript_file(path) {
-        return usage;
+        remember_transcript_usage(path, stamp, usage);
+        return Ok(usage);
     }
 
     // If file doesn't exist, try to find usage from project history
     if !path.exists() {
         if let Some(usage) = try_find_usage_from_project_history(path) {
-            return usage;
+            return Ok(usage);
         }
+    } else {
+        // A transcript that is there but cannot be read is an error, not 0
+        fs::File::open(path)?;
     }
 
-    0
+    Ok(0)
+}
+
+/// Usage from the lines appended since the cached read, falling back to the
+/// cached value when none of them is an assistant message. `None` when
+/// there is nothing cached or the transcript needs a full parse.
+fn parse_appended_lines(path: &Path) -> Option<u32> {
+    let key = path.to_str()?;
+    let cached = session_cache::with_session(key, |session| session.transcript.clone())?;
+    let stamp = FileStamp::of(path)?;
+    if stamp == cached.stamp {
+        return Some(cached.tokens);
+    }
+    if stamp.len < cached.offset {
+        // Rewritten rather than appended to
+        return None;
+    }
+
+    let mut file = fs::File::open(path).ok()?;
+    file.seek(SeekFrom::Start(cached.offset)).ok()?;
+    let mut appended = Vec::new();
+    file.read_to_end(&mut appended).ok()?;
+
+    // A line still being written is read on a later render
+    let complete = appended
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let text = std::str::from_utf8(&appended[..complete]).ok()?;
+
+    let mut tokens = cached.tokens;
+    let mut last_type = None;
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let Ok(entry) = serde_json::from_str::<TranscriptEntry>(line) else {
+            continue;
+        };
+        if entry.r#type.as_deref() == Some("assistant") {
+            if let Some(message) = &entry.message {
+                if let Some(raw_usage) = &message.usage {
+                    tokens = message_tokens(message, raw_usage);
+                }
+            }
+        }
+        last_type = entry.r#type;
+    }
+
+    // A trailing summary points at usage in another session file
+    if last_type.as_deref() == Some("summary") {
+        return None;
+    }
+
+    session_cache::update_session(key, |session| {
+        session.transcript = Some(TranscriptUsage {
+            offset: cached.offset + complete as u64,
+            stamp,
+            tokens,
+        });
+    });
+    Some(tokens)
+}
+
+/// Cache a full parse when it covered complete lines only
+fn remember_transcript_usage(path: &Path, stamp: Option<FileStamp>, tokens: u32) {
+    let (Some(key), Some(stamp)) = (path.to_str(), stamp) else {
+        return;
+    };
+    let ends_with_newline = fs::File::open(path)
+        .and_then(|mut file| {
+            file.seek(SeekFrom::End(-1))?;
+            let mut last = [0u8; 1];
+            file.read_exact(&mut last)?;
+            Ok(last[0] == b'\n')
+        })
+        .unwrap_or(false);
+    // The file must not have grown while it was parsed
+    if !ends_with_newline || FileStamp::of(path) != Some(stamp) {
+        return;
+    }
+
+    session_cache::update_session(key, |session| {
+        session.transcript = Some(TranscriptUsage {
+            offset: stamp.len,
+            stamp,
+            tokens,
+        });
+    });
 }
 
 fn try_parse_transcript_file(path: &Path) -> Option<u32> {
@@ -136,8 +380,7 @@ fn try_parse_transcript_file(path: &Path) -> Option<u32> {
             if entry.r#type.as_deref() == Some("assistant") {
                 if let Some(message) = &entry.message {
                     if let Some(raw_usage) = &message.usage {
-                        let normalized = raw_usage.clone().normalize();
-                        return Some(normalized.display_tokens());
+                        return Some(message_tokens(message, raw_usage));
                     }
                 }
             }
@@ -190,8 +433,7 @@ fn search_uuid_in_file(path: &Path, target_uuid: &str) -> Option<u32> {
                         // Direct assistant message with usage
                         if let Some(message) = &entry.message {
                             if let Some(raw_usage) = &message.usage {
-                                let normalized = raw_usage.clone().normalize();
-                                return Some(normalized.display_tokens());
+                                return Some(message_tokens(message, raw_usage));
                             }
                         }
                     } else if entry.r#type.as_deref() == Some("user") {
@@ -221,8 +463,7 @@ fn find_assistant_message_by_uuid(lines: &[String], target_uuid: &str) -> Option
                 if uuid == target_uuid && entry.r#type.as_deref() == Some("assistant") {
                     if let Some(message) = &entry.message {
                         if let Some(raw_usage) = &message.usage {
-                            let normalized = raw_usage.clone().normalize();
-                            return Some(normalized.display_tokens());
+                            return Some(message_tokens(message, raw_usage));
                         }
                     }
                 }
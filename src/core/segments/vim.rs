@@ -0,0 +1,149 @@
+use super::{Segment, SegmentData, SegmentIcons, SegmentInfo, SegmentOptionInfo};
+use crate::config::{AnsiColor, InputData, SegmentId};
+use std::collections::HashMap;
+use std::fs;
+
+/// Powerline-style mode colors used when the segment sets no `state_colors`:
+/// state, background, text
+pub const DEFAULT_STATE_COLORS: &[(&str, AnsiColor, AnsiColor)] = &[
+    (
+        "normal",
+        AnsiColor::Color256 { c256: 31 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "insert",
+        AnsiColor::Color256 { c256: 70 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+    (
+        "visual",
+        AnsiColor::Color256 { c256: 208 },
+        AnsiColor::Color256 { c256: 16 },
+    ),
+    (
+        "replace",
+        AnsiColor::Color256 { c256: 160 },
+        AnsiColor::Color256 { c256: 231 },
+    ),
+];
+
+pub struct VimSegment {
+    short: bool,
+}
+
+impl Default for VimSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VimSegment {
+    pub const INFO: SegmentInfo = SegmentInfo {
+        id: SegmentId::Vim,
+        name: "Vim Mode",
+        description: "Input mode (NORMAL/INSERT) while vim editing is enabled",
+        features: &[],
+        options: &[
+            SegmentOptionInfo {
+                name: "short",
+                kind: "bool",
+                default: "false",
+                description: "Show the first letter of the mode (N, I) instead of the word",
+            },
+            SegmentOptionInfo {
+                name: "state_colors",
+                kind: "table",
+                default: "{}",
+                description: "Colors per mode, e.g. normal = { background = { c256 = 31 } }",
+            },
+        ],
+        data_sources: &["input: vim.mode", "~/.claude.json: editorMode"],
+        network: false,
+        icons: SegmentIcons {
+            ascii: "vi",
+            emoji: "⌨️",
+            nerd_font: "\u{e62b}",
+            nerd_font_v2: "\u{e62b}",
+        },
+    };
+
+    pub fn new() -> Self {
+        Self { short: false }
+    }
+
+    pub fn with_short(mut self, short: bool) -> Self {
+        self.short = short;
+        self
+    }
+
+    /// Whether vim editing is switched on in Claude Code's settings. Older
+    /// Claude Code versions only record it there, not in the statusline input.
+    fn enabled_in_settings() -> bool {
+        let Some(path) = crate::utils::paths::home_dir().map(|home| home.join(".claude.json"))
+        else {
+            return false;
+        };
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|state| {
+                state
+                    .get("editorMode")
+                    .and_then(|mode| mode.as_str())
+                    .map(|mode| mode == "vim")
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl Segment for VimSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mode = match input.vim.as_ref() {
+            Some(vim) => vim.mode.trim().to_uppercase(),
+            // The mode itself is unknown; only say that vim bindings are on
+            None if Self::enabled_in_settings() => "VIM".to_string(),
+            None => return None,
+        };
+        if mode.is_empty() {
+            return None;
+        }
+
+        let state = match mode.as_str() {
+            "NORMAL" => "normal",
+            "INSERT" => "insert",
+            "VISUAL" | "VISUAL LINE" | "V-LINE" => "visual",
+            "REPLACE" => "replace",
+            _ => "other",
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("mode".to_string(), mode.clone());
+        metadata.insert("state".to_string(), state.to_string());
+        metadata.insert(
+            "source".to_string(),
+            if input.vim.is_some() {
+                "input"
+            } else {
+                "settings"
+            }
+            .to_string(),
+        );
+
+        let primary = if self.short {
+            mode.chars().take(1).collect()
+        } else {
+            mode
+        };
+
+        Some(SegmentData {
+            primary,
+            secondary: String::new(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Vim
+    }
+}
@@ -0,0 +1,315 @@
+//! Derived state reused between renders: the parsed config, how far each
+//! session's transcript has been read, which directories are git
+//! repositories, and the last value of segments with a `refresh_interval_ms`.
+//!
+//! State lives in memory for the life of the process (the daemon keeps it
+//! across requests) and is written to `cache/` in the cache directory in bincode
+//! after a one-shot render, so the next invocation can pick it up. Every
+//! entry carries what it was derived from (file length, modification time)
+//! and is recomputed when that no longer matches.
+
+use crate::config::{Config, ConfigLoader};
+use crate::core::segments::SegmentData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// Sessions kept in memory before the least recently used is dropped
+const MAX_SESSIONS: usize = 32;
+
+/// How long "not a git repository" is trusted while the directory is unchanged
+const NEGATIVE_GIT_TTL: Duration = Duration::from_secs(300);
+
+/// Identifies the file an entry was derived from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+        })
+    }
+}
+
+/// Context usage found in a transcript, and how far the transcript was read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptUsage {
+    /// Bytes up to the end of the last complete line that was read
+    pub offset: u64,
+    pub stamp: FileStamp,
+    pub tokens: u32,
+}
+
+/// Activity found in a transcript's timestamps, for the session segment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptActivity {
+    /// Bytes up to the end of the last complete line that was read
+    pub offset: u64,
+    /// Gaps longer than this many seconds were left out of `active_seconds`
+    pub idle_gap_seconds: u64,
+    /// Unix seconds of the first and last timestamped entry
+    pub first: Option<f64>,
+    pub last: Option<f64>,
+    pub active_seconds: f64,
+}
+
+/// Messages counted in a transcript, for the transcript segment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptMessages {
+    /// Bytes up to the end of the last complete line that was read
+    pub offset: u64,
+    /// User and assistant entries
+    pub count: u64,
+}
+
+/// Result of looking for a git repository from a working directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitDiscovery {
+    /// Absolute git directory, `None` outside a repository
+    pub git_dir: Option<PathBuf>,
+    /// Working directory modification time when discovered; creating `.git`
+    /// changes it
+    pub dir_modified: Option<SystemTime>,
+    pub discovered_at: SystemTime,
+}
+
+impl GitDiscovery {
+    pub fn is_valid_for(&self, working_dir: &str) -> bool {
+        match &self.git_dir {
+            Some(git_dir) => git_dir.exists(),
+            None => {
+                let dir_modified = fs::metadata(working_dir)
+                    .and_then(|meta| meta.modified())
+                    .ok();
+                dir_modified == self.dir_modified
+                    && self
+                        .discovered_at
+                        .elapsed()
+                        .is_ok_and(|age| age < NEGATIVE_GIT_TTL)
+            }
+        }
+    }
+}
+
+/// A segment's last collected value, for segments with `refresh_interval_ms`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSegment {
+    /// `None` when the segment had nothing to show
+    pub data: Option<SegmentData>,
+    pub collected_at: SystemTime,
+}
+
+/// Session cost as of a moment, recorded when it changes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CostSample {
+    pub at: SystemTime,
+    pub total_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    pub transcript: Option<TranscriptUsage>,
+    /// Keyed by working directory
+    pub git: HashMap<String, GitDiscovery>,
+    /// Keyed by segment id and working directory
+    pub segments: HashMap<String, CachedSegment>,
+    /// When the session was last queued for team usage reporting
+    pub reported_at: Option<SystemTime>,
+    /// Oldest first, for the cost segment's sparkline
+    pub cost_samples: Vec<CostSample>,
+    /// Permission flags Claude Code was started with, for the modes segment
+    pub launch_args: Option<Vec<String>>,
+    pub activity: Option<TranscriptActivity>,
+    pub messages: Option<TranscriptMessages>,
+    /// State of each `[alerts]` condition at the last render, so events
+    /// fire when it starts rather than on every render
+    pub alerts: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionFile {
+    /// ccline version that wrote the file; other versions start fresh
+    version: String,
+    state: SessionState,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFile {
+    version: String,
+    stamp: FileStamp,
+    /// Config as JSON: bincode cannot hold the untagged color and free-form
+    /// option values, and JSON still parses far faster than TOML
+    config_json: String,
+}
+
+struct Session {
+    state: SessionState,
+    last_used: SystemTime,
+    dirty: bool,
+}
+
+#[derive(Default)]
+struct Cache {
+    sessions: HashMap<String, Session>,
+    config: Option<(FileStamp, Config)>,
+    config_dirty: bool,
+}
+
+static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+
+fn cache() -> std::sync::MutexGuard<'static, Cache> {
+    CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+fn cache_dir() -> PathBuf {
+    crate::utils::paths::cache_dir().join("cache")
+}
+
+/// Sessions are keyed by transcript path, which embeds the session id
+fn session_file(transcript_path: &str) -> PathBuf {
+    let hash = crate::utils::hash::fnv1a(transcript_path.as_bytes());
+    cache_dir().join(format!("session-{:016x}.bin", hash))
+}
+
+fn read_session(transcript_path: &str) -> SessionState {
+    fs::read(session_file(transcript_path))
+        .ok()
+        .and_then(|bytes| bincode::deserialize::<SessionFile>(&bytes).ok())
+        .filter(|file| file.version == env!("CARGO_PKG_VERSION"))
+        .map(|file| file.state)
+        .unwrap_or_default()
+}
+
+/// Run `f` on the cached state of the session writing `transcript_path`,
+/// loading it from disk on first use
+pub fn with_session<R>(transcript_path: &str, f: impl FnOnce(&mut SessionState) -> R) -> R {
+    let mut cache = cache();
+    if !cache.sessions.contains_key(transcript_path) {
+        if cache.sessions.len() >= MAX_SESSIONS {
+            if let Some(oldest) = cache
+                .sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_used)
+                .map(|(key, _)| key.clone())
+            {
+                cache.sessions.remove(&oldest);
+            }
+        }
+        let state = read_session(transcript_path);
+        cache.sessions.insert(
+            transcript_path.to_string(),
+            Session {
+                state,
+                last_used: SystemTime::now(),
+                dirty: false,
+            },
+        );
+    }
+
+    let session = cache
+        .sessions
+        .get_mut(transcript_path)
+        .expect("session was just inserted");
+    session.last_used = SystemTime::now();
+    f(&mut session.state)
+}
+
+/// Like [`with_session`], marking the session for [`flush`]
+pub fn update_session<R>(transcript_path: &str, f: impl FnOnce(&mut SessionState) -> R) -> R {
+    let result = with_session(transcript_path, f);
+    if let Some(session) = cache().sessions.get_mut(transcript_path) {
+        session.dirty = true;
+    }
+    result
+}
+
+/// The config, parsed again only when config.toml changed since the cached parse
+pub fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
+    let path = Config::get_config_path();
+    let Some(stamp) = FileStamp::of(&path) else {
+        return Config::load();
+    };
+
+    let mut cache = cache();
+    if cache
+        .config
+        .as_ref()
+        .is_none_or(|(cached, _)| *cached != stamp)
+    {
+        let from_disk = fs::read(cache_dir().join("config.bin"))
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<ConfigFile>(&bytes).ok())
+            .filter(|file| file.version == env!("CARGO_PKG_VERSION") && file.stamp == stamp)
+            .and_then(|file| serde_json::from_str::<Config>(&file.config_json).ok());
+
+        let config = match from_disk {
+            Some(config) => config,
+            None => {
+                // Adaptive themes are resolved per render below, so the
+                // cached config is the file as written
+                ConfigLoader::ensure_themes_exist();
+                let config = ConfigLoader::load_from_path(&path)?;
+                cache.config_dirty = true;
+                config
+            }
+        };
+        cache.config = Some((stamp, config));
+    }
+
+    let (_, config) = cache.config.as_ref().expect("config was just cached");
+    let mut config = config.clone();
+    // The stamp covers config.toml only, so included files are read anew
+    if !config.include.is_empty() {
+        config = ConfigLoader::load_from_path(&path)?;
+    }
+    config.resolve_adaptive_theme();
+    Ok(config)
+}
+
+/// Write sessions and config that changed in this process to disk
+pub fn flush() {
+    let mut cache = cache();
+    let dir = cache_dir();
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    if cache.config_dirty {
+        if let Some((stamp, config)) = &cache.config {
+            if let Ok(config_json) = serde_json::to_string(config) {
+                let file = ConfigFile {
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    stamp: *stamp,
+                    config_json,
+                };
+                if let Ok(bytes) = bincode::serialize(&file) {
+                    let _ = crate::utils::atomic::write(&dir.join("config.bin"), &bytes);
+                }
+            }
+        }
+        cache.config_dirty = false;
+    }
+
+    for (transcript_path, session) in cache.sessions.iter_mut().filter(|(_, s)| s.dirty) {
+        let file = SessionFile {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            state: session.state.clone(),
+        };
+        if let Ok(bytes) = bincode::serialize(&file) {
+            let _ = crate::utils::atomic::write(&session_file(transcript_path), &bytes);
+        }
+        session.dirty = false;
+    }
+}
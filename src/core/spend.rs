@@ -0,0 +1,73 @@
+//! Spend statistics kept across sessions: the last known cost of every
+//! session, grouped by project, in `spend.json` in the state directory.
+//!
+//! Claude Code reports a session's cost cumulatively, so each session keeps
+//! only its latest cost and a project's lifetime spend is the sum over its
+//! sessions. The file is only rewritten when a session's cost changed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SpendFile {
+    /// Keyed by project directory
+    #[serde(default)]
+    projects: HashMap<String, ProjectSpend>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProjectSpend {
+    /// Latest cost in USD, keyed by session id
+    #[serde(default)]
+    pub sessions: HashMap<String, f64>,
+}
+
+impl ProjectSpend {
+    /// USD over all sessions recorded for the project
+    pub fn total(&self) -> f64 {
+        self.sessions.values().sum()
+    }
+}
+
+fn spend_path() -> PathBuf {
+    crate::utils::paths::state_dir().join("spend.json")
+}
+
+fn load() -> SpendFile {
+    fs::read_to_string(spend_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Latest recorded cost of `session`, in whichever project it ran
+pub fn session_cost(session: &str) -> Option<f64> {
+    load()
+        .projects
+        .values()
+        .find_map(|spend| spend.sessions.get(session).copied())
+}
+
+/// Record `cost` as the latest cost of `session` in `project` and return the
+/// project's statistics including it
+pub fn record(project: &str, session: &str, cost: f64) -> ProjectSpend {
+    let path = spend_path();
+    // Every session writes this file, so reading it and writing it back must
+    // not interleave with another session doing the same
+    crate::utils::atomic::locked(&path, || {
+        let mut file = load();
+        let spend = file.projects.entry(project.to_string()).or_default();
+        let changed = spend.sessions.get(session) != Some(&cost);
+        spend.sessions.insert(session.to_string(), cost);
+        let spend = spend.clone();
+
+        if changed {
+            if let Ok(json) = serde_json::to_vec(&file) {
+                let _ = crate::utils::atomic::write(&path, &json);
+            }
+        }
+        spend
+    })
+}
@@ -1,68 +1,334 @@
-use crate::config::{AnsiColor, Config, SegmentConfig, StyleMode};
-use crate::core::segments::SegmentData;
-
-/// Strip ANSI escape sequences and return visible text length
-fn visible_width(text: &str) -> usize {
-    let mut visible = String::new();
-    let mut in_escape = false;
-    let mut chars = text.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Start of ANSI escape sequence
-            in_escape = true;
-            // Skip the [ character
-            if chars.peek() == Some(&'[') {
-                chars.next();
-            }
-        } else if in_escape {
-            // Skip until we find the end of the escape sequence (letter)
-            if ch.is_alphabetic() {
-                in_escape = false;
+use crate::config::{
+    Alignment, AnsiColor, Config, GradientConfig, IconSet, PaddingConfig, SegmentConfig, SegmentId,
+    StyleConfig, StyleMode, TextStyle, ThemeBackground, Truncation,
+};
+use crate::core::segments::{with_registry, SegmentData};
+use crate::core::session_cache::{self, CachedSegment};
+use crate::core::{emergency, rules};
+use crate::error::Error;
+use crate::utils::ascii::to_ascii;
+use crate::utils::color::interpolate;
+use crate::utils::redact::redact;
+use crate::utils::width::{
+    display_width, grapheme_width, truncate_middle_to_width, truncate_start_to_width,
+    truncate_to_width,
+};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::{Duration, SystemTime};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Secondary text color for segments whose `level` metadata is `warning`
+const WARNING_COLOR: AnsiColor = AnsiColor::Color16 { c16: 11 };
+/// Secondary text color for segments whose `level` metadata is `critical`
+const CRITICAL_COLOR: AnsiColor = AnsiColor::Color16 { c16: 9 };
+/// Text color of placeholders
+const PLACEHOLDER_COLOR: AnsiColor = AnsiColor::Color256 { c256: 244 };
+
+/// One entry of a segment's `state_colors` option
+#[derive(Deserialize)]
+struct StateColors {
+    #[serde(default)]
+    icon: Option<AnsiColor>,
+    #[serde(default)]
+    text: Option<AnsiColor>,
+    #[serde(default)]
+    background: Option<AnsiColor>,
+}
+
+/// Built-in colors per state for segments that report a `state`: state,
+/// background, text
+fn default_state_colors(id: SegmentId) -> &'static [(&'static str, AnsiColor, AnsiColor)] {
+    match id {
+        SegmentId::Git => crate::core::segments::git::DEFAULT_STATE_COLORS,
+        SegmentId::Vim => crate::core::segments::vim::DEFAULT_STATE_COLORS,
+        SegmentId::Attention => crate::core::segments::attention::DEFAULT_STATE_COLORS,
+        SegmentId::Modes => crate::core::segments::modes::DEFAULT_STATE_COLORS,
+        SegmentId::Hooks => crate::core::segments::hooks::DEFAULT_STATE_COLORS,
+        _ => &[],
+    }
+}
+
+/// The segment's colors for the state in its `state` metadata: the entry for
+/// that state in its `state_colors` option, else the built-in one. Colors an
+/// entry leaves out keep the segment's own.
+fn with_state_colors(mut config: SegmentConfig, data: &SegmentData) -> SegmentConfig {
+    let Some(state) = data.metadata.get("state") else {
+        return config;
+    };
+
+    let configured = config
+        .options
+        .get("state_colors")
+        .and_then(|states| states.get(state))
+        .and_then(|colors| serde_json::from_value::<StateColors>(colors.clone()).ok());
+    let colors = configured.or_else(|| {
+        default_state_colors(config.id)
+            .iter()
+            .find(|(name, _, _)| name == state)
+            .map(|(_, background, text)| StateColors {
+                icon: Some(text.clone()),
+                text: Some(text.clone()),
+                background: Some(background.clone()),
+            })
+    });
+
+    if let Some(colors) = colors {
+        if colors.background.is_some() {
+            config.colors.background = colors.background;
+            config.colors.background_gradient = None;
+        }
+        config.colors.icon = colors.icon.or(config.colors.icon);
+        config.colors.text = colors.text.or(config.colors.text);
+    }
+    config
+}
+
+/// What a segment shows when it has no data: its `placeholder` option, so
+/// the layout stays the same when, say, there is no git repository
+pub fn placeholder(config: &SegmentConfig) -> Option<SegmentData> {
+    let text = config.options.get("placeholder")?.as_str()?;
+    let mut metadata = HashMap::new();
+    metadata.insert("placeholder".to_string(), "true".to_string());
+    Some(SegmentData {
+        primary: text.to_string(),
+        secondary: String::new(),
+        metadata,
+    })
+}
+
+/// Placeholders render dimmed in gray
+fn with_placeholder_style(mut config: SegmentConfig, data: &SegmentData) -> SegmentConfig {
+    if data.metadata.contains_key("placeholder") {
+        config.colors.icon = Some(PLACEHOLDER_COLOR);
+        config.colors.text = Some(PLACEHOLDER_COLOR);
+        config.styles.icon.dim = true;
+        config.styles.primary = TextStyle {
+            dim: true,
+            ..TextStyle::default()
+        };
+        config.styles.text_bold = false;
+    }
+    config
+}
+
+/// `text` cut to `max_width` columns the way `strategy` says
+fn truncate(text: &str, max_width: usize, strategy: Truncation) -> String {
+    match strategy {
+        Truncation::End => truncate_to_width(text, max_width, "…"),
+        Truncation::Middle => truncate_middle_to_width(text, max_width, "…"),
+        Truncation::Start => truncate_start_to_width(text, max_width, "…"),
+    }
+}
+
+/// Primary and secondary text fitted to the segment's `width`, and the
+/// columns of fill to add before and after the content
+fn fit_width<'a>(
+    config: &SegmentConfig,
+    icon: &str,
+    primary: &'a str,
+    secondary: &'a str,
+) -> (Cow<'a, str>, Cow<'a, str>, PaddingConfig) {
+    let mut primary = Cow::Borrowed(primary);
+    let mut secondary = Cow::Borrowed(secondary);
+    let Some(width) = config.width else {
+        return (primary, secondary, PaddingConfig::default());
+    };
+    let content_width = |primary: &str, secondary: &str| {
+        let mut columns = display_width(icon) + 1 + display_width(primary);
+        if !secondary.is_empty() {
+            columns += 1 + display_width(secondary);
+        }
+        columns
+    };
+
+    // Cut the secondary text first, then the primary
+    if let Some(max) = width.fixed.or(width.max) {
+        if content_width(&primary, &secondary) > max {
+            let text_width = max.saturating_sub(display_width(icon) + 1);
+            let primary_width = display_width(&primary);
+            if !secondary.is_empty() && primary_width + 2 < text_width {
+                secondary = Cow::Owned(truncate(
+                    &secondary,
+                    text_width - primary_width - 1,
+                    width.truncate,
+                ));
+            } else {
+                secondary = Cow::Borrowed("");
+                primary = Cow::Owned(truncate(&primary, text_width, width.truncate));
             }
-        } else {
-            // Regular character
-            visible.push(ch);
         }
     }
 
-    visible.chars().count()
+    let Some(min) = width.fixed.or(width.min) else {
+        return (primary, secondary, PaddingConfig::default());
+    };
+    let fill = min.saturating_sub(content_width(&primary, &secondary));
+    let fill = match width.align {
+        Alignment::Left => PaddingConfig {
+            left: 0,
+            right: fill,
+        },
+        Alignment::Right => PaddingConfig {
+            left: fill,
+            right: 0,
+        },
+        Alignment::Center => PaddingConfig {
+            left: fill / 2,
+            right: fill - fill / 2,
+        },
+    };
+    (primary, secondary, fill)
 }
 
 pub struct StatusLineGenerator {
     config: Config,
+    truecolor: bool,
+}
+
+/// Whether the terminal advertises 24-bit color support
+fn supports_truecolor() -> bool {
+    std::env::var("COLORTERM")
+        .map(|value| value == "truecolor" || value == "24bit")
+        .unwrap_or(false)
+}
+
+/// Whether the style renders any Nerd Font glyphs (icons or Powerline arrows)
+fn uses_nerd_font(style: &StyleConfig) -> bool {
+    style.mode != StyleMode::Plain
+        || matches!(style.icons, Some(IconSet::NerdFont | IconSet::NerdFontV2))
+        || style.separator.chars().any(is_private_use)
+}
+
+fn is_private_use(ch: char) -> bool {
+    ('\u{e000}'..='\u{f8ff}').contains(&ch) || ch >= '\u{f0000}'
+}
+
+/// Swap Nerd Font glyphs for plain icons and separators
+fn without_nerd_font(mut config: Config) -> Config {
+    config.style.mode = StyleMode::Plain;
+    if matches!(
+        config.style.icons,
+        Some(IconSet::NerdFont | IconSet::NerdFontV2)
+    ) {
+        config.style.icons = Some(IconSet::Emoji);
+    }
+    if config.style.separator.chars().any(is_private_use) {
+        config.style.separator = " | ".to_string();
+    }
+    config
+}
+
+/// ASCII icon tags and separators for `style.ascii`; Powerline arrows
+/// become ">"
+fn ascii_only(mut config: Config) -> Config {
+    config.style.mode = StyleMode::Plain;
+    config.style.icons = Some(IconSet::Ascii);
+    if config.style.separator == "\u{e0b0}" {
+        config.style.separator = " > ".to_string();
+    } else if !config.style.separator.is_ascii() {
+        config.style.separator = " | ".to_string();
+    }
+    config
 }
 
 impl StatusLineGenerator {
+    /// Create a generator. Styles that need Nerd Font glyphs fall back to plain
+    /// icons when the font is known to lack them (`style.nerd_font`, or detection).
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let config = if config.style.ascii {
+            ascii_only(config)
+        } else if uses_nerd_font(&config.style)
+            && !config
+                .style
+                .nerd_font
+                .or_else(crate::utils::nerd_font::detect)
+                .unwrap_or(true)
+        {
+            without_nerd_font(config)
+        } else {
+            config
+        };
+
+        Self {
+            config,
+            truecolor: supports_truecolor(),
+        }
     }
 
     pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
-        let mut output = Vec::new();
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
-            .collect();
+        let emergency = emergency::is_active(&self.config.emergency, &segments);
+        let segments = self.prepare(segments);
+        if segments.is_empty() {
+            return String::new();
+        }
 
-        for (config, data) in enabled_segments.iter() {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                output.push(rendered);
+        // The line is built in one buffer sized for the text of every
+        // segment and the escape sequences around it
+        let capacity = segments
+            .iter()
+            .map(|(config, data)| {
+                config.icon.nerd_font.len().max(config.icon.plain.len())
+                    + data.primary.len()
+                    + data.secondary.len()
+                    + ESCAPES_PER_SEGMENT
+            })
+            .sum();
+        let mut line = String::with_capacity(capacity);
+        // Gradient segments are built here before being colored column by column
+        let mut scratch = String::new();
+
+        let (leading, trailing) = self.edge_separators(
+            segments.first().map(|(config, _)| config),
+            segments.last().map(|(config, _)| config),
+        );
+        line.push_str(&leading);
+        for (i, (config, data)) in segments.iter().enumerate() {
+            if i > 0 {
+                self.push_separator(&mut line, Some(&segments[i - 1].0), Some(config));
             }
+            self.push_segment(&mut line, &mut scratch, config, data);
         }
+        line.push_str(&trailing);
 
-        if output.is_empty() {
-            return String::new();
+        // Reset colors after Powerline arrows
+        if self.config.style.separator == "\u{e0b0}" {
+            line.push_str("\x1b[0m");
         }
 
-        // Handle Powerline arrow separators with color transition
-        if self.config.style.separator == "\u{e0b0}" {
-            self.join_with_powerline_arrows(&output, &enabled_segments)
-        } else {
-            // For all other separators, use white color and simple join
-            self.join_with_white_separators(&output)
+        if emergency {
+            if let Some(warning) = self.config.emergency.warning.as_deref() {
+                let style = TextStyle {
+                    bold: true,
+                    blink: self.config.emergency.blink,
+                    ..TextStyle::default()
+                };
+                let warning = if self.config.style.ascii {
+                    Cow::Owned(to_ascii(warning))
+                } else {
+                    Cow::Borrowed(warning)
+                };
+                line.push(' ');
+                push_styled(&mut line, &warning, Some(&CRITICAL_COLOR), style);
+            }
         }
+        line
+    }
+
+    /// Enabled segments the rules leave visible, in render order, with their
+    /// state colors and the emergency styling applied
+    fn prepare(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> Vec<(SegmentConfig, SegmentData)> {
+        let segments = rules::visible(&self.config, segments)
+            .into_iter()
+            .map(|(config, data)| (with_state_colors(config, &data), data))
+            .map(|(config, data)| (with_placeholder_style(config, &data), data))
+            .collect();
+        emergency::apply(&self.config.emergency, segments)
     }
 
     /// Generate statusline for TUI preview with proper width calculation
@@ -89,6 +355,7 @@ impl StatusLineGenerator {
     }
 
     /// Generate TUI-optimized text with intelligent wrapping by segment for preview
+    #[cfg(feature = "tui")]
     pub fn generate_for_tui_preview(
         &self,
         segments: Vec<(SegmentConfig, SegmentData)>,
@@ -97,10 +364,7 @@ impl StatusLineGenerator {
         use ansi_to_tui::IntoText;
         use ratatui::text::{Line, Span, Text};
 
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
-            .collect();
+        let enabled_segments = self.prepare(segments);
 
         if enabled_segments.is_empty() {
             return Text::from(vec![Line::default()]);
@@ -122,23 +386,19 @@ impl StatusLineGenerator {
             return Text::from(vec![Line::default()]);
         }
 
+        // Edge separators stay attached to the first and last segment when wrapping
+        let (leading, trailing) =
+            self.edge_separators(segment_configs.first(), segment_configs.last());
+        rendered_segments[0].insert_str(0, &leading);
+        if let Some(last) = rendered_segments.last_mut() {
+            last.push_str(&trailing);
+        }
+
         // Pre-calculate separators between segments
         let mut separators = Vec::new();
         for i in 0..rendered_segments.len().saturating_sub(1) {
-            let separator = if self.config.style.separator == "\u{e0b0}" {
-                // Powerline arrows with color transition
-                let prev_bg = segment_configs
-                    .get(i)
-                    .and_then(|config| config.colors.background.as_ref());
-                let curr_bg = segment_configs
-                    .get(i + 1)
-                    .and_then(|config| config.colors.background.as_ref());
-                self.create_powerline_arrow(prev_bg, curr_bg)
-            } else {
-                // Regular separators with white color
-                format!("\x1b[37m{}\x1b[0m", self.config.style.separator)
-            };
-            separators.push(separator);
+            separators
+                .push(self.separator_between(segment_configs.get(i), segment_configs.get(i + 1)));
         }
 
         // Intelligent line wrapping by segment
@@ -149,7 +409,7 @@ impl StatusLineGenerator {
 
         for i in 0..rendered_segments.len() {
             let segment = &rendered_segments[i];
-            let segment_width = visible_width(segment);
+            let segment_width = display_width(segment);
 
             // Check if adding this segment would exceed max_width
             if current_width > 0 && current_width + segment_width > max_w {
@@ -166,12 +426,12 @@ impl StatusLineGenerator {
             // Handle separator if not the last segment
             if i < separators.len() {
                 let separator = &separators[i];
-                let separator_width = visible_width(separator);
+                let separator_width = display_width(separator);
 
                 // Check if next segment exists
                 if i + 1 < rendered_segments.len() {
                     let next_segment = &rendered_segments[i + 1];
-                    let next_width = visible_width(next_segment);
+                    let next_width = display_width(next_segment);
 
                     // Check if separator AND next segment both fit
                     if current_width + separator_width + next_width <= max_w {
@@ -214,239 +474,409 @@ impl StatusLineGenerator {
         Text::from(tui_lines)
     }
 
+    /// One segment on its own, for the TUI preview that wraps by segment
+    #[cfg(feature = "tui")]
     fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
-        let icon = self.get_icon(config);
-
-        // Apply background color to the entire segment if set
-        if let Some(bg_color) = &config.colors.background {
-            let bg_code = self.apply_background_color(bg_color);
-
-            // Build the entire segment content first
-            let icon_colored = if let Some(icon_color) = &config.colors.icon {
-                self.apply_color(&icon, Some(icon_color))
-                    .replace("\x1b[0m", "")
-            } else {
-                icon.clone()
-            };
-
-            let text_styled = self
-                .apply_style(
-                    &data.primary,
-                    config.colors.text.as_ref(),
-                    config.styles.text_bold,
-                )
-                .replace("\x1b[0m", "");
-
-            let mut segment_content = format!(" {} {} ", icon_colored, text_styled);
-
-            if !data.secondary.is_empty() {
-                let secondary_styled = self
-                    .apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold,
-                    )
-                    .replace("\x1b[0m", "");
-                segment_content.push_str(&format!("{} ", secondary_styled));
-            }
+        let mut segment = String::new();
+        self.push_segment(&mut segment, &mut String::new(), config, data);
+        segment
+    }
 
-            // Apply background to the entire content and reset at the end
-            format!("{}{}\x1b[49m", bg_code, segment_content)
+    /// Append a segment to `out`. `scratch` holds the content of gradient
+    /// segments, kept across segments so its allocation is reused.
+    fn push_segment(
+        &self,
+        out: &mut String,
+        scratch: &mut String,
+        config: &SegmentConfig,
+        data: &SegmentData,
+    ) {
+        let icon = self.get_icon(config);
+        let gradient = self.active_gradient(config);
+        let (primary, secondary): (Cow<str>, Cow<str>) = if self.config.style.ascii {
+            (
+                Cow::Owned(to_ascii(&data.primary)),
+                Cow::Owned(to_ascii(&data.secondary)),
+            )
         } else {
-            // No background color, use original logic
-            let icon_colored = self.apply_color(&icon, config.colors.icon.as_ref());
-            let text_styled = self.apply_style(
-                &data.primary,
+            (Cow::Borrowed(&data.primary), Cow::Borrowed(&data.secondary))
+        };
+        let (primary, secondary, fill) = fit_width(config, &icon, &primary, &secondary);
+
+        let Some(bg_color) = self.solid_background(config).or(gradient.map(|g| &g.from)) else {
+            let padding = self.padding(config, false).widened(fill);
+            push_spaces(out, padding.left);
+            push_styled(out, &icon, config.colors.icon.as_ref(), config.styles.icon);
+            out.push(' ');
+            push_styled(
+                out,
+                &primary,
                 config.colors.text.as_ref(),
-                config.styles.text_bold,
+                config.styles.primary_style(),
             );
-
-            let mut segment = format!("{} {}", icon_colored, text_styled);
-
-            if !data.secondary.is_empty() {
-                segment.push_str(&format!(
-                    " {}",
-                    self.apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold
-                    )
-                ));
+            if !secondary.is_empty() {
+                out.push(' ');
+                push_styled(
+                    out,
+                    &secondary,
+                    self.secondary_color(config, data),
+                    config.styles.secondary_style(),
+                );
             }
+            push_spaces(out, padding.right);
+            return;
+        };
 
-            segment
+        // Attributes are switched off after each part rather than reset, so
+        // the background stays on for the whole segment
+        let content = if gradient.is_some() {
+            scratch.clear();
+            &mut *scratch
+        } else {
+            push_background(out, bg_color);
+            &mut *out
+        };
+        let padding = self.padding(config, true).widened(fill);
+        push_spaces(content, padding.left);
+        push_styled_on_background(
+            content,
+            &icon,
+            config.colors.icon.as_ref(),
+            config.styles.icon,
+        );
+        content.push(' ');
+        push_styled_on_background(
+            content,
+            &primary,
+            config.colors.text.as_ref(),
+            config.styles.primary_style(),
+        );
+        if !secondary.is_empty() {
+            content.push(' ');
+            push_styled_on_background(
+                content,
+                &secondary,
+                self.secondary_color(config, data),
+                config.styles.secondary_style(),
+            );
         }
+        push_spaces(content, padding.right);
+
+        if let Some(gradient) = gradient {
+            push_gradient_background(out, scratch, gradient);
+        }
+        out.push_str("\x1b[49m");
     }
 
-    fn get_icon(&self, config: &SegmentConfig) -> String {
-        match self.config.style.mode {
-            StyleMode::Plain => config.icon.plain.clone(),
-            StyleMode::NerdFont => config.icon.nerd_font.clone(),
-            StyleMode::Powerline => config.icon.nerd_font.clone(), // Future: use Powerline icons
+    /// Color of the secondary text. Segments flag a crossed threshold with
+    /// `level` metadata (`warning` or `critical`), which overrides the text color.
+    fn secondary_color<'a>(
+        &self,
+        config: &'a SegmentConfig,
+        data: &SegmentData,
+    ) -> Option<&'a AnsiColor> {
+        match data.metadata.get("level").map(String::as_str) {
+            Some("warning") => Some(&WARNING_COLOR),
+            Some("critical") => Some(&CRITICAL_COLOR),
+            _ => config.colors.text.as_ref(),
         }
     }
 
-    fn apply_color(&self, text: &str, color: Option<&AnsiColor>) -> String {
-        match color {
-            Some(AnsiColor::Color16 { c16 }) => {
-                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
-                format!("\x1b[{}m{}\x1b[0m", code, text)
-            }
-            Some(AnsiColor::Color256 { c256 }) => {
-                format!("\x1b[38;5;{}m{}\x1b[0m", c256, text)
-            }
-            Some(AnsiColor::Rgb { r, g, b }) => {
-                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+    /// Padding for a segment: its own override, then `style.spacing.padding`,
+    /// then one space on each side for segments with a background
+    fn padding(&self, config: &SegmentConfig, has_background: bool) -> PaddingConfig {
+        config
+            .padding
+            .or(self.config.style.spacing.padding)
+            .unwrap_or(if has_background {
+                PaddingConfig { left: 1, right: 1 }
+            } else {
+                PaddingConfig::default()
+            })
+    }
+
+    fn get_icon<'a>(&self, config: &'a SegmentConfig) -> Cow<'a, str> {
+        if self.config.style.ascii {
+            let tag =
+                with_registry(|registry| registry.info(config.id).map(|info| info.icons.ascii))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| to_ascii(&config.icon.plain));
+            return Cow::Owned(format!("[{}]", tag));
+        }
+        if let Some(set) = self.config.style.icons {
+            let glyph =
+                with_registry(|registry| registry.info(config.id).map(|info| info.icons.get(set)));
+            if let Some(glyph) = glyph {
+                return Cow::Borrowed(glyph);
             }
-            None => text.to_string(),
+        }
+
+        match self.config.style.mode {
+            StyleMode::Plain => Cow::Borrowed(&config.icon.plain),
+            StyleMode::NerdFont => Cow::Borrowed(&config.icon.nerd_font),
+            StyleMode::Powerline => Cow::Borrowed(&config.icon.nerd_font), // Future: use Powerline icons
         }
     }
 
-    fn apply_style(&self, text: &str, color: Option<&AnsiColor>, bold: bool) -> String {
-        let mut codes = Vec::new();
+    /// Gradient to render for the segment, only on truecolor terminals
+    fn active_gradient<'a>(&self, config: &'a SegmentConfig) -> Option<&'a GradientConfig> {
+        if self.truecolor {
+            config.colors.background_gradient.as_ref()
+        } else {
+            None
+        }
+    }
 
-        // Add style codes
-        if bold {
-            codes.push("1".to_string()); // Bold: \x1b[1m
+    /// Solid background of the segment; without truecolor a gradient falls
+    /// back to `background`, or to its start color when no background is set
+    fn solid_background<'a>(&self, config: &'a SegmentConfig) -> Option<&'a AnsiColor> {
+        if self.active_gradient(config).is_some() {
+            return None;
         }
+        config.colors.background.as_ref().or(config
+            .colors
+            .background_gradient
+            .as_ref()
+            .map(|gradient| &gradient.from))
+    }
 
-        // Add color codes
-        match color {
-            Some(AnsiColor::Color16 { c16 }) => {
-                let color_code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
-                codes.push(color_code.to_string());
-            }
-            Some(AnsiColor::Color256 { c256 }) => {
-                codes.push("38".to_string());
-                codes.push("5".to_string());
-                codes.push(c256.to_string());
-            }
-            Some(AnsiColor::Rgb { r, g, b }) => {
-                codes.push("38".to_string());
-                codes.push("2".to_string());
-                codes.push(r.to_string());
-                codes.push(g.to_string());
-                codes.push(b.to_string());
+    /// Background colors at the left and right edge of a segment, used for
+    /// Powerline arrow transitions
+    fn edge_backgrounds(&self, config: &SegmentConfig) -> (Option<AnsiColor>, Option<AnsiColor>) {
+        match self.active_gradient(config) {
+            Some(gradient) => (Some(gradient.from.clone()), Some(gradient.to.clone())),
+            None => {
+                let background = self.solid_background(config).cloned();
+                (background.clone(), background)
             }
-            None => {}
         }
+    }
+
+    /// Separator drawn after `prev` and before `next`, either of which is
+    /// `None` at the edges of the line. Its color is `prev`'s
+    /// `colors.separator`, else `style.separator_color`; unset, a Powerline
+    /// arrow takes the previous background on the next one and a plain
+    /// separator a neutral color.
+    fn push_separator(
+        &self,
+        out: &mut String,
+        prev: Option<&SegmentConfig>,
+        next: Option<&SegmentConfig>,
+    ) {
+        let color = prev
+            .and_then(|config| config.colors.separator.as_ref())
+            .or(self.config.style.separator_color.as_ref());
 
-        if codes.is_empty() {
-            text.to_string()
+        if self.config.style.separator == "\u{e0b0}" {
+            let prev_bg = prev.and_then(|config| self.edge_backgrounds(config).1);
+            let next_bg = next.and_then(|config| self.edge_backgrounds(config).0);
+            push_powerline_arrow(out, color.or(prev_bg.as_ref()), next_bg.as_ref());
         } else {
-            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+            // Without a color, white on dark terminals and dark gray on
+            // light ones
+            let gap = self.config.style.spacing.gap;
+            push_spaces(out, gap);
+            match color {
+                Some(color) => push_foreground(out, color),
+                None => out.push_str(match self.config.style.background {
+                    ThemeBackground::Dark => "\x1b[37m",
+                    ThemeBackground::Light => "\x1b[90m",
+                }),
+            }
+            out.push_str(&self.config.style.separator);
+            out.push_str("\x1b[0m");
+            push_spaces(out, gap);
         }
     }
 
-    fn apply_background_color(&self, color: &AnsiColor) -> String {
-        match color {
-            AnsiColor::Color16 { c16 } => {
-                let code = if *c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
-                format!("\x1b[{}m", code)
-            }
-            AnsiColor::Color256 { c256 } => {
-                format!("\x1b[48;5;{}m", c256)
+    fn separator_between(
+        &self,
+        prev: Option<&SegmentConfig>,
+        next: Option<&SegmentConfig>,
+    ) -> String {
+        let mut separator = String::new();
+        self.push_separator(&mut separator, prev, next);
+        separator
+    }
+
+    /// Separators drawn before the first and after the last segment, empty
+    /// unless enabled in `style.spacing`
+    fn edge_separators(
+        &self,
+        first: Option<&SegmentConfig>,
+        last: Option<&SegmentConfig>,
+    ) -> (String, String) {
+        let spacing = &self.config.style.spacing;
+        let powerline = self.config.style.separator == "\u{e0b0}";
+        let leading = if spacing.leading_separator {
+            let leading = self.separator_between(None, first);
+            if powerline {
+                leading
+            } else {
+                leading.trim_start_matches(' ').to_string()
             }
-            AnsiColor::Rgb { r, g, b } => {
-                format!("\x1b[48;2;{};{};{}m", r, g, b)
+        } else {
+            String::new()
+        };
+        let trailing = if spacing.trailing_separator {
+            let trailing = self.separator_between(last, None);
+            if powerline {
+                trailing
+            } else {
+                trailing.trim_end_matches(' ').to_string()
             }
-        }
+        } else {
+            String::new()
+        };
+        (leading, trailing)
     }
+}
 
-    /// Join segments with white separators (non-Powerline)
-    fn join_with_white_separators(&self, rendered_segments: &[String]) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+/// Room for the escape sequences of a segment and the separator after it,
+/// added to the text when sizing the line
+const ESCAPES_PER_SEGMENT: usize = 96;
+
+fn push_spaces(out: &mut String, count: usize) {
+    out.extend(std::iter::repeat_n(' ', count));
+}
+
+/// Append the SGR parameters of the foreground `color`
+fn push_foreground_params(out: &mut String, color: &AnsiColor) {
+    // Writing to a String cannot fail
+    let _ = match color {
+        AnsiColor::Color16 { c16 } => {
+            let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
+            write!(out, "{}", code)
         }
+        AnsiColor::Color256 { c256 } => write!(out, "38;5;{}", c256),
+        AnsiColor::Rgb { r, g, b } => write!(out, "38;2;{};{};{}", r, g, b),
+    };
+}
 
-        // Use white color for separator
-        let white_separator = format!("\x1b[37m{}\x1b[0m", self.config.style.separator);
-        rendered_segments.join(&white_separator)
-    }
+fn push_foreground(out: &mut String, color: &AnsiColor) {
+    out.push_str("\x1b[");
+    push_foreground_params(out, color);
+    out.push('m');
+}
 
-    /// Join segments with Powerline arrow separators with proper color transitions
-    fn join_with_powerline_arrows(
-        &self,
-        rendered_segments: &[String],
-        segment_configs: &[(SegmentConfig, SegmentData)],
-    ) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+fn push_background(out: &mut String, color: &AnsiColor) {
+    let _ = match color {
+        AnsiColor::Color16 { c16 } => {
+            let code = if *c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
+            write!(out, "\x1b[{}m", code)
         }
+        AnsiColor::Color256 { c256 } => write!(out, "\x1b[48;5;{}m", c256),
+        AnsiColor::Rgb { r, g, b } => write!(out, "\x1b[48;2;{};{};{}m", r, g, b),
+    };
+}
 
-        if rendered_segments.len() == 1 {
-            return rendered_segments[0].clone();
+/// Append the escape sequence switching on `style` and the foreground
+/// `color`; returns false, having appended nothing, when neither is set
+fn push_sgr(out: &mut String, color: Option<&AnsiColor>, style: TextStyle) -> bool {
+    let start = out.len();
+    out.push_str("\x1b[");
+    let mut empty = true;
+    for code in style.sgr_codes() {
+        if !empty {
+            out.push(';');
+        }
+        out.push_str(code);
+        empty = false;
+    }
+    if let Some(color) = color {
+        if !empty {
+            out.push(';');
         }
+        push_foreground_params(out, color);
+        empty = false;
+    }
 
-        let mut result = rendered_segments[0].clone();
+    if empty {
+        out.truncate(start);
+        return false;
+    }
+    out.push('m');
+    true
+}
 
-        for (i, _) in rendered_segments.iter().enumerate().skip(1) {
-            let prev_bg = segment_configs
-                .get(i - 1)
-                .and_then(|(config, _)| config.colors.background.as_ref());
-            let curr_bg = segment_configs
-                .get(i)
-                .and_then(|(config, _)| config.colors.background.as_ref());
+/// Append `text` in `color` and `style`, followed by a reset
+fn push_styled(out: &mut String, text: &str, color: Option<&AnsiColor>, style: TextStyle) {
+    let styled = push_sgr(out, color, style);
+    out.push_str(text);
+    if styled {
+        out.push_str("\x1b[0m");
+    }
+}
 
-            // Create Powerline arrow with color transition
-            let arrow = self.create_powerline_arrow(prev_bg, curr_bg);
+/// Like [`push_styled`], but only switches the attributes off afterwards so
+/// the segment background survives
+fn push_styled_on_background(
+    out: &mut String,
+    text: &str,
+    color: Option<&AnsiColor>,
+    style: TextStyle,
+) {
+    push_sgr(out, color, style);
+    out.push_str(text);
+    if !style.is_plain() {
+        // Bold/dim, italic, underline, blink and strikethrough off
+        out.push_str("\x1b[22;23;24;25;29m");
+    }
+}
 
-            result.push_str(&arrow);
-            result.push_str(&rendered_segments[i]);
+/// Append `content` with every visible character on its own background,
+/// interpolated from `gradient.from` to `gradient.to`. Escape sequences are
+/// copied as-is.
+fn push_gradient_background(out: &mut String, content: &str, gradient: &GradientConfig) {
+    let columns = display_width(content);
+    let mut column = 0;
+    let mut rest = content;
+
+    while !rest.is_empty() {
+        if rest.starts_with('\x1b') {
+            // Copy the escape sequence through its final letter
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| c.is_ascii_alphabetic())
+                .map(|(i, c)| i + c.len_utf8())
+                .unwrap_or(rest.len());
+            out.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
         }
 
-        // Reset colors at the end
-        result.push_str("\x1b[0m");
-        result
-    }
-
-    /// Create a Powerline arrow with proper color transition
-    fn create_powerline_arrow(
-        &self,
-        prev_bg: Option<&AnsiColor>,
-        curr_bg: Option<&AnsiColor>,
-    ) -> String {
-        let arrow_char = "\u{e0b0}";
-
-        match (prev_bg, curr_bg) {
-            (Some(prev), Some(curr)) => {
-                // Arrow foreground = previous segment's background
-                // Arrow background = current segment's background
-                let fg_code = self.color_to_foreground_code(prev);
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}{}\x1b[0m", bg_code, fg_code, arrow_char)
-            }
-            (Some(prev), None) => {
-                // Previous segment has background, current doesn't
-                let fg_code = self.color_to_foreground_code(prev);
-                format!("{}{}\x1b[0m", fg_code, arrow_char)
-            }
-            (None, Some(curr)) => {
-                // Current segment has background, previous doesn't
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}\x1b[0m", bg_code, arrow_char)
-            }
-            (None, None) => {
-                // Neither segment has background color
-                arrow_char.to_string()
-            }
+        let end = rest.find('\x1b').unwrap_or(rest.len());
+        for grapheme in rest[..end].graphemes(true) {
+            let t = if columns > 1 {
+                column as f64 / (columns - 1) as f64
+            } else {
+                0.0
+            };
+            push_background(out, &interpolate(&gradient.from, &gradient.to, t));
+            out.push_str(grapheme);
+            column += grapheme_width(grapheme);
         }
+        rest = &rest[end..];
     }
+}
 
-    /// Convert AnsiColor to foreground color code
-    fn color_to_foreground_code(&self, color: &AnsiColor) -> String {
-        match color {
-            AnsiColor::Color16 { c16 } => {
-                let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
-                format!("\x1b[{}m", code)
-            }
-            AnsiColor::Color256 { c256 } => {
-                format!("\x1b[38;5;{}m", c256)
-            }
-            AnsiColor::Rgb { r, g, b } => {
-                format!("\x1b[38;2;{};{};{}m", r, g, b)
-            }
-        }
+/// Append a Powerline arrow in the previous segment's background (or the
+/// separator color) on the next segment's background
+fn push_powerline_arrow(
+    out: &mut String,
+    prev_bg: Option<&AnsiColor>,
+    next_bg: Option<&AnsiColor>,
+) {
+    if let Some(next) = next_bg {
+        push_background(out, next);
+    }
+    if let Some(prev) = prev_bg {
+        push_foreground(out, prev);
+    }
+    out.push('\u{e0b0}');
+    if prev_bg.is_some() || next_bg.is_some() {
+        out.push_str("\x1b[0m");
     }
 }
 
@@ -454,59 +884,241 @@ pub fn collect_all_segments(
     config: &Config,
     input: &crate::config::InputData,
 ) -> Vec<(SegmentConfig, SegmentData)> {
-    use crate::core::segments::*;
+    use crate::core::executor::{join_with_deadline, Task, NETWORK_DEADLINE};
+
+    crate::i18n::set_language(config.language.as_deref());
+    let segments = config.resolved_segments();
+    let mut collected: Vec<Option<SegmentData>> = vec![None; segments.len()];
+    let mut network_tasks: Vec<Task<Result<Option<SegmentData>, Error>>> = Vec::new();
+    let mut network_slots = Vec::new();
+    let mut local_slots = Vec::new();
+
+    for (index, segment_config) in segments.iter().enumerate() {
+        // Within its refresh interval a segment shows its last value
+        if let Some(cached) = fresh_cached_segment(segment_config, input) {
+            collected[index] = cached;
+            continue;
+        }
 
-    let mut results = Vec::new();
+        let is_network = with_registry(|registry| {
+            registry
+                .info(segment_config.id)
+                .map(|info| info.network)
+                .unwrap_or(false)
+        });
+
+        if is_network {
+            let segment_config = segment_config.clone();
+            let input = input.clone();
+            network_tasks.push(Box::new(move || {
+                try_collect_segment(&segment_config, &input)
+            }));
+            network_slots.push(index);
+        } else {
+            local_slots.push(index);
+        }
+    }
 
-    for segment_config in &config.segments {
-        let segment_data = match segment_config.id {
-            crate::config::SegmentId::Model => {
-                let segment = ModelSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Directory => {
-                let segment = DirectorySegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Git => {
-                let show_sha = segment_config
-                    .options
-                    .get("show_sha")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let segment = GitSegment::new().with_sha(show_sha);
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Usage => {
-                let segment = UsageSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Cost => {
-                let segment = CostSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Session => {
-                let segment = SessionSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::OutputStyle => {
-                let segment = OutputStyleSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Update => {
-                let segment = UpdateSegment::new();
-                segment.collect(input)
-            }
-            crate::config::SegmentId::Quota => {
-                let segment = QuotaSegment::new();
-                segment.collect(input)
-            }
-        };
+    // Start network segments first so they overlap with local collection;
+    // the whole network batch is bounded by a single deadline
+    let pending = if network_tasks.is_empty() {
+        None
+    } else {
+        Some(std::thread::spawn(move || {
+            join_with_deadline(network_tasks, NETWORK_DEADLINE)
+        }))
+    };
+
+    for index in local_slots {
+        let segment_config = &segments[index];
+        collected[index] = collect_segment(segment_config, input);
+        remember_segment(segment_config, input, &collected[index]);
+    }
 
-        if let Some(data) = segment_data {
-            results.push((segment_config.clone(), data));
+    if let Some(handle) = pending {
+        let network_results = handle.join().unwrap_or_default();
+        for (slot, result) in network_slots.into_iter().zip(network_results) {
+            let segment_config = &segments[slot];
+            collected[slot] = match result {
+                Some(Ok(data)) => {
+                    remember_segment(segment_config, input, &data);
+                    data
+                }
+                Some(Err(error)) => or_on_error(segment_config, Err(error)),
+                None => past_deadline(segment_config, input),
+            };
         }
     }
+    crate::core::segments::computed::fill(&segments, &mut collected);
+
+    segments
+        .into_iter()
+        .zip(collected)
+        .filter_map(|(segment_config, data)| {
+            let data = data.or_else(|| placeholder(&segment_config))?;
+            Some((segment_config, data))
+        })
+        .collect()
+}
+
+/// Collect a segment, or reuse its last value while its refresh interval
+/// has not passed
+pub fn collect_segment_cached(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<SegmentData> {
+    if let Some(cached) = fresh_cached_segment(segment_config, input) {
+        return cached;
+    }
+    let data = collect_segment(segment_config, input);
+    remember_segment(segment_config, input, &data);
+    data
+}
+
+/// Segment values are cached per session, working directory and segment
+fn segment_cache_key(segment_config: &SegmentConfig, input: &crate::config::InputData) -> String {
+    format!(
+        "{}@{}",
+        segment_config.id.as_str(),
+        input.workspace.current_dir
+    )
+}
+
+fn cached_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<CachedSegment> {
+    segment_config.refresh_interval_ms?;
+    let key = segment_cache_key(segment_config, input);
+    session_cache::with_session(&input.transcript_path, |session| {
+        session.segments.get(&key).cloned()
+    })
+}
+
+/// The last value of a segment whose refresh interval has not passed, with
+/// its age in the `cache_age_ms` metadata. The outer `None` means the segment
+/// needs collecting.
+fn fresh_cached_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<Option<SegmentData>> {
+    let interval = Duration::from_millis(segment_config.refresh_interval_ms?);
+    let cached = cached_segment(segment_config, input)?;
+    let age = cached.collected_at.elapsed().ok()?;
+    if age >= interval {
+        return None;
+    }
+
+    Some(cached.data.map(|mut data| {
+        data.metadata
+            .insert("cache_age_ms".to_string(), age.as_millis().to_string());
+        data
+    }))
+}
+
+/// The last value of a segment that could not be collected in time, marked
+/// with `stale` and `cache_age_ms` metadata
+fn stale_cached_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<SegmentData> {
+    let cached = cached_segment(segment_config, input)?;
+    let age = cached.collected_at.elapsed().unwrap_or_default();
+    cached.data.map(|mut data| {
+        data.metadata
+            .insert("stale".to_string(), "true".to_string());
+        data.metadata
+            .insert("cache_age_ms".to_string(), age.as_millis().to_string());
+        data
+    })
+}
+
+/// What a network segment that missed the render deadline shows: its last
+/// value, marked stale, or without one a timeout as its `on_error` option
+/// asks
+pub(crate) fn past_deadline(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<SegmentData> {
+    stale_cached_segment(segment_config, input)
+        .or_else(|| or_on_error(segment_config, Err(Error::Timeout)))
+}
+
+/// Keep a freshly collected value for segments with a refresh interval
+fn remember_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+    data: &Option<SegmentData>,
+) {
+    if segment_config.refresh_interval_ms.is_none() {
+        return;
+    }
+    let key = segment_cache_key(segment_config, input);
+    let cached = CachedSegment {
+        data: data.clone(),
+        collected_at: SystemTime::now(),
+    };
+    session_cache::update_session(&input.transcript_path, |session| {
+        session.segments.insert(key, cached);
+    });
+}
 
-    results
+/// Collect data for a single configured segment using the global segment
+/// registry; a failure is shown as the segment's `on_error` option asks
+pub fn collect_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Option<SegmentData> {
+    or_on_error(segment_config, try_collect_segment(segment_config, input))
+}
+
+/// Like `collect_segment`, but returns the error instead of rendering it.
+/// The segment's `transforms` are applied to the data, then escape
+/// sequences and control characters removed.
+pub fn try_collect_segment(
+    segment_config: &SegmentConfig,
+    input: &crate::config::InputData,
+) -> Result<Option<SegmentData>, Error> {
+    crate::core::segments::collect_registered(segment_config, input).map(|data| {
+        data.map(|data| crate::core::transform::apply(&segment_config.transforms, data))
+            .map(SegmentData::sanitized)
+    })
+}
+
+/// The collected data, or what the segment's `on_error` option shows for
+/// the error: nothing (`hide`, the default), "—" (`dash`) or a warning
+/// icon with the error as tooltip (`icon`). The error is kept in the
+/// `error` and `error_kind` metadata either way.
+fn or_on_error(
+    segment_config: &SegmentConfig,
+    result: Result<Option<SegmentData>, Error>,
+) -> Option<SegmentData> {
+    let error = match result {
+        Ok(data) => return data,
+        Err(error) => error,
+    };
+
+    // Transport errors can carry the request URL and its credentials
+    let message = redact(&error.to_string());
+    let mut metadata = HashMap::new();
+    metadata.insert("error".to_string(), message.clone());
+    metadata.insert("error_kind".to_string(), error.kind().to_string());
+    let policy = segment_config.options.get("on_error");
+    let primary = match policy.and_then(|policy| policy.as_str()) {
+        Some("dash") => "—",
+        Some("icon") => {
+            metadata.insert("tooltip".to_string(), message);
+            "⚠"
+        }
+        _ => return None,
+    };
+
+    Some(
+        SegmentData {
+            primary: primary.to_string(),
+            secondary: String::new(),
+            metadata,
+        }
+        .sanitized(),
+    )
 }
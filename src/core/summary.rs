@@ -0,0 +1,376 @@
+//! Summary of a whole session read back from its transcript, for `ccline
+//! report`: how long it ran, which models answered, tokens, cost, the tools
+//! Claude used and the files it read or changed.
+
+use crate::config::{ModelConfig, NormalizedUsage, PricingConfig, Usage};
+use crate::core::pricing::Prices;
+use crate::core::{handoff, session_cache, spend};
+use crate::utils::transcript;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+/// Tools whose `file_path` (or `notebook_path`) input is a file they change
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// The fields of a transcript entry the summary reads
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    timestamp: Option<String>,
+    cwd: Option<String>,
+    #[serde(rename = "gitBranch")]
+    git_branch: Option<String>,
+    message: Option<EntryMessage>,
+}
+
+#[derive(Deserialize)]
+struct EntryMessage {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<Usage>,
+    content: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TokenCounts {
+    pub input: u64,
+    pub output: u64,
+    pub cache_creation: u64,
+    pub cache_read: u64,
+}
+
+impl TokenCounts {
+    fn add(&mut self, usage: &NormalizedUsage) {
+        self.input += u64::from(usage.input_tokens);
+        self.output += u64::from(usage.output_tokens);
+        self.cache_creation += u64::from(usage.cache_creation_input_tokens);
+        self.cache_read += u64::from(usage.cache_read_input_tokens);
+    }
+
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_creation + self.cache_read
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelUsage {
+    pub model: String,
+    pub responses: u64,
+    pub tokens: TokenCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolUsage {
+    pub name: String,
+    pub calls: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub session: String,
+    pub transcript: PathBuf,
+    /// Working directory of the session's first entry
+    pub project: Option<String>,
+    pub git_branch: Option<String>,
+    /// Timestamps of the first and last entry, as Claude Code writes them
+    /// ("2025-10-16T12:34:56.789Z")
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// Messages you typed, not counting tool results
+    pub prompts: u64,
+    /// Assistant messages, each counted once however many lines it spans
+    pub responses: u64,
+    /// Most used first
+    pub models: Vec<ModelUsage>,
+    pub tokens: TokenCounts,
+    /// Last cost Claude Code reported to the statusline for the session
+    pub cost_usd: Option<f64>,
+    /// Cost worked out from the tokens and model prices; `None` when a model
+    /// has no known price
+    pub estimated_cost_usd: Option<f64>,
+    /// Most used first
+    pub tools: Vec<ToolUsage>,
+    /// Relative to `project` when inside it, sorted
+    pub files_edited: Vec<String>,
+    pub files_read: Vec<String>,
+}
+
+/// Claude Code's transcripts, one directory per project
+fn projects_dir() -> Option<PathBuf> {
+    Some(
+        crate::utils::paths::home_dir()?
+            .join(".claude")
+            .join("projects"),
+    )
+}
+
+/// Every transcript in `~/.claude/projects`
+pub fn transcripts() -> Vec<PathBuf> {
+    let Some(Ok(projects)) = projects_dir().map(fs::read_dir) else {
+        return Vec::new();
+    };
+    projects
+        .filter_map(Result::ok)
+        .filter_map(|project| fs::read_dir(project.path()).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect()
+}
+
+/// The transcript of `session`: a path to a transcript, `latest` for the most
+/// recently written one, or a session id or unambiguous id prefix
+pub fn find_transcript(session: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = Path::new(session);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let transcripts = transcripts();
+    if session == "latest" {
+        return transcripts
+            .into_iter()
+            .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+            .max()
+            .map(|(_, path)| path)
+            .ok_or_else(|| "No transcripts found in ~/.claude/projects".into());
+    }
+
+    let mut matches: Vec<PathBuf> = transcripts
+        .into_iter()
+        .filter(|path| {
+            path.file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().starts_with(session))
+        })
+        .collect();
+    if let Some(exact) = matches
+        .iter()
+        .find(|path| path.file_stem().is_some_and(|stem| stem == session))
+    {
+        return Ok(exact.clone());
+    }
+    match matches.len() {
+        0 => Err(format!("No transcript found for session {}", session).into()),
+        1 => Ok(matches.remove(0)),
+        n => Err(format!("Session prefix {} matches {} transcripts", session, n).into()),
+    }
+}
+
+/// `path` relative to `project` when inside it
+fn relative(path: &str, project: Option<&str>) -> String {
+    project
+        .and_then(|project| Path::new(path).strip_prefix(project).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .map(|relative| relative.display().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Read the whole transcript at `path`
+pub fn summarize(
+    path: &Path,
+    pricing: &PricingConfig,
+) -> Result<SessionSummary, Box<dyn std::error::Error>> {
+    let file = fs::File::open(path)?;
+    let transcript_path = path.to_string_lossy().into_owned();
+    let session = handoff::session_id(&transcript_path);
+
+    let mut project = None;
+    let mut git_branch = None;
+    let mut first: Option<(f64, String)> = None;
+    let mut last: Option<(f64, String)> = None;
+    let mut prompts = 0;
+    // Claude Code writes one line per content block, repeating the message's
+    // model and usage, so those are kept once per message id
+    let mut responses: HashMap<String, (String, Usage)> = HashMap::new();
+    let mut unnamed: Vec<(String, Usage)> = Vec::new();
+    let mut tool_calls: HashSet<String> = HashSet::new();
+    let mut tools: HashMap<String, u64> = HashMap::new();
+    let mut edited: HashSet<String> = HashSet::new();
+    let mut read: HashSet<String> = HashSet::new();
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Entry>(&line) else {
+            continue;
+        };
+        if let Some(timestamp) = entry.timestamp {
+            if let Some(seconds) = transcript::timestamp_seconds(&timestamp) {
+                if first.as_ref().is_none_or(|(first, _)| seconds < *first) {
+                    first = Some((seconds, timestamp.clone()));
+                }
+                if last.as_ref().is_none_or(|(last, _)| seconds >= *last) {
+                    last = Some((seconds, timestamp));
+                }
+            }
+        }
+        project = project.or(entry.cwd);
+        if entry
+            .git_branch
+            .as_ref()
+            .is_some_and(|branch| !branch.is_empty())
+        {
+            git_branch = entry.git_branch;
+        }
+        let Some(message) = entry.message else {
+            continue;
+        };
+
+        match entry.kind.as_deref() {
+            Some("user") => {
+                let typed = match &message.content {
+                    Some(serde_json::Value::String(_)) => true,
+                    Some(serde_json::Value::Array(blocks)) => blocks
+                        .iter()
+                        .any(|block| block.get("type").and_then(|t| t.as_str()) == Some("text")),
+                    _ => false,
+                };
+                if typed {
+                    prompts += 1;
+                }
+            }
+            Some("assistant") => {
+                let model = message.model.unwrap_or_default();
+                // Claude Code's own notices, not model output
+                if model == "<synthetic>" {
+                    continue;
+                }
+                if let Some(usage) = message.usage {
+                    match message.id {
+                        Some(id) => {
+                            responses.insert(id, (model, usage));
+                        }
+                        None => unnamed.push((model, usage)),
+                    }
+                }
+
+                let Some(serde_json::Value::Array(blocks)) = message.content else {
+                    continue;
+                };
+                for block in blocks {
+                    if block.get("type").and_then(|t| t.as_str()) != Some("tool_use") {
+                        continue;
+                    }
+                    let id = block
+                        .get("id")
+                        .and_then(|id| id.as_str())
+                        .unwrap_or_default();
+                    if !id.is_empty() && !tool_calls.insert(id.to_string()) {
+                        continue;
+                    }
+                    let name = block
+                        .get("name")
+                        .and_then(|name| name.as_str())
+                        .unwrap_or("unknown");
+                    *tools.entry(name.to_string()).or_default() += 1;
+
+                    let file = block.get("input").and_then(|input| {
+                        input
+                            .get("file_path")
+                            .or_else(|| input.get("notebook_path"))
+                            .and_then(|path| path.as_str())
+                    });
+                    if let Some(file) = file {
+                        if EDIT_TOOLS.contains(&name) {
+                            edited.insert(file.to_string());
+                        } else if name == "Read" {
+                            read.insert(file.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut tokens = TokenCounts::default();
+    let mut models: HashMap<String, ModelUsage> = HashMap::new();
+    let response_count = (responses.len() + unnamed.len()) as u64;
+    let model_config = ModelConfig::load();
+    for (model, usage) in responses.into_values().chain(unnamed) {
+        let mut usage = usage.normalize();
+        if model_config
+            .entry(&model)
+            .is_some_and(|entry| entry.input_includes_cache)
+        {
+            usage = usage.without_cached_input();
+        }
+        let entry = models.entry(model.clone()).or_insert_with(|| ModelUsage {
+            model,
+            responses: 0,
+            tokens: TokenCounts::default(),
+        });
+        entry.responses += 1;
+        entry.tokens.add(&usage);
+        tokens.add(&usage);
+    }
+    let mut models: Vec<ModelUsage> = models.into_values().collect();
+    models.sort_by(|a, b| b.responses.cmp(&a.responses).then(a.model.cmp(&b.model)));
+
+    let mut tools: Vec<ToolUsage> = tools
+        .into_iter()
+        .map(|(name, calls)| ToolUsage { name, calls })
+        .collect();
+    tools.sort_by(|a, b| b.calls.cmp(&a.calls).then(a.name.cmp(&b.name)));
+
+    // Files that were changed are not listed again as read
+    let files = |files: HashSet<String>| {
+        let mut files: Vec<String> = files
+            .iter()
+            .map(|file| relative(file, project.as_deref()))
+            .collect();
+        files.sort();
+        files
+    };
+    let read: HashSet<String> = read.difference(&edited).cloned().collect();
+    let files_edited = files(edited);
+    let files_read = files(read);
+
+    let cost_usd = spend::session_cost(&session).or_else(|| {
+        session_cache::with_session(&transcript_path, |state| {
+            state.cost_samples.last().map(|sample| sample.total_usd)
+        })
+    });
+    let prices = Prices::load(pricing);
+    let estimated_cost_usd = models
+        .iter()
+        .map(|model| {
+            let tokens = &model.tokens;
+            prices.get(&model.model).map(|price| {
+                price.cost(
+                    tokens.input,
+                    tokens.output,
+                    tokens.cache_creation,
+                    tokens.cache_read,
+                )
+            })
+        })
+        .sum::<Option<f64>>();
+    let duration_ms = first
+        .as_ref()
+        .zip(last.as_ref())
+        .map(|((first, _), (last, _))| ((last - first) * 1000.0) as u64);
+
+    Ok(SessionSummary {
+        session,
+        transcript: path.to_path_buf(),
+        project,
+        git_branch,
+        started_at: first.map(|(_, timestamp)| timestamp),
+        ended_at: last.map(|(_, timestamp)| timestamp),
+        duration_ms,
+        prompts,
+        responses: response_count,
+        models,
+        tokens,
+        cost_usd,
+        estimated_cost_usd,
+        tools,
+        files_edited,
+        files_read,
+    })
+}
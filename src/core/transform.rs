@@ -0,0 +1,80 @@
+//! The `transforms` of a segment, applied to its text between collection
+//! and rendering.
+//!
+//! A replace pattern that is not a valid regex leaves the text as it is;
+//! `ccline --check` reports it.
+
+use crate::config::{CaseMapping, Transform, TransformConfig, TransformTarget};
+use crate::core::segments::SegmentData;
+use crate::utils::pattern::Regex;
+
+/// `data` with `transforms` applied in order
+pub fn apply(transforms: &[TransformConfig], mut data: SegmentData) -> SegmentData {
+    for transform in transforms {
+        if matches!(
+            transform.on,
+            TransformTarget::Primary | TransformTarget::Both
+        ) {
+            data.primary = apply_one(&transform.kind, &data.primary);
+        }
+        if matches!(
+            transform.on,
+            TransformTarget::Secondary | TransformTarget::Both
+        ) {
+            data.secondary = apply_one(&transform.kind, &data.secondary);
+        }
+    }
+    data
+}
+
+fn apply_one(transform: &Transform, text: &str) -> String {
+    match transform {
+        Transform::Replace { pattern, with } => match Regex::new(pattern) {
+            Ok(regex) => regex.replace_all(text, with.as_str()).into_owned(),
+            Err(_) => text.to_string(),
+        },
+        Transform::Case { case } => match case {
+            CaseMapping::Upper => text.to_uppercase(),
+            CaseMapping::Lower => text.to_lowercase(),
+            CaseMapping::Title => title_case(text),
+        },
+        // Empty text stays empty, so a segment with nothing to show is not
+        // left holding only the prefix
+        Transform::Prefix { text: prefix } if !text.is_empty() => format!("{}{}", prefix, text),
+        Transform::Suffix { text: suffix } if !text.is_empty() => format!("{}{}", text, suffix),
+        Transform::Prefix { .. } | Transform::Suffix { .. } => String::new(),
+        Transform::Round { decimals } => round_first_number(text, *decimals),
+    }
+}
+
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut at_word_start = true;
+    for ch in text.chars() {
+        if at_word_start {
+            result.extend(ch.to_uppercase());
+        } else {
+            result.push(ch);
+        }
+        at_word_start = !ch.is_alphanumeric();
+    }
+    result
+}
+
+/// "$12.3456/h" with 2 decimals is "$12.35/h"
+fn round_first_number(text: &str, decimals: usize) -> String {
+    let number = Regex::new(r"-?\d+(?:\.\d+)?").expect("number pattern is valid");
+    let Some(found) = number.find(text) else {
+        return text.to_string();
+    };
+    let Ok(value) = found.as_str().parse::<f64>() else {
+        return text.to_string();
+    };
+    format!(
+        "{}{:.*}{}",
+        &text[..found.start()],
+        decimals,
+        value,
+        &text[found.end()..]
+    )
+}
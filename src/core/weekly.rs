@@ -0,0 +1,228 @@
+//! Token usage over the last seven days, across every session: Claude
+//! Code's transcripts in `~/.claude/projects` are tallied into hourly
+//! buckets in `weekly_usage.json` in the cache directory.
+//!
+//! Each transcript is read on from where the last tally stopped, and each
+//! response is counted once by its message id, however many lines or
+//! transcripts repeat it. Buckets older than the window are dropped, so the
+//! total rolls over hour by hour rather than resetting once a week.
+
+use crate::config::{ModelConfig, Usage};
+use crate::core::summary;
+use crate::utils::transcript;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of the rolling window
+pub const WINDOW_SECS: u64 = 7 * 24 * 3600;
+const BUCKET_SECS: u64 = 3600;
+/// Transcripts are read again at most this often
+const RESCAN_SECS: u64 = 60;
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct HourUsage {
+    pub tokens: u64,
+    /// Tokens of the total used by Opus models, which Max plans cap
+    /// separately
+    #[serde(default)]
+    pub opus_tokens: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WeeklyFile {
+    #[serde(default)]
+    scanned_at: u64,
+    /// Bytes already read, keyed by transcript path
+    #[serde(default)]
+    offsets: HashMap<String, u64>,
+    /// Hour each counted message id fell in, so copies are skipped
+    #[serde(default)]
+    seen: HashMap<String, u64>,
+    /// Keyed by the start of the hour in Unix seconds
+    #[serde(default)]
+    hours: BTreeMap<u64, HourUsage>,
+}
+
+/// The fields of a transcript entry the tally reads
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    timestamp: Option<String>,
+    message: Option<EntryMessage>,
+}
+
+#[derive(Deserialize)]
+struct EntryMessage {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<Usage>,
+}
+
+/// Usage inside the window, hour by hour
+#[derive(Debug, Default, Clone)]
+pub struct WeeklyUsage {
+    pub tokens: u64,
+    pub opus_tokens: u64,
+    /// Oldest first
+    hours: Vec<(u64, HourUsage)>,
+}
+
+impl WeeklyUsage {
+    /// Seconds until enough usage rolls out of the window to bring the
+    /// total, or the Opus total, under `cap`; `None` when it already is
+    pub fn under_cap_in(&self, cap: u64, opus: bool, now: u64) -> Option<u64> {
+        let tokens = |usage: &HourUsage| {
+            if opus {
+                usage.opus_tokens
+            } else {
+                usage.tokens
+            }
+        };
+        let mut total: u64 = self.hours.iter().map(|(_, usage)| tokens(usage)).sum();
+        for (hour, usage) in &self.hours {
+            if total < cap {
+                break;
+            }
+            total -= tokens(usage);
+            if total < cap {
+                return Some((hour + BUCKET_SECS + WINDOW_SECS).saturating_sub(now));
+            }
+        }
+        None
+    }
+}
+
+fn weekly_path() -> PathBuf {
+    crate::utils::paths::cache_dir().join("weekly_usage.json")
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Usage of the last seven days, tallying what the transcripts gained since
+/// the last call more than a minute ago
+pub fn usage() -> WeeklyUsage {
+    let path = weekly_path();
+    let now = now();
+    // The hour the window starts in counts whole
+    let start = now.saturating_sub(WINDOW_SECS);
+    let first_hour = start - start % BUCKET_SECS;
+
+    // Sessions render at the same time, and each must add what it read to
+    // what the others did
+    let file = crate::utils::atomic::locked(&path, || {
+        let mut file: WeeklyFile = fs::read(&path)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+        if now.saturating_sub(file.scanned_at) < RESCAN_SECS {
+            return file;
+        }
+
+        scan(&mut file, first_hour);
+        file.scanned_at = now;
+        file.hours = file.hours.split_off(&first_hour);
+        file.seen.retain(|_, hour| *hour >= first_hour);
+        if let Ok(json) = serde_json::to_vec(&file) {
+            let _ = crate::utils::atomic::write(&path, &json);
+        }
+        file
+    });
+
+    let hours: Vec<(u64, HourUsage)> = file
+        .hours
+        .into_iter()
+        .filter(|(hour, _)| *hour >= first_hour)
+        .collect();
+    WeeklyUsage {
+        tokens: hours.iter().map(|(_, usage)| usage.tokens).sum(),
+        opus_tokens: hours.iter().map(|(_, usage)| usage.opus_tokens).sum(),
+        hours,
+    }
+}
+
+/// Add what was appended to transcripts written since `start`
+fn scan(file: &mut WeeklyFile, start: u64) {
+    let model_config = ModelConfig::load();
+    let mut offsets = HashMap::new();
+
+    for path in summary::transcripts() {
+        let written = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .is_some_and(|modified| modified.as_secs() >= start);
+        if !written {
+            continue;
+        }
+
+        let key = path.to_string_lossy().into_owned();
+        let offset = file.offsets.get(&key).copied().unwrap_or(0);
+        // A transcript shorter than what was read was rewritten; reading it
+        // again is safe since counted messages are skipped
+        let Some((lines, offset)) =
+            transcript::appended(&path, offset).or_else(|| transcript::appended(&path, 0))
+        else {
+            continue;
+        };
+        offsets.insert(key, offset);
+
+        for line in lines.lines() {
+            let Ok(entry) = serde_json::from_str::<Entry>(line) else {
+                continue;
+            };
+            if entry.kind.as_deref() != Some("assistant") {
+                continue;
+            }
+            let (Some(message), Some(seconds)) = (
+                entry.message,
+                entry
+                    .timestamp
+                    .as_deref()
+                    .and_then(transcript::timestamp_seconds),
+            ) else {
+                continue;
+            };
+            let seconds = seconds as u64;
+            let model = message.model.unwrap_or_default();
+            let (Some(id), Some(usage)) = (message.id, message.usage) else {
+                continue;
+            };
+            if seconds < start || model == "<synthetic>" {
+                continue;
+            }
+
+            let hour = seconds - seconds % BUCKET_SECS;
+            if file.seen.insert(id, hour).is_some() {
+                continue;
+            }
+            let mut usage = usage.normalize();
+            if model_config
+                .entry(&model)
+                .is_some_and(|entry| entry.input_includes_cache)
+            {
+                usage = usage.without_cached_input();
+            }
+            let tokens = u64::from(usage.input_tokens)
+                + u64::from(usage.output_tokens)
+                + u64::from(usage.cache_creation_input_tokens)
+                + u64::from(usage.cache_read_input_tokens);
+
+            let bucket = file.hours.entry(hour).or_default();
+            bucket.tokens += tokens;
+            if model.to_ascii_lowercase().contains("opus") {
+                bucket.opus_tokens += tokens;
+            }
+        }
+    }
+    // Transcripts not written to within the window are forgotten
+    file.offsets = offsets;
+}
@@ -0,0 +1,501 @@
+//! Long-running renderer for `ccline --daemon`, and the `ccline --client`
+//! side that talks to it.
+//!
+//! The daemon listens on a Unix socket (a named pipe on Windows), keeps the
+//! parsed config and recent network segment results in memory, and answers
+//! every request with a rendered statusline. A request is a header line
+//! `ccline <version> <length>` followed by exactly `length` bytes of
+//! statusline JSON; the reply is `ok` on its own line followed by the
+//! rendered statusline. A daemon built from another version closes the
+//! connection without replying and exits, so an upgraded client starts a
+//! fresh one.
+
+use crate::config::{Config, InputData, SegmentConfig, SegmentId};
+use crate::core::captured_input;
+use crate::core::executor::{join_with_deadline, uncancelled, Task, NETWORK_DEADLINE};
+use crate::core::segments::{with_registry, SegmentData};
+use crate::core::statusline::{past_deadline, placeholder};
+use crate::core::{collect_segment, collect_segment_cached, StatusLineGenerator};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a network segment result is served before it is refreshed in
+/// the background, unless the segment sets `refresh_interval_ms`
+const NETWORK_REFRESH: Duration = Duration::from_secs(30);
+
+/// The daemon exits after this long without requests
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Largest payload the daemon accepts
+const MAX_PAYLOAD: usize = 4 * 1024 * 1024;
+
+/// How long the client waits for a reply before rendering on its own
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+struct CachedSegment {
+    data: Option<SegmentData>,
+    fetched_at: Instant,
+    refreshing: bool,
+}
+
+/// Network results are keyed by segment and working directory, since a
+/// network segment may report per-project data
+type NetworkKey = (SegmentId, String);
+type NetworkCache = Arc<Mutex<HashMap<NetworkKey, CachedSegment>>>;
+
+struct LoadedConfig {
+    config: Config,
+    modified: Option<SystemTime>,
+}
+
+struct Daemon {
+    config: Mutex<LoadedConfig>,
+    network: NetworkCache,
+    last_request: Mutex<Instant>,
+}
+
+impl Daemon {
+    fn new() -> Self {
+        Self {
+            config: Mutex::new(LoadedConfig {
+                config: Self::load_config(),
+                modified: Self::config_modified(),
+            }),
+            network: Arc::new(Mutex::new(HashMap::new())),
+            last_request: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn load_config() -> Config {
+        Config::load().unwrap_or_else(|_| Config::default())
+    }
+
+    fn config_modified() -> Option<SystemTime> {
+        std::fs::metadata(Config::get_config_path())
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+
+    /// The current config, reloaded when config.toml changed on disk.
+    /// Reloading drops cached network results, whose options may have changed.
+    fn config(&self) -> Config {
+        let modified = Self::config_modified();
+        let mut loaded = self.config.lock().unwrap_or_else(|e| e.into_inner());
+        if loaded.modified != modified {
+            loaded.config = Self::load_config();
+            loaded.modified = modified;
+            self.network
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+        }
+        loaded.config.clone()
+    }
+
+    fn render(&self, raw: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let input: InputData = serde_json::from_str(raw)?;
+        captured_input::save(raw);
+
+        let mut config = self.config();
+        #[cfg(feature = "http")]
+        crate::utils::http::configure(&config.network);
+        config.resolve_platform_defaults();
+        config.resolve_theme_overrides(&input);
+        let segments = self.collect(&config, &input);
+        #[cfg(feature = "report")]
+        crate::reporter::schedule(&input, &config.report);
+        crate::core::alerts::check(&config.alerts, &input, &segments);
+        let handoff = config.handoff.clone();
+        let statusline = StatusLineGenerator::new(config).generate(segments.clone());
+        crate::core::handoff::write(&handoff, &input, &segments, &statusline);
+        Ok(statusline)
+    }
+
+    /// Like [`crate::core::collect_all_segments`], but network segments are
+    /// served from memory: fresh results as they are, stale ones while a
+    /// refresh runs in the background. Only segments never collected before
+    /// wait for the network.
+    fn collect(&self, config: &Config, input: &InputData) -> Vec<(SegmentConfig, SegmentData)> {
+        crate::i18n::set_language(config.language.as_deref());
+        let segments = config.resolved_segments();
+        let mut collected: Vec<Option<SegmentData>> = vec![None; segments.len()];
+        let mut missing_tasks: Vec<Task<Option<Option<SegmentData>>>> = Vec::new();
+        let mut missing_slots = Vec::new();
+
+        for (index, segment_config) in segments.iter().enumerate() {
+            let is_network = with_registry(|registry| {
+                registry
+                    .info(segment_config.id)
+                    .map(|info| info.network)
+                    .unwrap_or(false)
+            });
+            if !is_network {
+                collected[index] = collect_segment_cached(segment_config, input);
+                continue;
+            }
+            let refresh = segment_config
+                .refresh_interval_ms
+                .map(Duration::from_millis)
+                .unwrap_or(NETWORK_REFRESH);
+
+            let key = (segment_config.id, input.workspace.current_dir.clone());
+            let mut cache = self.network.lock().unwrap_or_else(|e| e.into_inner());
+            match cache.get_mut(&key) {
+                Some(entry) => {
+                    collected[index] = entry.data.clone();
+                    if entry.fetched_at.elapsed() >= refresh && !entry.refreshing {
+                        entry.refreshing = true;
+                        let task = self.refresh_task(key, segment_config, input);
+                        std::thread::spawn(task);
+                    }
+                }
+                None => {
+                    // Mark as in flight so concurrent requests do not fetch too
+                    cache.insert(
+                        key.clone(),
+                        CachedSegment {
+                            data: None,
+                            fetched_at: Instant::now(),
+                            refreshing: true,
+                        },
+                    );
+                    missing_tasks.push(uncancelled(Box::new(self.refresh_task(
+                        key,
+                        segment_config,
+                        input,
+                    ))));
+                    missing_slots.push(index);
+                }
+            }
+        }
+
+        // A task that misses the deadline is not cancelled, and still stores
+        // its result when it finishes
+        let results = join_with_deadline(missing_tasks, NETWORK_DEADLINE);
+        for (slot, result) in missing_slots.into_iter().zip(results) {
+            collected[slot] = match result.flatten() {
+                Some(data) => data,
+                None => past_deadline(&segments[slot], input),
+            };
+        }
+        crate::core::segments::computed::fill(&segments, &mut collected);
+
+        segments
+            .into_iter()
+            .zip(collected)
+            .filter_map(|(segment_config, data)| {
+                let data = data.or_else(|| placeholder(&segment_config))?;
+                Some((segment_config, data))
+            })
+            .collect()
+    }
+
+    /// Collect one network segment and store the result in the cache
+    fn refresh_task(
+        &self,
+        key: NetworkKey,
+        segment_config: &SegmentConfig,
+        input: &InputData,
+    ) -> impl FnOnce() -> Option<SegmentData> + Send + 'static {
+        let cache = Arc::clone(&self.network);
+        let segment_config = segment_config.clone();
+        let input = input.clone();
+        move || {
+            let data = collect_segment(&segment_config, &input);
+            cache.lock().unwrap_or_else(|e| e.into_inner()).insert(
+                key,
+                CachedSegment {
+                    data: data.clone(),
+                    fetched_at: Instant::now(),
+                    refreshing: false,
+                },
+            );
+            data
+        }
+    }
+
+    /// Answer one connection. Returns false when the client runs another
+    /// version and the daemon should make way for a new one.
+    fn handle<S: Read + Write>(&self, stream: S) -> bool {
+        *self.last_request.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+
+        let mut reader = BufReader::new(stream);
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() {
+            return true;
+        }
+        let mut fields = header.split_whitespace();
+        if fields.next() != Some("ccline") {
+            return true;
+        }
+        if fields.next() != Some(env!("CARGO_PKG_VERSION")) {
+            return false;
+        }
+        let Some(length) = fields
+            .next()
+            .and_then(|length| length.parse::<usize>().ok())
+            .filter(|length| *length <= MAX_PAYLOAD)
+        else {
+            return true;
+        };
+
+        let mut payload = vec![0; length];
+        if reader.read_exact(&mut payload).is_err() {
+            return true;
+        }
+        let Ok(raw) = String::from_utf8(payload) else {
+            return true;
+        };
+
+        // A payload that does not parse gets no reply; the client renders
+        // it itself and reports the error
+        if let Ok(statusline) = self.render(&raw) {
+            let mut stream = reader.into_inner();
+            let _ = write!(stream, "ok\n{}", statusline);
+            let _ = stream.flush();
+        }
+        true
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_request
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .elapsed()
+    }
+}
+
+/// Exit once no request arrived for [`IDLE_TIMEOUT`], so daemons started
+/// on demand by `--client` do not linger
+fn spawn_idle_watchdog(daemon: Arc<Daemon>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(60));
+        if daemon.idle_for() >= IDLE_TIMEOUT {
+            shutdown();
+        }
+    });
+}
+
+fn shutdown() -> ! {
+    #[cfg(unix)]
+    let _ = std::fs::remove_file(socket_path());
+    std::process::exit(0);
+}
+
+fn request_header(raw: &str) -> String {
+    format!("ccline {} {}\n", env!("CARGO_PKG_VERSION"), raw.len())
+}
+
+/// Split a daemon reply into the statusline, or `None` when the daemon did not render
+fn parse_reply(reply: &str) -> Option<String> {
+    reply.strip_prefix("ok\n").map(str::to_string)
+}
+
+/// Send one request over `stream` and read the statusline back
+fn exchange<S: Read + Write>(mut stream: S, raw: &str) -> Option<String> {
+    stream.write_all(request_header(raw).as_bytes()).ok()?;
+    stream.write_all(raw.as_bytes()).ok()?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).ok()?;
+    parse_reply(&reply)
+}
+
+/// What [`request`] got from the daemon
+pub enum Reply {
+    Rendered(String),
+    /// A daemon took the request but did not render it in time, or at all
+    NoAnswer,
+    /// Nothing is listening; start a daemon for the next render
+    NotRunning,
+}
+
+/// Start a daemon in the background for later renders
+pub fn spawn_daemon() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::process::Command::new(exe)
+            .arg("--daemon")
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+    }
+}
+
+/// Where the daemon listens: `daemon/daemon.sock` in the state directory.
+/// The `daemon` directory is private to the user, so the socket is never
+/// reachable by others, even before its own mode is set.
+#[cfg(unix)]
+pub fn socket_path() -> std::path::PathBuf {
+    crate::utils::paths::state_dir()
+        .join("daemon")
+        .join("daemon.sock")
+}
+
+/// Run the daemon in the foreground until it has been idle for an hour
+#[cfg(unix)]
+pub fn serve() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = socket_path();
+    if UnixStream::connect(&path).is_ok() {
+        return Err(format!("A daemon is already listening on {}", path.display()).into());
+    }
+    // Left behind by a daemon that did not shut down cleanly
+    let _ = std::fs::remove_file(&path);
+    if let Some(parent) = path.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(parent)?;
+        std::fs::set_permissions(parent, std::fs::Permissions::from_mode(0o700))?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    let daemon = Arc::new(Daemon::new());
+    spawn_idle_watchdog(Arc::clone(&daemon));
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let daemon = Arc::clone(&daemon);
+        std::thread::spawn(move || {
+            if !daemon.handle(stream) {
+                shutdown();
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Render through a running daemon, waiting at most [`CLIENT_TIMEOUT`]
+#[cfg(unix)]
+pub fn request(raw: &str) -> Reply {
+    use std::os::unix::net::UnixStream;
+
+    let Ok(stream) = UnixStream::connect(socket_path()) else {
+        return Reply::NotRunning;
+    };
+    let timeouts_set = stream.set_read_timeout(Some(CLIENT_TIMEOUT)).is_ok()
+        && stream.set_write_timeout(Some(CLIENT_TIMEOUT)).is_ok();
+    match timeouts_set.then(|| exchange(stream, raw)).flatten() {
+        Some(statusline) => Reply::Rendered(statusline),
+        None => Reply::NoAnswer,
+    }
+}
+
+/// Where the daemon listens: a per-user named pipe
+#[cfg(windows)]
+pub fn pipe_name() -> String {
+    let user = std::env::var("USERNAME").unwrap_or_else(|_| "default".to_string());
+    format!(r"\\.\pipe\ccline-{}", user)
+}
+
+/// Run the daemon in the foreground until it has been idle for an hour
+#[cfg(windows)]
+pub fn serve() -> Result<(), Box<dyn std::error::Error>> {
+    use std::fs::File;
+    use std::os::windows::io::FromRawHandle;
+    use windows_sys::Win32::Foundation::{
+        GetLastError, ERROR_PIPE_CONNECTED, INVALID_HANDLE_VALUE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX,
+    };
+    use windows_sys::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE,
+        PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+    };
+
+    let name: Vec<u16> = pipe_name().encode_utf16().chain(Some(0)).collect();
+    let daemon = Arc::new(Daemon::new());
+    spawn_idle_watchdog(Arc::clone(&daemon));
+
+    let mut first = true;
+    loop {
+        let open_mode = if first {
+            PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE
+        } else {
+            PIPE_ACCESS_DUPLEX
+        };
+        // SAFETY: `name` is NUL-terminated and outlives the call; a null
+        // security descriptor gives the default ACL (owner and SYSTEM)
+        let handle = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                open_mode,
+                PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                64 * 1024,
+                64 * 1024,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            if first {
+                return Err(format!("A daemon is already listening on {}", pipe_name()).into());
+            }
+            return Err(std::io::Error::last_os_error().into());
+        }
+        first = false;
+
+        // SAFETY: `handle` is a valid pipe instance owned by no one else
+        let connected = unsafe {
+            ConnectNamedPipe(handle, std::ptr::null_mut()) != 0
+                || GetLastError() == ERROR_PIPE_CONNECTED
+        };
+        // SAFETY: the File takes ownership and closes the handle on drop
+        let pipe = unsafe { File::from_raw_handle(handle as _) };
+        if !connected {
+            continue;
+        }
+
+        let daemon = Arc::clone(&daemon);
+        std::thread::spawn(move || {
+            if !daemon.handle(&pipe) {
+                shutdown();
+            }
+            let _ = pipe.sync_all();
+        });
+    }
+}
+
+/// Render through a running daemon, waiting at most [`CLIENT_TIMEOUT`].
+/// Pipes have no read timeout, so the exchange runs on a worker thread
+/// that is abandoned when the daemon stalls.
+#[cfg(windows)]
+pub fn request(raw: &str) -> Reply {
+    let Ok(pipe) = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(pipe_name())
+    else {
+        return Reply::NotRunning;
+    };
+
+    let raw = raw.to_string();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(exchange(pipe, &raw));
+    });
+    match receiver.recv_timeout(CLIENT_TIMEOUT) {
+        Ok(Some(statusline)) => Reply::Rendered(statusline),
+        _ => Reply::NoAnswer,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn serve() -> Result<(), Box<dyn std::error::Error>> {
+    Err("The daemon needs Unix sockets or Windows named pipes".into())
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn request(_raw: &str) -> Reply {
+    Reply::NoAnswer
+}
@@ -0,0 +1,56 @@
+//! Why a segment failed to collect. A failed segment is not simply left
+//! out: its `on_error` option decides whether it is hidden, shown as "—" or
+//! as an error icon, and the error travels in its metadata as `error` and
+//! `error_kind`.
+
+use std::io;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An API key or sign-in the segment needs is not there
+    #[error("no credentials: {0}")]
+    MissingCredentials(&'static str),
+    /// The request did not get an answer
+    #[error("request failed: {0}")]
+    Network(String),
+    /// The server answered with an error status
+    #[error("HTTP {0}")]
+    Status(u16),
+    /// The answer is not what was expected
+    #[error("unexpected response: {0}")]
+    Parse(String),
+    /// A file or command the segment reads could not be read or run
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// No segment of that id is registered
+    #[error("unknown segment: {0}")]
+    UnknownSegment(String),
+    /// The source did not answer before the render deadline
+    #[error("no answer in time")]
+    Timeout,
+}
+
+impl Error {
+    /// Short name of the variant, for the `error_kind` metadata
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::MissingCredentials(_) => "credentials",
+            Error::Network(_) => "network",
+            Error::Status(_) => "status",
+            Error::Parse(_) => "parse",
+            Error::Io(_) => "io",
+            Error::UnknownSegment(_) => "unknown_segment",
+            Error::Timeout => "timeout",
+        }
+    }
+}
+
+#[cfg(feature = "quota")]
+impl From<ureq::Error> for Error {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(code, _) => Error::Status(code),
+            ureq::Error::Transport(transport) => Error::Network(transport.to_string()),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Translations of the words segments print: status words, units and short
+//! labels. English and Simplified Chinese are built in; any entry can be
+//! replaced, and other languages added, in
+//! `~/.claude/ccline/locales/<language>.toml`:
+//!
+//! ```toml
+//! "git.detached" = "détachée"
+//! "cost.projected_today" = "→ {} aujourd'hui"
+//! ```
+//!
+//! `{}` in a text is replaced by its arguments in order.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+
+const EN: &[(&str, &str)] = &[
+    ("quota.resets", "resets {}"),
+    ("quota.rate_limit", "{} {} left"),
+    ("quota.rate_limited", "Rate limited"),
+    ("quota.retry_in", "retry in {}"),
+    ("usage.tokens", "{} tokens"),
+    ("usage.of_limit", "{}% of {}"),
+    ("cost.projected_today", "→ {} today"),
+    ("cost.api_duration", "API {}"),
+    ("cost.wall_duration", "wall {}"),
+    ("cost.project_total", "this repo: {} lifetime"),
+    ("plan.weekly_left", "{}% left this week"),
+    ("plan.time_left", "≈ {} left at current pace"),
+    ("plan.weekly_cap", "{}% of weekly cap"),
+    ("plan.weekly_opus_cap", "Opus {}% of weekly cap"),
+    ("plan.under_cap_in", "under cap in {}"),
+    ("update.ready", "Update v{}!"),
+    ("update.installing", "Installing..."),
+    ("update.completed", "Updated v{}!"),
+    ("update.behind", "{} behind"),
+    ("git.detached", "detached"),
+    ("identity.key", "key"),
+    ("identity.account", "account"),
+    ("container.host", "host"),
+    ("attention.permission", "Approve {}"),
+    ("attention.question", "Question waiting"),
+    ("attention.plan", "Review plan"),
+    ("attention.interrupted", "Interrupted"),
+    ("memory.files", "{} CLAUDE.md"),
+    ("hooks.ok", "ok"),
+    ("hooks.failed", "failed"),
+    ("hooks.failed_code", "failed ({})"),
+    ("hooks.blocked", "blocked"),
+    ("memory.kb", "{} KB"),
+    ("transcript.messages", "{} msgs"),
+    ("transcript.mb", "{} MB"),
+    ("modes.bypass", "YOLO"),
+    ("modes.accept_edits", "Auto-edit"),
+    ("modes.plan", "Plan"),
+    ("modes.no_auto_compact", "No auto-compact"),
+    ("duration.ms", "{}ms"),
+    ("duration.s", "{}s"),
+    ("duration.m", "{}m"),
+    ("duration.m_s", "{}m{}s"),
+    ("duration.h", "{}h"),
+    ("duration.h_m", "{}h{}m"),
+    ("session.since", "since {}"),
+    ("a11y.git_branch", "Git branch {}"),
+    ("a11y.git_clean", "clean"),
+    ("a11y.git_dirty", "uncommitted changes"),
+    ("a11y.git_conflicts", "merge conflicts"),
+    ("a11y.ahead", "{} ahead"),
+    ("a11y.behind", "{} behind"),
+];
+
+const ZH_CN: &[(&str, &str)] = &[
+    ("quota.resets", "{}后重置"),
+    ("quota.rate_limit", "{}剩余 {}"),
+    ("quota.rate_limited", "已限流"),
+    ("quota.retry_in", "{}后重试"),
+    ("usage.tokens", "{} tokens"),
+    ("usage.of_limit", "{}% / {}"),
+    ("cost.projected_today", "→ 今日预计 {}"),
+    ("cost.api_duration", "API {}"),
+    ("cost.wall_duration", "总时长 {}"),
+    ("cost.project_total", "本项目累计 {}"),
+    ("plan.weekly_left", "本周剩余 {}%"),
+    ("plan.time_left", "按当前速度约剩 {}"),
+    ("plan.weekly_cap", "已用每周上限的 {}%"),
+    ("plan.weekly_opus_cap", "Opus 已用每周上限的 {}%"),
+    ("plan.under_cap_in", "{}后回落到上限以下"),
+    ("update.ready", "可更新 v{}"),
+    ("update.installing", "安装中..."),
+    ("update.completed", "已更新 v{}"),
+    ("update.behind", "落后 {} 个版本"),
+    ("git.detached", "分离头指针"),
+    ("identity.key", "密钥"),
+    ("identity.account", "账号"),
+    ("container.host", "主机"),
+    ("attention.permission", "待批准 {}"),
+    ("attention.question", "等待回答"),
+    ("attention.plan", "待审阅计划"),
+    ("attention.interrupted", "已中断"),
+    ("memory.files", "{} 个 CLAUDE.md"),
+    ("hooks.ok", "成功"),
+    ("hooks.failed", "失败"),
+    ("hooks.failed_code", "失败（{}）"),
+    ("hooks.blocked", "已拦截"),
+    ("memory.kb", "{} KB"),
+    ("transcript.messages", "{} 条消息"),
+    ("transcript.mb", "{} MB"),
+    ("modes.bypass", "YOLO"),
+    ("modes.accept_edits", "自动编辑"),
+    ("modes.plan", "计划模式"),
+    ("modes.no_auto_compact", "自动压缩已关闭"),
+    ("duration.ms", "{}毫秒"),
+    ("duration.s", "{}秒"),
+    ("duration.m", "{}分"),
+    ("duration.m_s", "{}分{}秒"),
+    ("duration.h", "{}小时"),
+    ("duration.h_m", "{}小时{}分"),
+    ("session.since", "{}开始"),
+    ("a11y.git_branch", "Git 分支 {}"),
+    ("a11y.git_clean", "无改动"),
+    ("a11y.git_dirty", "有未提交的改动"),
+    ("a11y.git_conflicts", "有合并冲突"),
+    ("a11y.ahead", "领先 {} 个提交"),
+    ("a11y.behind", "落后 {} 个提交"),
+];
+
+/// The language in effect and its user overrides
+struct Catalog {
+    language: String,
+    overrides: HashMap<String, String>,
+}
+
+fn catalog() -> &'static RwLock<Catalog> {
+    static CATALOG: OnceLock<RwLock<Catalog>> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let language = resolve(None);
+        RwLock::new(Catalog {
+            overrides: load_overrides(&language),
+            language,
+        })
+    })
+}
+
+/// Language code for the `language` config value: "en", "zh-CN", or, unset
+/// or "auto", the language of `LC_ALL` / `LC_MESSAGES` / `LANG`
+fn resolve(configured: Option<&str>) -> String {
+    let requested = match configured.map(str::trim) {
+        Some(value) if !value.is_empty() && !value.eq_ignore_ascii_case("auto") => {
+            value.to_string()
+        }
+        _ => ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default(),
+    };
+
+    // "zh_CN.UTF-8" and "zh-cn" both name Simplified Chinese
+    let requested = requested.split(['.', '@']).next().unwrap_or("");
+    let mut parts = requested.split(['-', '_']);
+    let language = parts.next().unwrap_or("").to_ascii_lowercase();
+    let region = parts.next().unwrap_or("").to_ascii_uppercase();
+    match (language.as_str(), region.as_str()) {
+        ("" | "c" | "posix", _) => "en".to_string(),
+        ("zh", "TW" | "HK" | "MO") => format!("zh-{}", region),
+        ("zh", _) => "zh-CN".to_string(),
+        (language, "") => language.to_string(),
+        (language, region) => format!("{}-{}", language, region),
+    }
+}
+
+fn locales_dir() -> PathBuf {
+    crate::utils::paths::config_dir().join("locales")
+}
+
+/// Entries of `<language>.toml`, falling back to the file of the bare
+/// language ("pt" for "pt-BR"); an unreadable file is ignored
+fn load_overrides(language: &str) -> HashMap<String, String> {
+    let dir = locales_dir();
+    let bare = language.split('-').next().unwrap_or(language);
+    [language, bare]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(dir.join(format!("{}.toml", name))).ok())
+        .and_then(|text| toml::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Select the language from the `language` config value. Cheap when it is
+/// unchanged, so it can run before every render.
+pub fn set_language(configured: Option<&str>) {
+    let language = resolve(configured);
+    if catalog()
+        .read()
+        .map(|catalog| catalog.language == language)
+        .unwrap_or(false)
+    {
+        return;
+    }
+    let overrides = load_overrides(&language);
+    let mut catalog = catalog()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *catalog = Catalog {
+        language,
+        overrides,
+    };
+}
+
+/// The text for `key` in the language in effect: a user override, the
+/// built-in translation, or the English text
+pub fn text(key: &str) -> String {
+    let catalog = catalog()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(text) = catalog.overrides.get(key) {
+        return text.clone();
+    }
+    let builtin = match catalog.language.as_str() {
+        "zh-CN" => ZH_CN,
+        _ => EN,
+    };
+    builtin
+        .iter()
+        .chain(EN)
+        .find(|(name, _)| *name == key)
+        .map(|(_, text)| text.to_string())
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// [`text`] with each `{}` replaced by the next of `args`
+pub fn text_with(key: &str, args: &[&str]) -> String {
+    let template = text(key);
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut pieces = template.split("{}");
+    if let Some(first) = pieces.next() {
+        out.push_str(first);
+    }
+    for piece in pieces {
+        out.push_str(args.next().copied().unwrap_or(""));
+        out.push_str(piece);
+    }
+    out
+}
@@ -1,8 +1,63 @@
+//! CCometixLine statusline pipeline as a library.
+//!
+//! The binary reads Claude Code's statusline JSON from stdin, collects data for
+//! every configured segment and renders a single ANSI line. The same pipeline is
+//! available to other tools (tmux plugins, GUI wrappers) through [`collect`] and
+//! [`render`]:
+//!
+//! ```no_run
+//! use ccometixline_packycc::{collect, parse_input, render, Config};
+//!
+//! let input = parse_input(r#"{
+//!     "model": { "id": "claude-sonnet-4", "display_name": "Sonnet 4" },
+//!     "workspace": { "current_dir": "/tmp" },
+//!     "transcript_path": "/tmp/session.jsonl"
+//! }"#)?;
+//! let config = Config::load()?;
+//!
+//! let segments = collect(&input, &config);
+//! println!("{}", render(&config, segments));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
 pub mod cli;
+pub mod commands;
 pub mod config;
 pub mod core;
+pub mod daemon;
+pub mod error;
+pub mod i18n;
 pub mod ui;
 pub mod utils;
 
+#[cfg(feature = "report")]
+pub mod reporter;
 #[cfg(feature = "self-update")]
 pub mod updater;
+
+pub use config::{Config, InputData, SegmentConfig, SegmentId};
+pub use core::segments::SegmentData;
+pub use core::StatusLineGenerator;
+
+/// Parse a statusline payload in the format Claude Code writes to stdin
+pub fn parse_input(json: &str) -> Result<InputData, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// Collect data for every segment in `config`, in config order.
+///
+/// Segments that have nothing to show are omitted; disabled segments are still
+/// collected and filtered out by [`render`].
+pub fn collect(input: &InputData, config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+    core::collect_all_segments(config, input)
+}
+
+/// Render collected segments into a single ANSI-colored statusline
+pub fn render(config: &Config, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
+    StatusLineGenerator::new(config.clone()).generate(segments)
+}
+
+/// Collect and render in one step
+pub fn render_input(input: &InputData, config: &Config) -> String {
+    render(config, collect(input, config))
+}
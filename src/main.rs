@@ -1,14 +1,84 @@
-use ccometixline_packycc::cli::Cli;
-use ccometixline_packycc::config::{Config, InputData};
-use ccometixline_packycc::core::{collect_all_segments, StatusLineGenerator};
-use std::io::{self, IsTerminal};
+use ccometixline_packycc::cli::{CacheAction, Cli, Commands, SegmentsAction, ThemeAction};
+use ccometixline_packycc::config::{Config, InputData, StyleMode};
+use ccometixline_packycc::core::output::{self, OutputFormat};
+use ccometixline_packycc::core::{captured_input, session_cache, StatusLineGenerator};
+use ccometixline_packycc::utils::console::Renderer;
+use ccometixline_packycc::utils::redact::redact;
+use std::io::{self, IsTerminal, Read};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() {
+    // Errors can carry URLs and response bodies; mask credentials before
+    // they reach the terminal or a pasted bug report
+    if let Err(error) = run() {
+        eprintln!("Error: {}", redact(&format!("{:?}", error)));
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse_args();
 
+    if let Some(command) = cli.command {
+        let config = Config::load().unwrap_or_else(|_| Config::default());
+        #[cfg(feature = "http")]
+        ccometixline_packycc::utils::http::configure(&config.network);
+        match command {
+            Commands::Segments { action } => match action {
+                SegmentsAction::List { json } => {
+                    ccometixline_packycc::commands::segments::list(&config, json)?
+                }
+            },
+            Commands::Theme { action } => match action {
+                ThemeAction::Preview {
+                    name,
+                    nerd_font,
+                    plain,
+                } => {
+                    let mode = if plain {
+                        Some(StyleMode::Plain)
+                    } else if nerd_font {
+                        Some(StyleMode::NerdFont)
+                    } else {
+                        None
+                    };
+                    ccometixline_packycc::commands::theme::preview(name.as_deref(), mode)?
+                }
+                ThemeAction::Check { name } => {
+                    ccometixline_packycc::commands::theme::check(name.as_deref())?
+                }
+                ThemeAction::Create { name } => {
+                    ccometixline_packycc::commands::theme::create(name.as_deref())?
+                }
+            },
+            Commands::Report { session, format } => {
+                ccometixline_packycc::commands::report::run(&config, &session, format)?
+            }
+            Commands::Cache { action } => match action {
+                CacheAction::Stats { json } => {
+                    ccometixline_packycc::commands::cache::stats(&config.cache, json)?
+                }
+                CacheAction::Prune => ccometixline_packycc::commands::cache::prune(&config.cache)?,
+            },
+            Commands::Balance { json } => {
+                ccometixline_packycc::commands::balance::run(&config, json)?
+            }
+            Commands::Health { segments } => {
+                // Configuration errors count as failed checks, not degraded ones
+                let code = ccometixline_packycc::commands::health::run(&config, &segments)
+                    .unwrap_or_else(|error| {
+                        eprintln!("Error: {}", redact(&error.to_string()));
+                        2
+                    });
+                std::process::exit(code);
+            }
+            Commands::Paths { migrate } => ccometixline_packycc::commands::paths::run(migrate)?,
+        }
+        return Ok(());
+    }
+
     // Handle configuration commands
     if cli.init {
-        Config::init()?;
+        ccometixline_packycc::commands::init::run(cli.statusline_command.as_deref())?;
         return Ok(());
     }
 
@@ -31,31 +101,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    #[cfg(feature = "tui")]
     if cli.config {
-        #[cfg(feature = "tui")]
-        {
-            ccometixline_packycc::ui::run_configurator()?;
-        }
-        #[cfg(not(feature = "tui"))]
-        {
-            eprintln!("TUI feature is not enabled. Please install with --features tui");
-            std::process::exit(1);
-        }
+        let input = captured_input::preview_input(cli.input.as_deref())?;
+        ccometixline_packycc::ui::run_configurator(input)?;
         return Ok(());
     }
 
+    #[cfg(feature = "self-update")]
+    if cli.check_update_background {
+        ccometixline_packycc::updater::UpdateState::run_check();
+        return Ok(());
+    }
+
+    #[cfg(feature = "report")]
+    if cli.report_background {
+        ccometixline_packycc::reporter::run_upload();
+        return Ok(());
+    }
+
+    #[cfg(feature = "self-update")]
     if cli.update {
-        #[cfg(feature = "self-update")]
-        {
-            println!("Update feature not implemented in new architecture yet");
-        }
-        #[cfg(not(feature = "self-update"))]
-        {
-            println!("Update check not available (self-update feature disabled)");
+        let config = Config::load().unwrap_or_else(|_| Config::default());
+        ccometixline_packycc::utils::http::configure(&config.network);
+        match ccometixline_packycc::updater::download_latest(&config.update)? {
+            Some((version, path)) => {
+                println!("✓ Downloaded and verified v{}: {}", version, path.display());
+                println!("Extract it over your ccline binary, or run:");
+                println!("  npm update -g @ding113/ccline-packycc");
+            }
+            None => println!("✓ ccline v{} is up to date", env!("CARGO_PKG_VERSION")),
         }
         return Ok(());
     }
 
+    if cli.daemon {
+        ccometixline_packycc::daemon::serve()?;
+        return Ok(());
+    }
+
     // Handle Claude Code patcher
     if let Some(claude_path) = cli.patch {
         use ccometixline_packycc::utils::ClaudeCodePatcher;
@@ -91,16 +175,60 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    // Load configuration
-    let mut config = Config::load().unwrap_or_else(|_| Config::default());
+    // Load configuration, reusing the previous render's parse when unchanged
+    let mut config = session_cache::load_config().unwrap_or_else(|_| Config::default());
 
     // Apply theme override if provided
-    if let Some(theme) = cli.theme {
-        config = ccometixline_packycc::ui::themes::ThemePresets::get_theme(&theme);
+    if let Some(theme) = &cli.theme {
+        config = config.with_theme(ccometixline_packycc::ui::themes::ThemePresets::get_theme(
+            theme,
+        ));
+    }
+
+    // One-off segment overrides
+    config.override_segments(&cli.enable, &cli.disable)?;
+    #[cfg(feature = "http")]
+    ccometixline_packycc::utils::http::configure(&config.network);
+
+    // Accessible mode in the config stands in for --output a11y
+    let output_format = if cli.output == OutputFormat::Ansi && config.style.accessible {
+        OutputFormat::A11y
+    } else {
+        cli.output
+    };
+
+    // Render a mock scenario instead of live input
+    if let Some(scenario) = cli.mock {
+        use ccometixline_packycc::core::mock::MockScenario;
+
+        if scenario == "list" {
+            for (name, description) in MockScenario::list_available() {
+                println!("{:<16} {}", name, description);
+            }
+            return Ok(());
+        }
+
+        let scenario = MockScenario::load(&scenario)?;
+        config.resolve_platform_defaults();
+        config.resolve_theme_overrides(&scenario.input);
+        let segments_data = scenario.collect(&config);
+        let generator = StatusLineGenerator::new(config);
+        println!(
+            "{}",
+            Renderer::init().finish(generator.generate(segments_data))
+        );
+        return Ok(());
+    }
+
+    // Render a payload saved to a file
+    if let Some(path) = cli.input {
+        let input = captured_input::load_file(&path)?;
+        println!("{}", render_output(&input, &config, output_format));
+        return Ok(());
     }
 
-    // Check if stdin has data
-    if io::stdin().is_terminal() {
+    // Check if stdin has data; desktop bars run ccline without any
+    if io::stdin().is_terminal() && !output_format.is_widget() {
         // No input data available, show main menu
         #[cfg(feature = "tui")]
         {
@@ -109,7 +237,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             if let Some(result) = MainMenu::run()? {
                 match result {
                     MenuResult::LaunchConfigurator => {
-                        ccometixline_packycc::ui::run_configurator()?;
+                        let input = captured_input::preview_input(None)?;
+                        ccometixline_packycc::ui::run_configurator(input)?;
                     }
                     MenuResult::InitConfig => {
                         ccometixline_packycc::config::Config::init()?;
@@ -136,17 +265,71 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Read Claude Code data from stdin
-    let stdin = io::stdin();
-    let input: InputData = serde_json::from_reader(stdin.lock())?;
+    let mut raw = String::new();
+    if !io::stdin().is_terminal() {
+        io::stdin().read_to_string(&mut raw)?;
+    }
 
-    // Collect segment data
-    let segments_data = collect_all_segments(&config, &input);
+    // Bars render the last payload Claude Code sent
+    if output_format.is_widget() && raw.trim().is_empty() {
+        match captured_input::load_last() {
+            Some(input) => println!("{}", render_output(&input, &config, output_format)),
+            None => println!("{}", output::empty(output_format)),
+        }
+        session_cache::flush();
+        return Ok(());
+    }
 
-    // Render statusline
-    let generator = StatusLineGenerator::new(config);
-    let statusline = generator.generate(segments_data);
+    // The daemon renders with its own copy of config.toml, so --theme,
+    // segment overrides and other output formats are always rendered here.
+    // So are sample renders, which the daemon would capture.
+    if cli.client
+        && captured_input::enabled()
+        && cli.theme.is_none()
+        && cli.enable.is_empty()
+        && cli.disable.is_empty()
+        && output_format == OutputFormat::Ansi
+    {
+        use ccometixline_packycc::daemon;
 
-    println!("{}", statusline);
+        match daemon::request(&raw) {
+            daemon::Reply::Rendered(statusline) => {
+                println!("{}", Renderer::init().finish(statusline));
+                return Ok(());
+            }
+            daemon::Reply::NotRunning => daemon::spawn_daemon(),
+            daemon::Reply::NoAnswer => {}
+        }
+    }
+
+    let input: InputData = serde_json::from_str(&raw)?;
+    if captured_input::enabled() {
+        captured_input::save(&raw);
+    }
+
+    println!("{}", render_output(&input, &config, output_format));
+    #[cfg(feature = "report")]
+    ccometixline_packycc::reporter::schedule(&input, &config.report);
+    session_cache::flush();
+    ccometixline_packycc::core::cache_gc::maybe_prune(&config.cache);
 
     Ok(())
 }
+
+/// Collect and render `input`, update the hand-off file and format the
+/// result for `format`
+fn render_output(input: &InputData, config: &Config, format: OutputFormat) -> String {
+    let mut config = config.clone();
+    config.resolve_platform_defaults();
+    config.resolve_theme_overrides(input);
+    let config = &config;
+    let segments = ccometixline_packycc::collect(input, config);
+    let statusline = ccometixline_packycc::render(config, segments.clone());
+    ccometixline_packycc::core::alerts::check(&config.alerts, input, &segments);
+    ccometixline_packycc::core::handoff::write(&config.handoff, input, &segments, &statusline);
+
+    match format {
+        OutputFormat::Ansi => Renderer::init().finish(statusline),
+        format => output::format(format, &segments, &statusline),
+    }
+}
@@ -0,0 +1,379 @@
+//! Opt-in team usage reporting, configured under `[report]` in config.toml.
+//!
+//! Rendering only queues work: when a session is due, a small record is
+//! written to `~/.claude/ccline/reports/` and a detached `ccline
+//! --report-background` process builds the summary and POSTs it. Records that
+//! could not be delivered stay queued and are retried by the next upload.
+//!
+//! A summary holds the model, token totals, cost, duration and line counts of
+//! one session. Projects and sessions are identified by SHA-256 hashes, the
+//! machine by a random id; paths, prompts and file contents are never sent.
+//! Summaries are cumulative, so a later summary of a session replaces earlier
+//! ones.
+
+use crate::config::{InputData, ReportConfig, TranscriptEntry};
+use crate::core::session_cache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const BACKGROUND_REPORT_FLAG: &str = "--report-background";
+
+/// Version of the JSON document POSTed to the endpoint
+const SCHEMA_VERSION: u32 = 1;
+
+/// An upload lock older than this belongs to a process that died
+const STALE_LOCK: Duration = Duration::from_secs(120);
+
+/// What the render left for the uploader; stays on this machine
+#[derive(Debug, Serialize, Deserialize)]
+struct QueuedReport {
+    transcript_path: String,
+    working_dir: String,
+    model_id: String,
+    cost_usd: Option<f64>,
+    duration_ms: Option<u64>,
+    lines_added: Option<u32>,
+    lines_removed: Option<u32>,
+    queued_at: u64,
+}
+
+/// The document sent to the endpoint
+#[derive(Debug, Serialize)]
+pub struct SessionReport {
+    pub schema: u32,
+    /// Random id of this machine's ccline install
+    pub reporter: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    /// SHA-256 of the Claude Code session id
+    pub session: String,
+    /// SHA-256 of the git remote URL, or of the directory outside a repository
+    pub project: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cost_usd: Option<f64>,
+    pub duration_ms: Option<u64>,
+    pub lines_added: Option<u32>,
+    pub lines_removed: Option<u32>,
+    pub ccline_version: &'static str,
+    /// Unix seconds when the session was last seen by a render
+    pub reported_at: u64,
+}
+
+fn ccline_dir() -> PathBuf {
+    crate::utils::paths::state_dir()
+}
+
+fn queue_dir() -> PathBuf {
+    ccline_dir().join("reports")
+}
+
+fn sha256_hex(value: &str) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Session id as Claude Code names it: the transcript file name
+fn session_id(transcript_path: &str) -> &str {
+    Path::new(transcript_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(transcript_path)
+}
+
+/// Queue a report of the session behind `input` when reporting is enabled
+/// and the session was not queued within `interval_minutes`, then start an
+/// upload in a detached process. Never blocks on the network.
+pub fn schedule(input: &InputData, config: &ReportConfig) {
+    if !config.enabled || config.validate().is_err() || input.transcript_path.is_empty() {
+        return;
+    }
+
+    let interval = Duration::from_secs(config.interval_minutes.saturating_mul(60));
+    let due = session_cache::with_session(&input.transcript_path, |state| {
+        state
+            .reported_at
+            .is_none_or(|at| at.elapsed().map_or(true, |age| age >= interval))
+    });
+    if !due {
+        return;
+    }
+
+    let cost = input.cost.as_ref();
+    let queued = QueuedReport {
+        transcript_path: input.transcript_path.clone(),
+        working_dir: input.workspace.current_dir.clone(),
+        model_id: input.model.id.clone(),
+        cost_usd: cost.and_then(|c| c.total_cost_usd),
+        duration_ms: cost.and_then(|c| c.total_duration_ms),
+        lines_added: cost.and_then(|c| c.total_lines_added),
+        lines_removed: cost.and_then(|c| c.total_lines_removed),
+        queued_at: unix_now(),
+    };
+
+    let dir = queue_dir();
+    let Ok(json) = serde_json::to_string(&queued) else {
+        return;
+    };
+    let file = dir.join(format!(
+        "{}.json",
+        &sha256_hex(&input.transcript_path)[..16]
+    ));
+    if fs::create_dir_all(&dir).is_err()
+        || crate::utils::atomic::write(&file, json.as_bytes()).is_err()
+    {
+        return;
+    }
+
+    session_cache::update_session(&input.transcript_path, |state| {
+        state.reported_at = Some(SystemTime::now());
+    });
+
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = Command::new(exe)
+            .arg(BACKGROUND_REPORT_FLAG)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+}
+
+/// Held while an upload runs, so concurrent renders do not send twice
+struct UploadLock(PathBuf);
+
+impl UploadLock {
+    fn acquire() -> Option<Self> {
+        let path = queue_dir().join(".lock");
+        let stale = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > STALE_LOCK);
+        if stale {
+            let _ = fs::remove_file(&path);
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .ok()
+            .map(|_| Self(path))
+    }
+}
+
+impl Drop for UploadLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Send every queued report; entry point of the detached process started by
+/// [`schedule`]. Stops at the first failure and leaves the rest queued.
+pub fn run_upload() {
+    let config = crate::config::Config::load().unwrap_or_default();
+    crate::utils::http::configure(&config.network);
+    let config = config.report;
+    if !config.enabled || config.validate().is_err() {
+        return;
+    }
+    let Some(endpoint) = config.endpoint.as_deref() else {
+        return;
+    };
+    let Some(_lock) = UploadLock::acquire() else {
+        return;
+    };
+
+    let token = config
+        .token_env
+        .as_deref()
+        .and_then(|name| std::env::var(name).ok())
+        .filter(|token| !token.is_empty());
+    let reporter = reporter_id();
+
+    let Ok(entries) = fs::read_dir(queue_dir()) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(queued) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<QueuedReport>(&json).ok())
+        else {
+            let _ = fs::remove_file(&path);
+            continue;
+        };
+
+        let report = build_report(&queued, &reporter, config.member.clone());
+        let mut request = crate::utils::http::agent()
+            .post(endpoint)
+            .set("content-type", "application/json")
+            .timeout(Duration::from_secs(10));
+        if let Some(token) = &token {
+            request = request.set("authorization", &format!("Bearer {}", token));
+        }
+        match request.send_json(&report) {
+            Ok(_) => {
+                let _ = fs::remove_file(&path);
+            }
+            // The endpoint rejected this summary; retrying will not help
+            Err(ureq::Error::Status(code, _)) if (400..500).contains(&code) && code != 429 => {
+                let _ = fs::remove_file(&path);
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+/// Anonymous id of this install, created on first use
+fn reporter_id() -> String {
+    let path = ccline_dir().join("reporter_id");
+    if let Some(id) = fs::read_to_string(&path)
+        .ok()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+    {
+        return id;
+    }
+
+    let seed = format!(
+        "{:?}-{}-{:?}",
+        SystemTime::now(),
+        std::process::id(),
+        std::thread::current().id()
+    );
+    let id = sha256_hex(&seed)[..32].to_string();
+    let _ = crate::utils::atomic::write(&path, id.as_bytes());
+    id
+}
+
+fn build_report(queued: &QueuedReport, reporter: &str, member: Option<String>) -> SessionReport {
+    let tokens = transcript_totals(Path::new(&queued.transcript_path));
+    SessionReport {
+        schema: SCHEMA_VERSION,
+        reporter: reporter.to_string(),
+        member,
+        session: sha256_hex(session_id(&queued.transcript_path)),
+        project: sha256_hex(&project_identity(&queued.working_dir)),
+        model: queued.model_id.clone(),
+        input_tokens: tokens.input,
+        output_tokens: tokens.output,
+        cache_creation_tokens: tokens.cache_creation,
+        cache_read_tokens: tokens.cache_read,
+        cost_usd: queued.cost_usd,
+        duration_ms: queued.duration_ms,
+        lines_added: queued.lines_added,
+        lines_removed: queued.lines_removed,
+        ccline_version: env!("CARGO_PKG_VERSION"),
+        reported_at: queued.queued_at,
+    }
+}
+
+#[derive(Debug, Default)]
+struct TokenTotals {
+    input: u64,
+    output: u64,
+    cache_creation: u64,
+    cache_read: u64,
+}
+
+/// Token usage summed over the assistant messages of a transcript. Claude
+/// Code writes one line per content block, repeating the message's usage,
+/// so lines are counted once per message id.
+fn transcript_totals(path: &Path) -> TokenTotals {
+    let mut by_message = HashMap::new();
+    let mut totals = TokenTotals::default();
+    let Ok(file) = fs::File::open(path) else {
+        return totals;
+    };
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<TranscriptEntry>(&line) else {
+            continue;
+        };
+        if entry.r#type.as_deref() != Some("assistant") {
+            continue;
+        }
+        let Some(message) = entry.message else {
+            continue;
+        };
+        let Some(usage) = message.usage else {
+            continue;
+        };
+        let usage = usage.normalize();
+        match message.id {
+            Some(id) => {
+                by_message.insert(id, usage);
+            }
+            None => {
+                totals.input += u64::from(usage.input_tokens);
+                totals.output += u64::from(usage.output_tokens);
+                totals.cache_creation += u64::from(usage.cache_creation_input_tokens);
+                totals.cache_read += u64::from(usage.cache_read_input_tokens);
+            }
+        }
+    }
+
+    for usage in by_message.into_values() {
+        totals.input += u64::from(usage.input_tokens);
+        totals.output += u64::from(usage.output_tokens);
+        totals.cache_creation += u64::from(usage.cache_creation_input_tokens);
+        totals.cache_read += u64::from(usage.cache_read_input_tokens);
+    }
+    totals
+}
+
+/// What identifies a project across machines: the origin remote without
+/// credentials, scheme or `.git` suffix, so `git@github.com:org/repo.git`
+/// and `https://github.com/org/repo` hash the same. Directories outside a
+/// repository fall back to their path.
+fn project_identity(working_dir: &str) -> String {
+    let remote = Command::new("git")
+        .args(["config", "--get", "remote.origin.url"])
+        .current_dir(working_dir)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+
+    match remote {
+        Some(url) => normalize_remote(&url),
+        None => working_dir.to_string(),
+    }
+}
+
+fn normalize_remote(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_credentials = without_scheme
+        .split_once('@')
+        .map_or(without_scheme, |(_, rest)| rest);
+    // scp-style `host:org/repo`
+    let path_style = match without_credentials.split_once(':') {
+        Some((host, rest)) if !url.contains("://") => format!("{}/{}", host, rest),
+        _ => without_credentials.to_string(),
+    };
+    path_style
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
@@ -1,23 +1,33 @@
-use crate::config::{Config, SegmentId, StyleMode};
+use crate::config::{Config, InputData, StyleMode};
+use crate::core::segments::segment_name;
 use crate::ui::components::{
     color_picker::{ColorPickerComponent, NavDirection},
+    config_diff::ConfigDiffComponent,
+    glyph_browser::GlyphBrowserComponent,
     help::HelpComponent,
     icon_selector::IconSelectorComponent,
     name_input::NameInputComponent,
+    nerd_font_prompt::NerdFontPromptComponent,
     preview::PreviewComponent,
+    quit_prompt::QuitPromptComponent,
     segment_list::{FieldSelection, Panel, SegmentListComponent},
     separator_editor::SeparatorEditorComponent,
     settings::SettingsComponent,
+    text_style_editor::{self, TextStyleEditorComponent},
+    theme_gallery::ThemeGalleryComponent,
     theme_selector::ThemeSelectorComponent,
 };
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Position, Rect},
     style::{Color, Style},
     widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
@@ -31,17 +41,39 @@ pub struct App {
     selected_field: FieldSelection,
     should_quit: bool,
     color_picker: ColorPickerComponent,
+    config_diff: ConfigDiffComponent,
+    glyph_browser: GlyphBrowserComponent,
     icon_selector: IconSelectorComponent,
     name_input: NameInputComponent,
+    nerd_font_prompt: NerdFontPromptComponent,
+    text_style_editor: TextStyleEditorComponent,
     preview: PreviewComponent,
     segment_list: SegmentListComponent,
     separator_editor: SeparatorEditorComponent,
     settings: SettingsComponent,
     theme_selector: ThemeSelectorComponent,
+    theme_gallery: ThemeGalleryComponent,
     help: HelpComponent,
     status_message: Option<String>,
+    /// Original index of the segment picked up for moving
+    dragging: Option<usize>,
+    quit_prompt: QuitPromptComponent,
+    /// Configs before each edit, newest last
+    undo_stack: Vec<Config>,
+    redo_stack: Vec<Config>,
+    /// Config as of the last recorded edit, to detect the next one
+    last_snapshot: Config,
+    /// Serialized config as last loaded from or saved to config.toml
+    saved_snapshot: String,
+    /// Panel areas from the last frame, for mouse hit testing
+    segment_list_area: Rect,
+    settings_area: Rect,
+    theme_selector_area: Rect,
 }
 
+/// Upper bound on remembered edits
+const MAX_UNDO_HISTORY: usize = 100;
+
 impl App {
     pub fn new(config: Config) -> Self {
         let mut app = Self {
@@ -51,21 +83,35 @@ impl App {
             selected_field: FieldSelection::Enabled,
             should_quit: false,
             color_picker: ColorPickerComponent::new(),
+            config_diff: ConfigDiffComponent::new(),
+            glyph_browser: GlyphBrowserComponent::new(),
             icon_selector: IconSelectorComponent::new(),
             name_input: NameInputComponent::new(),
+            nerd_font_prompt: NerdFontPromptComponent::new(),
+            text_style_editor: TextStyleEditorComponent::new(),
             preview: PreviewComponent::new(),
             segment_list: SegmentListComponent::new(),
             separator_editor: SeparatorEditorComponent::new(),
             settings: SettingsComponent::new(),
             theme_selector: ThemeSelectorComponent::new(),
+            theme_gallery: ThemeGalleryComponent::new(),
             help: HelpComponent::new(),
             status_message: None,
+            dragging: None,
+            quit_prompt: QuitPromptComponent::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_snapshot: config.clone(),
+            saved_snapshot: toml::to_string(&config).unwrap_or_default(),
+            segment_list_area: Rect::default(),
+            settings_area: Rect::default(),
+            theme_selector_area: Rect::default(),
         };
         app.preview.update_preview(&config);
         app
     }
 
-    pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+    pub fn run(input: Option<InputData>) -> Result<(), Box<dyn std::error::Error>> {
         // Ensure themes directory and built-in themes exist
         if let Err(e) = crate::config::loader::ConfigLoader::init_themes() {
             eprintln!("Warning: Failed to initialize themes: {}", e);
@@ -73,37 +119,76 @@ impl App {
 
         // Load config
         let mut config = Config::load().unwrap_or_else(|_| Config::default());
+        #[cfg(feature = "http")]
+        crate::utils::http::configure(&config.network);
 
         // If a theme is specified, reload it to get the latest changes
-        if !config.theme.is_empty() && config.theme != "default" {
+        // (adaptive selections are already resolved from their theme files on load)
+        let theme_name = config.active_theme().to_string();
+        if !config.theme.is_adaptive() && !theme_name.is_empty() && theme_name != "default" {
             if let Ok(theme_config) =
-                crate::ui::themes::ThemePresets::load_theme_from_file(&config.theme)
+                crate::ui::themes::ThemePresets::load_theme_from_file(&theme_name)
             {
-                config = theme_config;
+                config = config.with_theme(theme_config);
             }
         }
 
         // Terminal setup
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
         let mut app = App::new(config);
+        if input.is_some() {
+            app.preview.set_input(input);
+            app.preview.update_preview(&app.config);
+        }
+
+        // Ask once whether Nerd Font glyphs render, unless the config already says
+        if app.config.style.nerd_font.is_none() && !crate::utils::nerd_font::is_confirmed() {
+            app.nerd_font_prompt.open();
+        }
 
         // Main loop
         let result = loop {
             terminal.draw(|f| app.ui(f))?;
 
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                app.handle_mouse(mouse);
+                app.record_edit();
+            }
+
+            if let Event::Key(key) = event {
                 // Only handle KeyDown events to prevent double triggering on Windows
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
 
                 // Handle popup events first
-                if app.name_input.is_open {
+                if app.quit_prompt.is_open {
+                    match key.code {
+                        KeyCode::Char('s') | KeyCode::Char('S') => match app.save_config() {
+                            Ok(()) => app.should_quit = true,
+                            Err(e) => {
+                                app.quit_prompt.close();
+                                app.status_message = Some(format!("Failed to save config: {}", e));
+                            }
+                        },
+                        KeyCode::Char('q') | KeyCode::Char('Q') => app.should_quit = true,
+                        KeyCode::Esc => app.quit_prompt.close(),
+                        _ => {}
+                    }
+                } else if app.nerd_font_prompt.is_open {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_nerd_font(true),
+                        KeyCode::Char('n') | KeyCode::Char('N') => app.confirm_nerd_font(false),
+                        KeyCode::Esc => app.nerd_font_prompt.close(),
+                        _ => {}
+                    }
+                } else if app.name_input.is_open {
                     match key.code {
                         KeyCode::Esc => app.name_input.close(),
                         KeyCode::Enter => {
@@ -155,6 +240,67 @@ impl App {
                         KeyCode::Backspace => app.color_picker.backspace(),
                         _ => {}
                     }
+                } else if app.text_style_editor.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.text_style_editor.close(),
+                        KeyCode::Up => app.text_style_editor.move_direction(NavDirection::Up),
+                        KeyCode::Down => app.text_style_editor.move_direction(NavDirection::Down),
+                        KeyCode::Left => app.text_style_editor.move_direction(NavDirection::Left),
+                        KeyCode::Right => app.text_style_editor.move_direction(NavDirection::Right),
+                        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_text_style(),
+                        _ => {}
+                    }
+                } else if app.theme_gallery.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.theme_gallery.close(),
+                        KeyCode::Up => app.theme_gallery.move_selection(-1),
+                        KeyCode::Down => app.theme_gallery.move_selection(1),
+                        KeyCode::Enter => {
+                            if let Some(theme) = app.theme_gallery.get_selected_theme() {
+                                let theme = theme.to_string();
+                                app.switch_to_theme(&theme);
+                            }
+                            app.theme_gallery.close();
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('N') => {
+                            app.theme_gallery.close();
+                            app.name_input.open("Save as New Theme", "Enter theme name");
+                        }
+                        _ => {}
+                    }
+                } else if app.config_diff.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.config_diff.close(),
+                        KeyCode::Up => app.config_diff.move_selection(-1),
+                        KeyCode::Down => app.config_diff.move_selection(1),
+                        KeyCode::Char('r') | KeyCode::Char('R') => app.revert_selected_change(),
+                        KeyCode::Char('s') | KeyCode::Char('S') => {
+                            app.config_diff.close();
+                            if let Err(e) = app.save_config() {
+                                app.status_message = Some(format!("Failed to save config: {}", e));
+                            } else {
+                                app.status_message =
+                                    Some("Configuration saved to config.toml!".to_string());
+                            }
+                        }
+                        _ => {}
+                    }
+                } else if app.glyph_browser.is_open {
+                    match key.code {
+                        KeyCode::Esc => app.glyph_browser.close(),
+                        KeyCode::Up => app.glyph_browser.move_selection(-1),
+                        KeyCode::Down => app.glyph_browser.move_selection(1),
+                        KeyCode::PageUp => app.glyph_browser.move_selection(-10),
+                        KeyCode::PageDown => app.glyph_browser.move_selection(10),
+                        KeyCode::Enter => {
+                            if let Some(glyph) = app.glyph_browser.get_selected_glyph() {
+                                app.apply_nerd_font_glyph(glyph);
+                            }
+                        }
+                        KeyCode::Char(c) => app.glyph_browser.input_char(c),
+                        KeyCode::Backspace => app.glyph_browser.backspace(),
+                        _ => {}
+                    }
                 } else if app.icon_selector.is_open {
                     match key.code {
                         KeyCode::Esc => app.icon_selector.close(),
@@ -162,6 +308,9 @@ impl App {
                         KeyCode::Down => app.icon_selector.move_selection(1),
                         KeyCode::Tab => app.icon_selector.toggle_style(),
                         KeyCode::Char('c') => app.icon_selector.start_custom_input(),
+                        KeyCode::Char('/') if !app.icon_selector.editing_custom => {
+                            app.glyph_browser.open()
+                        }
                         KeyCode::Enter => {
                             if app.icon_selector.editing_custom {
                                 app.icon_selector.finish_custom_input();
@@ -180,10 +329,27 @@ impl App {
                         }
                         _ => {}
                     }
+                } else if app.dragging.is_some() {
+                    // A segment is picked up: arrows carry it, Enter drops it
+                    match key.code {
+                        KeyCode::Up => app.move_segment_up(),
+                        KeyCode::Down => app.move_segment_down(),
+                        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('m') => {
+                            app.drop_segment()
+                        }
+                        KeyCode::Esc => app.cancel_drag(),
+                        _ => {}
+                    }
                 } else {
                     // Handle main app events
                     match key.code {
-                        KeyCode::Esc => app.should_quit = true,
+                        KeyCode::Esc => app.request_quit(),
+                        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.undo()
+                        }
+                        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.redo()
+                        }
                         KeyCode::Char('s') => {
                             if key.modifiers.contains(KeyModifiers::CONTROL) {
                                 // Ctrl+S: Save as new theme with name input
@@ -218,17 +384,26 @@ impl App {
                             }
                         }
                         KeyCode::Enter => app.toggle_current(),
+                        KeyCode::Char('m') | KeyCode::Char(' ') => app.pick_up_segment(),
                         KeyCode::Tab => app.switch_panel(),
                         KeyCode::Char('1') => app.switch_to_theme("default"),
                         KeyCode::Char('2') => app.switch_to_theme("minimal"),
                         KeyCode::Char('3') => app.switch_to_theme("gruvbox"),
                         KeyCode::Char('4') => app.switch_to_theme("nord"),
                         KeyCode::Char('p') => app.cycle_theme(),
+                        KeyCode::Char('g') | KeyCode::Char('G') => {
+                            app.theme_gallery.open(&app.config, &app.preview)
+                        }
                         KeyCode::Char('r') => app.reset_to_theme_defaults(),
+                        KeyCode::Char('v') | KeyCode::Char('V') => app.cycle_preview_width(),
+                        KeyCode::Char('d') | KeyCode::Char('D') => app.open_config_diff(),
+                        KeyCode::Char('l') | KeyCode::Char('L') => app.toggle_preview_layout(),
                         KeyCode::Char('e') | KeyCode::Char('E') => app.open_separator_editor(),
                         _ => {}
                     }
                 }
+
+                app.record_edit();
             }
 
             if app.should_quit {
@@ -238,7 +413,11 @@ impl App {
 
         // Restore terminal
         disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
         terminal.show_cursor()?;
 
         result
@@ -257,7 +436,7 @@ impl App {
         let mut first_line = true;
 
         for (i, theme) in available_themes.iter().enumerate() {
-            let marker = if self.config.theme == *theme {
+            let marker = if self.config.active_theme() == theme {
                 "[✓]"
             } else {
                 "[ ]"
@@ -295,21 +474,30 @@ impl App {
                 "[↑↓] Navigate",
                 "[Tab] Style",
                 "[C] Custom",
+                "[/] Browse Glyphs",
                 "[Enter] Select",
                 "[Esc] Cancel",
             ]
+        } else if self.dragging.is_some() {
+            vec!["[↑↓] Move", "[Enter] Drop", "[Esc] Cancel"]
         } else {
             vec![
                 "[Tab] Switch Panel",
                 "[Enter] Toggle/Edit",
+                "[M] Move",
                 "[Shift+↑↓] Reorder",
                 "[1-4] Theme",
                 "[P] Switch Theme",
+                "[G] Gallery",
                 "[R] Reset",
                 "[E] Edit Separator",
+                "[V] Preview Width",
+                "[L] Preview Layout",
+                "[D] Diff",
                 "[S] Save Config",
                 "[W] Write Theme",
                 "[Ctrl+S] Save Theme",
+                "[Ctrl+Z/Y] Undo/Redo",
                 "[Esc] Quit",
             ]
         };
@@ -383,7 +571,15 @@ impl App {
             .split(f.area());
 
         // Title
-        let title_text = format!("CCometixLine Configurator v{}", env!("CARGO_PKG_VERSION"));
+        let title_text = format!(
+            "CCometixLine Configurator v{}{}",
+            env!("CARGO_PKG_VERSION"),
+            if self.is_dirty() {
+                " ● unsaved changes"
+            } else {
+                ""
+            }
+        );
         let title = Paragraph::new(title_text)
             .block(Block::default().borders(Borders::ALL))
             .style(Style::default().fg(Color::Cyan))
@@ -402,12 +598,15 @@ impl App {
 
         // Theme selector
         self.theme_selector.render(f, layout[2], &self.config);
+        self.theme_selector_area = layout[2];
 
         // Main content (split horizontally)
         let content_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
             .split(layout[3]);
+        self.segment_list_area = content_layout[0];
+        self.settings_area = content_layout[1];
 
         // Segment list
         self.segment_list.render(
@@ -416,6 +615,7 @@ impl App {
             &self.config,
             self.selected_segment,
             &self.selected_panel,
+            self.dragging.is_some(),
         );
 
         // Settings panel
@@ -435,6 +635,7 @@ impl App {
             self.status_message.as_deref(),
             self.color_picker.is_open,
             self.icon_selector.is_open,
+            self.dragging.is_some(),
         );
 
         // Render popups on top
@@ -444,12 +645,56 @@ impl App {
         if self.icon_selector.is_open {
             self.icon_selector.render(f, f.area());
         }
+        if self.glyph_browser.is_open {
+            self.glyph_browser.render(f, f.area());
+        }
         if self.name_input.is_open {
             self.name_input.render(f, f.area());
         }
         if self.separator_editor.is_open {
             self.separator_editor.render(f, f.area());
         }
+        if self.text_style_editor.is_open {
+            self.text_style_editor.render(f, f.area());
+        }
+        if self.theme_gallery.is_open {
+            self.theme_gallery.render(f, f.area());
+        }
+        if self.config_diff.is_open {
+            self.config_diff.render(f, f.area());
+        }
+        if self.nerd_font_prompt.is_open {
+            self.nerd_font_prompt.render(f, f.area());
+        }
+        if self.quit_prompt.is_open {
+            self.quit_prompt.render(f, f.area());
+        }
+    }
+
+    /// Toggle the attribute under the cursor and show it in the preview right away
+    fn toggle_text_style(&mut self) {
+        let styles = self.text_style_editor.toggle_selected().clone();
+        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
+            self.status_message = Some(format!(
+                "{} text style: {}",
+                segment_name(segment.id),
+                text_style_editor::describe(&styles)
+            ));
+            segment.styles = styles;
+            self.preview.update_preview(&self.config);
+        }
+    }
+
+    /// Remember whether Nerd Font glyphs render and refresh the preview
+    fn confirm_nerd_font(&mut self, available: bool) {
+        crate::utils::nerd_font::save_confirmation(available);
+        self.nerd_font_prompt.close();
+        self.preview.update_preview(&self.config);
+        self.status_message = Some(if available {
+            "Nerd Font icons enabled".to_string()
+        } else {
+            "No Nerd Font: Nerd Font styles will render with plain icons".to_string()
+        });
     }
 
     fn move_selection(&mut self, delta: i32) {
@@ -493,17 +738,7 @@ impl App {
                 // Toggle segment enabled/disabled in segment list
                 if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
                     segment.enabled = !segment.enabled;
-                    let segment_name = match segment.id {
-                        SegmentId::Model => "Model",
-                        SegmentId::Directory => "Directory",
-                        SegmentId::Git => "Git",
-                        SegmentId::Usage => "Usage",
-                        SegmentId::Cost => "Cost",
-                        SegmentId::Session => "Session",
-                        SegmentId::OutputStyle => "Output Style",
-                        SegmentId::Update => "Update",
-                        SegmentId::Quota => "Quota",
-                    };
+                    let segment_name = segment_name(segment.id);
                     let is_enabled = segment.enabled;
                     self.status_message = Some(format!(
                         "{} segment {}",
@@ -520,17 +755,7 @@ impl App {
                         // Toggle enabled state in settings panel too
                         if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
                             segment.enabled = !segment.enabled;
-                            let segment_name = match segment.id {
-                                SegmentId::Model => "Model",
-                                SegmentId::Directory => "Directory",
-                                SegmentId::Git => "Git",
-                                SegmentId::Usage => "Usage",
-                                SegmentId::Cost => "Cost",
-                                SegmentId::Session => "Session",
-                                SegmentId::OutputStyle => "Output Style",
-                                SegmentId::Update => "Update",
-                                SegmentId::Quota => "Quota",
-                            };
+                            let segment_name = segment_name(segment.id);
                             let is_enabled = segment.enabled;
                             self.status_message = Some(format!(
                                 "{} segment {}",
@@ -545,18 +770,8 @@ impl App {
                     | FieldSelection::TextColor
                     | FieldSelection::BackgroundColor => self.open_color_picker(),
                     FieldSelection::TextStyle => {
-                        // Toggle text bold style
-                        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
-                            segment.styles.text_bold = !segment.styles.text_bold;
-                            self.status_message = Some(format!(
-                                "Text bold {}",
-                                if segment.styles.text_bold {
-                                    "enabled"
-                                } else {
-                                    "disabled"
-                                }
-                            ));
-                            self.preview.update_preview(&self.config);
+                        if let Some(segment) = self.config.segments.get(self.selected_segment) {
+                            self.text_style_editor.open(&segment.styles);
                         }
                     }
                     FieldSelection::Options => {
@@ -582,7 +797,16 @@ impl App {
                 || self.selected_field == FieldSelection::TextColor
                 || self.selected_field == FieldSelection::BackgroundColor)
         {
-            self.color_picker.open();
+            let current = self
+                .config
+                .segments
+                .get(self.selected_segment)
+                .and_then(|segment| match self.selected_field {
+                    FieldSelection::IconColor => segment.colors.icon.as_ref(),
+                    FieldSelection::TextColor => segment.colors.text.as_ref(),
+                    _ => segment.colors.background.as_ref(),
+                });
+            self.color_picker.open(current);
         }
     }
 
@@ -593,6 +817,7 @@ impl App {
     }
 
     fn apply_selected_color(&mut self, color: crate::config::AnsiColor) {
+        self.color_picker.remember(&color);
         if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
             match self.selected_field {
                 FieldSelection::IconColor => segment.colors.icon = Some(color),
@@ -614,9 +839,25 @@ impl App {
         }
     }
 
+    /// Use a glyph from the browser as the segment's Nerd Font icon
+    fn apply_nerd_font_glyph(&mut self, glyph: String) {
+        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
+            self.status_message = Some(format!(
+                "{} Nerd Font icon set to {} (U+{:04X})",
+                segment_name(segment.id),
+                glyph,
+                glyph.chars().next().map(u32::from).unwrap_or_default()
+            ));
+            segment.icon.nerd_font = glyph;
+            self.preview.update_preview(&self.config);
+        }
+        self.glyph_browser.close();
+        self.icon_selector.close();
+    }
+
     fn cycle_theme(&mut self) {
         let themes = crate::ui::themes::ThemePresets::list_available_themes();
-        let current_theme = &self.config.theme;
+        let current_theme = self.config.active_theme();
         let current_index = themes.iter().position(|t| t == current_theme).unwrap_or(0);
         let next_index = (current_index + 1) % themes.len();
         let next_theme = &themes[next_index];
@@ -625,8 +866,54 @@ impl App {
         self.switch_to_theme(next_theme);
     }
 
+    fn open_config_diff(&mut self) {
+        if let Err(e) = self.config_diff.open(&self.config) {
+            self.status_message = Some(format!("Failed to diff config: {}", e));
+        }
+    }
+
+    /// Undo one change from the diff view, leaving the other edits in place
+    fn revert_selected_change(&mut self) {
+        match self.config_diff.revert_selected() {
+            Ok(config) => {
+                self.config = config;
+                self.selected_segment = self
+                    .selected_segment
+                    .min(self.config.segments.len().saturating_sub(1));
+                self.preview.update_preview(&self.config);
+                self.status_message = Some("Change reverted".to_string());
+                if let Err(e) = self.config_diff.refresh(&self.config) {
+                    self.status_message = Some(format!("Failed to diff config: {}", e));
+                }
+            }
+            Err(e) => {
+                // TOML errors span several lines; the first one names the problem
+                let reason = e.to_string();
+                self.status_message = Some(format!(
+                    "Cannot revert this change on its own: {}",
+                    reason.lines().next().unwrap_or_default()
+                ));
+            }
+        }
+    }
+
+    fn cycle_preview_width(&mut self) {
+        let preset = self.preview.cycle_width();
+        self.status_message = Some(format!("Preview width: {}", preset.label()));
+    }
+
+    fn toggle_preview_layout(&mut self) {
+        self.status_message = Some(if self.preview.toggle_single_line() {
+            "Preview layout: one line, clipped at the terminal edge".to_string()
+        } else {
+            "Preview layout: wrapped by segment".to_string()
+        });
+    }
+
     fn switch_to_theme(&mut self, theme_name: &str) {
-        self.config = crate::ui::themes::ThemePresets::get_theme(theme_name);
+        self.config = self
+            .config
+            .with_theme(crate::ui::themes::ThemePresets::get_theme(theme_name));
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Switched to {} theme", theme_name));
@@ -634,8 +921,10 @@ impl App {
 
     /// Reset current theme to its default configuration
     fn reset_to_theme_defaults(&mut self) {
-        let current_theme = self.config.theme.clone();
-        self.config = crate::ui::themes::ThemePresets::get_theme(&current_theme);
+        let current_theme = self.config.active_theme().to_string();
+        self.config = self
+            .config
+            .with_theme(crate::ui::themes::ThemePresets::get_theme(&current_theme));
         self.selected_segment = 0;
         self.preview.update_preview(&self.config);
         self.status_message = Some(format!("Reset {} theme to defaults", current_theme));
@@ -643,9 +932,180 @@ impl App {
 
     fn save_config(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.config.save()?;
+        self.saved_snapshot = toml::to_string(&self.config)?;
         Ok(())
     }
 
+    /// Whether the config differs from what config.toml holds
+    fn is_dirty(&self) -> bool {
+        toml::to_string(&self.config).unwrap_or_default() != self.saved_snapshot
+    }
+
+    /// Quit right away, or ask first when there are unsaved edits
+    fn request_quit(&mut self) {
+        if self.is_dirty() {
+            self.quit_prompt.open();
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Clicks select segments, fields and themes; the wheel scrolls the panel
+    /// under the cursor; the left button drags the HSV sliders
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+
+        if self.color_picker.is_open {
+            if let MouseEventKind::Down(MouseButton::Left)
+            | MouseEventKind::Drag(MouseButton::Left) = mouse.kind
+            {
+                let is_drag = matches!(mouse.kind, MouseEventKind::Drag(_));
+                self.color_picker
+                    .drag_slider(mouse.column, mouse.row, is_drag);
+            }
+            return;
+        }
+
+        // Other popups and segment moves stay keyboard-only
+        if self.icon_selector.is_open
+            || self.glyph_browser.is_open
+            || self.name_input.is_open
+            || self.separator_editor.is_open
+            || self.text_style_editor.is_open
+            || self.theme_gallery.is_open
+            || self.config_diff.is_open
+            || self.nerd_font_prompt.is_open
+            || self.quit_prompt.is_open
+            || self.dragging.is_some()
+        {
+            return;
+        }
+
+        let panel = if self.segment_list_area.contains(position) {
+            Panel::SegmentList
+        } else if self.settings_area.contains(position) {
+            Panel::Settings
+        } else {
+            if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                && self.theme_selector_area.contains(position)
+            {
+                if let Some(theme) = self.theme_selector.theme_at(
+                    self.theme_selector_area,
+                    &self.config,
+                    mouse.column,
+                    mouse.row,
+                ) {
+                    self.switch_to_theme(&theme);
+                }
+            }
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp => {
+                self.selected_panel = panel;
+                self.move_selection(-1);
+            }
+            MouseEventKind::ScrollDown => {
+                self.selected_panel = panel;
+                self.move_selection(1);
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Rows start below the top border
+                let area = match panel {
+                    Panel::SegmentList => self.segment_list_area,
+                    Panel::Settings => self.settings_area,
+                };
+                let Some(row) = (mouse.row as usize).checked_sub(area.y as usize + 1) else {
+                    return;
+                };
+
+                match panel {
+                    Panel::SegmentList if row < self.config.segments.len() => {
+                        self.selected_panel = Panel::SegmentList;
+                        self.selected_segment = row;
+                        // The ●/○ marker after the cursor column toggles the segment
+                        if mouse.column == area.x + 3 {
+                            self.toggle_current();
+                        }
+                    }
+                    Panel::Settings => {
+                        // Row 0 is the segment name; clicking a selected field edits it
+                        let Some(field) = row.checked_sub(1).and_then(FieldSelection::from_index)
+                        else {
+                            return;
+                        };
+                        let was_selected =
+                            self.selected_panel == Panel::Settings && self.selected_field == field;
+                        self.selected_panel = Panel::Settings;
+                        self.selected_field = field;
+                        if was_selected {
+                            self.toggle_current();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Push the previous config onto the undo stack if the last key changed it
+    fn record_edit(&mut self) {
+        // A move in progress is recorded once, when the segment is dropped
+        if self.dragging.is_some() {
+            return;
+        }
+        let changed =
+            toml::to_string(&self.config).ok() != toml::to_string(&self.last_snapshot).ok();
+        if !changed {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.last_snapshot, self.config.clone());
+        self.undo_stack.push(previous);
+        if self.undo_stack.len() > MAX_UNDO_HISTORY {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some(previous) => {
+                let current = std::mem::replace(&mut self.config, previous);
+                self.redo_stack.push(current);
+                self.restore_history_entry("Undone");
+            }
+            None => self.status_message = Some("Nothing to undo".to_string()),
+        }
+    }
+
+    fn redo(&mut self) {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                let current = std::mem::replace(&mut self.config, next);
+                self.undo_stack.push(current);
+                self.restore_history_entry("Redone");
+            }
+            None => self.status_message = Some("Nothing to redo".to_string()),
+        }
+    }
+
+    fn restore_history_entry(&mut self, action: &str) {
+        self.last_snapshot = self.config.clone();
+        self.selected_segment = self
+            .selected_segment
+            .min(self.config.segments.len().saturating_sub(1));
+        self.preview.update_preview(&self.config);
+        self.status_message = Some(format!(
+            "{} ({} undo, {} redo left)",
+            action,
+            self.undo_stack.len(),
+            self.redo_stack.len()
+        ));
+    }
+
     /// Move the currently selected segment up in the list
     fn move_segment_up(&mut self) {
         if self.selected_panel == Panel::SegmentList && self.selected_segment > 0 {
@@ -670,9 +1130,42 @@ impl App {
         }
     }
 
+    /// Pick up the selected segment so the arrow keys move it
+    fn pick_up_segment(&mut self) {
+        if self.selected_panel == Panel::SegmentList
+            && self.selected_segment < self.config.segments.len()
+        {
+            self.dragging = Some(self.selected_segment);
+            self.status_message = Some(format!(
+                "Moving {}: ↑↓ to move, Enter to drop, Esc to cancel",
+                segment_name(self.config.segments[self.selected_segment].id)
+            ));
+        }
+    }
+
+    fn drop_segment(&mut self) {
+        if self.dragging.take().is_some() {
+            self.status_message = Some(format!(
+                "Segment moved to position {} (S to save)",
+                self.selected_segment + 1
+            ));
+        }
+    }
+
+    /// Put the picked up segment back where it started
+    fn cancel_drag(&mut self) {
+        if let Some(origin) = self.dragging.take() {
+            let segment = self.config.segments.remove(self.selected_segment);
+            self.config.segments.insert(origin, segment);
+            self.selected_segment = origin;
+            self.preview.update_preview(&self.config);
+            self.status_message = Some("Move cancelled".to_string());
+        }
+    }
+
     /// Write current config to the current theme file
     fn write_to_current_theme(&mut self) {
-        let current_theme = &self.config.theme;
+        let current_theme = self.config.active_theme();
         match crate::ui::themes::ThemePresets::save_theme(current_theme, &self.config) {
             Ok(_) => {
                 self.status_message = Some(format!("Wrote config to theme: {}", current_theme));
@@ -689,7 +1182,7 @@ impl App {
         match crate::ui::themes::ThemePresets::save_theme(theme_name, &self.config) {
             Ok(_) => {
                 // Update current theme to the new one
-                self.config.theme = theme_name.to_string();
+                self.config.theme = theme_name.into();
                 self.status_message = Some(format!("Saved as new theme: {}", theme_name));
             }
             Err(e) => {
@@ -1,7 +1,9 @@
 use crate::config::AnsiColor;
+use crate::utils::color::{hsv_to_rgb, rgb_to_hsv, to_rgb};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    text::{Line, Span},
     widgets::{
         Block, Borders, Clear, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
         ScrollbarState,
@@ -21,9 +23,14 @@ pub enum NavDirection {
 pub enum ColorPickerMode {
     Basic16,
     Extended256,
+    Hsv,
     RgbInput,
+    Recent,
 }
 
+/// How many recently applied colors are remembered
+const MAX_RECENT_COLORS: usize = 8;
+
 #[derive(Debug, Clone)]
 pub struct ColorPickerComponent {
     pub is_open: bool,
@@ -38,6 +45,27 @@ pub struct ColorPickerComponent {
     // Cache columns per row for navigation
     pub cached_basic_cols: usize,
     pub cached_extended_cols: usize,
+    pub hsv_input: HsvInput,
+    /// Recently applied colors, newest first
+    pub recent_colors: Vec<AnsiColor>,
+    pub selected_recent: usize,
+    /// Screen area of the HSV slider bars, for mouse dragging
+    slider_area: Option<Rect>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HsvInput {
+    pub hue: u16,
+    pub saturation: u8,
+    pub value: u8,
+    pub editing_field: HsvField,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HsvField {
+    Hue,
+    Saturation,
+    Value,
 }
 
 #[derive(Debug, Clone)]
@@ -83,13 +111,60 @@ impl ColorPickerComponent {
             basic_scrollbar_state: ScrollbarState::new(16),
             cached_basic_cols: 4,
             cached_extended_cols: 16,
+            hsv_input: HsvInput {
+                hue: 0,
+                saturation: 100,
+                value: 100,
+                editing_field: HsvField::Hue,
+            },
+            recent_colors: load_recent_colors(),
+            selected_recent: 0,
+            slider_area: None,
         }
     }
 
-    pub fn open(&mut self) {
+    /// Open the picker on the mode and position of the color being edited
+    pub fn open(&mut self, current: Option<&AnsiColor>) {
         self.is_open = true;
         self.mode = ColorPickerMode::Basic16;
         self.selected_basic = 0;
+        self.selected_recent = 0;
+        self.current_color = current.cloned();
+
+        let Some(color) = current else {
+            return;
+        };
+        match color {
+            AnsiColor::Color16 { c16 } => {
+                self.selected_basic = (*c16).min(15) as usize;
+                self.basic_list_state.select(Some(self.selected_basic));
+            }
+            AnsiColor::Color256 { c256 } => {
+                self.mode = ColorPickerMode::Extended256;
+                self.selected_extended = *c256 as usize;
+            }
+            AnsiColor::Rgb { r, g, b } => {
+                self.mode = ColorPickerMode::RgbInput;
+                self.rgb_input.r = r.to_string();
+                self.rgb_input.g = g.to_string();
+                self.rgb_input.b = b.to_string();
+                self.rgb_input.hex = format!("{:02X}{:02X}{:02X}", r, g, b);
+            }
+        }
+        self.show_extended = matches!(self.mode, ColorPickerMode::Extended256);
+
+        let (hue, saturation, value) = rgb_to_hsv(to_rgb(color));
+        self.hsv_input.hue = hue;
+        self.hsv_input.saturation = saturation;
+        self.hsv_input.value = value;
+    }
+
+    /// Remember a color that was applied, newest first
+    pub fn remember(&mut self, color: &AnsiColor) {
+        self.recent_colors.retain(|recent| recent != color);
+        self.recent_colors.insert(0, color.clone());
+        self.recent_colors.truncate(MAX_RECENT_COLORS);
+        save_recent_colors(&self.recent_colors);
     }
 
     pub fn close(&mut self) {
@@ -112,9 +187,16 @@ impl ColorPickerComponent {
     pub fn cycle_mode(&mut self) {
         self.mode = match self.mode {
             ColorPickerMode::Basic16 => ColorPickerMode::Extended256,
-            ColorPickerMode::Extended256 => ColorPickerMode::RgbInput,
-            ColorPickerMode::RgbInput => ColorPickerMode::Basic16,
+            ColorPickerMode::Extended256 => ColorPickerMode::Hsv,
+            ColorPickerMode::Hsv => ColorPickerMode::RgbInput,
+            ColorPickerMode::RgbInput => ColorPickerMode::Recent,
+            ColorPickerMode::Recent => ColorPickerMode::Basic16,
         };
+        match self.mode {
+            ColorPickerMode::Hsv => self.update_hsv_color(),
+            ColorPickerMode::Recent => self.select_recent(self.selected_recent),
+            _ => {}
+        }
 
         // Update show_extended for compatibility with existing logic
         self.show_extended = matches!(self.mode, ColorPickerMode::Extended256);
@@ -137,6 +219,11 @@ impl ColorPickerComponent {
                     c256: new_selection as u8,
                 });
             }
+            ColorPickerMode::Hsv => self.move_hsv_field(delta),
+            ColorPickerMode::Recent => {
+                let index = (self.selected_recent as i32 + delta).max(0) as usize;
+                self.select_recent(index);
+            }
             ColorPickerMode::RgbInput => {
                 // Handle RGB input field navigation
                 match self.rgb_input.editing_field {
@@ -245,6 +332,20 @@ impl ColorPickerComponent {
                     c256: new_selection as u8,
                 });
             }
+            ColorPickerMode::Hsv => match direction {
+                NavDirection::Up => self.move_hsv_field(-1),
+                NavDirection::Down => self.move_hsv_field(1),
+                NavDirection::Left => self.adjust_hsv(-1),
+                NavDirection::Right => self.adjust_hsv(1),
+            },
+            ColorPickerMode::Recent => match direction {
+                NavDirection::Left | NavDirection::Up => {
+                    self.select_recent(self.selected_recent.saturating_sub(1))
+                }
+                NavDirection::Right | NavDirection::Down => {
+                    self.select_recent(self.selected_recent + 1)
+                }
+            },
             ColorPickerMode::RgbInput => {
                 // RGB输入模式：左右切换字段
                 match direction {
@@ -345,6 +446,87 @@ impl ColorPickerComponent {
         }
     }
 
+    fn move_hsv_field(&mut self, delta: i32) {
+        let fields = [HsvField::Hue, HsvField::Saturation, HsvField::Value];
+        let current = fields
+            .iter()
+            .position(|field| *field == self.hsv_input.editing_field)
+            .unwrap_or(0) as i32;
+        let next = (current + delta).clamp(0, fields.len() as i32 - 1) as usize;
+        self.hsv_input.editing_field = fields[next].clone();
+    }
+
+    /// Step the focused slider; hue wraps around, saturation and value clamp
+    fn adjust_hsv(&mut self, delta: i32) {
+        match self.hsv_input.editing_field {
+            HsvField::Hue => {
+                self.hsv_input.hue = (self.hsv_input.hue as i32 + delta * 6).rem_euclid(360) as u16;
+            }
+            HsvField::Saturation => {
+                self.hsv_input.saturation =
+                    (self.hsv_input.saturation as i32 + delta * 5).clamp(0, 100) as u8;
+            }
+            HsvField::Value => {
+                self.hsv_input.value =
+                    (self.hsv_input.value as i32 + delta * 5).clamp(0, 100) as u8;
+            }
+        }
+        self.update_hsv_color();
+    }
+
+    /// Set the slider under the mouse to the clicked position. A click must
+    /// land on a bar; a drag past either end pins the slider to its limit.
+    /// Returns false when the position is not on a slider.
+    pub fn drag_slider(&mut self, column: u16, row: u16, is_drag: bool) -> bool {
+        let Some(area) = self.slider_area else {
+            return false;
+        };
+        if self.mode != ColorPickerMode::Hsv
+            || row < area.y
+            || row >= area.y + area.height
+            || !(row - area.y).is_multiple_of(2)
+            || (!is_drag && (column < area.x || column >= area.x + area.width))
+        {
+            return false;
+        }
+
+        let cell = column.clamp(area.x, area.x + area.width - 1) - area.x;
+        let ratio = |max: u32| (cell as u32 * max / (area.width.max(2) as u32 - 1)) as u16;
+        match (row - area.y) / 2 {
+            0 => {
+                self.hsv_input.editing_field = HsvField::Hue;
+                self.hsv_input.hue = ratio(359);
+            }
+            1 => {
+                self.hsv_input.editing_field = HsvField::Saturation;
+                self.hsv_input.saturation = ratio(100) as u8;
+            }
+            _ => {
+                self.hsv_input.editing_field = HsvField::Value;
+                self.hsv_input.value = ratio(100) as u8;
+            }
+        }
+        self.update_hsv_color();
+        true
+    }
+
+    fn update_hsv_color(&mut self) {
+        let (r, g, b) = hsv_to_rgb(
+            self.hsv_input.hue,
+            self.hsv_input.saturation,
+            self.hsv_input.value,
+        );
+        self.current_color = Some(AnsiColor::Rgb { r, g, b });
+    }
+
+    fn select_recent(&mut self, index: usize) {
+        if self.recent_colors.is_empty() {
+            return;
+        }
+        self.selected_recent = index.min(self.recent_colors.len() - 1);
+        self.current_color = Some(self.recent_colors[self.selected_recent].clone());
+    }
+
     pub fn get_selected_color(&self) -> Option<AnsiColor> {
         self.current_color.clone()
     }
@@ -375,9 +557,21 @@ impl ColorPickerComponent {
 
         // Mode selector - show all three modes
         let mode_text = match self.mode {
-            ColorPickerMode::Basic16 => "[•] Basic (ANSI 16)  [ ] Extended (256)  [ ] RGB",
-            ColorPickerMode::Extended256 => "[ ] Basic (ANSI 16)  [•] Extended (256)  [ ] RGB",
-            ColorPickerMode::RgbInput => "[ ] Basic (ANSI 16)  [ ] Extended (256)  [•] RGB",
+            ColorPickerMode::Basic16 => {
+                "[•] Basic (ANSI 16)  [ ] Extended (256)  [ ] HSV  [ ] RGB  [ ] Recent"
+            }
+            ColorPickerMode::Extended256 => {
+                "[ ] Basic (ANSI 16)  [•] Extended (256)  [ ] HSV  [ ] RGB  [ ] Recent"
+            }
+            ColorPickerMode::Hsv => {
+                "[ ] Basic (ANSI 16)  [ ] Extended (256)  [•] HSV  [ ] RGB  [ ] Recent"
+            }
+            ColorPickerMode::RgbInput => {
+                "[ ] Basic (ANSI 16)  [ ] Extended (256)  [ ] HSV  [•] RGB  [ ] Recent"
+            }
+            ColorPickerMode::Recent => {
+                "[ ] Basic (ANSI 16)  [ ] Extended (256)  [ ] HSV  [ ] RGB  [•] Recent"
+            }
         };
 
         f.render_widget(
@@ -389,7 +583,9 @@ impl ColorPickerComponent {
         match self.mode {
             ColorPickerMode::Basic16 => self.render_basic_colors(f, chunks[1]),
             ColorPickerMode::Extended256 => self.render_extended_colors(f, chunks[1]),
+            ColorPickerMode::Hsv => self.render_hsv_sliders(f, chunks[1]),
             ColorPickerMode::RgbInput => self.render_rgb_input(f, chunks[1]),
+            ColorPickerMode::Recent => self.render_recent_colors(f, chunks[1]),
         }
 
         // Preview
@@ -397,7 +593,7 @@ impl ColorPickerComponent {
 
         // Actions
         f.render_widget(
-            Paragraph::new("[Enter] Select  [Esc] Cancel  [Tab] Cycle Mode  [R] RGB")
+            Paragraph::new("[Enter] Select  [Esc] Cancel  [Tab] Cycle Mode  [R] RGB/Hex")
                 .block(Block::default().borders(Borders::ALL)),
             chunks[3],
         );
@@ -594,6 +790,101 @@ impl ColorPickerComponent {
         f.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
     }
 
+    fn render_hsv_sliders(&mut self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("HSV (↑↓ slider, ←→ adjust)");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let HsvInput {
+            hue,
+            saturation,
+            value,
+            ..
+        } = self.hsv_input;
+        let bar_width = inner.width.saturating_sub(16).max(1) as usize;
+        // Bars start after the "> Hue " label; one blank line between sliders
+        self.slider_area = Some(Rect {
+            x: inner.x + 6,
+            y: inner.y,
+            width: bar_width as u16,
+            height: 5.min(inner.height),
+        });
+        let sliders = [
+            (HsvField::Hue, "Hue", hue, 359),
+            (HsvField::Saturation, "Sat", saturation as u16, 100),
+            (HsvField::Value, "Val", value as u16, 100),
+        ];
+        // Each cell shows the color the slider would produce at that position;
+        // the hue bar stays visible even at low saturation or value
+        let color_at = |field: &HsvField, position: u16| match field {
+            HsvField::Hue => hsv_to_rgb(position, saturation.max(40), value.max(40)),
+            HsvField::Saturation => hsv_to_rgb(hue, position as u8, value),
+            HsvField::Value => hsv_to_rgb(hue, saturation, position as u8),
+        };
+
+        let mut lines = Vec::new();
+        for (field, label, current, max) in sliders {
+            let focused = field == self.hsv_input.editing_field;
+            let marker = current as usize * (bar_width - 1) / max as usize;
+            let mut spans = vec![Span::styled(
+                format!("{} {:<4}", if focused { ">" } else { " " }, label),
+                if focused {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default()
+                },
+            )];
+            for cell in 0..bar_width {
+                let (r, g, b) = color_at(&field, (cell * max as usize / bar_width.max(2)) as u16);
+                let glyph = if cell == marker { "┃" } else { "█" };
+                let style = if cell == marker {
+                    Style::default().fg(Color::White).bg(Color::Rgb(r, g, b))
+                } else {
+                    Style::default().fg(Color::Rgb(r, g, b))
+                };
+                spans.push(Span::styled(glyph, style));
+            }
+            spans.push(Span::raw(format!(" {:>3}", current)));
+            lines.push(Line::from(spans));
+            lines.push(Line::default());
+        }
+
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_recent_colors(&self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Recently Used");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        if self.recent_colors.is_empty() {
+            f.render_widget(
+                Paragraph::new("No recent colors yet").style(Style::default().fg(Color::Gray)),
+                inner,
+            );
+            return;
+        }
+
+        let spans: Vec<Span> = self
+            .recent_colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| {
+                let text = if index == self.selected_recent {
+                    "[ ██ ]"
+                } else {
+                    "  ██  "
+                };
+                Span::styled(text, Style::default().fg(to_ratatui_color(color)))
+            })
+            .collect();
+        f.render_widget(Paragraph::new(Line::from(spans)), inner);
+    }
+
     fn render_rgb_input(&self, f: &mut Frame, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -652,7 +943,10 @@ impl ColorPickerComponent {
                     format!("████ Color 16: {} ({})", c16, get_color_name(*c16))
                 }
                 AnsiColor::Color256 { c256 } => format!("████ Color 256: {}", c256),
-                AnsiColor::Rgb { r, g, b } => format!("████ RGB: ({}, {}, {})", r, g, b),
+                AnsiColor::Rgb { r, g, b } => format!(
+                    "████ RGB: ({}, {}, {})  #{:02X}{:02X}{:02X}",
+                    r, g, b, r, g, b
+                ),
             }
         } else {
             "████ No color selected".to_string()
@@ -661,11 +955,7 @@ impl ColorPickerComponent {
         let color = self
             .current_color
             .as_ref()
-            .map(|c| match c {
-                AnsiColor::Color16 { c16 } => ansi_to_ratatui_color(*c16),
-                AnsiColor::Color256 { c256 } => Color::Indexed(*c256),
-                AnsiColor::Rgb { r, g, b } => Color::Rgb(*r, *g, *b),
-            })
+            .map(to_ratatui_color)
             .unwrap_or(Color::White);
 
         f.render_widget(
@@ -697,6 +987,35 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+fn to_ratatui_color(color: &AnsiColor) -> Color {
+    match color {
+        AnsiColor::Color16 { c16 } => ansi_to_ratatui_color(*c16),
+        AnsiColor::Color256 { c256 } => Color::Indexed(*c256),
+        AnsiColor::Rgb { r, g, b } => Color::Rgb(*r, *g, *b),
+    }
+}
+
+fn get_recent_colors_path() -> std::path::PathBuf {
+    crate::utils::paths::state_dir().join("recent_colors.json")
+}
+
+fn load_recent_colors() -> Vec<AnsiColor> {
+    std::fs::read_to_string(get_recent_colors_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_recent_colors(colors: &[AnsiColor]) {
+    let path = get_recent_colors_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(colors) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
 fn ansi_to_ratatui_color(ansi: u8) -> Color {
     match ansi {
         0 => Color::Black,
@@ -0,0 +1,144 @@
+use crate::config::Config;
+use crate::utils::diff::{self, DiffKind, Hunk};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::fs;
+
+/// Unified diff of the TOML that saving would write against config.toml
+#[derive(Debug, Clone, Default)]
+pub struct ConfigDiffComponent {
+    pub is_open: bool,
+    pub selected: usize,
+    hunks: Vec<Hunk>,
+    /// What config.toml would contain after saving
+    pending: String,
+    on_disk: bool,
+}
+
+impl ConfigDiffComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        self.refresh(config)?;
+        self.selected = 0;
+        self.is_open = true;
+        Ok(())
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.hunks.clear();
+    }
+
+    /// Recompute the diff after the config changed, keeping the selection in range
+    pub fn refresh(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let saved = fs::read_to_string(Config::get_config_path());
+        self.on_disk = saved.is_ok();
+        self.pending = toml::to_string_pretty(config)?;
+        self.hunks = diff::unified_diff(&saved.unwrap_or_default(), &self.pending);
+        self.selected = self.selected.min(self.hunks.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        if self.hunks.is_empty() {
+            return;
+        }
+        self.selected =
+            (self.selected as i32 + delta).clamp(0, self.hunks.len() as i32 - 1) as usize;
+    }
+
+    /// Config with the selected change undone. Fails when the hunk cannot be
+    /// reverted on its own, e.g. when the result would not parse.
+    pub fn revert_selected(&self) -> Result<Config, Box<dyn std::error::Error>> {
+        let hunk = self.hunks.get(self.selected).ok_or("No change selected")?;
+        let reverted = diff::revert_hunk(&self.pending, hunk);
+        Ok(toml::from_str(&reverted)?)
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_area = Rect {
+            x: area.width / 20,
+            y: area.height / 20,
+            width: area.width - area.width / 10,
+            height: area.height - area.height / 10,
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let title = if self.on_disk {
+            format!("Pending Changes ({} hunks)", self.hunks.len())
+        } else {
+            "Pending Changes (config.toml does not exist yet)".to_string()
+        };
+        let popup_block = Block::default().borders(Borders::ALL).title(title);
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Diff
+                Constraint::Length(1), // Actions
+            ])
+            .split(inner);
+
+        let mut lines = Vec::new();
+        let mut selected_top = 0;
+        if self.hunks.is_empty() {
+            lines.push(Line::styled(
+                "No changes: config.toml already matches the editor.",
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        for (index, hunk) in self.hunks.iter().enumerate() {
+            let is_selected = index == self.selected;
+            if is_selected {
+                selected_top = lines.len();
+            }
+            let header_style = if is_selected {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if is_selected { "▶ " } else { "  " },
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(hunk.header(), header_style),
+            ]));
+            for line in &hunk.lines {
+                let (prefix, style) = match line.kind {
+                    DiffKind::Context => (" ", Style::default().fg(Color::Gray)),
+                    DiffKind::Removed => ("-", Style::default().fg(Color::Red)),
+                    DiffKind::Added => ("+", Style::default().fg(Color::Green)),
+                };
+                lines.push(Line::styled(format!("  {}{}", prefix, line.text), style));
+            }
+        }
+
+        // Keep the selected hunk's header in view
+        let scroll = selected_top.saturating_sub(chunks[0].height as usize / 3) as u16;
+        f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), chunks[0]);
+
+        f.render_widget(
+            Paragraph::new("[↑↓] Select Change  [R] Revert Change  [S] Save  [Esc] Close")
+                .style(Style::default().fg(Color::Gray)),
+            chunks[1],
+        );
+    }
+}
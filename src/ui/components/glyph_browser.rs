@@ -0,0 +1,221 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState,
+    },
+    Frame,
+};
+
+/// Bundled index of Nerd Font glyphs: codepoint, icon set, name, category
+const GLYPH_INDEX: &str = include_str!("../../../assets/nerd_font_glyphs.tsv");
+
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    pub glyph: String,
+    pub codepoint: u32,
+    pub icon_set: &'static str,
+    pub name: &'static str,
+    pub category: &'static str,
+}
+
+impl Glyph {
+    /// Every space-separated term must match the name, category, icon set
+    /// (e.g. "fa", "md") or codepoint (e.g. "f07b", "u+f07b")
+    fn matches(&self, query: &str) -> bool {
+        let codepoint = format!("{:x}", self.codepoint);
+        query.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            let term = term.strip_prefix("u+").unwrap_or(&term);
+            self.name.contains(term)
+                || self.category.contains(term)
+                || self.icon_set == term
+                || codepoint == term
+        })
+    }
+}
+
+/// Searchable picker over the bundled Nerd Font index
+#[derive(Debug, Clone)]
+pub struct GlyphBrowserComponent {
+    pub is_open: bool,
+    pub query: String,
+    pub selected: usize,
+    glyphs: Vec<Glyph>,
+    list_state: ListState,
+}
+
+impl Default for GlyphBrowserComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GlyphBrowserComponent {
+    pub fn new() -> Self {
+        Self {
+            is_open: false,
+            query: String::new(),
+            selected: 0,
+            glyphs: load_glyph_index(),
+            list_state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.query.clear();
+        self.select(0);
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if !c.is_control() {
+            self.query.push(c);
+            self.select(0);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.select(0);
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        let count = self.filtered().len();
+        if count == 0 {
+            return;
+        }
+        let index = (self.selected as i32 + delta).clamp(0, count as i32 - 1) as usize;
+        self.select(index);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.list_state.select(Some(index));
+    }
+
+    pub fn filtered(&self) -> Vec<&Glyph> {
+        self.glyphs
+            .iter()
+            .filter(|glyph| glyph.matches(&self.query))
+            .collect()
+    }
+
+    pub fn get_selected_glyph(&self) -> Option<String> {
+        self.filtered()
+            .get(self.selected)
+            .map(|glyph| glyph.glyph.clone())
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = 70_u16.min(area.width.saturating_sub(4));
+        let popup_height = 24_u16.min(area.height.saturating_sub(2));
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Nerd Font Glyphs");
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Search
+                Constraint::Min(3),    // Results
+                Constraint::Length(1), // Actions
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(format!("{}_", self.query)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Search name, category, set or codepoint"),
+            ),
+            chunks[0],
+        );
+
+        let filtered = self.filtered();
+        let count = filtered.len();
+        let items: Vec<ListItem> = filtered
+            .iter()
+            .map(|glyph| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {}  ", glyph.glyph),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(format!("{:<28}", glyph.name)),
+                    Span::styled(
+                        format!("{:<12}", glyph.category),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(
+                        format!("{:<5} U+{:04X}", glyph.icon_set, glyph.codepoint),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let results_layout =
+            Layout::horizontal([Constraint::Min(10), Constraint::Length(1)]).split(chunks[1]);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("{} glyphs", count)),
+            )
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+        f.render_stateful_widget(list, results_layout[0], &mut self.list_state);
+
+        let mut scrollbar_state = ScrollbarState::new(count).position(self.selected);
+        f.render_stateful_widget(
+            Scrollbar::default().orientation(ScrollbarOrientation::VerticalRight),
+            results_layout[1],
+            &mut scrollbar_state,
+        );
+
+        f.render_widget(
+            Paragraph::new("[↑↓] Navigate  [Enter] Use Glyph  [Esc] Back")
+                .style(Style::default().fg(Color::Gray)),
+            chunks[2],
+        );
+    }
+}
+
+fn load_glyph_index() -> Vec<Glyph> {
+    GLYPH_INDEX
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let codepoint = u32::from_str_radix(fields.next()?, 16).ok()?;
+            Some(Glyph {
+                glyph: char::from_u32(codepoint)?.to_string(),
+                codepoint,
+                icon_set: fields.next()?,
+                name: fields.next()?,
+                category: fields.next()?,
+            })
+        })
+        .collect()
+}
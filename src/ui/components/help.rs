@@ -21,6 +21,7 @@ impl HelpComponent {
         status_message: Option<&str>,
         color_picker_open: bool,
         icon_selector_open: bool,
+        dragging: bool,
     ) {
         let help_items = if color_picker_open {
             vec![
@@ -34,21 +35,30 @@ impl HelpComponent {
                 ("[↑↓]", "Navigate"),
                 ("[Tab]", "Style"),
                 ("[C]", "Custom"),
+                ("[/]", "Browse Glyphs"),
                 ("[Enter]", "Select"),
                 ("[Esc]", "Cancel"),
             ]
+        } else if dragging {
+            vec![("[↑↓]", "Move"), ("[Enter]", "Drop"), ("[Esc]", "Cancel")]
         } else {
             vec![
                 ("[Tab]", "Switch Panel"),
                 ("[Enter]", "Toggle/Edit"),
+                ("[M]", "Move"),
                 ("[Shift+↑↓]", "Reorder"),
                 ("[1-4]", "Theme"),
                 ("[P]", "Switch Theme"),
+                ("[G]", "Gallery"),
                 ("[R]", "Reset"),
                 ("[E]", "Edit Separator"),
+                ("[V]", "Preview Width"),
+                ("[L]", "Preview Layout"),
+                ("[D]", "Diff"),
                 ("[S]", "Save Config"),
                 ("[W]", "Write Theme"),
                 ("[Ctrl+S]", "Save Theme"),
+                ("[Ctrl+Z/Y]", "Undo/Redo"),
                 ("[Esc]", "Quit"),
             ]
         };
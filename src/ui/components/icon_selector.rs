@@ -254,7 +254,7 @@ impl IconSelectorComponent {
         let actions = if self.editing_custom {
             "[Enter] Confirm  [Esc] Cancel"
         } else {
-            "[Enter] Select  [Tab] Switch Style  [c] Custom  [Esc] Cancel"
+            "[Enter] Select  [Tab] Switch Style  [c] Custom  [/] Browse  [Esc] Cancel"
         };
 
         f.render_widget(
@@ -1,10 +1,16 @@
 pub mod color_picker;
+pub mod config_diff;
 pub mod editor;
+pub mod glyph_browser;
 pub mod help;
 pub mod icon_selector;
 pub mod name_input;
+pub mod nerd_font_prompt;
 pub mod preview;
+pub mod quit_prompt;
 pub mod segment_list;
 pub mod separator_editor;
 pub mod settings;
+pub mod text_style_editor;
+pub mod theme_gallery;
 pub mod theme_selector;
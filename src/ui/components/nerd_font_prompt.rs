@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Glyphs shown to the user; all of them are tofu without a Nerd Font
+const SAMPLE_GLYPHS: &str = "\u{f024b}  \u{f02a2}  \u{e26d}  \u{f49b}";
+
+#[derive(Debug, Clone, Default)]
+pub struct NerdFontPromptComponent {
+    pub is_open: bool,
+}
+
+impl NerdFontPromptComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = 60_u16.min(area.width.saturating_sub(4));
+        let popup_height = 9_u16;
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height.min(area.height),
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Nerd Font Check");
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Question
+                Constraint::Length(3), // Sample glyphs
+                Constraint::Length(3), // Actions
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new("Do these look like folder, git, chip and bolt icons?"),
+            chunks[0],
+        );
+        f.render_widget(
+            Paragraph::new(SAMPLE_GLYPHS)
+                .style(Style::default().fg(Color::Yellow))
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new("[Y] Yes  [N] No, use plain icons  [Esc] Ask later")
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[2],
+        );
+    }
+}
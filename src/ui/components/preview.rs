@@ -1,6 +1,6 @@
-use crate::config::{Config, SegmentId};
-use crate::core::segments::SegmentData;
-use crate::core::StatusLineGenerator;
+use crate::config::{Config, InputData, SegmentConfig, SegmentId};
+use crate::core::segments::{with_registry, SegmentData};
+use crate::core::{collect_segment, StatusLineGenerator};
 use ratatui::{
     layout::Rect,
     text::{Line, Text},
@@ -9,9 +9,58 @@ use ratatui::{
 };
 use std::collections::HashMap;
 
+/// Terminal width the preview simulates
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PreviewWidth {
+    #[default]
+    Full,
+    Wide,
+    Standard,
+    /// A split pane or a small editor-embedded terminal
+    Narrow,
+}
+
+impl PreviewWidth {
+    pub fn columns(self) -> Option<u16> {
+        match self {
+            PreviewWidth::Full => None,
+            PreviewWidth::Wide => Some(120),
+            PreviewWidth::Standard => Some(80),
+            PreviewWidth::Narrow => Some(50),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            PreviewWidth::Full => PreviewWidth::Wide,
+            PreviewWidth::Wide => PreviewWidth::Standard,
+            PreviewWidth::Standard => PreviewWidth::Narrow,
+            PreviewWidth::Narrow => PreviewWidth::Full,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PreviewWidth::Full => "full",
+            PreviewWidth::Wide => "120 cols",
+            PreviewWidth::Standard => "80 cols",
+            PreviewWidth::Narrow => "50 cols narrow",
+        }
+    }
+}
+
 pub struct PreviewComponent {
     preview_cache: String,
     preview_text: Text<'static>,
+    /// Real payload to render from instead of placeholder data
+    input: Option<InputData>,
+    width_preset: PreviewWidth,
+    /// Show the statusline as the terminal draws it: one line, cut at the edge
+    single_line: bool,
+    /// Columns of the current preview width, including borders
+    display_width: u16,
+    /// Columns cut off in single-line mode
+    overflow: usize,
 }
 
 impl Default for PreviewComponent {
@@ -25,16 +74,43 @@ impl PreviewComponent {
         Self {
             preview_cache: String::new(),
             preview_text: Text::default(),
+            input: None,
+            width_preset: PreviewWidth::default(),
+            single_line: false,
+            display_width: 0,
+            overflow: 0,
         }
     }
 
+    pub fn cycle_width(&mut self) -> PreviewWidth {
+        self.width_preset = self.width_preset.next();
+        self.width_preset
+    }
+
+    /// Switch between segment-wrapped and single-line layout; returns true for single-line
+    pub fn toggle_single_line(&mut self) -> bool {
+        self.single_line = !self.single_line;
+        self.single_line
+    }
+
+    /// Render from a captured payload; `None` goes back to placeholder data
+    pub fn set_input(&mut self, input: Option<InputData>) {
+        self.input = input;
+    }
+
     pub fn update_preview(&mut self, config: &Config) {
         self.update_preview_with_width(config, 80); // Default width
     }
 
     pub fn update_preview_with_width(&mut self, config: &Config, width: u16) {
-        // Generate mock segments data directly for preview
-        let segments_data = self.generate_mock_segments_data(config);
+        let segments_data = self.segments_data(config);
+
+        // Presets wider than the panel are shown at the panel width
+        let width = match self.width_preset.columns() {
+            Some(columns) => (columns + 2).min(width),
+            None => width,
+        };
+        self.display_width = width;
 
         // Generate both string and TUI text versions
         let renderer = StatusLineGenerator::new(config.clone());
@@ -42,9 +118,18 @@ impl PreviewComponent {
         // Keep string version for compatibility (if needed elsewhere)
         self.preview_cache = renderer.generate(segments_data.clone());
 
-        // Generate TUI-optimized text with smart segment wrapping for preview display
         // Use actual available width minus borders
         let content_width = width.saturating_sub(2);
+
+        if self.single_line {
+            let line = renderer.generate_for_tui(segments_data);
+            self.overflow = line.width().saturating_sub(content_width as usize);
+            self.preview_text = Text::from(line);
+            return;
+        }
+        self.overflow = 0;
+
+        // Generate TUI-optimized text with smart segment wrapping for preview display
         let preview_result = renderer.generate_for_tui_preview(segments_data, content_width);
 
         // Convert to owned text by cloning the spans
@@ -71,9 +156,42 @@ impl PreviewComponent {
     }
 
     pub fn render(&self, f: &mut Frame, area: Rect) {
+        let mut title = if self.input.is_some() {
+            "Preview (live input)".to_string()
+        } else {
+            "Preview".to_string()
+        };
+        if self.width_preset != PreviewWidth::Full || self.single_line {
+            title.push_str(&format!(
+                " · {} · {}",
+                self.width_preset.label(),
+                if self.single_line {
+                    "one line"
+                } else {
+                    "wrapped"
+                }
+            ));
+        }
+        if self.overflow > 0 {
+            title.push_str(&format!(" · {} cols cut", self.overflow));
+        }
+
+        let area = Rect {
+            width: if self.display_width > 0 {
+                self.display_width.min(area.width)
+            } else {
+                area.width
+            },
+            ..area
+        };
         let preview = Paragraph::new(self.preview_text.clone())
-            .block(Block::default().borders(Borders::ALL).title("Preview"))
-            .wrap(ratatui::widgets::Wrap { trim: false });
+            .block(Block::default().borders(Borders::ALL).title(title));
+        // Single-line mode clips at the edge like a real terminal would
+        let preview = if self.single_line {
+            preview
+        } else {
+            preview.wrap(ratatui::widgets::Wrap { trim: false })
+        };
         f.render_widget(preview, area);
     }
 
@@ -81,122 +199,250 @@ impl PreviewComponent {
         &self.preview_cache
     }
 
-    /// Generate mock segments data for preview display
+    /// Collect preview data from the real payload when one is set. Network
+    /// segments, and segments with nothing to show for this payload, fall back
+    /// to placeholder data so their styling stays visible while editing.
+    pub fn segments_data(&self, config: &Config) -> Vec<(SegmentConfig, SegmentData)> {
+        crate::i18n::set_language(config.language.as_deref());
+        config
+            .resolved_segments()
+            .iter()
+            .filter(|segment_config| segment_config.enabled)
+            .map(|segment_config| {
+                let live_data = self.input.as_ref().and_then(|input| {
+                    let is_network = with_registry(|registry| {
+                        registry
+                            .info(segment_config.id)
+                            .map(|info| info.network)
+                            .unwrap_or(false)
+                    });
+                    if is_network {
+                        None
+                    } else {
+                        collect_segment(segment_config, input)
+                    }
+                });
+                let data = live_data.unwrap_or_else(|| Self::mock_segment_data(segment_config.id));
+                (segment_config.clone(), data)
+            })
+            .collect()
+    }
+
+    /// Placeholder data for preview display
     /// This creates perfect preview data without depending on real environment
-    fn generate_mock_segments_data(
-        &self,
-        config: &Config,
-    ) -> Vec<(crate::config::SegmentConfig, SegmentData)> {
-        let mut segments_data = Vec::new();
-
-        for segment_config in &config.segments {
-            if !segment_config.enabled {
-                continue;
-            }
-
-            let mock_data = match segment_config.id {
-                SegmentId::Model => SegmentData {
-                    primary: "Sonnet 4".to_string(),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("model".to_string(), "claude-4-sonnet-20250512".to_string());
-                        map
-                    },
-                },
-                SegmentId::Directory => SegmentData {
-                    primary: "CCometixLine".to_string(),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("current_dir".to_string(), "~/CCometixLine".to_string());
-                        map
-                    },
-                },
-                SegmentId::Git => SegmentData {
-                    primary: "master".to_string(),
-                    secondary: "✓".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("branch".to_string(), "master".to_string());
-                        map.insert("status".to_string(), "Clean".to_string());
-                        map.insert("ahead".to_string(), "0".to_string());
-                        map.insert("behind".to_string(), "0".to_string());
-                        map
-                    },
-                },
-                SegmentId::Usage => SegmentData {
-                    primary: "78.2%".to_string(),
-                    secondary: "· 156.4k".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("total_tokens".to_string(), "156400".to_string());
-                        map.insert("percentage".to_string(), "78.2".to_string());
-                        map.insert("session_tokens".to_string(), "48200".to_string());
-                        map
-                    },
-                },
-                SegmentId::Cost => SegmentData {
-                    primary: "$0.02".to_string(),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("cost".to_string(), "0.01234".to_string());
-                        map
-                    },
-                },
-                SegmentId::Session => SegmentData {
-                    primary: "3m45s".to_string(),
-                    secondary: "+156 -23".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("duration_ms".to_string(), "225000".to_string());
-                        map.insert("lines_added".to_string(), "156".to_string());
-                        map.insert("lines_removed".to_string(), "23".to_string());
-                        map
-                    },
-                },
-                SegmentId::OutputStyle => SegmentData {
-                    primary: "default".to_string(),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("style_name".to_string(), "default".to_string());
-                        map
-                    },
-                },
-                SegmentId::Update => SegmentData {
-                    primary: format!("v{}", env!("CARGO_PKG_VERSION")),
-                    secondary: "".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert(
-                            "current_version".to_string(),
-                            env!("CARGO_PKG_VERSION").to_string(),
-                        );
-                        map.insert("update_available".to_string(), "false".to_string());
-                        map
-                    },
-                },
-                SegmentId::Quota => SegmentData {
-                    primary: "$88.48".to_string(),
-                    secondary: "Opus✓".to_string(),
-                    metadata: {
-                        let mut map = HashMap::new();
-                        map.insert("raw_spent".to_string(), "88.4846".to_string());
-                        map.insert("opus_enabled".to_string(), "true".to_string());
-                        map.insert(
-                            "endpoint_used".to_string(),
-                            "https://www.packycode.com/api/backend/users/info".to_string(),
-                        );
-                        map
-                    },
-                },
-            };
-
-            segments_data.push((segment_config.clone(), mock_data));
+    fn mock_segment_data(id: SegmentId) -> SegmentData {
+        match id {
+            SegmentId::Model => SegmentData {
+                primary: "Sonnet 4".to_string(),
+                secondary: "".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("model".to_string(), "claude-4-sonnet-20250512".to_string());
+                    map
+                },
+            },
+            SegmentId::Directory => SegmentData {
+                primary: "CCometixLine".to_string(),
+                secondary: "".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("current_dir".to_string(), "~/CCometixLine".to_string());
+                    map
+                },
+            },
+            SegmentId::Git => SegmentData {
+                primary: "master".to_string(),
+                secondary: "✓".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("branch".to_string(), "master".to_string());
+                    map.insert("status".to_string(), "Clean".to_string());
+                    map.insert("ahead".to_string(), "0".to_string());
+                    map.insert("behind".to_string(), "0".to_string());
+                    map
+                },
+            },
+            SegmentId::Usage => SegmentData {
+                primary: "78.2%".to_string(),
+                secondary: "· 156.4k".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("total_tokens".to_string(), "156400".to_string());
+                    map.insert("percentage".to_string(), "78.2".to_string());
+                    map.insert("session_tokens".to_string(), "48200".to_string());
+                    map
+                },
+            },
+            SegmentId::Cost => SegmentData {
+                primary: "$0.02".to_string(),
+                secondary: "".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("cost".to_string(), "0.01234".to_string());
+                    map
+                },
+            },
+            SegmentId::Session => SegmentData {
+                primary: "3m45s".to_string(),
+                secondary: "+156 -23".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("duration_ms".to_string(), "225000".to_string());
+                    map.insert("lines_added".to_string(), "156".to_string());
+                    map.insert("lines_removed".to_string(), "23".to_string());
+                    map
+                },
+            },
+            SegmentId::OutputStyle => SegmentData {
+                primary: "default".to_string(),
+                secondary: "".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("style_name".to_string(), "default".to_string());
+                    map
+                },
+            },
+            SegmentId::Update => SegmentData {
+                primary: format!("v{}", env!("CARGO_PKG_VERSION")),
+                secondary: "".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert(
+                        "current_version".to_string(),
+                        env!("CARGO_PKG_VERSION").to_string(),
+                    );
+                    map.insert("update_available".to_string(), "false".to_string());
+                    map
+                },
+            },
+            SegmentId::Quota => SegmentData {
+                primary: "$88.48".to_string(),
+                secondary: "Opus✓".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("raw_spent".to_string(), "88.4846".to_string());
+                    map.insert("opus_enabled".to_string(), "true".to_string());
+                    map.insert(
+                        "endpoint_used".to_string(),
+                        "https://www.packycode.com/api/backend/users/info".to_string(),
+                    );
+                    map
+                },
+            },
+            SegmentId::Container => SegmentData {
+                primary: "my-project".to_string(),
+                secondary: "Dev Container".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("kind".to_string(), "devcontainer".to_string());
+                    map.insert("name".to_string(), "my-project".to_string());
+                    map
+                },
+            },
+            SegmentId::Plan => SegmentData {
+                primary: "Max 5x".to_string(),
+                secondary: "64% left this week".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("plan".to_string(), "Max 5x".to_string());
+                    map.insert("source".to_string(), "credentials".to_string());
+                    map.insert("weekly_used_percent".to_string(), "36.0".to_string());
+                    map.insert("weekly_left_percent".to_string(), "64.0".to_string());
+                    map
+                },
+            },
+            SegmentId::Identity => SegmentData {
+                primary: "amber-falcon".to_string(),
+                secondary: "key".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("fingerprint".to_string(), "amber-falcon".to_string());
+                    map.insert("kind".to_string(), "key".to_string());
+                    map.insert("source".to_string(), "env:ANTHROPIC_API_KEY".to_string());
+                    map
+                },
+            },
+            SegmentId::Hooks => SegmentData {
+                primary: "PreToolUse:Bash".to_string(),
+                secondary: "failed (1)".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("state".to_string(), "failed".to_string());
+                    map.insert("hook".to_string(), "PreToolUse:Bash".to_string());
+                    map.insert("exit_code".to_string(), "1".to_string());
+                    map
+                },
+            },
+            SegmentId::Memory => SegmentData {
+                primary: "3 CLAUDE.md".to_string(),
+                secondary: "12.4 KB".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("count".to_string(), "3".to_string());
+                    map.insert("bytes".to_string(), "12698".to_string());
+                    map.insert("user_count".to_string(), "1".to_string());
+                    map.insert("project_count".to_string(), "2".to_string());
+                    map
+                },
+            },
+            SegmentId::Transcript => SegmentData {
+                primary: "214 msgs".to_string(),
+                secondary: "3.2 MB".to_string(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("bytes".to_string(), "3355443".to_string());
+                    map.insert("messages".to_string(), "214".to_string());
+                    map
+                },
+            },
+            SegmentId::Computed => SegmentData {
+                primary: "$4.12/h".to_string(),
+                secondary: String::new(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("value".to_string(), "4.12".to_string());
+                    map
+                },
+            },
+            SegmentId::Modes => SegmentData {
+                primary: "YOLO".to_string(),
+                secondary: String::new(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("state".to_string(), "bypass".to_string());
+                    map.insert("modes".to_string(), "bypass".to_string());
+                    map.insert("level".to_string(), "critical".to_string());
+                    map
+                },
+            },
+            SegmentId::Attention => SegmentData {
+                primary: "Approve Bash".to_string(),
+                secondary: String::new(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("state".to_string(), "permission".to_string());
+                    map.insert("tool".to_string(), "Bash".to_string());
+                    map.insert("waiting_seconds".to_string(), "12".to_string());
+                    map
+                },
+            },
+            SegmentId::Vim => SegmentData {
+                primary: "NORMAL".to_string(),
+                secondary: String::new(),
+                metadata: {
+                    let mut map = HashMap::new();
+                    map.insert("mode".to_string(), "NORMAL".to_string());
+                    map.insert("state".to_string(), "normal".to_string());
+                    map.insert("source".to_string(), "input".to_string());
+                    map
+                },
+            },
+            // Runtime segments show their name until they produce data
+            SegmentId::Custom(name) => SegmentData {
+                primary: name.to_string(),
+                secondary: String::new(),
+                metadata: HashMap::new(),
+            },
         }
-
-        segments_data
     }
 }
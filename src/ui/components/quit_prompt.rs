@@ -0,0 +1,68 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Asks before quitting with edits that were not saved to config.toml
+#[derive(Debug, Clone, Default)]
+pub struct QuitPromptComponent {
+    pub is_open: bool,
+}
+
+impl QuitPromptComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = 60_u16.min(area.width.saturating_sub(4));
+        let popup_height = 6_u16;
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height.min(area.height),
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Unsaved Changes")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Question
+                Constraint::Length(3), // Actions
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new("Your edits are not saved to config.toml. Quit anyway?"),
+            chunks[0],
+        );
+        f.render_widget(
+            Paragraph::new("[S] Save and quit  [Q] Quit without saving  [Esc] Keep editing")
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+}
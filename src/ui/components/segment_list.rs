@@ -1,4 +1,5 @@
-use crate::config::{Config, SegmentId};
+use crate::config::Config;
+use crate::core::segments::segment_name;
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -24,6 +25,22 @@ pub enum FieldSelection {
     Options,
 }
 
+impl FieldSelection {
+    /// Field shown on the given row of the settings panel, in display order
+    pub fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(FieldSelection::Enabled),
+            1 => Some(FieldSelection::Icon),
+            2 => Some(FieldSelection::IconColor),
+            3 => Some(FieldSelection::TextColor),
+            4 => Some(FieldSelection::BackgroundColor),
+            5 => Some(FieldSelection::TextStyle),
+            6 => Some(FieldSelection::Options),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct SegmentListComponent;
 
@@ -39,6 +56,7 @@ impl SegmentListComponent {
         config: &Config,
         selected_segment: usize,
         selected_panel: &Panel,
+        dragging: bool,
     ) {
         let items: Vec<ListItem> = config
             .segments
@@ -47,19 +65,18 @@ impl SegmentListComponent {
             .map(|(i, segment)| {
                 let is_selected = i == selected_segment && *selected_panel == Panel::SegmentList;
                 let enabled_marker = if segment.enabled { "●" } else { "○" };
-                let segment_name = match segment.id {
-                    SegmentId::Model => "Model",
-                    SegmentId::Directory => "Directory",
-                    SegmentId::Git => "Git",
-                    SegmentId::Usage => "Usage",
-                    SegmentId::Cost => "Cost",
-                    SegmentId::Session => "Session",
-                    SegmentId::OutputStyle => "Output Style",
-                    SegmentId::Update => "Update",
-                    SegmentId::Quota => "Quota",
-                };
+                let segment_name = segment_name(segment.id);
 
-                if is_selected {
+                if is_selected && dragging {
+                    // Picked up item follows the arrow keys
+                    ListItem::new(Line::from(vec![
+                        Span::styled("↕ ", Style::default().fg(Color::Yellow)),
+                        Span::styled(
+                            format!("{} {}", enabled_marker, segment_name),
+                            Style::default().fg(Color::Black).bg(Color::Yellow),
+                        ),
+                    ]))
+                } else if is_selected {
                     // Selected item with colored cursor
                     ListItem::new(Line::from(vec![
                         Span::styled("▶ ", Style::default().fg(Color::Cyan)),
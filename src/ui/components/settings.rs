@@ -1,5 +1,7 @@
 use super::segment_list::{FieldSelection, Panel};
-use crate::config::{Config, SegmentId, StyleMode};
+use super::text_style_editor;
+use crate::config::{Config, StyleMode};
+use crate::core::segments::segment_name;
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -26,17 +28,7 @@ impl SettingsComponent {
         selected_field: &FieldSelection,
     ) {
         if let Some(segment) = config.segments.get(selected_segment) {
-            let segment_name = match segment.id {
-                SegmentId::Model => "Model",
-                SegmentId::Directory => "Directory",
-                SegmentId::Git => "Git",
-                SegmentId::Usage => "Usage",
-                SegmentId::Cost => "Cost",
-                SegmentId::Session => "Session",
-                SegmentId::OutputStyle => "Output Style",
-                SegmentId::Update => "Update",
-                SegmentId::Quota => "Quota",
-            };
+            let segment_name = segment_name(segment.id);
             let current_icon = match config.style.mode {
                 StyleMode::Plain => &segment.icon.plain,
                 StyleMode::NerdFont | StyleMode::Powerline => &segment.icon.nerd_font,
@@ -258,12 +250,8 @@ impl SettingsComponent {
                 create_field_line(
                     FieldSelection::TextStyle,
                     vec![Span::raw(format!(
-                        "├─ Text Style: Bold {}",
-                        if segment.styles.text_bold {
-                            "[✓]"
-                        } else {
-                            "[ ]"
-                        }
+                        "├─ Text Style: {}",
+                        text_style_editor::describe(&segment.styles)
                     ))],
                 ),
                 create_field_line(
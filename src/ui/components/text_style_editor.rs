@@ -0,0 +1,199 @@
+use super::color_picker::NavDirection;
+use crate::config::{TextStyle, TextStyleConfig};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const PARTS: [&str; 3] = ["Icon", "Primary", "Secondary"];
+const ATTRIBUTES: [&str; 6] = ["Bold", "Italic", "Underline", "Strike", "Dim", "Blink"];
+
+/// Grid of segment parts (rows) and text attributes (columns)
+#[derive(Debug, Clone, Default)]
+pub struct TextStyleEditorComponent {
+    pub is_open: bool,
+    pub styles: TextStyleConfig,
+    pub selected_part: usize,
+    pub selected_attribute: usize,
+}
+
+impl TextStyleEditorComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, current: &TextStyleConfig) {
+        self.is_open = true;
+        // Fold the legacy flag into the per-part styles so each part can be edited on its own
+        self.styles = TextStyleConfig {
+            text_bold: false,
+            icon: current.icon,
+            primary: current.primary_style(),
+            secondary: current.secondary_style(),
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub fn move_direction(&mut self, direction: NavDirection) {
+        match direction {
+            NavDirection::Up => {
+                self.selected_part = self.selected_part.saturating_sub(1);
+            }
+            NavDirection::Down => {
+                self.selected_part = (self.selected_part + 1).min(PARTS.len() - 1);
+            }
+            NavDirection::Left => {
+                self.selected_attribute = self.selected_attribute.saturating_sub(1);
+            }
+            NavDirection::Right => {
+                self.selected_attribute = (self.selected_attribute + 1).min(ATTRIBUTES.len() - 1);
+            }
+        }
+    }
+
+    /// Flip the selected attribute and return the updated styles
+    pub fn toggle_selected(&mut self) -> &TextStyleConfig {
+        let style = match self.selected_part {
+            0 => &mut self.styles.icon,
+            1 => &mut self.styles.primary,
+            _ => &mut self.styles.secondary,
+        };
+        let flag = match self.selected_attribute {
+            0 => &mut style.bold,
+            1 => &mut style.italic,
+            2 => &mut style.underline,
+            3 => &mut style.strikethrough,
+            4 => &mut style.dim,
+            _ => &mut style.blink,
+        };
+        *flag = !*flag;
+        &self.styles
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_width = 80_u16.min(area.width.saturating_sub(4));
+        let popup_height = 10_u16;
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height.min(area.height),
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default().borders(Borders::ALL).title("Text Style");
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5), // Grid
+                Constraint::Length(3), // Actions
+            ])
+            .split(inner);
+
+        let mut header = vec![Span::raw(format!("{:<11}", ""))];
+        header.extend(
+            ATTRIBUTES
+                .iter()
+                .map(|name| Span::raw(format!("{:<11}", name))),
+        );
+        let mut lines = vec![Line::from(header)];
+
+        for (row, part) in PARTS.iter().enumerate() {
+            let style = match row {
+                0 => self.styles.icon,
+                1 => self.styles.primary,
+                _ => self.styles.secondary,
+            };
+            let mut spans = vec![Span::styled(
+                format!("{:<11}", part),
+                to_modifier_style(style),
+            )];
+            for (col, enabled) in attribute_flags(style).into_iter().enumerate() {
+                let cell = format!("{:<11}", if enabled { "[✓]" } else { "[ ]" });
+                let cell_style = if row == self.selected_part && col == self.selected_attribute {
+                    Style::default().fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default()
+                };
+                spans.push(Span::styled(cell, cell_style));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+        f.render_widget(
+            Paragraph::new("[←↑↓→] Navigate  [Space/Enter] Toggle  [Esc] Done")
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+}
+
+fn attribute_flags(style: TextStyle) -> [bool; 6] {
+    [
+        style.bold,
+        style.italic,
+        style.underline,
+        style.strikethrough,
+        style.dim,
+        style.blink,
+    ]
+}
+
+/// Show the part name in its own style
+fn to_modifier_style(style: TextStyle) -> Style {
+    let mut modifier = Modifier::empty();
+    for (enabled, flag) in attribute_flags(style).into_iter().zip([
+        Modifier::BOLD,
+        Modifier::ITALIC,
+        Modifier::UNDERLINED,
+        Modifier::CROSSED_OUT,
+        Modifier::DIM,
+        Modifier::SLOW_BLINK,
+    ]) {
+        if enabled {
+            modifier |= flag;
+        }
+    }
+    Style::default().add_modifier(modifier)
+}
+
+/// Short summary like "primary bold+italic" for the settings panel
+pub fn describe(styles: &TextStyleConfig) -> String {
+    let parts: Vec<String> = [
+        ("icon", styles.icon),
+        ("primary", styles.primary_style()),
+        ("secondary", styles.secondary_style()),
+    ]
+    .into_iter()
+    .filter(|(_, style)| !style.is_plain())
+    .map(|(part, style)| {
+        let names: Vec<&str> = attribute_flags(style)
+            .into_iter()
+            .zip(ATTRIBUTES)
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect();
+        format!("{} {}", part, names.join("+").to_lowercase())
+    })
+    .collect();
+
+    if parts.is_empty() {
+        "Normal".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
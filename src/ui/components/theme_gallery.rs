@@ -0,0 +1,168 @@
+use super::preview::PreviewComponent;
+use crate::config::Config;
+use crate::core::StatusLineGenerator;
+use crate::ui::themes::ThemePresets;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Lines taken by one theme: title, preview and a spacer
+const ENTRY_HEIGHT: usize = 3;
+
+struct GalleryEntry {
+    name: String,
+    description: String,
+    preview: Line<'static>,
+}
+
+/// Every built-in and user theme rendered against the same segment data
+#[derive(Default)]
+pub struct ThemeGalleryComponent {
+    pub is_open: bool,
+    pub selected: usize,
+    current: Option<Line<'static>>,
+    entries: Vec<GalleryEntry>,
+}
+
+impl ThemeGalleryComponent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render all themes once up front; collecting segment data is too slow per frame
+    pub fn open(&mut self, config: &Config, preview: &PreviewComponent) {
+        let descriptions = ThemePresets::get_available_themes();
+
+        self.current = Some(
+            StatusLineGenerator::new(config.clone())
+                .generate_for_tui(preview.segments_data(config)),
+        );
+        self.entries = ThemePresets::list_available_themes()
+            .into_iter()
+            .map(|name| {
+                let theme = ThemePresets::get_theme(&name);
+                let description = descriptions
+                    .iter()
+                    .find(|(builtin, _)| *builtin == name)
+                    .map(|(_, description)| description.to_string())
+                    .unwrap_or_else(|| "Custom theme".to_string());
+                let preview = StatusLineGenerator::new(theme.clone())
+                    .generate_for_tui(preview.segments_data(&theme));
+                GalleryEntry {
+                    name,
+                    description,
+                    preview,
+                }
+            })
+            .collect();
+
+        self.selected = self
+            .entries
+            .iter()
+            .position(|entry| entry.name == config.active_theme())
+            .unwrap_or(0);
+        self.is_open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.current = None;
+        self.entries.clear();
+    }
+
+    pub fn move_selection(&mut self, delta: i32) {
+        if self.entries.is_empty() {
+            return;
+        }
+        self.selected =
+            (self.selected as i32 + delta).clamp(0, self.entries.len() as i32 - 1) as usize;
+    }
+
+    pub fn get_selected_theme(&self) -> Option<&str> {
+        self.entries
+            .get(self.selected)
+            .map(|entry| entry.name.as_str())
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        if !self.is_open {
+            return;
+        }
+
+        let popup_area = Rect {
+            x: area.width / 20,
+            y: area.height / 20,
+            width: area.width - area.width / 10,
+            height: area.height - area.height / 10,
+        };
+
+        f.render_widget(Clear, popup_area);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Theme Gallery ({} themes)", self.entries.len()));
+        let inner = popup_block.inner(popup_area);
+        f.render_widget(popup_block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Current edits
+                Constraint::Min(3),    // Themes
+                Constraint::Length(1), // Actions
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(self.current.clone().unwrap_or_default()).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Current edits"),
+            ),
+            chunks[0],
+        );
+
+        // Scroll so the selected theme stays visible
+        let visible = (chunks[1].height as usize / ENTRY_HEIGHT).max(1);
+        let first = self.selected.saturating_sub(visible - 1);
+
+        let mut lines = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate().skip(first).take(visible) {
+            let is_selected = index == self.selected;
+            let marker = if is_selected { "▶ " } else { "  " };
+            let name_style = if is_selected {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Cyan)),
+                Span::styled(entry.name.clone(), name_style),
+                Span::styled(
+                    format!("  {}", entry.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+
+            let mut preview_spans = vec![Span::raw("  ")];
+            preview_spans.extend(entry.preview.spans.iter().cloned());
+            lines.push(Line::from(preview_spans));
+            lines.push(Line::default());
+        }
+        f.render_widget(Paragraph::new(lines), chunks[1]);
+
+        f.render_widget(
+            Paragraph::new(
+                "[↑↓] Navigate  [Enter] Apply Theme  [N] Save Edits as New Theme  [Esc] Close",
+            )
+            .style(Style::default().fg(Color::Gray)),
+            chunks[2],
+        );
+    }
+}
@@ -4,6 +4,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Default)]
 pub struct ThemeSelectorComponent;
@@ -17,19 +18,57 @@ impl ThemeSelectorComponent {
         let is_modified = config.is_modified_from_theme();
         let modified_indicator = if is_modified { "*" } else { "" };
 
-        // Get all available themes dynamically
-        let available_themes = crate::ui::themes::ThemePresets::list_available_themes();
-
         // Calculate available width (minus borders and spacing)
         let content_width = area.width.saturating_sub(2); // Remove borders
 
-        // Build theme options with auto-wrapping
+        let lines: Vec<String> = Self::wrap_themes(config, content_width as usize)
+            .into_iter()
+            .map(|line| line.into_iter().map(|(part, _)| part).collect())
+            .collect();
+
+        // Add separator display at the end
+        let separator_display = format!("\nSeparator: \"{}\"", config.style.separator);
+
+        let full_text = format!("{}{}", lines.join("\n"), separator_display);
+        let title = format!("Themes{}", modified_indicator);
+        let theme_selector = Paragraph::new(full_text)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        f.render_widget(theme_selector, area);
+    }
+
+    /// Theme whose entry is at the given screen position, for mouse clicks
+    pub fn theme_at(&self, area: Rect, config: &Config, column: u16, row: u16) -> Option<String> {
+        if column <= area.x || row <= area.y {
+            return None;
+        }
+        let content_width = area.width.saturating_sub(2) as usize;
+        let lines = Self::wrap_themes(config, content_width);
+        let line = lines.get((row - area.y - 1) as usize)?;
+
+        let mut start = area.x + 1;
+        for (part, theme) in line {
+            let end = start + part.width() as u16;
+            if column < end {
+                return Some(theme.clone());
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Theme options with auto-wrapping: each line holds (text, theme name) parts
+    fn wrap_themes(config: &Config, content_width: usize) -> Vec<Vec<(String, String)>> {
+        // Get all available themes dynamically
+        let available_themes = crate::ui::themes::ThemePresets::list_available_themes();
+
         let mut lines = Vec::new();
-        let mut current_line = String::new();
+        let mut current_line: Vec<(String, String)> = Vec::new();
+        let mut current_len = 0;
         let mut first_line = true;
 
         for (i, theme) in available_themes.iter().enumerate() {
-            let marker = if config.theme == *theme {
+            let marker = if config.active_theme() == theme {
                 "[✓]"
             } else {
                 "[ ]"
@@ -39,30 +78,23 @@ impl ThemeSelectorComponent {
             let part_with_sep = format!("{}{}", separator, theme_part);
 
             // Check if this part fits in current line
-            let would_fit = current_line.len() + part_with_sep.len() <= content_width as usize;
+            let would_fit = current_len + part_with_sep.len() <= content_width;
 
             if would_fit || first_line {
-                current_line.push_str(&part_with_sep);
+                current_len += part_with_sep.len();
+                current_line.push((part_with_sep, theme.clone()));
                 first_line = false;
             } else {
                 // Start new line
-                lines.push(current_line);
-                current_line = theme_part; // No indent for continuation lines
+                lines.push(std::mem::take(&mut current_line));
+                current_len = theme_part.len();
+                current_line.push((theme_part, theme.clone())); // No indent for continuation lines
             }
         }
 
-        if !current_line.trim().is_empty() {
+        if !current_line.is_empty() {
             lines.push(current_line);
         }
-
-        // Add separator display at the end
-        let separator_display = format!("\nSeparator: \"{}\"", config.style.separator);
-
-        let full_text = format!("{}{}", lines.join("\n"), separator_display);
-        let title = format!("Themes{}", modified_indicator);
-        let theme_selector = Paragraph::new(full_text)
-            .block(Block::default().borders(Borders::ALL).title(title))
-            .wrap(ratatui::widgets::Wrap { trim: false });
-        f.render_widget(theme_selector, area);
+        lines
     }
 }
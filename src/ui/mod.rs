@@ -8,7 +8,6 @@ pub mod events;
 pub mod layout;
 #[cfg(feature = "tui")]
 pub mod main_menu;
-#[cfg(feature = "tui")]
 pub mod themes;
 
 #[cfg(feature = "tui")]
@@ -16,13 +15,18 @@ pub use app::App;
 #[cfg(feature = "tui")]
 pub use main_menu::{MainMenu, MenuResult};
 
+/// Launch the TUI editor; with `input` the preview renders from a real payload
 #[cfg(feature = "tui")]
-pub fn run_configurator() -> Result<(), Box<dyn std::error::Error>> {
-    App::run()
+pub fn run_configurator(
+    input: Option<crate::config::InputData>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    App::run(input)
 }
 
 #[cfg(not(feature = "tui"))]
-pub fn run_configurator() -> Result<(), Box<dyn std::error::Error>> {
+pub fn run_configurator(
+    _input: Option<crate::config::InputData>,
+) -> Result<(), Box<dyn std::error::Error>> {
     eprintln!("TUI feature is not enabled. Please install with --features tui");
     std::process::exit(1);
 }
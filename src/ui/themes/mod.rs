@@ -1,12 +1,22 @@
 pub mod presets;
+pub mod theme_catppuccin;
+pub mod theme_colorblind_deuteranopia;
+pub mod theme_colorblind_protanopia;
 pub mod theme_cometix;
 pub mod theme_default;
+pub mod theme_dracula;
 pub mod theme_gruvbox;
+pub mod theme_high_contrast_light;
 pub mod theme_minimal;
 pub mod theme_nord;
+pub mod theme_powerline_catppuccin;
 pub mod theme_powerline_dark;
+pub mod theme_powerline_dracula;
+pub mod theme_powerline_gruvbox;
 pub mod theme_powerline_light;
 pub mod theme_powerline_rose_pine;
 pub mod theme_powerline_tokyo_night;
+pub mod theme_solarized_light;
+pub mod theme_tokyo_night;
 
 pub use presets::*;
@@ -1,12 +1,20 @@
 // Theme presets for TUI configuration
 
-use crate::config::{Config, StyleConfig, StyleMode, SegmentConfig, SegmentId, IconConfig, ColorConfig, AnsiColor, TextStyleConfig};
+use crate::config::{
+    AlertsConfig, AnsiColor, BalanceConfig, CacheConfig, ColorConfig, Config, EmergencyConfig,
+    FocusConfig, HandoffConfig, IconConfig, NetworkConfig, NumberFormat, PlatformDefault,
+    PricingConfig, ReportConfig, SegmentConfig, SegmentId, SpacingConfig, StyleConfig, StyleMode,
+    TextStyleConfig, ThemeBackground, UpdateConfig, CONFIG_VERSION,
+};
 use std::collections::HashMap;
 
 // Import all theme modules
 use super::{
-    theme_cometix, theme_default, theme_gruvbox, theme_minimal, theme_nord, theme_powerline_dark,
-    theme_powerline_light, theme_powerline_rose_pine, theme_powerline_tokyo_night,
+    theme_catppuccin, theme_colorblind_deuteranopia, theme_colorblind_protanopia, theme_cometix,
+    theme_default, theme_dracula, theme_gruvbox, theme_high_contrast_light, theme_minimal,
+    theme_nord, theme_powerline_catppuccin, theme_powerline_dark, theme_powerline_dracula,
+    theme_powerline_gruvbox, theme_powerline_light, theme_powerline_rose_pine,
+    theme_powerline_tokyo_night, theme_solarized_light, theme_tokyo_night,
 };
 
 pub struct ThemePresets;
@@ -29,6 +37,16 @@ impl ThemePresets {
             "powerline-light" => Self::get_powerline_light(),
             "powerline-rose-pine" => Self::get_powerline_rose_pine(),
             "powerline-tokyo-night" => Self::get_powerline_tokyo_night(),
+            "catppuccin" => Self::get_catppuccin(),
+            "dracula" => Self::get_dracula(),
+            "tokyo-night" => Self::get_tokyo_night(),
+            "powerline-catppuccin" => Self::get_powerline_catppuccin(),
+            "powerline-dracula" => Self::get_powerline_dracula(),
+            "powerline-gruvbox" => Self::get_powerline_gruvbox(),
+            "solarized-light" => Self::get_solarized_light(),
+            "high-contrast-light" => Self::get_high_contrast_light(),
+            "colorblind-deuteranopia" => Self::get_colorblind_deuteranopia(),
+            "colorblind-protanopia" => Self::get_colorblind_protanopia(),
             _ => Self::get_default(),
         }
     }
@@ -42,22 +60,19 @@ impl ThemePresets {
             return Err(format!("Theme file not found: {}", theme_path.display()).into());
         }
 
+        // Theme files from older releases are migrated in memory only
         let content = std::fs::read_to_string(&theme_path)?;
-        let mut config: Config = toml::from_str(&content)?;
+        let mut config = crate::config::migrate::parse(&content)?.config;
 
         // Ensure the theme field matches the requested theme
-        config.theme = theme_name.to_string();
+        config.theme = theme_name.into();
 
         Ok(config)
     }
 
-    /// Get the themes directory path (~/.claude/ccline/themes/)
+    /// Get the themes directory path (themes/ in the config directory)
     fn get_themes_path() -> std::path::PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".claude").join("ccline").join("themes")
-        } else {
-            std::path::PathBuf::from(".claude/ccline/themes")
-        }
+        crate::utils::paths::config_dir().join("themes")
     }
 
     /// Save current config as a new theme
@@ -70,27 +85,20 @@ impl ThemePresets {
 
         // Create a copy of config with the correct theme name
         let mut theme_config = config.clone();
-        theme_config.theme = theme_name.to_string();
+        theme_config.theme = theme_name.into();
 
         let content = toml::to_string_pretty(&theme_config)?;
-        std::fs::write(&theme_path, content)?;
+        crate::utils::atomic::write(&theme_path, content.as_bytes())?;
 
         Ok(())
     }
 
     /// List all available themes (built-in + custom)
     pub fn list_available_themes() -> Vec<String> {
-        let mut themes = vec![
-            "cometix".to_string(),
-            "default".to_string(),
-            "minimal".to_string(),
-            "gruvbox".to_string(),
-            "nord".to_string(),
-            "powerline-dark".to_string(),
-            "powerline-light".to_string(),
-            "powerline-rose-pine".to_string(),
-            "powerline-tokyo-night".to_string(),
-        ];
+        let mut themes: Vec<String> = Self::get_available_themes()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
 
         // Add custom themes from file system
         if let Ok(themes_dir) = std::fs::read_dir(Self::get_themes_path()) {
@@ -120,6 +128,28 @@ impl ThemePresets {
             ("powerline-light", "Light powerline theme"),
             ("powerline-rose-pine", "Rose Pine powerline theme"),
             ("powerline-tokyo-night", "Tokyo Night powerline theme"),
+            ("catppuccin", "Catppuccin Mocha color scheme"),
+            ("dracula", "Dracula color scheme"),
+            ("tokyo-night", "Tokyo Night color scheme"),
+            ("powerline-catppuccin", "Catppuccin Mocha powerline theme"),
+            ("powerline-dracula", "Dracula powerline theme"),
+            ("powerline-gruvbox", "Gruvbox powerline theme"),
+            (
+                "solarized-light",
+                "Solarized color scheme for light terminals",
+            ),
+            (
+                "high-contrast-light",
+                "High-contrast theme for light terminals",
+            ),
+            (
+                "colorblind-deuteranopia",
+                "Okabe-Ito palette, safe for deuteranopia",
+            ),
+            (
+                "colorblind-protanopia",
+                "Blue/yellow palette, safe for protanopia",
+            ),
         ]
     }
 
@@ -128,6 +158,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_cometix::model_segment(),
@@ -140,7 +178,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "cometix".to_string(),
+            theme: "cometix".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -149,6 +202,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_default::model_segment(),
@@ -161,7 +222,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "default".to_string(),
+            theme: "default".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -170,6 +246,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " │ ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_minimal::model_segment(),
@@ -182,7 +266,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "minimal".to_string(),
+            theme: "minimal".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -191,6 +290,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_gruvbox::model_segment(),
@@ -203,7 +310,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "gruvbox".to_string(),
+            theme: "gruvbox".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -212,6 +334,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_nord::model_segment(),
@@ -224,7 +354,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "nord".to_string(),
+            theme: "nord".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -233,6 +378,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_powerline_dark::model_segment(),
@@ -245,7 +398,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "powerline-dark".to_string(),
+            theme: "powerline-dark".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -254,6 +422,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                background: ThemeBackground::Light,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_powerline_light::model_segment(),
@@ -264,9 +440,24 @@ impl ThemePresets {
                 theme_powerline_light::session_segment(),
                 theme_powerline_light::output_style_segment(),
                 // PackyCode quota segment
-                Self::quota_segment(),
+                theme_powerline_light::quota_segment(),
             ],
-            theme: "powerline-light".to_string(),
+            theme: "powerline-light".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -275,6 +466,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_powerline_rose_pine::model_segment(),
@@ -287,7 +486,22 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "powerline-rose-pine".to_string(),
+            theme: "powerline-rose-pine".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
@@ -296,6 +510,14 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
             },
             segments: vec![
                 theme_powerline_tokyo_night::model_segment(),
@@ -308,11 +530,466 @@ impl ThemePresets {
                 // PackyCode quota segment
                 Self::quota_segment(),
             ],
-            theme: "powerline-tokyo-night".to_string(),
+            theme: "powerline-tokyo-night".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_catppuccin() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_catppuccin::model_segment(),
+                theme_catppuccin::directory_segment(),
+                theme_catppuccin::git_segment(),
+                theme_catppuccin::usage_segment(),
+                theme_catppuccin::cost_segment(),
+                theme_catppuccin::session_segment(),
+                theme_catppuccin::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "catppuccin".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_dracula() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_dracula::model_segment(),
+                theme_dracula::directory_segment(),
+                theme_dracula::git_segment(),
+                theme_dracula::usage_segment(),
+                theme_dracula::cost_segment(),
+                theme_dracula::session_segment(),
+                theme_dracula::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "dracula".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_tokyo_night() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_tokyo_night::model_segment(),
+                theme_tokyo_night::directory_segment(),
+                theme_tokyo_night::git_segment(),
+                theme_tokyo_night::usage_segment(),
+                theme_tokyo_night::cost_segment(),
+                theme_tokyo_night::session_segment(),
+                theme_tokyo_night::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "tokyo-night".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_powerline_catppuccin() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_powerline_catppuccin::model_segment(),
+                theme_powerline_catppuccin::directory_segment(),
+                theme_powerline_catppuccin::git_segment(),
+                theme_powerline_catppuccin::usage_segment(),
+                theme_powerline_catppuccin::cost_segment(),
+                theme_powerline_catppuccin::session_segment(),
+                theme_powerline_catppuccin::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "powerline-catppuccin".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_powerline_dracula() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_powerline_dracula::model_segment(),
+                theme_powerline_dracula::directory_segment(),
+                theme_powerline_dracula::git_segment(),
+                theme_powerline_dracula::usage_segment(),
+                theme_powerline_dracula::cost_segment(),
+                theme_powerline_dracula::session_segment(),
+                theme_powerline_dracula::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "powerline-dracula".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_powerline_gruvbox() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: "".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_powerline_gruvbox::model_segment(),
+                theme_powerline_gruvbox::directory_segment(),
+                theme_powerline_gruvbox::git_segment(),
+                theme_powerline_gruvbox::usage_segment(),
+                theme_powerline_gruvbox::cost_segment(),
+                theme_powerline_gruvbox::session_segment(),
+                theme_powerline_gruvbox::output_style_segment(),
+                // PackyCode quota segment
+                Self::quota_segment(),
+            ],
+            theme: "powerline-gruvbox".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_solarized_light() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Light,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_solarized_light::model_segment(),
+                theme_solarized_light::directory_segment(),
+                theme_solarized_light::git_segment(),
+                theme_solarized_light::usage_segment(),
+                theme_solarized_light::cost_segment(),
+                theme_solarized_light::session_segment(),
+                theme_solarized_light::output_style_segment(),
+                // PackyCode quota segment
+                theme_solarized_light::quota_segment(),
+            ],
+            theme: "solarized-light".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_high_contrast_light() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Light,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_high_contrast_light::model_segment(),
+                theme_high_contrast_light::directory_segment(),
+                theme_high_contrast_light::git_segment(),
+                theme_high_contrast_light::usage_segment(),
+                theme_high_contrast_light::cost_segment(),
+                theme_high_contrast_light::session_segment(),
+                theme_high_contrast_light::output_style_segment(),
+                // PackyCode quota segment
+                theme_high_contrast_light::quota_segment(),
+            ],
+            theme: "high-contrast-light".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_colorblind_deuteranopia() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_colorblind_deuteranopia::model_segment(),
+                theme_colorblind_deuteranopia::directory_segment(),
+                theme_colorblind_deuteranopia::git_segment(),
+                theme_colorblind_deuteranopia::usage_segment(),
+                theme_colorblind_deuteranopia::cost_segment(),
+                theme_colorblind_deuteranopia::session_segment(),
+                theme_colorblind_deuteranopia::output_style_segment(),
+                // PackyCode quota segment
+                theme_colorblind_deuteranopia::quota_segment(),
+            ],
+            theme: "colorblind-deuteranopia".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
+        }
+    }
+
+    pub fn get_colorblind_protanopia() -> Config {
+        Config {
+            style: StyleConfig {
+                mode: StyleMode::NerdFont,
+                separator: " | ".to_string(),
+                background: ThemeBackground::Dark,
+                separator_color: None,
+                icons: None,
+                nerd_font: None,
+                ascii: false,
+                accessible: false,
+                spacing: SpacingConfig::default(),
+                numbers: NumberFormat::default(),
+            },
+            segments: vec![
+                theme_colorblind_protanopia::model_segment(),
+                theme_colorblind_protanopia::directory_segment(),
+                theme_colorblind_protanopia::git_segment(),
+                theme_colorblind_protanopia::usage_segment(),
+                theme_colorblind_protanopia::cost_segment(),
+                theme_colorblind_protanopia::session_segment(),
+                theme_colorblind_protanopia::output_style_segment(),
+                // PackyCode quota segment
+                theme_colorblind_protanopia::quota_segment(),
+            ],
+            theme: "colorblind-protanopia".into(),
+            theme_overrides: Vec::new(),
+            platform_defaults: PlatformDefault::builtin(),
+            update: UpdateConfig::default(),
+            report: ReportConfig::default(),
+            handoff: HandoffConfig::default(),
+            emergency: EmergencyConfig::default(),
+            pricing: PricingConfig::default(),
+            cache: CacheConfig::default(),
+            balance: BalanceConfig::default(),
+            network: NetworkConfig::default(),
+            alerts: AlertsConfig::default(),
+            focus: FocusConfig::default(),
+            language: None,
+            config_version: CONFIG_VERSION,
+            include: Vec::new(),
         }
     }
 
-    // PackyCode-specific quota segment for all themes
+    // PackyCode-specific quota segment for dark themes
     fn quota_segment() -> SegmentConfig {
         SegmentConfig {
             id: SegmentId::Quota,
@@ -325,9 +1002,15 @@ impl ThemePresets {
                 icon: Some(AnsiColor::Color16 { c16: 11 }), // Yellow
                 text: Some(AnsiColor::Color16 { c16: 11 }),
                 background: None,
+                background_gradient: None,
+                separator: None,
             },
             styles: TextStyleConfig::default(),
             options: HashMap::new(),
+            padding: None,
+            refresh_interval_ms: None,
+            width: None,
+            transforms: Vec::new(),
         }
     }
 }
@@ -0,0 +1,253 @@
+use crate::config::{
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+};
+use std::collections::HashMap;
+
+pub fn model_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Model,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🤖".to_string(),
+            nerd_font: "\u{e26d}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 203,
+                g: 166,
+                b: 247,
+            }), // Catppuccin mauve
+            text: Some(AnsiColor::Rgb {
+                r: 203,
+                g: 166,
+                b: 247,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn directory_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Directory,
+        enabled: true,
+        icon: IconConfig {
+            plain: "📁".to_string(),
+            nerd_font: "\u{f024b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 137,
+                g: 180,
+                b: 250,
+            }), // Catppuccin blue
+            text: Some(AnsiColor::Rgb {
+                r: 137,
+                g: 180,
+                b: 250,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn git_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Git,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🌿".to_string(),
+            nerd_font: "\u{f02a2}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 166,
+                g: 227,
+                b: 161,
+            }), // Catppuccin green
+            text: Some(AnsiColor::Rgb {
+                r: 166,
+                g: 227,
+                b: 161,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn usage_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Usage,
+        enabled: true,
+        icon: IconConfig {
+            plain: "⚡️".to_string(),
+            nerd_font: "\u{f49b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 250,
+                g: 179,
+                b: 135,
+            }), // Catppuccin peach
+            text: Some(AnsiColor::Rgb {
+                r: 250,
+                g: 179,
+                b: 135,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn cost_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Cost,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{eec1}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 249,
+                g: 226,
+                b: 175,
+            }), // Catppuccin yellow
+            text: Some(AnsiColor::Rgb {
+                r: 249,
+                g: 226,
+                b: 175,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn session_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Session,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏱️".to_string(),
+            nerd_font: "\u{f19bb}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 148,
+                g: 226,
+                b: 213,
+            }), // Catppuccin teal
+            text: Some(AnsiColor::Rgb {
+                r: 148,
+                g: 226,
+                b: 213,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn output_style_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::OutputStyle,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🎯".to_string(),
+            nerd_font: "\u{f12f5}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 180,
+                g: 190,
+                b: 254,
+            }), // Catppuccin lavender
+            text: Some(AnsiColor::Rgb {
+                r: 180,
+                g: 190,
+                b: 254,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
@@ -0,0 +1,288 @@
+use crate::config::{
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+};
+use std::collections::HashMap;
+
+pub fn model_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Model,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🤖".to_string(),
+            nerd_font: "\u{e26d}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 86,
+                g: 180,
+                b: 233,
+            }), // Okabe-Ito sky blue
+            text: Some(AnsiColor::Rgb {
+                r: 86,
+                g: 180,
+                b: 233,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn directory_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Directory,
+        enabled: true,
+        icon: IconConfig {
+            plain: "📁".to_string(),
+            nerd_font: "\u{f024b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 230,
+                g: 159,
+                b: 0,
+            }), // Okabe-Ito orange
+            text: Some(AnsiColor::Rgb {
+                r: 230,
+                g: 159,
+                b: 0,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn git_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Git,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🌿".to_string(),
+            nerd_font: "\u{f02a2}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 0,
+                g: 158,
+                b: 115,
+            }), // Okabe-Ito bluish green
+            text: Some(AnsiColor::Rgb {
+                r: 0,
+                g: 158,
+                b: 115,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn usage_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Usage,
+        enabled: true,
+        icon: IconConfig {
+            plain: "⚡️".to_string(),
+            nerd_font: "\u{f49b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 204,
+                g: 121,
+                b: 167,
+            }), // Okabe-Ito reddish purple
+            text: Some(AnsiColor::Rgb {
+                r: 204,
+                g: 121,
+                b: 167,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn cost_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Cost,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{eec1}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 240,
+                g: 228,
+                b: 66,
+            }), // Okabe-Ito yellow
+            text: Some(AnsiColor::Rgb {
+                r: 240,
+                g: 228,
+                b: 66,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn session_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Session,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏱️".to_string(),
+            nerd_font: "\u{f19bb}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 0,
+                g: 158,
+                b: 115,
+            }), // Okabe-Ito bluish green
+            text: Some(AnsiColor::Rgb {
+                r: 0,
+                g: 158,
+                b: 115,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn output_style_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::OutputStyle,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🎯".to_string(),
+            nerd_font: "\u{f12f5}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 86,
+                g: 180,
+                b: 233,
+            }), // Okabe-Ito sky blue
+            text: Some(AnsiColor::Rgb {
+                r: 86,
+                g: 180,
+                b: 233,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn quota_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Quota,
+        enabled: true,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{f155}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 240,
+                g: 228,
+                b: 66,
+            }), // Okabe-Ito yellow
+            text: Some(AnsiColor::Rgb {
+                r: 240,
+                g: 228,
+                b: 66,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
@@ -15,9 +15,18 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }),
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -33,9 +42,18 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 11 }),
             text: Some(AnsiColor::Color16 { c16: 10 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -51,13 +69,22 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 12 }),
             text: Some(AnsiColor::Color16 { c16: 12 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
             opts
         },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -73,9 +100,18 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 13 }),
             text: Some(AnsiColor::Color16 { c16: 13 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -91,9 +127,18 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 3 }),
             text: Some(AnsiColor::Color16 { c16: 3 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -109,9 +154,18 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 2 }),
             text: Some(AnsiColor::Color16 { c16: 2 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -127,8 +181,17 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 6 }),
             text: Some(AnsiColor::Color16 { c16: 6 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
@@ -15,9 +15,15 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }), // Cyan
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -33,9 +39,15 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 11 }), // Yellow
             text: Some(AnsiColor::Color16 { c16: 10 }), // Green
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -51,6 +63,8 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 12 }), // Blue
             text: Some(AnsiColor::Color16 { c16: 12 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: {
@@ -58,6 +72,10 @@ pub fn git_segment() -> SegmentConfig {
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
             opts
         },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -73,9 +91,15 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 13 }), // Magenta
             text: Some(AnsiColor::Color16 { c16: 13 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -91,9 +115,15 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 3 }), // Yellow
             text: Some(AnsiColor::Color16 { c16: 3 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -109,9 +139,15 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 2 }), // Green
             text: Some(AnsiColor::Color16 { c16: 2 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -127,8 +163,14 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 6 }), // Cyan
             text: Some(AnsiColor::Color16 { c16: 6 }),
             background: None,
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
@@ -0,0 +1,253 @@
+use crate::config::{
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+};
+use std::collections::HashMap;
+
+pub fn model_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Model,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🤖".to_string(),
+            nerd_font: "\u{e26d}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 189,
+                g: 147,
+                b: 249,
+            }), // Dracula purple
+            text: Some(AnsiColor::Rgb {
+                r: 189,
+                g: 147,
+                b: 249,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn directory_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Directory,
+        enabled: true,
+        icon: IconConfig {
+            plain: "📁".to_string(),
+            nerd_font: "\u{f024b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 139,
+                g: 233,
+                b: 253,
+            }), // Dracula cyan
+            text: Some(AnsiColor::Rgb {
+                r: 139,
+                g: 233,
+                b: 253,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn git_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Git,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🌿".to_string(),
+            nerd_font: "\u{f02a2}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 80,
+                g: 250,
+                b: 123,
+            }), // Dracula green
+            text: Some(AnsiColor::Rgb {
+                r: 80,
+                g: 250,
+                b: 123,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn usage_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Usage,
+        enabled: true,
+        icon: IconConfig {
+            plain: "⚡️".to_string(),
+            nerd_font: "\u{f49b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 255,
+                g: 121,
+                b: 198,
+            }), // Dracula pink
+            text: Some(AnsiColor::Rgb {
+                r: 255,
+                g: 121,
+                b: 198,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn cost_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Cost,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{eec1}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 241,
+                g: 250,
+                b: 140,
+            }), // Dracula yellow
+            text: Some(AnsiColor::Rgb {
+                r: 241,
+                g: 250,
+                b: 140,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn session_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Session,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏱️".to_string(),
+            nerd_font: "\u{f19bb}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 255,
+                g: 184,
+                b: 108,
+            }), // Dracula orange
+            text: Some(AnsiColor::Rgb {
+                r: 255,
+                g: 184,
+                b: 108,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn output_style_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::OutputStyle,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🎯".to_string(),
+            nerd_font: "\u{f12f5}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 139,
+                g: 233,
+                b: 253,
+            }), // Dracula cyan
+            text: Some(AnsiColor::Rgb {
+                r: 139,
+                g: 233,
+                b: 253,
+            }),
+            background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
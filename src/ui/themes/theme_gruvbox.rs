@@ -15,9 +15,18 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 208 }), // Gruvbox orange
             text: Some(AnsiColor::Color256 { c256: 208 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -33,9 +42,18 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 142 }), // Gruvbox green
             text: Some(AnsiColor::Color256 { c256: 142 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -51,13 +69,22 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 109 }), // Gruvbox cyan
             text: Some(AnsiColor::Color256 { c256: 109 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
             opts
         },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -73,9 +100,18 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 5 }),
             text: Some(AnsiColor::Color16 { c16: 5 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -91,9 +127,18 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 214 }), // Gruvbox yellow
             text: Some(AnsiColor::Color256 { c256: 214 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -109,9 +154,18 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 142 }), // Gruvbox green
             text: Some(AnsiColor::Color256 { c256: 142 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -127,8 +181,17 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color256 { c256: 109 }), // Gruvbox cyan
             text: Some(AnsiColor::Color256 { c256: 109 }),
             background: None,
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
@@ -0,0 +1,260 @@
+use crate::config::{
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+};
+use std::collections::HashMap;
+
+pub fn model_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Model,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🤖".to_string(),
+            nerd_font: "\u{e26d}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 254,
+                g: 128,
+                b: 25,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn directory_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Directory,
+        enabled: true,
+        icon: IconConfig {
+            plain: "📁".to_string(),
+            nerd_font: "\u{f024b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 184,
+                g: 187,
+                b: 38,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn git_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Git,
+        enabled: true,
+        icon: IconConfig {
+            plain: "🌿".to_string(),
+            nerd_font: "\u{f02a2}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 142,
+                g: 192,
+                b: 124,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn usage_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Usage,
+        enabled: true,
+        icon: IconConfig {
+            plain: "⚡️".to_string(),
+            nerd_font: "\u{f49b}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 211,
+                g: 134,
+                b: 155,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn cost_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Cost,
+        enabled: false,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{eec1}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 250,
+                g: 189,
+                b: 47,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn session_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Session,
+        enabled: false,
+        icon: IconConfig {
+            plain: "⏱️".to_string(),
+            nerd_font: "\u{f19bb}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 184,
+                g: 187,
+                b: 38,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn output_style_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::OutputStyle,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🎯".to_string(),
+            nerd_font: "\u{f12f5}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 131,
+                g: 165,
+                b: 152,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
@@ -19,9 +19,15 @@ pub fn model_segment() -> SegmentConfig {
                 g: 206,
                 b: 235,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -49,9 +55,15 @@ pub fn directory_segment() -> SegmentConfig {
                 g: 107,
                 b: 71,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -79,6 +91,8 @@ pub fn git_segment() -> SegmentConfig {
                 g: 179,
                 b: 217,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: {
@@ -86,6 +100,10 @@ pub fn git_segment() -> SegmentConfig {
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
             opts
         },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -113,9 +131,15 @@ pub fn usage_segment() -> SegmentConfig {
                 g: 114,
                 b: 128,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -143,9 +167,15 @@ pub fn cost_segment() -> SegmentConfig {
                 g: 193,
                 b: 7,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -173,9 +203,15 @@ pub fn session_segment() -> SegmentConfig {
                 g: 167,
                 b: 69,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -203,8 +239,42 @@ pub fn output_style_segment() -> SegmentConfig {
                 g: 201,
                 b: 151,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
+    }
+}
+
+pub fn quota_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Quota,
+        enabled: true,
+        icon: IconConfig {
+            plain: "💰".to_string(),
+            nerd_font: "\u{f155}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb { r: 0, g: 0, b: 0 }),
+            text: Some(AnsiColor::Rgb { r: 0, g: 0, b: 0 }),
+            background: Some(AnsiColor::Rgb {
+                r: 255,
+                g: 193,
+                b: 7,
+            }),
+            background_gradient: None,
+            separator: None,
+        },
+        styles: TextStyleConfig::default(),
+        options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
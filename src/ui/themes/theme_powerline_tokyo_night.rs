@@ -27,9 +27,15 @@ pub fn model_segment() -> SegmentConfig {
                 g: 27,
                 b: 41,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -57,9 +63,15 @@ pub fn directory_segment() -> SegmentConfig {
                 g: 51,
                 b: 77,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -87,6 +99,8 @@ pub fn git_segment() -> SegmentConfig {
                 g: 32,
                 b: 48,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: {
@@ -94,6 +108,10 @@ pub fn git_segment() -> SegmentConfig {
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
             opts
         },
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -121,9 +139,15 @@ pub fn usage_segment() -> SegmentConfig {
                 g: 89,
                 b: 161,
             }),
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -151,9 +175,15 @@ pub fn cost_segment() -> SegmentConfig {
                 g: 40,
                 b: 59,
             }), // Tokyo Night dark background
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -181,9 +211,15 @@ pub fn session_segment() -> SegmentConfig {
                 g: 46,
                 b: 66,
             }), // Tokyo Night darker background
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
 
@@ -211,8 +247,14 @@ pub fn output_style_segment() -> SegmentConfig {
                 g: 35,
                 b: 52,
             }), // Tokyo Night darkest background
+            background_gradient: None,
+            separator: None,
         },
         styles: TextStyleConfig::default(),
         options: HashMap::new(),
+        padding: None,
+        refresh_interval_ms: None,
+        width: None,
+        transforms: Vec::new(),
     }
 }
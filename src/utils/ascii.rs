@@ -0,0 +1,41 @@
+//! Plain-ASCII replacements for the symbols segments print, for
+//! `style.ascii`.
+
+/// ASCII stand-ins for symbols in segment text; sparkline blocks rise from
+/// "_" to "#"
+const REPLACEMENTS: &[(char, &str)] = &[
+    ('…', "..."),
+    ('→', "->"),
+    ('↑', "^"),
+    ('↓', "v"),
+    ('✓', "+"),
+    ('✗', "x"),
+    ('●', "*"),
+    ('·', "-"),
+    ('⚠', "!"),
+    ('▁', "_"),
+    ('▂', "."),
+    ('▃', ","),
+    ('▄', "-"),
+    ('▅', "="),
+    ('▆', "+"),
+    ('▇', "*"),
+    ('█', "#"),
+];
+
+/// `text` with every character outside ASCII replaced: known symbols by
+/// their stand-ins, emoji variation selectors dropped, anything else by "?".
+/// Escape sequences are ASCII and pass through.
+pub fn to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch.is_ascii() {
+            ascii.push(ch);
+        } else if let Some((_, replacement)) = REPLACEMENTS.iter().find(|(from, _)| *from == ch) {
+            ascii.push_str(replacement);
+        } else if !matches!(ch, '\u{fe0e}' | '\u{fe0f}' | '\u{200d}') {
+            ascii.push('?');
+        }
+    }
+    ascii
+}
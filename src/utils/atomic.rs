@@ -0,0 +1,70 @@
+//! Replacing files so that readers, and a crash halfway, see either the old
+//! or the new contents but never a partial write, and locking files that
+//! several sessions read, change and write back.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long [`locked`] waits for another process before going ahead anyway
+const LOCK_WAIT: Duration = Duration::from_millis(500);
+
+/// Write `contents` to a temporary file next to `path`, flush it to disk and
+/// rename it over `path`
+pub fn write(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let temp = temp_path(path);
+    let result = (|| {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Run `f` holding an exclusive lock on `.name.lock` next to `path`, so the
+/// read-modify-write cycles of concurrent statusline invocations do not
+/// lose each other's changes. The lock goes with the process, so a crash
+/// cannot leave it held. A render must not hang, so `f` runs without the
+/// lock once [`LOCK_WAIT`] has passed or when the lock file cannot be opened.
+pub fn locked<R>(path: &Path, f: impl FnOnce() -> R) -> R {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(sibling(path, "lock"));
+
+    if let Ok(lock) = &lock {
+        let started = Instant::now();
+        while let Err(fs::TryLockError::WouldBlock) = lock.try_lock() {
+            if started.elapsed() >= LOCK_WAIT {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+    // Closing the lock file releases the lock
+    f()
+}
+
+/// `.name.<pid>.tmp` in the same directory, so the rename stays on one
+/// filesystem and concurrent writers do not share a temporary file
+fn temp_path(path: &Path) -> PathBuf {
+    sibling(path, &format!("{}.tmp", std::process::id()))
+}
+
+/// `.name.<suffix>` in the same directory as `path`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}", name, suffix))
+}
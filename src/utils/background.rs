@@ -0,0 +1,237 @@
+use crate::config::ThemeBackground;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How long a detected background is reused before detecting again
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// How long to wait for the terminal to answer the OSC 11 query
+#[cfg(all(unix, feature = "tui"))]
+const OSC_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackgroundCache {
+    background: ThemeBackground,
+    detected_at: SystemTime,
+}
+
+/// Detected backgrounds keyed by [`terminal_key`]: sessions in different
+/// terminals, or terminals with different themes, each have their own
+type BackgroundCacheFile = HashMap<String, BackgroundCache>;
+
+/// Detect whether the terminal has a dark or light background.
+///
+/// Sources, in order: the `CCLINE_BACKGROUND` override (`dark`/`light`), a
+/// recent cached result, an OSC 11 query (interactive terminals only, since
+/// Claude Code owns the terminal input while rendering the statusline),
+/// `COLORFGBG`, and the macOS system appearance. Falls back to dark.
+pub fn detect() -> ThemeBackground {
+    if let Some(background) = std::env::var("CCLINE_BACKGROUND")
+        .ok()
+        .and_then(|value| parse_name(&value))
+    {
+        return background;
+    }
+
+    if let Some(background) = load_cache() {
+        return background;
+    }
+
+    let detected = query_terminal()
+        .or_else(from_colorfgbg)
+        .or_else(system_appearance);
+
+    match detected {
+        Some(background) => {
+            save_cache(background);
+            background
+        }
+        None => ThemeBackground::Dark,
+    }
+}
+
+fn parse_name(value: &str) -> Option<ThemeBackground> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "dark" => Some(ThemeBackground::Dark),
+        "light" => Some(ThemeBackground::Light),
+        _ => None,
+    }
+}
+
+/// `COLORFGBG` is set by rxvt, Konsole and others as "fg;bg" with ANSI color indices
+fn from_colorfgbg() -> Option<ThemeBackground> {
+    let value = std::env::var("COLORFGBG").ok()?;
+    let bg: u8 = value.rsplit(';').next()?.parse().ok()?;
+
+    if bg == 7 || bg >= 9 {
+        Some(ThemeBackground::Light)
+    } else {
+        Some(ThemeBackground::Dark)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn system_appearance() -> Option<ThemeBackground> {
+    // The key only exists while dark mode is active
+    let output = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleInterfaceStyle"])
+        .output()
+        .ok()?;
+
+    if String::from_utf8_lossy(&output.stdout).contains("Dark") {
+        Some(ThemeBackground::Dark)
+    } else {
+        Some(ThemeBackground::Light)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn system_appearance() -> Option<ThemeBackground> {
+    None
+}
+
+/// Ask the terminal for its background color with OSC 11
+#[cfg(all(unix, feature = "tui"))]
+fn query_terminal() -> Option<ThemeBackground> {
+    use std::io::{IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+
+    let mut tty = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+
+    crossterm::terminal::enable_raw_mode().ok()?;
+    let sent = tty
+        .write_all(b"\x1b]11;?\x1b\\")
+        .and_then(|_| tty.flush())
+        .is_ok();
+
+    let reply = sent.then(|| read_reply(&mut tty));
+    let _ = crossterm::terminal::disable_raw_mode();
+
+    parse_osc11_reply(&String::from_utf8_lossy(&reply?))
+}
+
+/// Read the reply to an OSC query from `tty` byte by byte, so keys typed
+/// after it are left alone, until it is complete or [`OSC_TIMEOUT`] passed.
+/// Each read waits in poll(2) for the time left, so a terminal that never
+/// answers leaves no read blocked on it.
+#[cfg(all(unix, feature = "tui"))]
+fn read_reply(tty: &mut fs::File) -> Vec<u8> {
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let deadline = Instant::now() + OSC_TIMEOUT;
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        let mut fd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = left.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+        // SAFETY: `fd` is one valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut fd, 1, timeout) };
+        if ready < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+            continue;
+        }
+        if ready <= 0 || tty.read(&mut byte).map_or(true, |n| n != 1) {
+            break;
+        }
+        reply.push(byte[0]);
+        if byte[0] == 0x07 || reply.ends_with(b"\x1b\\") || reply.len() > 64 {
+            break;
+        }
+    }
+    reply
+}
+
+#[cfg(not(all(unix, feature = "tui")))]
+fn query_terminal() -> Option<ThemeBackground> {
+    None
+}
+
+/// Parse a reply like `ESC ] 11 ; rgb:RRRR/GGGG/BBBB BEL` into a background
+#[cfg_attr(not(all(unix, feature = "tui")), allow(dead_code))]
+fn parse_osc11_reply(reply: &str) -> Option<ThemeBackground> {
+    let rgb = reply.split("rgb:").nth(1)?;
+    let rgb = rgb.trim_end_matches(['\x07', '\x1b', '\\']);
+
+    let mut channels = rgb.split('/').map(|component| {
+        let max = 16f64.powi(component.len() as i32) - 1.0;
+        u32::from_str_radix(component, 16)
+            .ok()
+            .map(|value| value as f64 / max)
+    });
+    let r = channels.next()??;
+    let g = channels.next()??;
+    let b = channels.next()??;
+
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    if luminance > 0.5 {
+        Some(ThemeBackground::Light)
+    } else {
+        Some(ThemeBackground::Dark)
+    }
+}
+
+fn get_cache_path() -> PathBuf {
+    super::paths::cache_dir().join("background_cache.json")
+}
+
+/// The terminal a detected background belongs to: `TERM_PROGRAM`, else
+/// `TERM`
+fn terminal_key() -> String {
+    ["TERM_PROGRAM", "TERM"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+fn read_cache_file(path: &PathBuf) -> BackgroundCacheFile {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn load_cache() -> Option<ThemeBackground> {
+    let cache = read_cache_file(&get_cache_path()).remove(&terminal_key())?;
+    let age = SystemTime::now().duration_since(cache.detected_at).ok()?;
+
+    (age < CACHE_TTL).then_some(cache.background)
+}
+
+fn save_cache(background: ThemeBackground) {
+    let path = get_cache_path();
+    super::atomic::locked(&path, || {
+        let mut file = read_cache_file(&path);
+        let now = SystemTime::now();
+        // Terminals not seen for a while are dropped
+        file.retain(|_, cache| {
+            now.duration_since(cache.detected_at)
+                .is_ok_and(|age| age < CACHE_TTL)
+        });
+        file.insert(
+            terminal_key(),
+            BackgroundCache {
+                background,
+                detected_at: now,
+            },
+        );
+        if let Ok(content) = serde_json::to_vec(&file) {
+            let _ = super::atomic::write(&path, &content);
+        }
+    });
+}
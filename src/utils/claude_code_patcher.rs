@@ -1,4 +1,4 @@
-use regex::Regex;
+use crate::utils::pattern::Regex;
 use std::fs;
 use std::path::Path;
 
@@ -0,0 +1,61 @@
+//! Claude Code's settings as they apply to a project: `~/.claude/settings.json`,
+//! then the project's `.claude/settings.json` and `.claude/settings.local.json`,
+//! later files taking precedence.
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct ClaudeSettings {
+    /// Parsed settings files, least specific first
+    files: Vec<Value>,
+}
+
+impl ClaudeSettings {
+    /// Settings for the project in `project_dir`; missing or invalid files
+    /// are skipped
+    pub fn load(project_dir: &str) -> Self {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        if let Some(home) = crate::utils::paths::home_dir() {
+            paths.push(home.join(".claude").join("settings.json"));
+        }
+        let project = Path::new(project_dir).join(".claude");
+        paths.push(project.join("settings.json"));
+        paths.push(project.join("settings.local.json"));
+
+        // The project may be the home directory itself
+        paths.dedup();
+        let files = paths
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        Self { files }
+    }
+
+    /// The value at `path` in the most specific file that sets it
+    pub fn get(&self, path: &[&str]) -> Option<&Value> {
+        self.files.iter().rev().find_map(|file| {
+            path.iter()
+                .try_fold(file, |value, key| value.get(key))
+                .filter(|value| !value.is_null())
+        })
+    }
+
+    /// `permissions.defaultMode`: "default", "acceptEdits", "plan" or
+    /// "bypassPermissions"
+    pub fn default_mode(&self) -> Option<&str> {
+        self.get(&["permissions", "defaultMode"])
+            .and_then(Value::as_str)
+    }
+
+    /// `permissions.allow` rules of every file, e.g. "Bash(npm test:*)" or "Edit"
+    pub fn allowed(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .filter_map(|file| file.get("permissions")?.get("allow")?.as_array())
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect()
+    }
+}
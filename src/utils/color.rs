@@ -0,0 +1,125 @@
+use crate::config::AnsiColor;
+
+/// xterm's default values for the 16 basic ANSI colors
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Approximate RGB value of a color. 16-color values depend on the terminal
+/// palette, so xterm defaults are assumed.
+pub fn to_rgb(color: &AnsiColor) -> (u8, u8, u8) {
+    match color {
+        AnsiColor::Color16 { c16 } => ANSI_16[(*c16 as usize).min(15)],
+        AnsiColor::Color256 { c256 } => match *c256 {
+            0..=15 => ANSI_16[*c256 as usize],
+            16..=231 => {
+                let index = c256 - 16;
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                (level(index / 36), level((index / 6) % 6), level(index % 6))
+            }
+            232..=255 => {
+                let gray = 8 + (c256 - 232) * 10;
+                (gray, gray, gray)
+            }
+        },
+        AnsiColor::Rgb { r, g, b } => (*r, *g, *b),
+    }
+}
+
+/// WCAG relative luminance of an sRGB color, from 0 (black) to 1 (white)
+pub fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// WCAG contrast ratio between two colors, from 1:1 to 21:1
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Linear interpolation between two colors in RGB space, `t` from 0 to 1
+pub fn interpolate(from: &AnsiColor, to: &AnsiColor, t: f64) -> AnsiColor {
+    let (r1, g1, b1) = to_rgb(from);
+    let (r2, g2, b2) = to_rgb(to);
+    let t = t.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+
+    AnsiColor::Rgb {
+        r: mix(r1, r2),
+        g: mix(g1, g2),
+        b: mix(b1, b2),
+    }
+}
+
+/// Convert RGB to hue (0-359), saturation and value (0-100)
+pub fn rgb_to_hsv((r, g, b): (u8, u8, u8)) -> (u16, u8, u8) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    (
+        (hue.round() as u16) % 360,
+        (saturation * 100.0).round() as u8,
+        (max * 100.0).round() as u8,
+    )
+}
+
+/// Convert hue (0-359), saturation and value (0-100) to RGB
+pub fn hsv_to_rgb(hue: u16, saturation: u8, value: u8) -> (u8, u8, u8) {
+    let h = (hue % 360) as f64 / 60.0;
+    let s = saturation.min(100) as f64 / 100.0;
+    let v = value.min(100) as f64 / 100.0;
+
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+
+    (channel(r), channel(g), channel(b))
+}
@@ -0,0 +1,65 @@
+use super::width::strip_ansi;
+
+/// How the statusline reaches the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Renderer {
+    /// ANSI escape sequences for colors and styles
+    Ansi,
+    /// Plain text for Windows consoles that cannot interpret escape sequences
+    WindowsNative,
+}
+
+impl Renderer {
+    /// Prepare stdout and pick the renderer. On Windows this turns on virtual
+    /// terminal processing for console output and falls back to
+    /// `windows-native` when the console refuses. `CCLINE_RENDERER=ansi` or
+    /// `CCLINE_RENDERER=windows-native` overrides the detection.
+    pub fn init() -> Self {
+        match std::env::var("CCLINE_RENDERER").as_deref() {
+            Ok("ansi") => return Renderer::Ansi,
+            Ok("windows-native") => return Renderer::WindowsNative,
+            _ => {}
+        }
+
+        if enable_virtual_terminal() {
+            Renderer::Ansi
+        } else {
+            Renderer::WindowsNative
+        }
+    }
+
+    /// Adapt a rendered statusline to this renderer
+    pub fn finish(self, statusline: String) -> String {
+        match self {
+            Renderer::Ansi => statusline,
+            Renderer::WindowsNative => strip_ansi(&statusline),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn enable_virtual_terminal() -> bool {
+    use windows_sys::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE,
+        ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_OUTPUT_HANDLE,
+    };
+
+    // SAFETY: the handle comes from GetStdHandle and `mode` outlives the calls
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut mode: CONSOLE_MODE = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            // Not a console, e.g. piped to Claude Code: escape sequences pass through
+            return true;
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            return true;
+        }
+        SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
+
+#[cfg(not(windows))]
+fn enable_virtual_terminal() -> bool {
+    true
+}
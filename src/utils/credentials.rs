@@ -0,0 +1,195 @@
+//! Credentials Claude Code is configured with.
+//!
+//! API and relay users have a key in the environment, in the `env` table of
+//! `~/.claude/settings.json` or in `~/.claude/api_key`. Subscription (Pro and
+//! Max) users sign in with OAuth instead: Claude Code keeps the tokens in
+//! `~/.claude/.credentials.json`, or on macOS in the login Keychain under
+//! "Claude Code-credentials". ccline only reads them; Claude Code refreshes
+//! expired tokens itself.
+
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Keychain service name Claude Code stores its OAuth tokens under
+#[cfg(target_os = "macos")]
+const KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
+
+/// How long a Keychain lookup is reused; each one starts `security`
+const KEYCHAIN_TTL: Duration = Duration::from_secs(300);
+
+/// OAuth tokens of a Claude subscription sign-in
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthCredentials {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// Unix milliseconds
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// "pro", "max", "team" or "enterprise"
+    #[serde(default)]
+    pub subscription_type: Option<String>,
+    /// e.g. "default_claude_max_5x"
+    #[serde(default)]
+    pub rate_limit_tier: Option<String>,
+}
+
+impl OAuthCredentials {
+    pub fn is_expired(&self) -> bool {
+        let Some(expires_at) = self.expires_at else {
+            return false;
+        };
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        now_ms >= expires_at
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CredentialsFile {
+    claude_ai_oauth: Option<OAuthCredentials>,
+}
+
+fn claude_dir() -> Option<PathBuf> {
+    crate::utils::paths::home_dir().map(|home| home.join(".claude"))
+}
+
+/// API key for API and relay users.
+///
+/// Priority: `PACKYCODE_API_KEY`, `ANTHROPIC_API_KEY` and
+/// `ANTHROPIC_AUTH_TOKEN` in the environment, then the same variables in the
+/// `env` table of `~/.claude/settings.json`, then `~/.claude/api_key`.
+pub fn api_key() -> Option<String> {
+    api_key_with_source().map(|(key, _)| key)
+}
+
+/// Like [`api_key`], with where the key was found, e.g. "env:ANTHROPIC_API_KEY"
+pub fn api_key_with_source() -> Option<(String, String)> {
+    for name in [
+        "PACKYCODE_API_KEY",
+        "ANTHROPIC_API_KEY",
+        "ANTHROPIC_AUTH_TOKEN",
+    ] {
+        if let Ok(key) = std::env::var(name) {
+            return Some((key, format!("env:{}", name)));
+        }
+    }
+
+    for name in ["ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_API_KEY"] {
+        if let Some(key) = settings_env(name) {
+            return Some((key, format!("settings:{}", name)));
+        }
+    }
+
+    let key = fs::read_to_string(claude_dir()?.join("api_key")).ok()?;
+    Some((key.trim().to_string(), "file:api_key".to_string()))
+}
+
+/// A variable from the `env` table of `~/.claude/settings.json`, which Claude
+/// Code exports to its own process but not to the statusline command
+fn settings_env(name: &str) -> Option<String> {
+    let content = fs::read_to_string(claude_dir()?.join("settings.json")).ok()?;
+    let settings = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    settings
+        .get("env")?
+        .get(name)?
+        .as_str()
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// API base URL Claude Code sends requests to when it is not Anthropic's:
+/// `ANTHROPIC_BASE_URL` in the environment, else in settings.json
+pub fn base_url() -> Option<String> {
+    std::env::var("ANTHROPIC_BASE_URL")
+        .ok()
+        .filter(|url| !url.is_empty())
+        .or_else(|| settings_env("ANTHROPIC_BASE_URL"))
+}
+
+/// Account id of the subscription sign-in, from the `oauthAccount` Claude
+/// Code records in `~/.claude.json`. Unlike the tokens it survives refreshes.
+pub fn oauth_account_id() -> Option<String> {
+    let path = crate::utils::paths::home_dir()?.join(".claude.json");
+    let state = serde_json::from_str::<serde_json::Value>(&fs::read_to_string(path).ok()?).ok()?;
+    state
+        .get("oauthAccount")?
+        .get("accountUuid")?
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+/// OAuth tokens of a subscription sign-in, expired or not: from
+/// `~/.claude/.credentials.json`, else the macOS Keychain
+pub fn oauth() -> Option<OAuthCredentials> {
+    oauth_from_file().or_else(oauth_from_keychain)
+}
+
+/// Access token for the Anthropic API when signed in with a subscription and
+/// the token has not expired
+pub fn oauth_token() -> Option<String> {
+    oauth()
+        .filter(|credentials| !credentials.is_expired())
+        .map(|credentials| credentials.access_token)
+}
+
+fn parse_credentials(json: &str) -> Option<OAuthCredentials> {
+    serde_json::from_str::<CredentialsFile>(json)
+        .ok()?
+        .claude_ai_oauth
+        .filter(|credentials| !credentials.access_token.is_empty())
+}
+
+fn oauth_from_file() -> Option<OAuthCredentials> {
+    let content = fs::read_to_string(claude_dir()?.join(".credentials.json")).ok()?;
+    parse_credentials(&content)
+}
+
+static KEYCHAIN: Mutex<Option<(Instant, Option<OAuthCredentials>)>> = Mutex::new(None);
+
+fn oauth_from_keychain() -> Option<OAuthCredentials> {
+    let mut cached = KEYCHAIN.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((read_at, credentials)) = cached.as_ref() {
+        if read_at.elapsed() < KEYCHAIN_TTL {
+            return credentials.clone();
+        }
+    }
+
+    let credentials = read_keychain().and_then(|json| parse_credentials(&json));
+    *cached = Some((Instant::now(), credentials.clone()));
+    credentials
+}
+
+/// The Keychain item is created by `security` on Claude Code's behalf, so
+/// reading it through `security` does not prompt
+#[cfg(target_os = "macos")]
+fn read_keychain() -> Option<String> {
+    let output = std::process::Command::new("security")
+        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-w"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|json| json.trim().to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_keychain() -> Option<String> {
+    None
+}
@@ -0,0 +1,153 @@
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Context,
+    Removed,
+    Added,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// One block of changes with its surrounding context, as in a unified diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    /// Zero-based first line of the hunk in the old text
+    pub old_start: usize,
+    pub old_len: usize,
+    /// Zero-based first line of the hunk in the new text
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    /// Header in unified diff notation, e.g. `@@ -12,7 +12,8 @@`
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start + 1,
+            self.old_len,
+            self.new_start + 1,
+            self.new_len
+        )
+    }
+}
+
+/// Line diff of two texts grouped into hunks; empty when they are equal
+pub fn unified_diff(old: &str, new: &str) -> Vec<Hunk> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut hunks = Vec::new();
+    let mut index = 0;
+    while index < ops.len() {
+        // Skip to the next change
+        let Some(change) = ops[index..]
+            .iter()
+            .position(|(kind, _)| *kind != DiffKind::Context)
+        else {
+            break;
+        };
+        let first_change = index + change;
+        let start = first_change.saturating_sub(CONTEXT_LINES).max(index);
+
+        // Extend while the next change is close enough to share context
+        let mut end = first_change;
+        let mut last_change = first_change;
+        while end < ops.len() {
+            if ops[end].0 != DiffKind::Context {
+                last_change = end;
+            } else if end - last_change > 2 * CONTEXT_LINES {
+                break;
+            }
+            end += 1;
+        }
+        let end = (last_change + 1 + CONTEXT_LINES).min(ops.len());
+
+        let old_start = ops[..start]
+            .iter()
+            .filter(|(kind, _)| *kind != DiffKind::Added)
+            .count();
+        let new_start = ops[..start]
+            .iter()
+            .filter(|(kind, _)| *kind != DiffKind::Removed)
+            .count();
+        let lines: Vec<DiffLine> = ops[start..end]
+            .iter()
+            .map(|(kind, text)| DiffLine {
+                kind: *kind,
+                text: text.to_string(),
+            })
+            .collect();
+        hunks.push(Hunk {
+            old_start,
+            old_len: lines.iter().filter(|l| l.kind != DiffKind::Added).count(),
+            new_start,
+            new_len: lines.iter().filter(|l| l.kind != DiffKind::Removed).count(),
+            lines,
+        });
+        index = end;
+    }
+
+    hunks
+}
+
+/// Undo one hunk in the new text, putting back the old lines it replaced
+pub fn revert_hunk(new: &str, hunk: &Hunk) -> String {
+    let new_lines: Vec<&str> = new.lines().collect();
+    let old_side = hunk
+        .lines
+        .iter()
+        .filter(|line| line.kind != DiffKind::Added)
+        .map(|line| line.text.as_str());
+
+    let mut result: Vec<&str> = new_lines[..hunk.new_start].to_vec();
+    result.extend(old_side);
+    result.extend(&new_lines[(hunk.new_start + hunk.new_len).min(new_lines.len())..]);
+
+    let mut text = result.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Longest-common-subsequence line diff; config files are small enough
+/// for the quadratic table
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffKind, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((DiffKind::Context, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push((DiffKind::Removed, old[i]));
+            i += 1;
+        } else {
+            ops.push((DiffKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| (DiffKind::Removed, *line)));
+    ops.extend(new[j..].iter().map(|line| (DiffKind::Added, *line)));
+    ops
+}
@@ -0,0 +1,191 @@
+//! Arithmetic over numbers and named values, for the `computed` segment:
+//! `+ - * /`, parentheses, unary minus, and names such as `cost.cost` or
+//! `session.duration_ms` that are looked up when evaluating.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Name(String),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, ch)) = chars.peek() {
+        match ch {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' => {
+                chars.next();
+                tokens.push(Token::Operator(match ch {
+                    '+' => Operator::Add,
+                    '-' => Operator::Subtract,
+                    '*' => Operator::Multiply,
+                    _ => Operator::Divide,
+                }));
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ if ch.is_ascii_digit() || ch == '.' => {
+                let mut end = start;
+                while let Some(&(index, ch)) = chars.peek() {
+                    if !(ch.is_ascii_digit() || ch == '.') {
+                        break;
+                    }
+                    end = index + ch.len_utf8();
+                    chars.next();
+                }
+                let text = &source[start..end];
+                let number = text
+                    .parse()
+                    .map_err(|_| format!("\"{}\" is not a number", text))?;
+                tokens.push(Token::Number(number));
+            }
+            _ if ch.is_ascii_alphabetic() || ch == '_' => {
+                let mut end = start;
+                while let Some(&(index, ch)) = chars.peek() {
+                    if !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '.') {
+                        break;
+                    }
+                    end = index + ch.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Name(source[start..end].to_string()));
+            }
+            _ => return Err(format!("unexpected \"{}\"", ch)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: sums of products of factors
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn sum(&mut self) -> Result<Expression, String> {
+        let mut left = self.product()?;
+        while let Some(Token::Operator(op @ (Operator::Add | Operator::Subtract))) = self.peek() {
+            let op = *op;
+            self.position += 1;
+            left = Expression::Binary(Box::new(left), op, Box::new(self.product()?));
+        }
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut left = self.factor()?;
+        while let Some(Token::Operator(op @ (Operator::Multiply | Operator::Divide))) = self.peek()
+        {
+            let op = *op;
+            self.position += 1;
+            left = Expression::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expression::Number(number)),
+            Some(Token::Name(name)) => Ok(Expression::Name(name)),
+            Some(Token::Operator(Operator::Subtract)) => {
+                Ok(Expression::Negate(Box::new(self.factor()?)))
+            }
+            Some(Token::Open) => {
+                let inner = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("missing \")\"".to_string()),
+                }
+            }
+            Some(_) => Err("expected a number, a name or \"(\"".to_string()),
+            None => Err("expression ends too early".to_string()),
+        }
+    }
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+        };
+        let expression = parser.sum()?;
+        if parser.position < parser.tokens.len() {
+            return Err("unexpected text after the expression".to_string());
+        }
+        Ok(expression)
+    }
+
+    /// The names the expression refers to, in order
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            Expression::Number(_) => Vec::new(),
+            Expression::Name(name) => vec![name.as_str()],
+            Expression::Negate(inner) => inner.names(),
+            Expression::Binary(left, _, right) => {
+                let mut names = left.names();
+                names.extend(right.names());
+                names
+            }
+        }
+    }
+
+    /// The value with names looked up in `lookup`; `None` when a name has
+    /// no value or the result is not finite, e.g. after dividing by zero
+    pub fn evaluate(&self, lookup: &impl Fn(&str) -> Option<f64>) -> Option<f64> {
+        let value = match self {
+            Expression::Number(number) => *number,
+            Expression::Name(name) => lookup(name)?,
+            Expression::Negate(inner) => -inner.evaluate(lookup)?,
+            Expression::Binary(left, op, right) => {
+                let (left, right) = (left.evaluate(lookup)?, right.evaluate(lookup)?);
+                match op {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                }
+            }
+        };
+        value.is_finite().then_some(value)
+    }
+}
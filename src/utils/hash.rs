@@ -0,0 +1,9 @@
+//! Hashing for names and fingerprints that outlive the process.
+
+/// FNV-1a: stable across builds and platforms, unlike `DefaultHasher`, so
+/// cache file names and key fingerprints survive an upgrade
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,447 @@
+//! The HTTP agent all of ccline's requests go through, set up from
+//! `[network]` in config.toml by whoever loaded it, with [`configure`].
+//!
+//! Every request carries the [`USER_AGENT`] and gives up after [`TIMEOUT`]
+//! unless it sets its own timeout, and gzip responses are decoded. Requests
+//! made by an executor task end by the task's deadline, so a task that was
+//! given up on does not keep its connection open.
+//! [`get_cached`] keeps response bodies in the `http` cache directory and
+//! revalidates them with their ETag or Last-Modified date, so an unchanged
+//! feed is not downloaded again.
+
+use crate::config::NetworkConfig;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{self, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Sent with every request
+pub const USER_AGENT: &str = concat!("ccline/", env!("CARGO_PKG_VERSION"));
+
+/// How long a request may take when it does not set its own timeout
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a DNS-over-HTTPS query may take before the system resolver is
+/// asked instead
+const DOH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Bounds on how long DNS-over-HTTPS answers are reused, whatever their TTL
+const MIN_DOH_TTL: Duration = Duration::from_secs(30);
+const MAX_DOH_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The shared agent and the settings it was built with
+static AGENT: Mutex<Option<(NetworkConfig, ureq::Agent)>> = Mutex::new(None);
+
+/// Why the last agent built refuses every request, if it does
+static TLS_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Use `network` for the requests made from now on. The agent is only built
+/// again when the settings changed, so pooled connections are kept.
+pub fn configure(network: &NetworkConfig) {
+    let mut agent = AGENT.lock().unwrap_or_else(|e| e.into_inner());
+    if agent.as_ref().is_none_or(|(current, _)| current != network) {
+        *agent = Some((network.clone(), build(network)));
+    }
+}
+
+/// The shared agent; connections are pooled across requests. Without a
+/// [`configure`] call it has the default `[network]` settings.
+pub fn agent() -> ureq::Agent {
+    let mut agent = AGENT.lock().unwrap_or_else(|e| e.into_inner());
+    agent
+        .get_or_insert_with(|| {
+            let network = NetworkConfig::default();
+            let built = build(&network);
+            (network, built)
+        })
+        .1
+        .clone()
+}
+
+/// Why requests are refused: a `ca_bundle` that cannot be read, as
+/// reported by `ccline health`
+pub fn tls_error() -> Option<String> {
+    TLS_ERROR.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// An agent with ureq's defaults, changed by the certificate and name
+/// resolution settings of `network`. When the CA bundle cannot be read, the
+/// agent refuses every request rather than trust the built-in roots alone.
+pub fn build(network: &NetworkConfig) -> ureq::Agent {
+    let tls = if network.ca_bundle.is_some() || network.danger_accept_invalid_certs {
+        Some(tls_config(network).map(Arc::new))
+    } else {
+        None
+    };
+    let refused = match &tls {
+        Some(Err(error)) => Some(error.clone()),
+        _ => None,
+    };
+    if let Some(error) = &refused {
+        if std::env::var("PACKYCODE_DEBUG").is_ok() {
+            crate::utils::redact::debug_log(error, &[]);
+        }
+    }
+    *TLS_ERROR.lock().unwrap_or_else(|e| e.into_inner()) = refused.clone();
+
+    let builder = |resolver: Option<Resolver>| {
+        let mut builder = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(TIMEOUT)
+            .middleware(TaskDeadline);
+        if let Some(error) = &refused {
+            builder = builder.middleware(Refuse(error.clone()));
+        }
+        if let Some(Ok(tls)) = &tls {
+            builder = builder.tls_config(tls.clone());
+        }
+        if let Some(resolver) = resolver {
+            builder = builder.resolver(resolver);
+        }
+        builder
+    };
+
+    if network.hosts.is_empty() && network.doh.is_none() {
+        return builder(None).build();
+    }
+    let hosts: HashMap<String, IpAddr> = network
+        .hosts
+        .iter()
+        .filter_map(|(host, address)| {
+            Some((host.to_ascii_lowercase(), address.trim().parse().ok()?))
+        })
+        .collect();
+    // The DoH endpoint itself is looked up through the fixed hosts only
+    let doh = network.doh.as_ref().map(|url| Doh {
+        url: url.clone(),
+        agent: builder(Some(Resolver {
+            hosts: hosts.clone(),
+            doh: None,
+        }))
+        .timeout(DOH_TIMEOUT)
+        .build(),
+        answers: Mutex::new(HashMap::new()),
+    });
+    builder(Some(Resolver { hosts, doh })).build()
+}
+
+/// Bounds the requests of executor tasks by their deadline, and fails those
+/// made after it without connecting
+struct TaskDeadline;
+
+impl ureq::Middleware for TaskDeadline {
+    fn handle(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        match crate::core::executor::time_left() {
+            None => next.handle(request),
+            Some(Duration::ZERO) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "cancelled: the task is past its deadline",
+            )
+            .into()),
+            Some(left) => next.handle(request.timeout(left.min(TIMEOUT))),
+        }
+    }
+}
+
+/// Fails every request with the reason the TLS settings could not be used
+struct Refuse(String);
+
+impl ureq::Middleware for Refuse {
+    fn handle(
+        &self,
+        _request: ureq::Request,
+        _next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        Err(io::Error::other(self.0.clone()).into())
+    }
+}
+
+/// A response body kept by [`get_cached`], with the validators it came with
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Unix seconds of the last time the server sent or confirmed the body
+    checked_at: u64,
+    body: String,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `http/<hash of url>.json` in the cache directory
+fn cache_path(url: &str) -> PathBuf {
+    let hash = crate::utils::hash::fnv1a(url.as_bytes());
+    crate::utils::paths::cache_dir()
+        .join("http")
+        .join(format!("{:016x}.json", hash))
+}
+
+fn load_entry(url: &str) -> Option<CacheEntry> {
+    let entry: CacheEntry = serde_json::from_slice(&fs::read(cache_path(url)).ok()?).ok()?;
+    (entry.url == url).then_some(entry)
+}
+
+fn store_entry(entry: &CacheEntry) {
+    let path = cache_path(&entry.url);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_vec(entry) {
+        let _ = crate::utils::atomic::write(&path, &json);
+    }
+}
+
+/// The body of a GET `request`, from the cache when it was fetched or
+/// revalidated within `max_age`. Otherwise the request is sent with the
+/// cached body's validators, and a 304 answer returns the cached body
+/// without downloading it again. Only 200 answers are cached; callers add
+/// their own headers to `request` before passing it in.
+pub fn get_cached(
+    request: ureq::Request,
+    max_age: Duration,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let url = request.url().to_string();
+    let cached = load_entry(&url);
+    if let Some(entry) = &cached {
+        if unix_now().saturating_sub(entry.checked_at) < max_age.as_secs() {
+            return Ok(entry.body.clone());
+        }
+    }
+
+    let mut request = request;
+    if let Some(entry) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+    }
+    let response = request.call()?;
+
+    if let (304, Some(mut entry)) = (response.status(), cached) {
+        entry.checked_at = unix_now();
+        store_entry(&entry);
+        return Ok(entry.body);
+    }
+
+    let etag = response.header("ETag").map(str::to_string);
+    let last_modified = response.header("Last-Modified").map(str::to_string);
+    let status = response.status();
+    let body = response.into_string()?;
+    if status == 200 {
+        store_entry(&CacheEntry {
+            url,
+            etag,
+            last_modified,
+            checked_at: unix_now(),
+            body: body.clone(),
+        });
+    }
+    Ok(body)
+}
+
+/// Looks up names in the fixed `hosts` of `[network]`, then through
+/// DNS-over-HTTPS, then with the system resolver
+struct Resolver {
+    hosts: HashMap<String, IpAddr>,
+    doh: Option<Doh>,
+}
+
+impl ureq::Resolver for Resolver {
+    fn resolve(&self, netloc: &str) -> io::Result<Vec<SocketAddr>> {
+        if let Some((host, port)) = split_netloc(netloc) {
+            let host = host.to_ascii_lowercase();
+            let addresses = match self.hosts.get(&host) {
+                Some(address) => Some(vec![*address]),
+                None => self.doh.as_ref().and_then(|doh| doh.lookup(&host)),
+            };
+            if let Some(addresses) = addresses.filter(|addresses| !addresses.is_empty()) {
+                return Ok(addresses
+                    .into_iter()
+                    .map(|address| SocketAddr::new(address, port))
+                    .collect());
+            }
+        }
+        netloc.to_socket_addrs().map(Iterator::collect)
+    }
+}
+
+/// "example.com:443" or "[::1]:443" as host and port; `None` for IP
+/// addresses, which need no lookup
+fn split_netloc(netloc: &str) -> Option<(&str, u16)> {
+    let (host, port) = netloc.rsplit_once(':')?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+    Some((host, port.parse().ok()?))
+}
+
+/// A DNS-over-HTTPS endpoint speaking the JSON format of Cloudflare's and
+/// Google's resolvers, with the answers it gave
+struct Doh {
+    url: String,
+    agent: ureq::Agent,
+    answers: Mutex<HashMap<String, (Vec<IpAddr>, Instant)>>,
+}
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohRecord>,
+}
+
+#[derive(Deserialize)]
+struct DohRecord {
+    #[serde(rename = "TTL", default)]
+    ttl: u64,
+    data: String,
+}
+
+impl Doh {
+    /// IPv4 addresses of `host`, else IPv6 ones; `None` when the endpoint
+    /// does not answer
+    fn lookup(&self, host: &str) -> Option<Vec<IpAddr>> {
+        let mut answers = self.answers.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((addresses, expires)) = answers.get(host) {
+            if *expires > Instant::now() {
+                return Some(addresses.clone());
+            }
+        }
+        drop(answers);
+
+        let mut records = self.query(host, "A")?;
+        if records.is_empty() {
+            records = self.query(host, "AAAA")?;
+        }
+        // CNAME records come along with the addresses they lead to
+        let addresses: Vec<IpAddr> = records
+            .iter()
+            .filter_map(|record| record.data.parse().ok())
+            .collect();
+        let ttl = records
+            .iter()
+            .map(|record| Duration::from_secs(record.ttl))
+            .min()
+            .unwrap_or(MIN_DOH_TTL)
+            .clamp(MIN_DOH_TTL, MAX_DOH_TTL);
+
+        answers = self.answers.lock().unwrap_or_else(|e| e.into_inner());
+        answers.insert(host.to_string(), (addresses.clone(), Instant::now() + ttl));
+        Some(addresses)
+    }
+
+    fn query(&self, host: &str, kind: &str) -> Option<Vec<DohRecord>> {
+        let response: DohResponse = self
+            .agent
+            .get(&self.url)
+            .query("name", host)
+            .query("type", kind)
+            .set("accept", "application/dns-json")
+            .call()
+            .ok()?
+            .into_json()
+            .ok()?;
+        Some(response.answer)
+    }
+}
+
+fn tls_config(network: &NetworkConfig) -> Result<ClientConfig, String> {
+    let provider = Arc::new(crypto::ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?;
+
+    if network.danger_accept_invalid_certs {
+        return Ok(builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+            .with_no_client_auth());
+    }
+
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    if let Some(path) = network.ca_bundle_path() {
+        let certificates = CertificateDer::pem_file_iter(&path)
+            .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("network.ca_bundle {}: {}", path.display(), e))?;
+        let (added, _) = roots.add_parsable_certificates(certificates);
+        if added == 0 {
+            return Err(format!(
+                "network.ca_bundle {} holds no usable certificates",
+                path.display()
+            ));
+        }
+    }
+    Ok(builder.with_root_certificates(roots).with_no_client_auth())
+}
+
+/// Certificate checks for `danger_accept_invalid_certs`: any certificate is
+/// accepted, but handshake signatures are still verified
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
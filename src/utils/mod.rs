@@ -1,3 +1,27 @@
+pub mod ascii;
+pub mod atomic;
+pub mod background;
 pub mod claude_code_patcher;
+pub mod claude_settings;
+pub mod color;
+pub mod console;
+pub mod credentials;
+pub mod diff;
+pub mod expression;
+pub mod hash;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod nerd_font;
+pub mod number;
+pub mod paths;
+pub mod pattern;
+pub mod rate_limit;
+pub mod redact;
+pub mod sanitize;
+pub mod sparkline;
+pub mod terminal;
+pub mod transcript;
+pub mod width;
+pub mod wsl;
 
 pub use claude_code_patcher::{ClaudeCodePatcher, LocationResult};
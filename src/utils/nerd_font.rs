@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long a heuristic result is reused; user confirmations never expire
+const DETECTION_TTL: Duration = Duration::from_secs(86400);
+
+/// Upper bound on font directory entries inspected while scanning
+const MAX_SCANNED_ENTRIES: usize = 20000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NerdFontCache {
+    available: bool,
+    /// Answered by the user in the TUI rather than guessed
+    confirmed: bool,
+    detected_at: SystemTime,
+}
+
+/// Whether the terminal font likely has Nerd Font glyphs, or None when there
+/// is no usable evidence (e.g. over SSH, where the fonts live on the client).
+///
+/// Sources, in order: the `CCLINE_NERD_FONT` override (`1`/`0`), a confirmation
+/// given in the TUI, a recent cached guess, terminals that bundle the Nerd Font
+/// symbols, and a scan of the installed fonts.
+pub fn detect() -> Option<bool> {
+    if let Ok(value) = std::env::var("CCLINE_NERD_FONT") {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" => return Some(true),
+            "0" | "false" | "no" => return Some(false),
+            _ => {}
+        }
+    }
+
+    if let Some(cache) = load_cache() {
+        let fresh = SystemTime::now()
+            .duration_since(cache.detected_at)
+            .map(|age| age < DETECTION_TTL)
+            .unwrap_or(false);
+        if cache.confirmed || fresh {
+            return Some(cache.available);
+        }
+    }
+
+    let detected = terminal_hint().or_else(|| {
+        if std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some() {
+            None
+        } else {
+            Some(has_installed_nerd_font())
+        }
+    })?;
+
+    save_cache(detected, false);
+    Some(detected)
+}
+
+/// Whether the user already answered the TUI prompt
+pub fn is_confirmed() -> bool {
+    load_cache().map(|cache| cache.confirmed).unwrap_or(false)
+}
+
+/// Whether the user said so, with `CCLINE_NERD_FONT` or in the TUI
+pub fn is_answered() -> bool {
+    std::env::var_os("CCLINE_NERD_FONT").is_some() || is_confirmed()
+}
+
+/// Remember the user's answer to "do you see the glyph?"
+pub fn save_confirmation(available: bool) {
+    save_cache(available, true);
+}
+
+/// Terminals that ship the Nerd Font symbols as a fallback font, or that
+/// cannot render them at all
+fn terminal_hint() -> Option<bool> {
+    let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+    let term = std::env::var("TERM").unwrap_or_default();
+
+    if term_program == "WezTerm"
+        || term_program == "ghostty"
+        || term == "xterm-ghostty"
+        || term == "xterm-kitty"
+    {
+        return Some(true);
+    }
+    if term == "linux" {
+        return Some(false);
+    }
+
+    None
+}
+
+fn font_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(home) = crate::utils::paths::home_dir() {
+        dirs.push(home.join(".local").join("share").join("fonts"));
+        dirs.push(home.join(".fonts"));
+        dirs.push(home.join("Library").join("Fonts"));
+    }
+    if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+        dirs.push(
+            PathBuf::from(local)
+                .join("Microsoft")
+                .join("Windows")
+                .join("Fonts"),
+        );
+    }
+    dirs.push(PathBuf::from("/usr/share/fonts"));
+    dirs.push(PathBuf::from("/usr/local/share/fonts"));
+    dirs.push(PathBuf::from("/Library/Fonts"));
+    dirs.push(PathBuf::from("C:\\Windows\\Fonts"));
+
+    dirs
+}
+
+fn has_installed_nerd_font() -> bool {
+    let mut budget = MAX_SCANNED_ENTRIES;
+    font_dirs()
+        .iter()
+        .any(|dir| scan_for_nerd_font(dir, 3, &mut budget))
+}
+
+fn scan_for_nerd_font(dir: &Path, depth: usize, budget: &mut usize) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    for entry in entries.flatten() {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+
+        let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+        // "JetBrainsMonoNerdFont-Regular.ttf", "Hack Nerd Font Complete.ttf", ...
+        if name.contains("nerd") {
+            return true;
+        }
+
+        let path = entry.path();
+        if depth > 0 && path.is_dir() && scan_for_nerd_font(&path, depth - 1, budget) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// State rather than cache: it holds the user's answer to the prompt
+fn get_cache_path() -> PathBuf {
+    super::paths::state_dir().join("nerd_font.json")
+}
+
+fn load_cache() -> Option<NerdFontCache> {
+    let content = fs::read_to_string(get_cache_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_cache(available: bool, confirmed: bool) {
+    let path = get_cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    let cache = NerdFontCache {
+        available,
+        confirmed,
+        detected_at: SystemTime::now(),
+    };
+    if let Ok(content) = serde_json::to_vec(&cache) {
+        let _ = super::atomic::write(&path, &content);
+    }
+}
@@ -0,0 +1,145 @@
+//! Number formatting for segment text, following [`NumberFormat`]: compact
+//! ("127.4k") or full ("127,431") notation, precision, and the separators of
+//! a locale.
+
+use crate::config::{NumberFormat, NumberNotation};
+
+/// Compact suffixes, largest first
+const SUFFIXES: &[(f64, &str)] = &[(1e12, "T"), (1e9, "B"), (1e6, "M"), (1e3, "k")];
+
+/// Thousands and decimal separators of a locale such as "de-DE" or
+/// "fr_FR.UTF-8"; unknown languages get none
+fn locale_separators(locale: &str) -> Option<(&'static str, &'static str)> {
+    let locale = locale.split(['.', '@']).next().unwrap_or(locale);
+    let mut parts = locale.split(['-', '_']);
+    let language = parts.next()?.to_ascii_lowercase();
+    let region = parts.next().unwrap_or("").to_ascii_uppercase();
+
+    let separators = match (language.as_str(), region.as_str()) {
+        ("de" | "it" | "fr", "CH") | ("rm", _) => ("'", "."),
+        ("en" | "ja" | "zh" | "ko" | "th" | "he" | "hi", _) => (",", "."),
+        ("es", "MX" | "US") => (",", "."),
+        ("de" | "es" | "it" | "nl" | "pt" | "tr" | "da" | "id" | "el" | "ro", _) => (".", ","),
+        ("fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" | "bg", _) => {
+            ("\u{a0}", ",")
+        }
+        _ => return None,
+    };
+    Some(separators)
+}
+
+/// The locale numbers are formatted for in "auto" mode
+fn environment_locale() -> Option<String> {
+    ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+/// Separators for `format`: explicit ones first, then the locale's, then
+/// no grouping and a point
+fn separators(format: &NumberFormat) -> (String, String) {
+    let locale = match format.locale.as_deref() {
+        Some(locale) if locale.eq_ignore_ascii_case("auto") => environment_locale(),
+        Some(locale) => Some(locale.to_string()),
+        None => None,
+    };
+    let (locale_thousands, locale_decimal) = locale
+        .as_deref()
+        .and_then(locale_separators)
+        .unwrap_or(("", "."));
+    (
+        format
+            .thousands_separator
+            .clone()
+            .unwrap_or_else(|| locale_thousands.to_string()),
+        format
+            .decimal_separator
+            .clone()
+            .unwrap_or_else(|| locale_decimal.to_string()),
+    )
+}
+
+/// `value` with `precision` decimals, digits grouped in threes. With `trim`,
+/// trailing zero decimals are dropped ("150.0" becomes "150").
+fn format_decimal(value: f64, precision: usize, trim: bool, format: &NumberFormat) -> String {
+    let (thousands, decimal) = separators(format);
+    let text = format!("{:.*}", precision, value.abs());
+    let (integer, fraction) = match text.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (text.as_str(), ""),
+    };
+    let fraction = if trim {
+        fraction.trim_end_matches('0')
+    } else {
+        fraction
+    };
+
+    let mut out = String::with_capacity(text.len() + integer.len() / 3 * thousands.len() + 1);
+    if value < 0.0 && text.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        out.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            out.push_str(&thousands);
+        }
+        out.push(digit);
+    }
+    if !fraction.is_empty() {
+        out.push_str(&decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// `value` with an SI suffix: 127431 is "127.4k". Values below a thousand
+/// are written in full.
+fn format_compact(value: f64, format: &NumberFormat, default_precision: usize) -> String {
+    let precision = format.precision.unwrap_or(default_precision);
+    // Only trim when the precision was not asked for, so "1.50k" stays
+    let trim = format.precision.is_none();
+    for &(scale, suffix) in SUFFIXES {
+        let scaled = value.abs() / scale;
+        // 999,960 rounds to "1000.0k"; move up to "1M" instead
+        let rounded: f64 = format!("{:.*}", precision, scaled)
+            .parse()
+            .unwrap_or(scaled);
+        if rounded >= 1.0 {
+            let sign = if value < 0.0 { -1.0 } else { 1.0 };
+            return format!(
+                "{}{}",
+                format_decimal(sign * scaled, precision, trim, format),
+                suffix
+            );
+        }
+    }
+    let precision = if value.fract() == 0.0 { 0 } else { precision };
+    format_decimal(value, precision, false, format)
+}
+
+/// Token counts: compact by default, "127.4k"
+pub fn format_tokens(tokens: u64, format: &NumberFormat) -> String {
+    match format.notation.unwrap_or(NumberNotation::Compact) {
+        NumberNotation::Compact => format_compact(tokens as f64, format, 1),
+        NumberNotation::Full => format_decimal(tokens as f64, 0, false, format),
+    }
+}
+
+/// Amounts of money, without the currency sign: full with two decimals by
+/// default, "1234.56"
+pub fn format_cost(amount: f64, format: &NumberFormat) -> String {
+    match format.notation.unwrap_or(NumberNotation::Full) {
+        NumberNotation::Compact => format_compact(amount, format, 2),
+        NumberNotation::Full => {
+            format_decimal(amount, format.precision.unwrap_or(2), false, format)
+        }
+    }
+}
+
+/// Whole counts such as lines changed: full by default, "1234"
+pub fn format_count(count: u64, format: &NumberFormat) -> String {
+    match format.notation.unwrap_or(NumberNotation::Full) {
+        NumberNotation::Compact => format_compact(count as f64, format, 1),
+        NumberNotation::Full => format_decimal(count as f64, 0, false, format),
+    }
+}
@@ -0,0 +1,245 @@
+//! Where ccline keeps its files.
+//!
+//! Config, caches and state (logs, the spend ledger, queued reports) live in
+//! three directories, resolved once per process:
+//!
+//! - `CCLINE_HOME` puts all three in that one directory.
+//! - Otherwise the XDG directories are used: `$XDG_CONFIG_HOME/ccline`,
+//!   `$XDG_CACHE_HOME/ccline` and `$XDG_STATE_HOME/ccline`, defaulting to
+//!   `~/.config`, `~/.cache` and `~/.local/state`.
+//! - An install that already uses `~/.claude/ccline` keeps using it until
+//!   `ccline paths --migrate` moves its files, and so do fresh installs on
+//!   Windows and macOS unless `XDG_CONFIG_HOME` is set.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Home directory holding `.claude`, resolved the way Claude Code resolves it.
+///
+/// On Windows Claude Code (Node's `os.homedir()`) uses `%USERPROFILE%`, while
+/// `dirs::home_dir()` asks the shell for the profile folder and ignores the
+/// environment; the two differ for redirected profiles and under some
+/// terminals. Other platforms use `dirs::home_dir()`, which honours `$HOME`.
+pub fn home_dir() -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        if let Some(profile) = std::env::var_os("USERPROFILE").filter(|p| !p.is_empty()) {
+            return Some(PathBuf::from(profile));
+        }
+    }
+
+    dirs::home_dir()
+}
+
+/// Files of the `~/.claude/ccline` layout, by the directory they belong in
+/// now. The binary installed there stays where Claude Code's settings point.
+pub const CONFIG_FILES: &[&str] = &[
+    "config.toml",
+    "models.toml",
+    "themes",
+    "locales",
+    "mock",
+    "backups",
+];
+pub const CACHE_FILES: &[&str] = &[
+    "cache",
+    "endpoint_cache.json",
+    "plan_cache.json",
+    "pricing.json",
+    "http",
+    "background_cache.json",
+    "updates",
+    "weekly_usage.json",
+];
+pub const STATE_FILES: &[&str] = &[
+    "spend.json",
+    "last_input.json",
+    "reports",
+    "reporter_id",
+    ".update_state.json",
+    "nerd_font.json",
+    "recent_colors.json",
+    "migrations.log",
+];
+
+#[derive(Debug, Clone)]
+pub struct Dirs {
+    pub config: PathBuf,
+    pub cache: PathBuf,
+    pub state: PathBuf,
+}
+
+impl Dirs {
+    fn single(dir: PathBuf) -> Self {
+        Self {
+            config: dir.clone(),
+            cache: dir.clone(),
+            state: dir,
+        }
+    }
+}
+
+/// `~/.claude/ccline`, or `.claude/ccline` without a home directory
+pub fn legacy_dir() -> PathBuf {
+    home_dir()
+        .map(|home| home.join(".claude").join("ccline"))
+        .unwrap_or_else(|| PathBuf::from(".claude/ccline"))
+}
+
+fn env_dir(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        // The XDG spec says relative paths are to be ignored
+        .filter(|path| path.is_absolute())
+}
+
+/// The XDG directories, whether or not they exist
+pub fn xdg_dirs() -> Option<Dirs> {
+    let base = |var: &str, default: &[&str]| {
+        env_dir(var)
+            .or_else(|| {
+                Some(
+                    default
+                        .iter()
+                        .fold(home_dir()?, |path, part| path.join(part)),
+                )
+            })
+            .map(|dir| dir.join("ccline"))
+    };
+    Some(Dirs {
+        config: base("XDG_CONFIG_HOME", &[".config"])?,
+        cache: base("XDG_CACHE_HOME", &[".cache"])?,
+        state: base("XDG_STATE_HOME", &[".local", "state"])?,
+    })
+}
+
+/// Whether `~/.claude/ccline` holds a config or caches from an earlier render
+fn legacy_in_use(legacy: &Path) -> bool {
+    legacy.join("config.toml").exists() || legacy.join("cache").is_dir()
+}
+
+/// `CCLINE_HOME`, when set to an absolute path
+pub fn ccline_home() -> Option<PathBuf> {
+    env_dir("CCLINE_HOME")
+}
+
+fn resolve() -> Dirs {
+    if let Some(home) = ccline_home() {
+        return Dirs::single(home);
+    }
+
+    let legacy = legacy_dir();
+    let Some(xdg) = xdg_dirs() else {
+        return Dirs::single(legacy);
+    };
+    if xdg.config.is_dir() {
+        return xdg;
+    }
+    if legacy_in_use(&legacy) {
+        return Dirs::single(legacy);
+    }
+    let xdg_by_default =
+        cfg!(not(any(windows, target_os = "macos"))) || env_dir("XDG_CONFIG_HOME").is_some();
+    if xdg_by_default {
+        xdg
+    } else {
+        Dirs::single(legacy)
+    }
+}
+
+/// The directories this process uses
+pub fn dirs() -> &'static Dirs {
+    static DIRS: OnceLock<Dirs> = OnceLock::new();
+    DIRS.get_or_init(resolve)
+}
+
+/// config.toml, themes, models.toml, locales and mock scenarios
+pub fn config_dir() -> PathBuf {
+    dirs().config.clone()
+}
+
+/// Files ccline can rebuild: the render cache, API responses and prices
+pub fn cache_dir() -> PathBuf {
+    dirs().cache.clone()
+}
+
+/// Files that are neither config nor rebuildable: the spend ledger, logs,
+/// queued reports and answers to one-time prompts
+pub fn state_dir() -> PathBuf {
+    dirs().state.clone()
+}
+
+/// Where `ccline paths --migrate` moves the `~/.claude/ccline` files:
+/// `CCLINE_HOME` when set, else the XDG directories
+pub fn migration_target() -> Option<Dirs> {
+    match ccline_home() {
+        Some(home) => Some(Dirs::single(home)),
+        None => xdg_dirs(),
+    }
+}
+
+/// Move `from` to `to`, copying when they are on different filesystems
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_path(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if !from.is_dir() {
+        return fs::copy(from, to).map(|_| ());
+    }
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        copy_path(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+/// Move the files of the `~/.claude/ccline` layout into `target`, skipping
+/// any that already exist there, and return what was moved. Migration
+/// backups (`config.v0.toml.bak`) go with the config.
+pub fn migrate_legacy(target: &Dirs) -> std::io::Result<Vec<(PathBuf, PathBuf)>> {
+    let legacy = legacy_dir();
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (names, dir) in [
+        (CONFIG_FILES, &target.config),
+        (CACHE_FILES, &target.cache),
+        (STATE_FILES, &target.state),
+    ] {
+        for name in names {
+            moves.push((legacy.join(name), dir.join(name)));
+        }
+    }
+    if let Ok(entries) = fs::read_dir(&legacy) {
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with("config.") && name.ends_with(".bak") {
+                moves.push((entry.path(), target.config.join(name)));
+            }
+        }
+    }
+
+    fs::create_dir_all(&target.config)?;
+    let mut moved = Vec::new();
+    for (from, to) in moves {
+        if from == to || !from.exists() || to.exists() {
+            continue;
+        }
+        move_path(&from, &to)?;
+        moved.push((from, to));
+    }
+    Ok(moved)
+}
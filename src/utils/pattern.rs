@@ -0,0 +1,12 @@
+//! The regex engine: `regex` with the `regex` feature, else `regex-lite`
+//! with the `regex-lite` feature (part of `minimal`), which has the same API
+//! but ASCII-only character classes (`\d`, `\w`, `\s`) and no `\p{..}`.
+//! Only one of them is linked.
+
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub use regex_lite::Regex;
+
+#[cfg(not(any(feature = "regex", feature = "regex-lite")))]
+compile_error!("enable the `regex` or the `regex-lite` feature (`minimal` includes it)");
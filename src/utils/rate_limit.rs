@@ -0,0 +1,214 @@
+//! Rate limits reported in HTTP response headers. Relays pass on
+//! Anthropic's (`anthropic-ratelimit-requests-remaining`), use OpenAI's
+//! (`x-ratelimit-remaining-requests`) or the IETF draft's
+//! (`ratelimit-remaining`); all are read into one shape.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// What is limited: "requests", "tokens", "input-tokens", ...
+    pub name: String,
+    pub remaining: u64,
+    pub limit: Option<u64>,
+    /// Seconds until the limit resets
+    pub reset_in: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimits {
+    pub limits: Vec<RateLimit>,
+    /// `retry-after` of a throttled response, in seconds
+    pub retry_after: Option<u64>,
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Remaining,
+    Limit,
+    Reset,
+}
+
+/// The limit and field a header reports, if it is a rate limit header
+fn classify(header: &str) -> Option<(String, Field)> {
+    let field = |name: &str| match name {
+        "remaining" => Some(Field::Remaining),
+        "limit" => Some(Field::Limit),
+        "reset" => Some(Field::Reset),
+        _ => None,
+    };
+
+    // anthropic-ratelimit-<name>-<field>
+    if let Some(rest) = header.strip_prefix("anthropic-ratelimit-") {
+        let (name, last) = rest.rsplit_once('-')?;
+        return Some((name.to_string(), field(last)?));
+    }
+    // x-ratelimit-<field>-<name>, or x-ratelimit-<field> for requests
+    if let Some(rest) = header.strip_prefix("x-ratelimit-") {
+        return match rest.split_once('-') {
+            Some((first, name)) => Some((name.to_string(), field(first)?)),
+            None => Some(("requests".to_string(), field(rest)?)),
+        };
+    }
+    // ratelimit-<field> (IETF draft)
+    let rest = header.strip_prefix("ratelimit-")?;
+    Some(("requests".to_string(), field(rest)?))
+}
+
+/// Seconds until a reset given as seconds ("30", "1.5"), a Go duration
+/// ("6m0s", "20ms"), Unix seconds, or an RFC 3339 time
+fn parse_reset(value: &str, now: u64) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<f64>() {
+        if number < 0.0 {
+            return None;
+        }
+        let number = number.ceil() as u64;
+        // Large numbers are points in time, not durations
+        return Some(if number > 1_000_000_000 {
+            number.saturating_sub(now)
+        } else {
+            number
+        });
+    }
+    if let Some(seconds) = parse_go_duration(value) {
+        return Some(seconds);
+    }
+    parse_rfc3339(value).map(|at| at.saturating_sub(now))
+}
+
+/// "1h2m3.5s", "20ms" as whole seconds, rounded up
+fn parse_go_duration(value: &str) -> Option<u64> {
+    let mut millis = 0f64;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|&end| end > 0)?;
+        let number: f64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_end] {
+            "ms" => 1.0,
+            "s" => 1_000.0,
+            "m" => 60_000.0,
+            "h" => 3_600_000.0,
+            _ => return None,
+        };
+        millis += number * scale;
+        rest = &rest[unit_end..];
+    }
+    Some((millis / 1000.0).ceil() as u64)
+}
+
+/// Unix seconds of an RFC 3339 time, e.g. "2025-08-14T18:00:00Z"
+#[cfg(feature = "chrono")]
+pub fn parse_rfc3339(value: &str) -> Option<u64> {
+    let time = chrono::DateTime::parse_from_rfc3339(value).ok()?;
+    u64::try_from(time.timestamp()).ok()
+}
+
+#[cfg(not(feature = "chrono"))]
+pub fn parse_rfc3339(_value: &str) -> Option<u64> {
+    None
+}
+
+impl RateLimits {
+    /// Read the rate limit headers among `headers` (name, value)
+    pub fn from_headers<'a>(headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut remaining: Vec<(String, u64)> = Vec::new();
+        let mut limits: Vec<(String, u64)> = Vec::new();
+        let mut resets: Vec<(String, u64)> = Vec::new();
+        let mut retry_after = None;
+
+        for (name, value) in headers {
+            let name = name.to_ascii_lowercase();
+            if name == "retry-after" {
+                retry_after = value.trim().parse().ok();
+                continue;
+            }
+            let Some((limit, field)) = classify(&name) else {
+                continue;
+            };
+            match field {
+                Field::Remaining => {
+                    if let Ok(value) = value.trim().parse() {
+                        remaining.push((limit, value));
+                    }
+                }
+                Field::Limit => {
+                    // The IETF draft may append a policy: "100, 100;w=60"
+                    let first = value.split([',', ';']).next().unwrap_or("");
+                    if let Ok(value) = first.trim().parse() {
+                        limits.push((limit, value));
+                    }
+                }
+                Field::Reset => {
+                    if let Some(value) = parse_reset(value, now) {
+                        resets.push((limit, value));
+                    }
+                }
+            }
+        }
+
+        let find = |list: &[(String, u64)], name: &str| {
+            list.iter()
+                .find(|(limit, _)| limit == name)
+                .map(|(_, value)| *value)
+        };
+        Self {
+            limits: remaining
+                .iter()
+                .map(|(name, remaining)| RateLimit {
+                    name: name.clone(),
+                    remaining: *remaining,
+                    limit: find(&limits, name),
+                    reset_in: find(&resets, name),
+                })
+                .collect(),
+            retry_after,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty() && self.retry_after.is_none()
+    }
+
+    /// The limit with the smallest share left, or the fewest left when no
+    /// maximum is reported
+    pub fn tightest(&self) -> Option<&RateLimit> {
+        self.limits.iter().min_by(|a, b| {
+            let share = |limit: &RateLimit| match limit.limit {
+                Some(max) if max > 0 => limit.remaining as f64 / max as f64,
+                _ => limit.remaining as f64,
+            };
+            share(a).total_cmp(&share(b))
+        })
+    }
+
+    /// Metadata entries: `ratelimit_<name>_remaining`, `_limit` and
+    /// `_reset_in_seconds` per limit, plus `retry_after`
+    pub fn metadata(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        for limit in &self.limits {
+            let key = format!("ratelimit_{}", limit.name.replace('-', "_"));
+            entries.push((format!("{}_remaining", key), limit.remaining.to_string()));
+            if let Some(max) = limit.limit {
+                entries.push((format!("{}_limit", key), max.to_string()));
+            }
+            if let Some(reset_in) = limit.reset_in {
+                entries.push((format!("{}_reset_in_seconds", key), reset_in.to_string()));
+            }
+        }
+        if let Some(retry_after) = self.retry_after {
+            entries.push(("retry_after".to_string(), retry_after.to_string()));
+        }
+        entries
+    }
+}
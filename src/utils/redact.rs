@@ -0,0 +1,184 @@
+//! Masking of secrets in text that ends up on a terminal or in a bug report:
+//! API keys, bearer tokens, and credentials embedded in URLs.
+
+/// Query parameters whose values are masked in URLs
+const SECRET_PARAMS: &[&str] = &[
+    "key",
+    "api_key",
+    "apikey",
+    "token",
+    "access_token",
+    "auth",
+    "secret",
+    "password",
+    "sig",
+    "signature",
+];
+
+/// Environment variables holding keys; their values are masked wherever
+/// they appear, whatever their format
+const SECRET_ENV: &[&str] = &[
+    "PACKYCODE_API_KEY",
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+];
+
+const MASK: &str = "***";
+
+/// Mask everything in `text` that looks like a credential:
+/// - values of the key environment variables ccline reads
+/// - `Bearer <token>` and `Basic <credentials>`
+/// - `sk-…` style keys (`sk-ant-api03-***`)
+/// - `user:password@` in URLs
+/// - secret query parameters (`?key=***`)
+pub fn redact(text: &str) -> String {
+    redact_with(text, &[])
+}
+
+/// Like [`redact`], also masking `secrets` known to the caller, such as a key
+/// read from a file
+pub fn redact_with(text: &str, secrets: &[&str]) -> String {
+    let env_values: Vec<String> = SECRET_ENV
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .collect();
+    let mut text = text.to_string();
+    for secret in secrets
+        .iter()
+        .copied()
+        .chain(env_values.iter().map(String::as_str))
+    {
+        if secret.len() >= 8 {
+            text = text.replace(secret, MASK);
+        }
+    }
+
+    let text = redact_auth_schemes(&text);
+    let text = redact_prefixed_keys(&text);
+    let text = redact_userinfo(&text);
+    redact_query_params(&text)
+}
+
+/// Print a `[DEBUG]` line to stderr with secrets, and `secrets`, masked
+pub fn debug_log(message: &str, secrets: &[&str]) {
+    eprintln!("[DEBUG] {}", redact_with(message, secrets));
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~' | '+' | '/' | '=')
+}
+
+fn redact_auth_schemes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = ["Bearer ", "bearer ", "Basic ", "basic "]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme).map(|index| (index, scheme.len())))
+        .min()
+    {
+        let (index, len) = start;
+        out.push_str(&rest[..index + len]);
+        let after = &rest[index + len..];
+        let token_len = after.find(|c| !is_token_char(c)).unwrap_or(after.len());
+        if token_len > 0 {
+            out.push_str(MASK);
+        }
+        rest = &after[token_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `sk-ant-api03-abc…` keeps its prefix up to the last dash before the
+/// random part, so the kind of key is still visible
+fn redact_prefixed_keys(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find("sk-") {
+        // Not the tail of a longer word such as "task-"
+        let boundary = rest[..index]
+            .chars()
+            .last()
+            .is_none_or(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        let after = &rest[index..];
+        let len = after.find(|c| !is_token_char(c)).unwrap_or(after.len());
+        let token = &after[..len];
+        if !boundary || len < 12 {
+            out.push_str(&rest[..index + 3]);
+            rest = &rest[index + 3..];
+            continue;
+        }
+
+        // Prefix segments are short words like "ant" and "api03"
+        let mut prefix_len = 3;
+        for part in token[3..].split('-') {
+            if part.len() > 6 || prefix_len + part.len() + 1 >= len {
+                break;
+            }
+            prefix_len += part.len() + 1;
+        }
+        out.push_str(&rest[..index]);
+        out.push_str(&token[..prefix_len]);
+        out.push_str(MASK);
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_userinfo(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find("://") {
+        out.push_str(&rest[..index + 3]);
+        let after = &rest[index + 3..];
+        let authority_len = after
+            .find(|c: char| c == '/' || c == '?' || c == '#' || c.is_whitespace())
+            .unwrap_or(after.len());
+        let authority = &after[..authority_len];
+        match authority.rfind('@') {
+            Some(at) => {
+                out.push_str(MASK);
+                out.push_str(&authority[at..]);
+            }
+            None => out.push_str(authority),
+        }
+        rest = &after[authority_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_query_params(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(['?', '&']) {
+        out.push_str(&rest[..=index]);
+        rest = &rest[index + 1..];
+
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        if !rest[name_len..].starts_with('=')
+            || !SECRET_PARAMS
+                .iter()
+                .any(|secret| secret.eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+
+        out.push_str(name);
+        out.push('=');
+        let value = &rest[name_len + 1..];
+        let value_len = value
+            .find(|c: char| c == '&' || c == '#' || c.is_whitespace() || c == '"' || c == '\'')
+            .unwrap_or(value.len());
+        if value_len > 0 {
+            out.push_str(MASK);
+        }
+        rest = &value[value_len..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+/// Whether `ch` can change terminal state or hide text: C0 and C1 controls,
+/// DEL, and the bidirectional overrides that reorder what follows them
+fn is_unsafe(ch: char) -> bool {
+    ch.is_control() || matches!(ch, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Make text from outside ccline (statusline JSON, branch names, API
+/// responses) safe to print: escape sequences are removed whole, so that
+/// e.g. a branch named `\e]0;pwned\a` cannot retitle the terminal, and
+/// other control characters are dropped. Tabs and newlines become spaces,
+/// since the statusline is a single line.
+pub fn sanitize(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_unsafe) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\t' | '\n' | '\r' => {
+                if !clean.ends_with(' ') {
+                    clean.push(' ');
+                }
+            }
+            '\x1b' => match chars.next() {
+                // CSI: parameters until a final byte in @..~
+                Some('[') => {
+                    for next in chars.by_ref() {
+                        if ('@'..='~').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+                // OSC, DCS, SOS, PM, APC: a string until BEL or ST (ESC \)
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_string(&mut chars),
+                // Two-character sequences; charset designations take one more
+                Some('(' | ')' | '*' | '+' | '-' | '.' | '/' | '#' | '%' | ' ') => {
+                    chars.next();
+                }
+                _ => {}
+            },
+            // 8-bit CSI
+            '\u{9b}' => {
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            // 8-bit OSC, DCS, SOS, PM, APC
+            '\u{9d}' | '\u{90}' | '\u{98}' | '\u{9e}' | '\u{9f}' => skip_string(&mut chars),
+            ch if is_unsafe(ch) => {}
+            ch => clean.push(ch),
+        }
+    }
+    Cow::Owned(clean)
+}
+
+/// Skip a control string up to and including its terminator: BEL, ESC \ or
+/// the 8-bit ST
+fn skip_string(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    while let Some(next) = chars.next() {
+        match next {
+            '\x07' | '\u{9c}' => break,
+            '\x1b' => {
+                if chars.peek() == Some(&'\\') {
+                    chars.next();
+                }
+                break;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// Block characters from lowest to highest
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One block character per value, scaled to the largest value. Zero and
+/// negative values get the lowest block; any positive value at least the
+/// second, so small but real activity stays visible next to a burst.
+pub fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+    values
+        .iter()
+        .map(|&value| {
+            if max <= 0.0 || value <= 0.0 || !value.is_finite() {
+                return LEVELS[0];
+            }
+            let top = (LEVELS.len() - 1) as f64;
+            let level = (value / max * top).ceil().clamp(1.0, top);
+            LEVELS[level as usize]
+        })
+        .collect()
+}
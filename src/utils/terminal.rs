@@ -0,0 +1,87 @@
+use std::sync::OnceLock;
+
+/// Terminal names `[[platform_defaults]]` entries can match
+pub const TERMINALS: &[&str] = &[
+    "windows-terminal",
+    "conhost",
+    "conemu",
+    "iterm2",
+    "apple-terminal",
+    "vscode",
+    "jetbrains",
+    "wezterm",
+    "ghostty",
+    "kitty",
+    "alacritty",
+    "konsole",
+    "vte",
+];
+
+static TERMINAL: OnceLock<Option<&'static str>> = OnceLock::new();
+
+/// The terminal program ccline renders in, detected once per process from the
+/// variables terminals set, or `CCLINE_TERMINAL`. `None` when unknown, e.g.
+/// inside tmux or over SSH, where most of them are not passed on.
+pub fn detect() -> Option<&'static str> {
+    *TERMINAL.get_or_init(detect_uncached)
+}
+
+fn detect_uncached() -> Option<&'static str> {
+    if let Ok(value) = std::env::var("CCLINE_TERMINAL") {
+        let value = value.trim().to_ascii_lowercase();
+        return TERMINALS.iter().copied().find(|name| *name == value);
+    }
+
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+
+    // Terminal-specific variables first: TERM_PROGRAM is inherited by
+    // terminals started from another one, e.g. the VS Code terminal of a
+    // VS Code launched from iTerm2
+    if set("WT_SESSION") {
+        return Some("windows-terminal");
+    }
+    if set("ConEmuANSI") {
+        return Some("conemu");
+    }
+    if var("TERMINAL_EMULATOR") == "JetBrains-JediTerm" {
+        return Some("jetbrains");
+    }
+    match var("TERM_PROGRAM").as_str() {
+        "iTerm.app" => return Some("iterm2"),
+        "Apple_Terminal" => return Some("apple-terminal"),
+        "vscode" => return Some("vscode"),
+        "WezTerm" => return Some("wezterm"),
+        "ghostty" => return Some("ghostty"),
+        _ => {}
+    }
+    if var("TERM") == "xterm-kitty" || set("KITTY_WINDOW_ID") {
+        return Some("kitty");
+    }
+    if set("ALACRITTY_SOCKET") || set("ALACRITTY_WINDOW_ID") {
+        return Some("alacritty");
+    }
+    if set("KONSOLE_VERSION") {
+        return Some("konsole");
+    }
+    // GNOME Terminal, Tilix, Xfce Terminal and others built on VTE
+    if set("VTE_VERSION") {
+        return Some("vte");
+    }
+    // The classic console host sets none of the above
+    if cfg!(windows) && !set("TERM") && !set("TERM_PROGRAM") {
+        return Some("conhost");
+    }
+    None
+}
+
+/// The operating system as `[[platform_defaults]]` names it: "windows",
+/// "macos", "linux", or "wsl" for Linux under WSL (which "linux" also
+/// matches)
+pub fn os() -> &'static str {
+    if crate::utils::wsl::detect().is_some() {
+        "wsl"
+    } else {
+        std::env::consts::OS
+    }
+}
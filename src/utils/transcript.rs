@@ -0,0 +1,77 @@
+//! Reading Claude Code's session transcripts, which are JSON lines appended
+//! as the session goes on.
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// The complete lines in the last `max_bytes` of the transcript
+pub fn tail(path: &Path, max_bytes: u64) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).ok()?;
+
+    // Reading from the middle of the file starts inside a line
+    let first = if start > 0 {
+        tail.iter().position(|byte| *byte == b'\n')? + 1
+    } else {
+        0
+    };
+    Some(String::from_utf8_lossy(&tail[first..]).into_owned())
+}
+
+/// The complete lines appended after `offset`, and the offset after them.
+/// A line still being written is left for a later read.
+pub fn appended(path: &Path, offset: u64) -> Option<(String, u64)> {
+    let mut file = fs::File::open(path).ok()?;
+    if file.metadata().ok()?.len() < offset {
+        return None;
+    }
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut appended = Vec::new();
+    file.read_to_end(&mut appended).ok()?;
+
+    let complete = appended
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    Some((
+        String::from_utf8_lossy(&appended[..complete]).into_owned(),
+        offset + complete as u64,
+    ))
+}
+
+/// Unix seconds of an entry's `timestamp`, which Claude Code writes in UTC
+/// as "2025-10-16T12:34:56.789Z"
+pub fn timestamp_seconds(timestamp: &str) -> Option<f64> {
+    let (date, time) = timestamp.trim_end_matches('Z').split_once('T')?;
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+    let mut time = time.splitn(3, ':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next()?.parse().ok()?;
+    let second: f64 = time.next()?.parse().ok()?;
+    // Out-of-range fields would overflow the arithmetic below
+    if !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0.0..61.0).contains(&second)
+    {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some((days * 86_400 + hour * 3600 + minute * 60) as f64 + second)
+}
@@ -0,0 +1,123 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Remove ANSI escape sequences (CSI such as colors, and OSC such as hyperlinks)
+pub fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            plain.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameters until a final byte in @..~
+            Some('[') => {
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            // OSC: until BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(next) = chars.next() {
+                    if next == '\x07' {
+                        break;
+                    }
+                    if next == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    plain
+}
+
+/// Terminal columns taken by a single grapheme cluster. Emoji and CJK take
+/// two columns; Nerd Font glyphs (private use area) take one.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    grapheme.width()
+}
+
+/// Terminal columns taken by text, ignoring ANSI escape sequences
+pub fn display_width(text: &str) -> usize {
+    strip_ansi(text).graphemes(true).map(grapheme_width).sum()
+}
+
+/// Cut plain text to at most `max_width` columns without splitting grapheme
+/// clusters or wide characters, appending `ellipsis` when anything was cut
+pub fn truncate_to_width(text: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(display_width(ellipsis));
+    let mut result = String::new();
+    let mut width = 0;
+
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme_width(grapheme);
+        if width + grapheme_width > budget {
+            break;
+        }
+        result.push_str(grapheme);
+        width += grapheme_width;
+    }
+
+    if max_width >= display_width(ellipsis) {
+        result.push_str(ellipsis);
+    }
+    result
+}
+
+/// Like [`truncate_to_width`], but keeps the end of the text:
+/// "…feature/login-form"
+pub fn truncate_start_to_width(text: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(display_width(ellipsis));
+    let mut kept = Vec::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true).rev() {
+        let grapheme_width = grapheme_width(grapheme);
+        if width + grapheme_width > budget {
+            break;
+        }
+        kept.push(grapheme);
+        width += grapheme_width;
+    }
+
+    let mut result = String::new();
+    if max_width >= display_width(ellipsis) {
+        result.push_str(ellipsis);
+    }
+    result.extend(kept.into_iter().rev());
+    result
+}
+
+/// Like [`truncate_to_width`], but keeps both ends of the text:
+/// "claude-…-20250514"
+pub fn truncate_middle_to_width(text: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    let ellipsis_width = display_width(ellipsis);
+    if max_width < ellipsis_width {
+        return truncate_to_width(text, max_width, ellipsis);
+    }
+
+    // The start gets the extra column of an odd budget
+    let budget = max_width - ellipsis_width;
+    let head = truncate_to_width(text, budget - budget / 2, "");
+    let tail = truncate_start_to_width(text, budget / 2, "");
+    format!("{}{}{}", head, ellipsis, tail)
+}
@@ -0,0 +1,100 @@
+use std::sync::OnceLock;
+
+/// Facts about the WSL environment ccline runs in, detected once per process
+#[derive(Debug, Clone, Default)]
+pub struct WslInfo {
+    /// Distribution name from `WSL_DISTRO_NAME`, e.g. "Ubuntu"
+    pub distro: Option<String>,
+    /// Where Windows drives are mounted, `/mnt` unless `/etc/wsl.conf` says otherwise
+    pub mount_root: String,
+}
+
+static WSL: OnceLock<Option<WslInfo>> = OnceLock::new();
+
+/// The WSL environment, or `None` outside WSL
+pub fn detect() -> Option<&'static WslInfo> {
+    WSL.get_or_init(detect_uncached).as_ref()
+}
+
+fn detect_uncached() -> Option<WslInfo> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // WSL_DISTRO_NAME and WSL_INTEROP are set by the WSL init process; the
+    // kernel version string covers shells that scrubbed the environment
+    let distro = std::env::var("WSL_DISTRO_NAME")
+        .ok()
+        .filter(|name| !name.is_empty());
+    let in_wsl = distro.is_some()
+        || std::env::var_os("WSL_INTEROP").is_some()
+        || std::fs::read_to_string("/proc/version")
+            .map(|version| version.to_lowercase().contains("microsoft"))
+            .unwrap_or(false);
+    if !in_wsl {
+        return None;
+    }
+
+    let mount_root = std::fs::read_to_string("/etc/wsl.conf")
+        .ok()
+        .and_then(|conf| automount_root(&conf))
+        .unwrap_or_else(|| "/mnt".to_string());
+
+    Some(WslInfo { distro, mount_root })
+}
+
+/// `root` from the `[automount]` section of wsl.conf, without a trailing slash
+fn automount_root(conf: &str) -> Option<String> {
+    let mut in_automount = false;
+    for line in conf.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            in_automount = line.eq_ignore_ascii_case("[automount]");
+        } else if in_automount {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "root" {
+                    let root = value.trim().trim_matches('"').trim_end_matches('/');
+                    return Some(if root.is_empty() { "/" } else { root }.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+impl WslInfo {
+    /// Translate a Windows path into the path WSL sees: `C:\work\proj`
+    /// becomes `/mnt/c/work/proj` and `\\wsl$\Ubuntu\home\me` becomes
+    /// `/home/me` when it points into the current distribution. Paths that
+    /// are not Windows-style, or that have no WSL equivalent, give `None`.
+    pub fn translate_windows_path(&self, path: &str) -> Option<String> {
+        // Drive paths: `C:\...` or `C:/...`
+        let bytes = path.as_bytes();
+        if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+            let rest = &path[2..];
+            if !(rest.is_empty() || rest.starts_with(['\\', '/'])) {
+                return None;
+            }
+            let drive = (bytes[0] as char).to_ascii_lowercase();
+            let root = self.mount_root.trim_end_matches('/');
+            return Some(
+                format!("{}/{}{}", root, drive, rest.replace('\\', "/"))
+                    .trim_end_matches('/')
+                    .to_string(),
+            );
+        }
+
+        // Distribution shares: `\\wsl$\<distro>\...` or `\\wsl.localhost\<distro>\...`
+        let share = path.replace('\\', "/");
+        let rest = share
+            .strip_prefix("//wsl$/")
+            .or_else(|| share.strip_prefix("//wsl.localhost/"))?;
+        let (distro, inner) = rest.split_once('/').unwrap_or((rest, ""));
+        let current = self.distro.as_deref()?;
+        if !distro.eq_ignore_ascii_case(current) {
+            return None;
+        }
+        let inner = inner.trim_end_matches('/');
+        Some(format!("/{}", inner))
+    }
+}
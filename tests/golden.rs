@@ -0,0 +1,174 @@
+//! Golden tests for the whole statusline: every fixture session in
+//! `tests/fixtures/` is collected and rendered with every built-in theme and
+//! compared with `tests/golden/<session>.txt`, followed by the default
+//! theme with every segment switched on so segments the presets leave off
+//! are covered too.
+//!
+//! A fixture is a mock scenario (`session.json`) whose `transcript_path`
+//! names a transcript next to it. Segments read the payload and transcript
+//! as they do in Claude Code; network segments only get the data the
+//! fixture's `segments` gives them, like `ccline --mock`.
+//!
+//! After an intended change to the output, rewrite the golden files with
+//! `CCLINE_UPDATE_GOLDEN=1 cargo test --test golden` and review the diff.
+
+use ccometixline_packycc::core::mock::MockScenario;
+use ccometixline_packycc::core::segments::with_registry;
+use ccometixline_packycc::ui::themes::ThemePresets;
+use ccometixline_packycc::{render, Config, SegmentId};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn golden_path(session: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/golden/{}.txt", session))
+}
+
+/// Keep renders independent of the machine: no config, caches, spending
+/// records or Claude Code settings from the real directories, no API key and
+/// no terminal detection
+fn isolate() {
+    let home = std::env::temp_dir().join(format!("ccline-golden-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    std::env::set_var("HOME", &home);
+    std::env::set_var("CCLINE_HOME", home.join(".claude/ccline"));
+    for key in [
+        "PACKYCODE_API_KEY",
+        "ANTHROPIC_API_KEY",
+        "ANTHROPIC_AUTH_TOKEN",
+        "ANTHROPIC_BASE_URL",
+    ] {
+        std::env::remove_var(key);
+    }
+    std::env::remove_var("COLORTERM");
+    std::env::set_var("CCLINE_NERD_FONT", "1");
+}
+
+/// The built-in theme `name` as a fresh install renders it
+fn theme(name: &str, scenario: &MockScenario) -> Config {
+    let mut config = ThemePresets::get_theme(name);
+    config.language = Some("en".to_string());
+    config.resolve_theme_overrides(&scenario.input);
+    config
+}
+
+/// Segments left out of the every-segment render: computed has nothing to
+/// show without an expression, and container and modes look at the machine
+/// and the parent process rather than anything a fixture can set
+const NOT_RENDERED: [SegmentId; 3] = [SegmentId::Computed, SegmentId::Container, SegmentId::Modes];
+
+/// The default theme with every registered segment enabled
+fn every_segment(scenario: &MockScenario) -> Config {
+    let mut config = theme("default", scenario);
+    let names: Vec<String> = with_registry(|registry| {
+        registry
+            .infos()
+            .filter(|info| !NOT_RENDERED.contains(&info.id))
+            .map(|info| info.id.as_str().to_string())
+            .collect()
+    });
+    config
+        .override_segments(&names, &[])
+        .expect("registered segments resolve");
+    config.resolve_theme_overrides(&scenario.input);
+    config
+}
+
+/// Escape sequences written as `\e` so the golden files stay readable
+fn escaped(line: &str) -> String {
+    line.replace('\x1b', "\\e")
+}
+
+/// The statusline of `session` in every built-in theme, one theme per
+/// paragraph
+fn render_session(session: &Path) -> String {
+    let mut scenario = MockScenario::load(&session.join("session.json").to_string_lossy())
+        .unwrap_or_else(|e| panic!("{}: {}", session.display(), e));
+    scenario.input.transcript_path = session
+        .join(&scenario.input.transcript_path)
+        .to_string_lossy()
+        .into_owned();
+
+    let mut configs: Vec<(&str, Config)> = ThemePresets::get_available_themes()
+        .into_iter()
+        .map(|(name, _)| (name, theme(name, &scenario)))
+        .collect();
+    configs.push(("every segment", every_segment(&scenario)));
+
+    let mut out = String::new();
+    for (name, config) in configs {
+        let segments = scenario.collect(&config);
+        let _ = writeln!(out, "{}\n{}\n", name, escaped(&render(&config, segments)));
+    }
+    out
+}
+
+/// The first line that differs, for the failure message
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for number in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (expected, actual) if expected == actual => continue,
+            (expected, actual) => {
+                return format!(
+                    "line {}:\n  expected: {}\n  actual:   {}",
+                    number,
+                    expected.unwrap_or("<end of file>"),
+                    actual.unwrap_or("<end of file>")
+                );
+            }
+        }
+    }
+    String::new()
+}
+
+#[test]
+fn sessions_render_as_recorded() {
+    isolate();
+    let update = std::env::var_os("CCLINE_UPDATE_GOLDEN").is_some();
+
+    let mut sessions: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .expect("tests/fixtures exists")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("session.json").is_file())
+        .collect();
+    sessions.sort();
+    assert!(
+        !sessions.is_empty(),
+        "no fixture sessions in tests/fixtures"
+    );
+
+    let mut failures = Vec::new();
+    for session in &sessions {
+        let name = session.file_name().unwrap().to_string_lossy().into_owned();
+        let actual = render_session(session);
+        let path = golden_path(&name);
+
+        if update {
+            fs::write(&path, &actual).expect("golden file is writable");
+            continue;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        if expected != actual {
+            failures.push(format!(
+                "{}: {}",
+                name,
+                first_difference(&expected, &actual)
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "rendered statuslines differ from tests/golden; if the change is intended, \
+         rerun with CCLINE_UPDATE_GOLDEN=1 and review the diff\n\n{}",
+        failures.join("\n\n")
+    );
+}
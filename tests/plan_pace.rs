@@ -0,0 +1,93 @@
+//! Edge cases of the plan segment's estimate of when the 5-hour session
+//! allowance runs out at the current pace.
+
+use ccometixline_packycc::core::segments::plan::{
+    session_time_left, MIN_PACE_SECS, PACE_WINDOW_SECS, SESSION_WINDOW_SECS,
+};
+
+const NOW: u64 = 1_700_000_000;
+
+/// `resets_at` for a window opened `elapsed` seconds before [`NOW`]
+fn opened_ago(elapsed: u64) -> u64 {
+    NOW - elapsed + SESSION_WINDOW_SECS
+}
+
+#[test]
+fn a_reset_in_the_past_has_no_estimate() {
+    assert_eq!(session_time_left(50.0, NOW - 1, NOW, &[]), None);
+    assert_eq!(session_time_left(100.0, NOW - 1, NOW, &[]), None);
+    assert_eq!(session_time_left(100.0, 0, NOW, &[(NOW - 900, 10.0)]), None);
+}
+
+#[test]
+fn too_little_time_has_no_estimate() {
+    // Just after the window opened
+    let resets_at = opened_ago(MIN_PACE_SECS - 1);
+    assert_eq!(session_time_left(90.0, resets_at, NOW, &[]), None);
+
+    // Readings closer together than the minimum, in a window opened long ago
+    let resets_at = opened_ago(3 * 3600);
+    let history = [(NOW - MIN_PACE_SECS + 1, 20.0)];
+    assert_eq!(session_time_left(60.0, resets_at, NOW, &history), None);
+}
+
+#[test]
+fn a_used_up_allowance_has_no_time_left() {
+    let resets_at = opened_ago(60);
+    assert_eq!(session_time_left(100.0, resets_at, NOW, &[]), Some(0));
+    assert_eq!(session_time_left(130.0, resets_at, NOW, &[]), Some(0));
+}
+
+#[test]
+fn nothing_used_has_no_estimate() {
+    let resets_at = opened_ago(3600);
+    assert_eq!(session_time_left(0.0, resets_at, NOW, &[]), None);
+    assert_eq!(session_time_left(f64::NAN, resets_at, NOW, &[]), None);
+}
+
+#[test]
+fn the_pace_is_measured_from_the_window_opening_when_recent() {
+    // 40% in 20 minutes: 2% a minute, so 60% lasts 30 minutes
+    let resets_at = opened_ago(20 * 60);
+    assert_eq!(session_time_left(40.0, resets_at, NOW, &[]), Some(30 * 60));
+}
+
+#[test]
+fn the_pace_is_measured_over_the_recent_past() {
+    // An idle start: 10% in the first three hours, then 30% more in the
+    // last half hour. Averaged over the window the allowance would last
+    // past the reset; at the recent 1% a minute it lasts an hour.
+    let resets_at = opened_ago(3 * 3600 + PACE_WINDOW_SECS);
+    let history = [
+        (NOW - 2 * 3600, 5.0),
+        (NOW - PACE_WINDOW_SECS, 10.0),
+        (NOW - 10 * 60, 30.0),
+    ];
+    assert_eq!(
+        session_time_left(40.0, resets_at, NOW, &history),
+        Some(3600)
+    );
+}
+
+#[test]
+fn an_allowance_that_lasts_until_the_reset_has_no_estimate() {
+    // 1% over the last half hour, an hour before the reset
+    let resets_at = NOW + 3600;
+    let history = [(NOW - PACE_WINDOW_SECS, 49.0)];
+    assert_eq!(session_time_left(50.0, resets_at, NOW, &history), None);
+
+    // No use at all since the last reading
+    let history = [(NOW - PACE_WINDOW_SECS, 50.0)];
+    assert_eq!(session_time_left(50.0, resets_at, NOW, &history), None);
+}
+
+#[test]
+fn readings_of_an_earlier_window_are_ignored() {
+    // A reading above the current share is from before a reset
+    let resets_at = opened_ago(20 * 60);
+    let history = [(NOW - 25 * 60, 90.0)];
+    assert_eq!(
+        session_time_left(40.0, resets_at, NOW, &history),
+        Some(30 * 60)
+    );
+}
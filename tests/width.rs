@@ -0,0 +1,260 @@
+//! Property tests for width-aware layout: truncation never exceeds its
+//! budget or splits a grapheme cluster, a segment's `width` bounds its
+//! columns whatever its text, the configurator preview wraps within the
+//! terminal width, and every escape sequence written is complete.
+//!
+//! Text is drawn from random characters mixed with the clusters that trip
+//! up width handling: wide CJK and emoji, ZWJ sequences, flags, combining
+//! marks, Nerd Font glyphs and stray escape sequences.
+
+use ccometixline_packycc::config::{Truncation, WidthConfig};
+use ccometixline_packycc::ui::themes::ThemePresets;
+use ccometixline_packycc::utils::width::{
+    display_width, strip_ansi, truncate_middle_to_width, truncate_start_to_width, truncate_to_width,
+};
+use ccometixline_packycc::{render, Config, SegmentConfig, SegmentData};
+use proptest::prelude::*;
+use proptest::sample::select;
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+const CLUSTERS: &[&str] = &[
+    "a",
+    " ",
+    "/",
+    "中",
+    "한",
+    "🤖",
+    "👨‍👩‍👧",
+    "🇯🇵",
+    "⚡️",
+    "e\u{301}",
+    "\u{301}",
+    "\u{200d}",
+    "\u{e0a0}",
+    "…",
+    "\x1b[31m",
+    "\x1b]8;;https://example.com\x07",
+    "\t",
+];
+
+/// Text as segments may receive it from git, transcripts or remote APIs
+fn any_text() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            any::<char>().prop_map(String::from),
+            select(CLUSTERS).prop_map(str::to_string),
+        ],
+        0..24,
+    )
+    .prop_map(|pieces| pieces.concat())
+}
+
+/// Text as the truncation helpers get it: sanitized, without control
+/// characters
+fn plain_text() -> impl Strategy<Value = String> {
+    any_text().prop_map(|text| text.chars().filter(|c| !c.is_control()).collect())
+}
+
+fn truncation() -> impl Strategy<Value = Truncation> {
+    prop_oneof![
+        Just(Truncation::End),
+        Just(Truncation::Middle),
+        Just(Truncation::Start)
+    ]
+}
+
+fn theme_name() -> impl Strategy<Value = &'static str> {
+    select(
+        ThemePresets::get_available_themes()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Byte offsets where `text` may be cut without splitting a grapheme cluster
+fn boundaries(text: &str) -> Vec<usize> {
+    text.grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(text.len()))
+        .collect()
+}
+
+/// Whether every ESC in `line` starts a complete SGR (`ESC [ digits ; m`) or
+/// OSC (`ESC ] ... BEL`) sequence
+fn escapes_complete(line: &str) -> bool {
+    let mut rest = line;
+    while let Some(start) = rest.find('\x1b') {
+        rest = &rest[start + 1..];
+        if let Some(params) = rest.strip_prefix('[') {
+            let end = params
+                .find(|c: char| !c.is_ascii_digit() && c != ';')
+                .unwrap_or(params.len());
+            if !params[end..].starts_with('m') {
+                return false;
+            }
+            rest = &params[end + 1..];
+        } else if let Some(osc) = rest.strip_prefix(']') {
+            match osc.find('\x07') {
+                Some(end) => rest = &osc[end + 1..],
+                None => return false,
+            }
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Keep the generator's font and color detection away from the machine
+fn isolate() {
+    let home = std::env::temp_dir().join(format!("ccline-width-{}", std::process::id()));
+    std::env::set_var("CCLINE_HOME", &home);
+    std::env::set_var("CCLINE_NERD_FONT", "1");
+}
+
+/// `config`'s first segment with `width`, alone on the line
+fn one_segment(
+    config: &Config,
+    width: WidthConfig,
+    primary: &str,
+    secondary: &str,
+) -> Vec<(SegmentConfig, SegmentData)> {
+    let segment = SegmentConfig {
+        enabled: true,
+        width: Some(width),
+        ..config.segments[0].clone()
+    };
+    let data = SegmentData {
+        primary: primary.to_string(),
+        secondary: secondary.to_string(),
+        metadata: HashMap::new(),
+    }
+    .sanitized();
+    vec![(segment, data)]
+}
+
+proptest! {
+    #[test]
+    fn truncation_fits_and_keeps_whole_graphemes(
+        text in plain_text(),
+        max in 0usize..32,
+        strategy in truncation(),
+    ) {
+        let cut = match strategy {
+            Truncation::End => truncate_to_width(&text, max, "…"),
+            Truncation::Middle => truncate_middle_to_width(&text, max, "…"),
+            Truncation::Start => truncate_start_to_width(&text, max, "…"),
+        };
+
+        if display_width(&text) <= max {
+            prop_assert_eq!(&cut, &text);
+            return Ok(());
+        }
+        prop_assert!(display_width(&cut) <= max, "{:?} is wider than {}", cut, max);
+
+        // What is left of the text is its start, its end, or both, cut
+        // between grapheme clusters. The text may hold ellipses of its own,
+        // so any of them may be the one truncation added.
+        let cuts = boundaries(&text);
+        let kept = |head: &str, tail: &str| {
+            text.starts_with(head)
+                && cuts.contains(&head.len())
+                && text.ends_with(tail)
+                && cuts.contains(&(text.len() - tail.len()))
+        };
+        let whole = match strategy {
+            Truncation::End => kept(cut.strip_suffix('…').unwrap_or(&cut), ""),
+            Truncation::Start => kept("", cut.strip_prefix('…').unwrap_or(&cut)),
+            Truncation::Middle => {
+                max == 0
+                    || cut
+                        .match_indices('…')
+                        .any(|(at, ellipsis)| kept(&cut[..at], &cut[at + ellipsis.len()..]))
+            }
+        };
+        prop_assert!(whole, "{:?} splits a grapheme cluster of {:?}", cut, text);
+    }
+
+    #[test]
+    fn fixed_width_does_not_depend_on_the_text(
+        theme in theme_name(),
+        primary in any_text(),
+        secondary in any_text(),
+        fixed in 6usize..40,
+        truncate in truncation(),
+    ) {
+        isolate();
+        let config = ThemePresets::get_theme(theme);
+        let width = WidthConfig { fixed: Some(fixed), truncate, ..WidthConfig::default() };
+
+        let line = render(&config, one_segment(&config, width, &primary, &secondary));
+        let reference = render(&config, one_segment(&config, width, "x", ""));
+        prop_assert_eq!(display_width(&line), display_width(&reference));
+        prop_assert!(escapes_complete(&line), "broken escape sequence in {:?}", line);
+        prop_assert!(!strip_ansi(&line).contains('\x1b'));
+    }
+
+    #[test]
+    fn max_width_is_never_exceeded(
+        theme in theme_name(),
+        primary in any_text(),
+        secondary in any_text(),
+        max in 6usize..40,
+        truncate in truncation(),
+    ) {
+        isolate();
+        let config = ThemePresets::get_theme(theme);
+        let width = WidthConfig { max: Some(max), truncate, ..WidthConfig::default() };
+        let fixed = WidthConfig { fixed: Some(max), ..width };
+
+        let line = render(&config, one_segment(&config, width, &primary, &secondary));
+        let full = render(&config, one_segment(&config, fixed, "x", ""));
+        prop_assert!(display_width(&line) <= display_width(&full));
+        prop_assert!(escapes_complete(&line), "broken escape sequence in {:?}", line);
+    }
+}
+
+#[cfg(feature = "tui")]
+proptest! {
+    #[test]
+    fn preview_wraps_within_the_terminal(
+        theme in theme_name(),
+        texts in prop::collection::vec((any_text(), any_text()), 1..8),
+        fixed in 6usize..24,
+        slack in 12u16..80,
+    ) {
+        use ccometixline_packycc::StatusLineGenerator;
+
+        isolate();
+        let config = ThemePresets::get_theme(theme);
+        let width = WidthConfig { fixed: Some(fixed), ..WidthConfig::default() };
+        let segments = config
+            .segments
+            .iter()
+            .zip(&texts)
+            .map(|(segment, (primary, secondary))| {
+                let data = SegmentData {
+                    primary: primary.clone(),
+                    secondary: secondary.clone(),
+                    metadata: HashMap::new(),
+                }
+                .sanitized();
+                (SegmentConfig { enabled: true, width: Some(width), ..segment.clone() }, data)
+            })
+            .collect();
+
+        // Every segment fits the terminal on its own, so every line must
+        let terminal = fixed as u16 + slack;
+        let generator = StatusLineGenerator::new(config);
+        let text = generator.generate_for_tui_preview(segments, terminal);
+        for line in &text.lines {
+            let content: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+            prop_assert!(
+                display_width(&content) <= terminal as usize,
+                "{:?} is wider than {}", content, terminal
+            );
+        }
+    }
+}